@@ -148,11 +148,89 @@ benchmark_padded!(
     Cipher::aes_256_cbc()
 );
 
+// Encrypts many small records with a single key, each with a freshly generated IV, so the key
+// schedule is only computed once.
+fn test_aes_128_key_reuse(c: &mut Criterion) {
+    let key_bytes = [0x42u8; 16];
+    let mut group = c.benchmark_group("AES-128-key-reuse-64-bytes");
+
+    let ctr_key = EncryptingKey::ctr(UnboundCipherKey::new(&AES_128, &key_bytes).unwrap()).unwrap();
+    group.bench_function("CTR", |b| {
+        b.iter(|| {
+            let mut in_out = [0u8; 64];
+            let _ = ctr_key.encrypt(&mut in_out).unwrap();
+        })
+    });
+
+    let cbc_key =
+        PaddedBlockEncryptingKey::cbc_pkcs7(UnboundCipherKey::new(&AES_128, &key_bytes).unwrap())
+            .unwrap();
+    group.bench_function("CBC", |b| {
+        b.iter(|| {
+            let mut in_out = vec![0u8; 64];
+            let _ = cbc_key.encrypt(&mut in_out).unwrap();
+        })
+    });
+}
+
+// Constructs a key for every record, as when per-record keys are derived, comparing a key
+// with both AES key schedules to one constructed for encryption only.
+fn test_aes_256_key_setup(c: &mut Criterion) {
+    let key_bytes = [0x42u8; 32];
+    let mut group = c.benchmark_group("AES-256-key-setup");
+
+    group.bench_function("new", |b| {
+        b.iter(|| UnboundCipherKey::new(&AES_256, &key_bytes).unwrap())
+    });
+
+    group.bench_function("new_encrypt_only", |b| {
+        b.iter(|| UnboundCipherKey::new_encrypt_only(&AES_256, &key_bytes).unwrap())
+    });
+}
+
+// Encrypts a batch of small records under one key, each with its own IV, comparing a key
+// constructed per record, a reused key, and a single call to `encrypt_batch`.
+fn test_aes_128_ctr_batch(c: &mut Criterion) {
+    let key_bytes = [0x42u8; 16];
+    let records = vec![vec![0u8; 200]; 1000];
+    let mut group = c.benchmark_group("AES-128-CTR-batch-1000x200-bytes");
+
+    group.bench_function("key per record", |b| {
+        b.iter(|| {
+            let mut records = records.clone();
+            for record in &mut records {
+                let key = UnboundCipherKey::new(&AES_128, &key_bytes).unwrap();
+                let _ = EncryptingKey::ctr(key).unwrap().encrypt(record).unwrap();
+            }
+        })
+    });
+
+    let key = EncryptingKey::ctr(UnboundCipherKey::new(&AES_128, &key_bytes).unwrap()).unwrap();
+    group.bench_function("reused key", |b| {
+        b.iter(|| {
+            let mut records = records.clone();
+            for record in &mut records {
+                let _ = key.encrypt(record).unwrap();
+            }
+        })
+    });
+
+    group.bench_function("encrypt_batch", |b| {
+        b.iter(|| {
+            let mut records = records.clone();
+            let _ = key.encrypt_batch(&mut records).unwrap();
+        })
+    });
+}
+
 criterion_group!(
     benches,
     test_aes_128_ctr,
     test_aes_128_cbc,
     test_aes_256_ctr,
-    test_aes_256_cbc
+    test_aes_256_cbc,
+    test_aes_128_key_reuse,
+    test_aes_256_key_setup,
+    test_aes_128_ctr_batch
 );
 criterion_main!(benches);
@@ -117,6 +117,7 @@
 use crate::{derive_debug_via_id, error::Unspecified, hkdf};
 use aead_ctx::AeadCtx;
 use core::{fmt::Debug, ops::RangeFrom};
+use zeroize::ZeroizeOnDrop;
 
 mod aead_ctx;
 mod aes_gcm;
@@ -176,6 +177,9 @@ pub trait BoundKey<N: NonceSequence>: Debug {
 /// of the nonce sequence.
 ///
 /// Prefer [`RandomizedNonceKey`] for opening operations.
+///
+/// The key schedule lives in an *AWS-LC* `EVP_AEAD_CTX`, which `OPENSSL_free` overwrites
+/// when the key is dropped. The nonce sequence is dropped without being cleared.
 pub struct OpeningKey<N: NonceSequence> {
     key: UnboundKey,
     nonce_sequence: N,
@@ -203,6 +207,8 @@ impl<N: NonceSequence> Debug for OpeningKey<N> {
     }
 }
 
+impl<N: NonceSequence> ZeroizeOnDrop for OpeningKey<N> {}
+
 impl<N: NonceSequence> OpeningKey<N> {
     /// Authenticates and decrypts (“opens”) data in place.
     ///
@@ -316,6 +322,9 @@ impl<N: NonceSequence> OpeningKey<N> {
 /// of the nonce sequence.
 ///
 /// Prefer [`RandomizedNonceKey`] for sealing operations.
+///
+/// Dropping the key releases its `EVP_AEAD_CTX` through `OPENSSL_free`, which clears the key
+/// schedule first. The nonce sequence is dropped without being cleared.
 pub struct SealingKey<N: NonceSequence> {
     key: UnboundKey,
     nonce_sequence: N,
@@ -343,6 +352,8 @@ impl<N: NonceSequence> Debug for SealingKey<N> {
     }
 }
 
+impl<N: NonceSequence> ZeroizeOnDrop for SealingKey<N> {}
+
 impl<N: NonceSequence> SealingKey<N> {
     /// Deprecated. Renamed to `seal_in_place_append_tag`.
     ///
@@ -479,6 +490,9 @@ impl hkdf::KeyType for &'static Algorithm {
 ///
 /// Prefer [`RandomizedNonceKey`] when practical.
 ///
+/// Its `EVP_AEAD_CTX`, which holds the key schedule, is overwritten and released by
+/// `OPENSSL_free` when the key is dropped.
+///
 // # FIPS
 // The following conditions must be met:
 // * `UnboundKey`'s algorithm is one of:
@@ -718,6 +732,8 @@ impl Debug for LessSafeKey {
     }
 }
 
+impl ZeroizeOnDrop for LessSafeKey {}
+
 /// An AEAD Algorithm.
 pub struct Algorithm {
     init: fn(key: &[u8], tag_len: usize) -> Result<AeadCtx, Unspecified>,
@@ -28,12 +28,17 @@ use crate::cipher::block::BLOCK_LEN;
 use crate::cipher::chacha::{self, ChaCha20Key};
 use crate::iv::FixedLength;
 use crate::{constant_time, endian::BigEndian, error};
+use zeroize::ZeroizeOnDrop;
 
 /// A key for sealing packets.
+///
+/// Both ChaCha20 keys are zeroized when dropped.
 pub struct SealingKey {
     key: Key,
 }
 
+impl ZeroizeOnDrop for SealingKey {}
+
 impl SealingKey {
     /// Constructs a new `SealingKey`.
     #[must_use]
@@ -80,10 +85,14 @@ impl SealingKey {
 }
 
 /// A key for opening packets.
+///
+/// Both ChaCha20 keys are zeroized when dropped.
 pub struct OpeningKey {
     key: Key,
 }
 
+impl ZeroizeOnDrop for OpeningKey {}
+
 impl OpeningKey {
     /// Constructs a new `OpeningKey`.
     #[must_use]
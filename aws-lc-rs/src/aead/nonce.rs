@@ -4,8 +4,8 @@
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
 use crate::endian::{ArrayEncoding, BigEndian, Encoding, FromArray, LittleEndian};
-use crate::error;
-use crate::iv::FixedLength;
+use crate::iv::{FixedLength, IvLength};
+use crate::{error, hkdf};
 
 /// A nonce for a single AEAD opening or sealing operation.
 ///
@@ -43,6 +43,20 @@ impl AsRef<[u8; NONCE_LEN]> for Nonce {
     }
 }
 
+impl From<FixedLength<NONCE_LEN>> for Nonce {
+    #[inline]
+    fn from(value: FixedLength<NONCE_LEN>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<hkdf::Okm<'_, IvLength<NONCE_LEN>>> for Nonce {
+    #[inline]
+    fn from(okm: hkdf::Okm<IvLength<NONCE_LEN>>) -> Self {
+        Self(FixedLength::from(okm))
+    }
+}
+
 impl From<&[u8; NONCE_LEN]> for Nonce {
     #[inline]
     fn from(bytes: &[u8; NONCE_LEN]) -> Self {
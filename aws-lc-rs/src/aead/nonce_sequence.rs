@@ -8,3 +8,72 @@ mod counter64;
 
 pub use counter32::{Counter32, Counter32Builder};
 pub use counter64::{Counter64, Counter64Builder};
+
+use crate::aead::{Nonce, NonceSequence, NONCE_LEN};
+use crate::error::Unspecified;
+use crate::iv::Counter;
+
+impl NonceSequence for Counter<NONCE_LEN> {
+    fn advance(&mut self) -> Result<Nonce, Unspecified> {
+        Counter::advance(self).map(Nonce::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aead::nonce_sequence::Counter64Builder;
+    use crate::aead::{
+        Aad, BoundKey, NonceSequence, OpeningKey, SealingKey, UnboundKey, AES_128_GCM, NONCE_LEN,
+    };
+    use crate::iv::{Counter, CounterBuilder};
+
+    fn counter() -> Counter<NONCE_LEN> {
+        CounterBuilder::new()
+            .identifier(&[0xA1, 0xB2, 0xC3, 0xD4])
+            .counter(7)
+            .limit(2)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_counter_nonce_sequence() {
+        let mut expected = Counter64Builder::new()
+            .identifier([0xA1, 0xB2, 0xC3, 0xD4])
+            .counter(7)
+            .limit(2)
+            .build();
+        let mut nonces = counter();
+        for _ in 0..2 {
+            assert_eq!(
+                expected.advance().unwrap().as_ref(),
+                NonceSequence::advance(&mut nonces).unwrap().as_ref()
+            );
+        }
+        assert!(NonceSequence::advance(&mut nonces).is_err());
+    }
+
+    #[test]
+    fn test_counter_bound_keys() {
+        let key_bytes = [0x42u8; 16];
+        let mut sealing_key = SealingKey::new(
+            UnboundKey::new(&AES_128_GCM, &key_bytes).unwrap(),
+            counter(),
+        );
+        let mut opening_key = OpeningKey::new(
+            UnboundKey::new(&AES_128_GCM, &key_bytes).unwrap(),
+            counter(),
+        );
+
+        for message in [&b"first"[..], &b"second"[..]] {
+            let mut in_out = message.to_vec();
+            sealing_key
+                .seal_in_place_append_tag(Aad::empty(), &mut in_out)
+                .unwrap();
+            let plaintext = opening_key
+                .open_in_place(Aad::empty(), &mut in_out)
+                .unwrap();
+            assert_eq!(message, plaintext);
+        }
+    }
+}
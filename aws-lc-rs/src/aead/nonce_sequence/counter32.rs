@@ -3,7 +3,7 @@
 
 use crate::aead::{Nonce, NonceSequence, NONCE_LEN};
 use crate::error::Unspecified;
-use crate::iv::FixedLength;
+use crate::iv::Counter;
 
 /// `Counter32` is an implementation of the `NonceSequence` trait.
 /// The internal state of a `Counter32` is a 32-bit unsigned counter that
@@ -11,14 +11,12 @@ use crate::iv::FixedLength;
 /// values are used to construct each nonce.
 /// A limit can be set on the number of nonces allowed to be generated, by default this limit is
 /// `u32::MAX`.
+/// No nonce is generated twice: `advance` fails once the counter has run out of values.
 ///
 /// See [Section 3.2 of RFC 5116](https://www.rfc-editor.org/rfc/rfc5116#section-3.2).
 #[allow(clippy::module_name_repetitions)]
 pub struct Counter32 {
-    limit: u32,
-    generated: u32,
-    identifier: [u8; 8],
-    counter: u32,
+    inner: Counter<NONCE_LEN>,
 }
 
 /// `NonceSequenceBuilder` facilitates the building of a `Counter32`.
@@ -72,11 +70,10 @@ impl Counter32Builder {
     /// values provided by this struct.
     #[must_use]
     pub fn build(self) -> Counter32 {
+        let mut prefix = [0u8; NONCE_LEN];
+        prefix[..8].copy_from_slice(&self.identifier);
         Counter32 {
-            limit: self.limit,
-            generated: 0,
-            identifier: self.identifier,
-            counter: self.counter,
+            inner: Counter::new(prefix, 4, self.counter.into(), 0, self.limit.into()),
         }
     }
 }
@@ -85,40 +82,35 @@ impl Counter32 {
     /// Provides the internal identifier.
     #[must_use]
     pub fn identifier(&self) -> [u8; 8] {
-        self.identifier
+        let mut identifier = [0u8; 8];
+        identifier.copy_from_slice(self.inner.identifier());
+        identifier
     }
 
     /// Provides the current internal counter value.
     #[must_use]
     pub fn counter(&self) -> u32 {
-        self.counter
+        // Only exceeds `u32::MAX` once the counter is exhausted.
+        u32::try_from(self.inner.counter()).unwrap_or(u32::MAX)
     }
 
     /// Provides the current counter indicating how many nonces have been generated.
     #[must_use]
     pub fn generated(&self) -> u32 {
-        self.generated
+        // Never exceeds the 32-bit limit.
+        u32::try_from(self.inner.generated()).unwrap_or(u32::MAX)
     }
 
     /// Provides the limit on the number of nonces allowed to be generate.
     #[must_use]
     pub fn limit(&self) -> u32 {
-        self.limit
+        u32::try_from(self.inner.limit()).unwrap_or(u32::MAX)
     }
 }
 
 impl NonceSequence for Counter32 {
     fn advance(&mut self) -> Result<Nonce, Unspecified> {
-        self.generated = self.generated.checked_add(1).ok_or(Unspecified)?;
-        if self.generated > self.limit {
-            return Err(Unspecified);
-        }
-        let counter_bytes: [u8; 4] = self.counter.to_be_bytes();
-        let mut nonce_bytes = [0u8; NONCE_LEN];
-        nonce_bytes[..8].copy_from_slice(&self.identifier);
-        nonce_bytes[8..].copy_from_slice(&counter_bytes);
-        self.counter = self.counter.wrapping_add(1);
-        Ok(Nonce(FixedLength::from(nonce_bytes)))
+        self.inner.advance().map(Nonce::from)
     }
 }
 
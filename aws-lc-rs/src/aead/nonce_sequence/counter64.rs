@@ -3,7 +3,7 @@
 
 use crate::aead::{Nonce, NonceSequence, NONCE_LEN};
 use crate::error::Unspecified;
-use crate::iv::FixedLength;
+use crate::iv::Counter;
 
 /// `Counter64` is an implementation of the `NonceSequence` trait.
 /// The internal state of a `Counter64` is a 64-bit unsigned counter that
@@ -11,13 +11,11 @@ use crate::iv::FixedLength;
 /// values are used to construct each nonce.
 /// A limit can be set on the number of nonces allowed to be generated, by default this limit is
 /// `u64::MAX`.
+/// No nonce is generated twice: `advance` fails once the counter has run out of values.
 /// See [Section 3.2 of RFC 5116](https://www.rfc-editor.org/rfc/rfc5116#section-3.2).
 #[allow(clippy::module_name_repetitions)]
 pub struct Counter64 {
-    limit: u64,
-    generated: u64,
-    identifier: [u8; 4],
-    counter: u64,
+    inner: Counter<NONCE_LEN>,
 }
 
 /// `NonceSequenceBuilder` facilitates the building of a `Counter64`.
@@ -71,11 +69,10 @@ impl Counter64Builder {
     /// values provided by this struct.
     #[must_use]
     pub fn build(self) -> Counter64 {
+        let mut prefix = [0u8; NONCE_LEN];
+        prefix[..4].copy_from_slice(&self.identifier);
         Counter64 {
-            limit: self.limit,
-            generated: 0,
-            identifier: self.identifier,
-            counter: self.counter,
+            inner: Counter::new(prefix, 8, self.counter, 0, self.limit),
         }
     }
 }
@@ -84,40 +81,33 @@ impl Counter64 {
     /// Provides the internal identifier.
     #[must_use]
     pub fn identifier(&self) -> [u8; 4] {
-        self.identifier
+        let mut identifier = [0u8; 4];
+        identifier.copy_from_slice(self.inner.identifier());
+        identifier
     }
 
     /// Provides the current internal counter value.
     #[must_use]
     pub fn counter(&self) -> u64 {
-        self.counter
+        self.inner.counter()
     }
 
     /// Provides the current counter indicating how many nonces have been generated.
     #[must_use]
     pub fn generated(&self) -> u64 {
-        self.generated
+        self.inner.generated()
     }
 
     /// Provides the limit on the number of nonces allowed to be generate.
     #[must_use]
     pub fn limit(&self) -> u64 {
-        self.limit
+        self.inner.limit()
     }
 }
 
 impl NonceSequence for Counter64 {
     fn advance(&mut self) -> Result<Nonce, Unspecified> {
-        self.generated = self.generated.checked_add(1).ok_or(Unspecified)?;
-        if self.generated > self.limit {
-            return Err(Unspecified);
-        }
-        let bytes: [u8; 8] = self.counter.to_be_bytes();
-        let mut nonce_bytes = [0u8; NONCE_LEN];
-        nonce_bytes[..4].copy_from_slice(&self.identifier);
-        nonce_bytes[4..].copy_from_slice(&bytes);
-        self.counter = self.counter.wrapping_add(1);
-        Ok(Nonce(FixedLength::from(nonce_bytes)))
+        self.inner.advance().map(Nonce::from)
     }
 }
 
@@ -13,13 +13,18 @@ use crate::cipher::chacha::encrypt_block_chacha20;
 use crate::cipher::key::SymmetricCipherKey;
 use crate::hkdf::KeyType;
 use crate::{derive_debug_via_id, error, hkdf};
+use zeroize::ZeroizeOnDrop;
 
 /// A key for generating QUIC Header Protection masks.
+///
+/// The key schedule is zeroized when dropped.
 pub struct HeaderProtectionKey {
     inner: SymmetricCipherKey,
     algorithm: &'static Algorithm,
 }
 
+impl ZeroizeOnDrop for HeaderProtectionKey {}
+
 impl From<hkdf::Okm<'_, &'static Algorithm>> for HeaderProtectionKey {
     fn from(okm: hkdf::Okm<&'static Algorithm>) -> Self {
         let mut key_bytes = [0; super::MAX_KEY_LEN];
@@ -148,9 +153,9 @@ fn cipher_new_mask(
     let block = block::Block::from(&sample);
 
     let encrypted_block = match cipher_key {
-        SymmetricCipherKey::Aes128 { enc_key, .. } | SymmetricCipherKey::Aes256 { enc_key, .. } => {
-            encrypt_block_aes(enc_key, block)
-        }
+        SymmetricCipherKey::Aes128 { enc_key, .. }
+        | SymmetricCipherKey::Aes192 { enc_key, .. }
+        | SymmetricCipherKey::Aes256 { enc_key, .. } => encrypt_block_aes(enc_key, block),
         SymmetricCipherKey::ChaCha20 { raw_key } => {
             let plaintext = block.as_ref();
             let counter_bytes: &[u8; 4] = plaintext[0..=3]
@@ -165,6 +170,8 @@ fn cipher_new_mask(
                 encrypt_block_chacha20(raw_key, input, nonce, counter)?
             }
         }
+        #[cfg(not(feature = "approved-only"))]
+        SymmetricCipherKey::TripleDes { .. } => return Err(error::Unspecified),
     };
 
     let mut out: [u8; 5] = [0; 5];
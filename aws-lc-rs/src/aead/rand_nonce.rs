@@ -3,6 +3,7 @@
 
 use crate::error::Unspecified;
 use core::fmt::Debug;
+use zeroize::ZeroizeOnDrop;
 
 use super::{aead_ctx::AeadCtx, Aad, Algorithm, AlgorithmID, Nonce, Tag, UnboundKey};
 
@@ -15,6 +16,9 @@ use super::{aead_ctx::AeadCtx, Aad, Algorithm, AlgorithmID, Nonce, Tag, UnboundK
 /// * `AES_256_GCM`
 ///
 /// Prefer this type in place of `LessSafeKey`, `OpeningKey`, `SealingKey`.
+///
+/// The AES-GCM key schedule is held in an `EVP_AEAD_CTX` that also generates the nonces. It is
+/// cleared and freed by `OPENSSL_free` when the key is dropped.
 pub struct RandomizedNonceKey {
     key: UnboundKey,
     algorithm: &'static Algorithm,
@@ -144,6 +148,8 @@ impl Debug for RandomizedNonceKey {
     }
 }
 
+impl ZeroizeOnDrop for RandomizedNonceKey {}
+
 #[cfg(test)]
 mod tests {
     use super::{Aad, RandomizedNonceKey};
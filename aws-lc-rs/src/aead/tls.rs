@@ -8,6 +8,7 @@ use super::{
 use crate::error::Unspecified;
 use core::fmt::Debug;
 use core::ops::RangeFrom;
+use zeroize::ZeroizeOnDrop;
 
 /// The Transport Layer Security (TLS) protocol version.
 #[allow(clippy::module_name_repetitions)]
@@ -31,6 +32,9 @@ pub enum TlsProtocolId {
 /// * `AES_256_GCM`
 ///
 /// Prefer this type in place of `LessSafeKey`, `OpeningKey`, `SealingKey` for TLS protocol implementations.
+///
+/// When dropped, the `EVP_AEAD_CTX` holding the key schedule and the record nonce state is
+/// cleared and freed by `OPENSSL_free`.
 #[allow(clippy::module_name_repetitions)]
 pub struct TlsRecordSealingKey {
     // The TLS specific construction for TLS ciphers in AWS-LC are not thread-safe!
@@ -167,6 +171,8 @@ impl Debug for TlsRecordSealingKey {
     }
 }
 
+impl ZeroizeOnDrop for TlsRecordSealingKey {}
+
 /// AEAD Encryption key used for TLS protocol record encryption.
 ///
 /// This type encapsulates decryption operations for TLS AEAD algorithms.
@@ -176,6 +182,9 @@ impl Debug for TlsRecordSealingKey {
 /// * `AES_256_GCM`
 ///
 /// Prefer this type in place of `LessSafeKey`, `OpeningKey`, `SealingKey` for TLS protocol implementations.
+///
+/// When dropped, the `EVP_AEAD_CTX` holding the key schedule is cleared and freed by
+/// `OPENSSL_free`.
 #[allow(clippy::module_name_repetitions)]
 pub struct TlsRecordOpeningKey {
     // The TLS specific construction for TLS ciphers in AWS-LC are not thread-safe!
@@ -293,6 +302,8 @@ impl Debug for TlsRecordOpeningKey {
     }
 }
 
+impl ZeroizeOnDrop for TlsRecordOpeningKey {}
+
 #[cfg(test)]
 mod tests {
     use super::{TlsProtocolId, TlsRecordOpeningKey, TlsRecordSealingKey};
@@ -10,6 +10,7 @@ use aws_lc::{
 };
 use core::fmt::Debug;
 use core::{mem::MaybeUninit, ops::RangeFrom, ptr::null};
+use zeroize::ZeroizeOnDrop;
 
 /// The maximum length of a nonce returned by our AEAD API.
 const MAX_NONCE_LEN: usize = NONCE_LEN;
@@ -18,6 +19,8 @@ const MAX_NONCE_LEN: usize = NONCE_LEN;
 const MAX_TAG_NONCE_BUFFER_LEN: usize = MAX_TAG_LEN + MAX_NONCE_LEN;
 
 /// An AEAD key without a designated role or nonce sequence.
+///
+/// The key is held by an *AWS-LC* `EVP_AEAD_CTX`, which is cleansed when the key is dropped.
 pub struct UnboundKey {
     ctx: AeadCtx,
     algorithm: &'static Algorithm,
@@ -32,6 +35,8 @@ impl Debug for UnboundKey {
     }
 }
 
+impl ZeroizeOnDrop for UnboundKey {}
+
 impl UnboundKey {
     /// Constructs an `UnboundKey`.
     /// # Errors
@@ -115,6 +120,40 @@ impl UnboundKey {
         }
     }
 
+    /// Verifies `in_tag` and decrypts `in_out` in place. On failure `in_out` is zeroed.
+    #[inline]
+    pub(crate) fn open_in_place_separate_tag(
+        &self,
+        nonce: &Nonce,
+        aad: &[u8],
+        in_out: &mut [u8],
+        in_tag: &[u8],
+    ) -> Result<(), Unspecified> {
+        self.check_per_nonce_max_bytes(in_out.len())?;
+
+        let nonce = nonce.as_ref();
+
+        // `EVP_AEAD_CTX_open_gather` allows its input and output to alias exactly.
+        if 1 != indicator_check!(unsafe {
+            EVP_AEAD_CTX_open_gather(
+                *self.ctx.as_ref().as_const(),
+                in_out.as_mut_ptr(),
+                nonce.as_ptr(),
+                nonce.len(),
+                in_out.as_ptr(),
+                in_out.len(),
+                in_tag.as_ptr(),
+                in_tag.len(),
+                aad.as_ptr(),
+                aad.len(),
+            )
+        }) {
+            in_out.fill(0);
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
+
     #[inline]
     pub(crate) fn seal_in_place_append_tag<'a, InOut>(
         &self,
@@ -74,6 +74,7 @@ use crate::encoding::{
 use core::fmt;
 use core::fmt::{Debug, Formatter};
 use core::ptr::null_mut;
+use zeroize::ZeroizeOnDrop;
 
 #[allow(non_camel_case_types)]
 #[derive(PartialEq, Eq)]
@@ -115,6 +116,18 @@ impl AlgorithmID {
     }
 }
 
+#[cfg(feature = "serde")]
+impl AlgorithmID {
+    fn name(&self) -> &'static str {
+        match self {
+            AlgorithmID::ECDH_P256 => "ECDH_P256",
+            AlgorithmID::ECDH_P384 => "ECDH_P384",
+            AlgorithmID::ECDH_P521 => "ECDH_P521",
+            AlgorithmID::X25519 => "X25519",
+        }
+    }
+}
+
 impl Debug for AlgorithmID {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
         let output = match self {
@@ -177,6 +190,8 @@ enum KeyInner {
 /// A private key for use (only) with `agree`. The
 /// signature of `agree` allows `PrivateKey` to be
 /// used for more than one key agreement.
+///
+/// The key is freed and zeroized by *AWS-LC* when dropped.
 pub struct PrivateKey {
     inner_key: KeyInner,
 }
@@ -221,6 +236,8 @@ impl Debug for PrivateKey {
     }
 }
 
+impl ZeroizeOnDrop for PrivateKey {}
+
 impl PrivateKey {
     fn new(alg: &'static Algorithm, evp_pkey: LcPtr<EVP_PKEY>) -> Self {
         match alg.id {
@@ -544,11 +561,53 @@ pub struct PublicKey {
 }
 
 impl PublicKey {
+    /// Constructs an ECDH public key from its affine coordinates, as big-endian integers.
+    ///
+    /// Each coordinate may be shorter than the field size, in which case it is zero-padded.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `alg` is `X25519`, a coordinate is longer than the field size, or
+    /// the point is not on the curve of `alg`.
+    pub fn from_affine_coordinates(
+        alg: &'static Algorithm,
+        x: &[u8],
+        y: &[u8],
+    ) -> Result<Self, KeyRejected> {
+        if alg.id == AlgorithmID::X25519 {
+            return Err(KeyRejected::wrong_algorithm());
+        }
+        let mut public_key = [0u8; MAX_PUBLIC_KEY_LEN];
+        let len = ec::ec_point_from_affine_coordinates(
+            alg.id.nid(),
+            alg.id.private_key_len(),
+            x,
+            y,
+            &mut public_key,
+        )?;
+        Ok(PublicKey {
+            alg,
+            public_key,
+            len,
+        })
+    }
+
     /// The algorithm for the public key.
     #[must_use]
     pub fn algorithm(&self) -> &'static Algorithm {
         self.alg
     }
+
+    /// The affine coordinates `(x, y)` of an ECDH public key, as big-endian integers zero-padded
+    /// to the field size.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the public key is an `X25519` key, which has no such coordinates.
+    pub fn to_affine_coordinates(&self) -> Result<(Vec<u8>, Vec<u8>), Unspecified> {
+        if self.alg.id == AlgorithmID::X25519 {
+            return Err(Unspecified);
+        }
+        Ok(ec::affine_coordinates_from_uncompressed(self.as_ref()))
+    }
 }
 
 impl Debug for PublicKey {
@@ -567,6 +626,54 @@ impl AsRef<[u8]> for PublicKey {
     }
 }
 
+/// Serialized as the algorithm name (e.g. `"X25519"`) and the encoded public key.
+#[cfg(feature = "serde")]
+impl serde::Serialize for PublicKey {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::serialization::serialize_with_algorithm(
+            "PublicKey",
+            self.alg.id.name(),
+            self.as_ref(),
+            serializer,
+        )
+    }
+}
+
+/// Rejects unknown algorithms and values whose length differs from the algorithm's public key
+/// length. NIST curve public keys must be uncompressed points.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PublicKey {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let (name, value) =
+            crate::serialization::deserialize_with_algorithm("PublicKey", deserializer)?;
+        let alg: &'static Algorithm = match name.as_str() {
+            "ECDH_P256" => &ECDH_P256,
+            "ECDH_P384" => &ECDH_P384,
+            "ECDH_P521" => &ECDH_P521,
+            "X25519" => &X25519,
+            _ => return Err(D::Error::custom("unknown key agreement algorithm")),
+        };
+        if value.len() != alg.id.pub_key_len() {
+            return Err(D::Error::invalid_length(
+                value.len(),
+                &"the public key length of the key agreement algorithm",
+            ));
+        }
+        if alg.id != AlgorithmID::X25519 && value[0] != 0x04 {
+            return Err(D::Error::custom("public key is not an uncompressed point"));
+        }
+        let mut public_key = [0u8; MAX_PUBLIC_KEY_LEN];
+        public_key[..value.len()].copy_from_slice(&value);
+        Ok(PublicKey {
+            alg,
+            public_key,
+            len: value.len(),
+        })
+    }
+}
+
 impl Clone for PublicKey {
     fn clone(&self) -> Self {
         PublicKey {
@@ -830,6 +937,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_affine_coordinates() {
+        for (alg, field_len) in [(&ECDH_P256, 32), (&ECDH_P384, 48), (&ECDH_P521, 66)] {
+            // Find a key whose x-coordinate has a leading zero byte.
+            let (public_key, x, y) = loop {
+                let private_key = PrivateKey::generate(alg).unwrap();
+                let public_key = private_key.compute_public_key().unwrap();
+                let (x, y) = public_key.to_affine_coordinates().unwrap();
+                if x[0] == 0 {
+                    break (public_key, x, y);
+                }
+            };
+            assert_eq!(field_len, x.len());
+            assert_eq!(field_len, y.len());
+
+            let decoded = PublicKey::from_affine_coordinates(alg, &x, &y).unwrap();
+            assert_eq!(public_key.as_ref(), decoded.as_ref());
+            let stripped = PublicKey::from_affine_coordinates(alg, &x[1..], &y).unwrap();
+            assert_eq!(public_key.as_ref(), stripped.as_ref());
+
+            let mut invalid_y = y.clone();
+            invalid_y[field_len - 1] ^= 1;
+            assert!(PublicKey::from_affine_coordinates(alg, &x, &invalid_y).is_err());
+            assert!(PublicKey::from_affine_coordinates(alg, &[0u8; 67], &y).is_err());
+        }
+
+        let x25519_public_key = PrivateKey::generate(&X25519)
+            .unwrap()
+            .compute_public_key()
+            .unwrap();
+        assert!(x25519_public_key.to_affine_coordinates().is_err());
+        assert!(PublicKey::from_affine_coordinates(&X25519, &[1], &[1]).is_err());
+    }
+
     #[test]
     fn test_agreement_invalid_keys() {
         fn test_with_key(alg: &'static Algorithm, my_private_key: &PrivateKey, test_key: &[u8]) {
@@ -6,10 +6,13 @@ use crate::error::Unspecified;
 use crate::rand::SecureRandom;
 use core::fmt;
 use core::fmt::{Debug, Formatter};
+use zeroize::ZeroizeOnDrop;
 
 /// An ephemeral private key for use (only) with `agree_ephemeral`. The
 /// signature of `agree_ephemeral` ensures that an `PrivateKey` can be
 /// used for at most one key agreement.
+///
+/// The wrapped `PrivateKey` is freed and zeroized by *AWS-LC* when dropped.
 #[allow(clippy::module_name_repetitions)]
 pub struct EphemeralPrivateKey(PrivateKey);
 
@@ -22,6 +25,8 @@ impl Debug for EphemeralPrivateKey {
     }
 }
 
+impl ZeroizeOnDrop for EphemeralPrivateKey {}
+
 impl EphemeralPrivateKey {
     #[inline]
     /// Generate a new ephemeral private key for the given algorithm.
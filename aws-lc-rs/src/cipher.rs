@@ -17,6 +17,11 @@
 //! If you are developing new applications requring data encryption see
 //! the algorithms provided in [`aead`](crate::aead).
 //!
+//! The keys in this module take their algorithm from the [`UnboundCipherKey`] they are
+//! constructed from, and their mode from the constructor that is used, such as
+//! [`EncryptingKey::ctr`]. To fix both the algorithm and the mode in a key's type, for example
+//! AES-128 in CTR mode, use the keys of the [`typed`] module.
+//!
 //! # Examples
 //!
 //! ## Encryption Modes
@@ -54,6 +59,42 @@
 //! # }
 //! ```
 //!
+//! ### AES-128 CBC without padding
+//!
+//! Prefer [`PaddedBlockEncryptingKey`] and [`PaddedBlockDecryptingKey`] for CBC, which pad the
+//! plaintext to a multiple of the block length and remove the padding after decryption. Use
+//! [`EncryptingKey::cbc`] and [`DecryptingKey::cbc`] only when the padding is handled elsewhere,
+//! such as by a protocol or device that pre-pads its data; the input must then already be a
+//! multiple of the block length. An IV chosen by the caller can be supplied with
+//! [`EncryptingKey::less_safe_encrypt`].
+//!
+//! ```rust
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::cipher::{DecryptingKey, EncryptingKey, UnboundCipherKey, AES_128};
+//!
+//! let original_message = "A 32 byte message, pre-padded.\0\0".as_bytes();
+//! let mut in_out_buffer = Vec::from(original_message);
+//!
+//! let key_bytes: &[u8] = &[
+//!     0xff, 0x0b, 0xe5, 0x84, 0x64, 0x0b, 0x00, 0xc8, 0x90, 0x7a, 0x4b, 0xbf, 0x82, 0x7c, 0xb6,
+//!     0xd1,
+//! ];
+//!
+//! let key = UnboundCipherKey::new(&AES_128, key_bytes)?;
+//! let encrypting_key = EncryptingKey::cbc(key)?;
+//! let context = encrypting_key.encrypt(&mut in_out_buffer)?;
+//!
+//! let key = UnboundCipherKey::new(&AES_128, key_bytes)?;
+//! let decrypting_key = DecryptingKey::cbc(key)?;
+//! let plaintext = decrypting_key.decrypt(&mut in_out_buffer, context)?;
+//! assert_eq!(original_message, plaintext);
+//! #
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! ### AES-128 CTR
 //!
 //! ```rust
@@ -121,7 +162,8 @@
 //!
 //! `TryFrom<&DecryptionContext>` is implemented for `&[u8]` allowing immutable references
 //! to IV bytes returned from cipher encryption operations. Note this is implemented as a `TryFrom` as it
-//! may fail for future enum variants that aren't representable as a single slice.
+//! may fail for enum variants that aren't representable as a single slice, such as
+//! `Nonce96Counter32`, whose block counter would otherwise be lost.
 //!
 //! ```rust
 //! # use std::error::Error;
@@ -139,26 +181,78 @@
 
 pub(crate) mod aes;
 pub(crate) mod block;
+mod builder;
+#[cfg(not(feature = "fips"))]
+mod ccm;
 pub(crate) mod chacha;
+pub mod cmac;
+#[cfg(not(feature = "approved-only"))]
+pub mod ecb;
+mod error;
+mod gcm;
 pub(crate) mod key;
-
+#[cfg(feature = "parallel")]
+mod parallel;
+mod reader;
+pub mod safe;
+mod siv;
+mod streaming;
+pub mod traits;
+pub mod typed;
+mod writer;
+mod xts;
+
+use crate::constant_time;
 use crate::error::Unspecified;
 use crate::fips::indicator_check;
 use crate::hkdf;
 use crate::hkdf::KeyType;
-use crate::iv::{FixedLength, IV_LEN_128_BIT};
-use aws_lc::{AES_cbc_encrypt, AES_ctr128_encrypt, AES_DECRYPT, AES_ENCRYPT, AES_KEY};
+use crate::iv::{FixedLength, IV_LEN_128_BIT, IV_LEN_64_BIT, IV_LEN_96_BIT};
+use crate::ptr::LcPtr;
+use crate::rand::{SecureRandom, SystemRandom};
+#[cfg(not(feature = "approved-only"))]
+use aws_lc::AES_ecb_encrypt;
+use aws_lc::{
+    AES_cbc_encrypt, AES_cfb128_encrypt, AES_ctr128_encrypt, AES_ofb128_encrypt,
+    EVP_CIPHER_CTX_new, EVP_CipherInit_ex, EVP_CipherUpdate, EVP_aes_128_cfb8, EVP_aes_192_cfb8,
+    EVP_aes_256_cfb8, AES_DECRYPT, AES_ENCRYPT, AES_KEY,
+};
+#[cfg(not(feature = "approved-only"))]
+use aws_lc::{DES_cblock, DES_ede3_cbc_encrypt, DES_key_schedule, DES_DECRYPT, DES_ENCRYPT};
 use core::fmt::Debug;
-use core::mem::MaybeUninit;
+use core::hash::{Hash, Hasher};
+use core::ops::DerefMut;
+use core::ptr::null_mut;
 use key::SymmetricCipherKey;
-use zeroize::Zeroize;
+use std::os::raw::c_int;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+pub use builder::{Builder, Decryptor, Encryptor};
+#[cfg(not(feature = "fips"))]
+pub use ccm::{CcmDecryptingKey, CcmEncryptingKey, CcmKey};
+pub use error::CipherError;
+pub use gcm::{GcmDecryptingKey, GcmEncryptingKey, GCM_TAG_LEN};
+pub use reader::DecryptingReader;
+pub use siv::{SivDecryptingKey, SivEncryptingKey, SivKey, SIV_LEN};
+pub use streaming::{BufferUpdate, StreamingDecryptingKey, StreamingEncryptingKey};
+pub use writer::EncryptingWriter;
+pub use xts::{XtsDecryptingKey, XtsEncryptingKey, XTS_TWEAK_LEN};
 
 /// The cipher block padding strategy.
 #[non_exhaustive]
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum PaddingStrategy {
+pub enum PaddingStrategy {
     /// PKCS#7 Padding. ([See RFC 5652](https://datatracker.ietf.org/doc/html/rfc5652#section-6.3))
     PKCS7,
+
+    /// ISO/IEC 7816-4 Padding. A mandatory `0x80` byte followed by zero or more `0x00` bytes.
+    ISO7816,
+
+    /// ANSI X9.23 Padding. Zero or more `0x00` bytes followed by a byte holding the padding length.
+    AnsiX923,
+
+    /// No padding. The data must be a multiple of the block length.
+    None,
 }
 
 impl PaddingStrategy {
@@ -166,25 +260,46 @@ impl PaddingStrategy {
     where
         InOut: AsMut<[u8]> + for<'in_out> Extend<&'in_out u8>,
     {
+        let padding_size = self.padding_len(block_len, in_out.as_mut().len());
+
+        // The padding is built on the stack; `in_out` only allocates if it has to grow.
+        let mut padding_buffer = [0u8; MAX_CIPHER_BLOCK_LEN];
+        let padding = &mut padding_buffer[..padding_size];
+        self.write_padding(padding)?;
+        in_out.extend(padding.iter());
+        Ok(())
+    }
+
+    /// Returns the number of padding bytes that are added to `len` bytes of data.
+    fn padding_len(self, block_len: usize, len: usize) -> usize {
         match self {
-            PaddingStrategy::PKCS7 => {
-                let mut padding_buffer = [0u8; MAX_CIPHER_BLOCK_LEN];
+            PaddingStrategy::None => 0,
+            _ => block_len - len % block_len,
+        }
+    }
 
-                let in_out_len = in_out.as_mut().len();
-                // This implements PKCS#7 padding scheme, used by aws-lc if we were using EVP_CIPHER API's
-                let remainder = in_out_len % block_len;
-                let padding_size = block_len - remainder;
-                let v: u8 = padding_size.try_into().map_err(|_| Unspecified)?;
-                padding_buffer.fill(v);
-                // Possible heap allocation here :(
-                in_out.extend(padding_buffer[0..padding_size].iter());
+    /// Writes the padding to `padding`, which must be [`padding_len`](Self::padding_len) bytes.
+    fn write_padding(self, padding: &mut [u8]) -> Result<(), Unspecified> {
+        let padding_size = padding.len();
+        let padding_len: u8 = padding_size.try_into().map_err(|_| Unspecified)?;
+        match self {
+            // This implements PKCS#7 padding scheme, used by aws-lc if we were using EVP_CIPHER API's
+            PaddingStrategy::PKCS7 => padding.fill(padding_len),
+            PaddingStrategy::ISO7816 => {
+                padding.fill(0);
+                padding[0] = 0x80;
             }
+            PaddingStrategy::AnsiX923 => {
+                padding.fill(0);
+                padding[padding_size - 1] = padding_len;
+            }
+            PaddingStrategy::None => {}
         }
         Ok(())
     }
 
     fn remove_padding(self, block_len: usize, in_out: &mut [u8]) -> Result<&mut [u8], Unspecified> {
-        match self {
+        let final_len = match self {
             PaddingStrategy::PKCS7 => {
                 let block_size: u8 = block_len.try_into().map_err(|_| Unspecified)?;
 
@@ -192,39 +307,145 @@ impl PaddingStrategy {
                     return Err(Unspecified);
                 }
 
+                // Every byte of the last block is examined, and the result is only branched on
+                // once all of them have been, so that the time taken does not depend on where
+                // the padding is malformed.
                 let padding: u8 = in_out[in_out.len() - 1];
-                if padding == 0 || padding > block_size {
+                let mut invalid = ct_is_zero_mask(padding) | ct_lt_mask(block_size, padding);
+                let last_block = &in_out[in_out.len() - block_len..];
+                for (distance, item) in (0..block_size).zip(last_block.iter().rev()) {
+                    invalid |= ct_lt_mask(distance, padding) & !ct_is_zero_mask(*item ^ padding);
+                }
+                if invalid != 0 {
                     return Err(Unspecified);
                 }
 
-                for item in in_out.iter().skip(in_out.len() - padding as usize) {
-                    if *item != padding {
-                        return Err(Unspecified);
-                    }
+                in_out.len() - padding as usize
+            }
+            PaddingStrategy::ISO7816 => {
+                let block_size: u8 = block_len.try_into().map_err(|_| Unspecified)?;
+
+                if in_out.is_empty() || in_out.len() < block_len {
+                    return Err(Unspecified);
+                }
+
+                // The whole of the last block is scanned from the end without branching on its
+                // contents, as for PKCS#7. `found` is set from the last non-zero byte onwards,
+                // which must be the 0x80 marker.
+                let last_block = &in_out[in_out.len() - block_len..];
+                let (mut found, mut invalid, mut marker) = (0u8, 0u8, 0u8);
+                for (index, item) in (0..block_size).zip(last_block.iter()).rev() {
+                    let non_zero = !ct_is_zero_mask(*item);
+                    let first = non_zero & !found;
+                    invalid |= first & !ct_is_zero_mask(*item ^ 0x80);
+                    marker |= first & index;
+                    found |= non_zero;
+                }
+                invalid |= !found;
+                if invalid != 0 {
+                    return Err(Unspecified);
                 }
 
-                let final_len = in_out.len() - padding as usize;
-                Ok(&mut in_out[0..final_len])
+                in_out.len() - block_len + marker as usize
             }
-        }
+            PaddingStrategy::AnsiX923 => {
+                let block_size: u8 = block_len.try_into().map_err(|_| Unspecified)?;
+
+                if in_out.is_empty() || in_out.len() < block_len {
+                    return Err(Unspecified);
+                }
+
+                // As for PKCS#7, but the bytes before the length byte must be zero.
+                let padding: u8 = in_out[in_out.len() - 1];
+                let mut invalid = ct_is_zero_mask(padding) | ct_lt_mask(block_size, padding);
+                let last_block = &in_out[in_out.len() - block_len..];
+                for (distance, item) in (1..block_size).zip(last_block.iter().rev().skip(1)) {
+                    invalid |= ct_lt_mask(distance, padding) & !ct_is_zero_mask(*item);
+                }
+                if invalid != 0 {
+                    return Err(Unspecified);
+                }
+
+                in_out.len() - padding as usize
+            }
+            PaddingStrategy::None => {
+                if in_out.len() % block_len != 0 {
+                    return Err(Unspecified);
+                }
+                in_out.len()
+            }
+        };
+
+        // The padding is plaintext too, so it is not left behind in the caller's buffer.
+        let (plaintext, padding) = in_out.split_at_mut(final_len);
+        padding.zeroize();
+        Ok(plaintext)
     }
 }
 
+/// Returns `0xff` if `a < b`, and `0` otherwise, without branching.
+#[allow(clippy::cast_possible_truncation)]
+fn ct_lt_mask(a: u8, b: u8) -> u8 {
+    (u16::from(a).wrapping_sub(u16::from(b)) >> 8) as u8
+}
+
+/// Returns `0xff` if `a == 0`, and `0` otherwise, without branching.
+#[allow(clippy::cast_possible_truncation)]
+fn ct_is_zero_mask(a: u8) -> u8 {
+    (u16::from(a).wrapping_sub(1) >> 8) as u8
+}
+
 /// The number of bytes in an AES 128-bit key
 pub const AES_128_KEY_LEN: usize = 16;
 
+/// The number of bytes in an AES 192-bit key
+pub const AES_192_KEY_LEN: usize = 24;
+
 /// The number of bytes in an AES 256-bit key
 pub const AES_256_KEY_LEN: usize = 32;
 
+/// The number of bytes in a ChaCha20 256-bit key
+pub const CHACHA20_KEY_LEN: usize = 32;
+
+/// The number of bytes in a three-key Triple DES key
+#[cfg(not(feature = "approved-only"))]
+pub const TRIPLE_DES_3KEY_KEY_LEN: usize = 24;
+
 const MAX_CIPHER_KEY_LEN: usize = AES_256_KEY_LEN;
 
+/// A 128-bit initialization vector (IV), as held by [`EncryptionContext::Iv128`] and
+/// [`DecryptionContext::Iv128`].
+///
+/// Use [`Iv128::as_bytes`] to get the IV bytes, for example to transmit them with the ciphertext.
+pub type Iv128 = FixedLength<IV_LEN_128_BIT>;
+
 /// The number of bytes for an AES-CBC initialization vector (IV)
 pub const AES_CBC_IV_LEN: usize = 16;
 
 /// The number of bytes for an AES-CTR initialization vector (IV)
 pub const AES_CTR_IV_LEN: usize = 16;
+
+/// The number of bytes for an AES-CFB initialization vector (IV)
+pub const AES_CFB_IV_LEN: usize = 16;
+
+/// The number of bytes for an AES-OFB initialization vector (IV)
+pub const AES_OFB_IV_LEN: usize = 16;
 const AES_BLOCK_LEN: usize = 16;
 
+/// The number of bytes for a ChaCha20 nonce
+pub const CHACHA20_NONCE_LEN: usize = IV_LEN_96_BIT;
+const CHACHA20_BLOCK_LEN: usize = 64;
+
+/// The number of bytes ChaCha20 can encrypt under a single nonce before its 32-bit block
+/// counter wraps.
+const CHACHA20_MAX_INPUT_LEN: u64 = (u32::MAX as u64 + 1) * CHACHA20_BLOCK_LEN as u64;
+
+/// The number of bytes for a Triple DES CBC initialization vector (IV)
+#[cfg(not(feature = "approved-only"))]
+pub const TRIPLE_DES_CBC_IV_LEN: usize = IV_LEN_64_BIT;
+#[cfg(not(feature = "approved-only"))]
+const TRIPLE_DES_BLOCK_LEN: usize = 8;
+
 const MAX_CIPHER_BLOCK_LEN: usize = AES_BLOCK_LEN;
 
 /// The cipher operating mode.
@@ -236,6 +457,42 @@ pub enum OperatingMode {
 
     /// Counter (CTR) mode.
     CTR,
+
+    /// 128-bit Cipher Feedback (CFB128) mode.
+    CFB128,
+
+    /// 8-bit Cipher Feedback (CFB8) mode.
+    CFB8,
+
+    /// Output Feedback (OFB) mode.
+    OFB,
+
+    /// Electronic Codebook (ECB) mode.
+    ///
+    /// ⚠️ ECB mode is not semantically secure. Identical plaintext blocks encrypt to identical
+    /// ciphertext blocks, so this mode must only be used where a protocol requires the raw block
+    /// cipher.
+    ///
+    /// Not available with the `approved-only` feature.
+    #[doc(hidden)]
+    #[cfg(not(feature = "approved-only"))]
+    ECB,
+
+    /// Stream mode, for algorithms that are stream ciphers.
+    Stream,
+}
+
+impl OperatingMode {
+    /// Whether the mode only processes whole blocks, so its input must be a multiple of the
+    /// block length.
+    fn is_block_aligned(self) -> bool {
+        match self {
+            OperatingMode::CBC => true,
+            #[cfg(not(feature = "approved-only"))]
+            OperatingMode::ECB => true,
+            _ => false,
+        }
+    }
 }
 
 macro_rules! define_cipher_context {
@@ -245,30 +502,203 @@ macro_rules! define_cipher_context {
         pub enum $name {
             /// A 128-bit Initialization Vector.
             Iv128(FixedLength<IV_LEN_128_BIT>),
+
+            /// A 64-bit Initialization Vector.
+            Iv64(FixedLength<IV_LEN_64_BIT>),
+
+            /// A 96-bit nonce.
+            Nonce96(FixedLength<IV_LEN_96_BIT>),
+
+            /// A 96-bit nonce and the initial 32-bit block counter.
+            Nonce96Counter32(FixedLength<IV_LEN_96_BIT>, u32),
+
+            /// No input to the cipher mode.
+            None,
+        }
+
+        impl $name {
+            // The IV or nonce of the context, without the block counter of a
+            // `Nonce96Counter32` context.
+            fn iv_or_nonce(&self) -> Result<&[u8], Unspecified> {
+                match self {
+                    $name::Iv128(iv) => Ok(iv.as_ref()),
+                    $name::Iv64(iv) => Ok(iv.as_ref()),
+                    $name::Nonce96(nonce) | $name::Nonce96Counter32(nonce, _) => Ok(nonce.as_ref()),
+                    $name::None => Err(Unspecified),
+                }
+            }
+
+            /// Returns a CTR mode context whose counter block is `nonce` followed by
+            /// `initial_counter` as a 32-bit big-endian integer, the layout used by many
+            /// protocols that combine a 96-bit nonce with a block counter starting at 0 or 1.
+            ///
+            /// Only the last 32 bits of the counter block are incremented between blocks. The
+            /// counter never wraps around into the nonce: processing more than
+            /// `2^32 - initial_counter` blocks with this context is an error, rather than
+            /// repeating keystream that has already been used.
+            #[must_use]
+            pub fn ctr_nonce_counter(nonce: &[u8; IV_LEN_96_BIT], initial_counter: u32) -> Self {
+                $name::Nonce96Counter32(FixedLength::from(*nonce), initial_counter)
+            }
+
+            /// Returns the context for a CTR mode operation that starts `blocks` blocks into the
+            /// keystream of this context. The IV of an `Iv128` context is incremented as a
+            /// 128-bit big-endian counter, as it is between blocks during encryption, and the
+            /// counter of a `Nonce96Counter32` context as a 32-bit counter.
+            ///
+            /// This allows a long CTR mode stream to be resumed part way through, rather than
+            /// from the beginning. To resume at an offset that is not a multiple of the block
+            /// length, use [`StreamingEncryptingKey::less_safe_resume`] or
+            /// [`StreamingDecryptingKey::resume`].
+            ///
+            /// # Errors
+            /// * [`Unspecified`]: Returned if the context is not an `Iv128` or a
+            ///   `Nonce96Counter32`, or if the counter would wrap around.
+            pub fn advanced_by_blocks(&self, blocks: u64) -> Result<Self, Unspecified> {
+                match self {
+                    $name::Iv128(iv) => {
+                        let counter = u128::from_be_bytes(*iv.as_bytes())
+                            .checked_add(u128::from(blocks))
+                            .ok_or(Unspecified)?;
+                        Ok($name::Iv128(FixedLength::from(counter.to_be_bytes())))
+                    }
+                    $name::Nonce96Counter32(nonce, counter) => {
+                        let counter = u32::try_from(blocks)
+                            .ok()
+                            .and_then(|blocks| counter.checked_add(blocks))
+                            .ok_or(Unspecified)?;
+                        Ok($name::Nonce96Counter32(
+                            FixedLength::from(*nonce.as_bytes()),
+                            counter,
+                        ))
+                    }
+                    _ => Err(Unspecified),
+                }
+            }
         }
 
         impl<'a> TryFrom<&'a $name> for &'a [u8] {
             type Error = Unspecified;
 
+            // A `Nonce96Counter32` context is refused, as its block counter would be lost.
             fn try_from(value: &'a $name) -> Result<Self, Unspecified> {
                 match value {
-                    $name::Iv128(iv) => Ok(iv.as_ref()),
+                    $name::Nonce96Counter32(_, _) => Err(Unspecified),
+                    value => value.iv_or_nonce(),
                 }
             }
         }
 
+        // The variant is printed without the IV or nonce it holds.
         impl Debug for $name {
             fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                 match self {
                     Self::Iv128(_) => write!(f, "Iv128"),
+                    Self::Iv64(_) => write!(f, "Iv64"),
+                    Self::Nonce96(_) => write!(f, "Nonce96"),
+                    Self::Nonce96Counter32(_, _) => write!(f, "Nonce96Counter32"),
+                    Self::None => write!(f, "None"),
+                }
+            }
+        }
+
+        impl Zeroize for $name {
+            fn zeroize(&mut self) {
+                match self {
+                    Self::Iv128(iv) => iv.zeroize(),
+                    Self::Iv64(iv) => iv.zeroize(),
+                    Self::Nonce96(nonce) => nonce.zeroize(),
+                    Self::Nonce96Counter32(nonce, counter) => {
+                        nonce.zeroize();
+                        counter.zeroize();
+                    }
+                    Self::None => {}
+                }
+            }
+        }
+
+        // The IV or nonce held by each variant is zeroized when it is dropped.
+        impl ZeroizeOnDrop for $name {}
+
+        impl<const L: usize> TryFrom<&$name> for [u8; L] {
+            type Error = Unspecified;
+
+            fn try_from(value: &$name) -> Result<Self, Unspecified> {
+                let bytes: &[u8] = value.try_into()?;
+                bytes.try_into().map_err(|_| Unspecified)
+            }
+        }
+
+        impl TryFrom<&$name> for Vec<u8> {
+            type Error = Unspecified;
+
+            fn try_from(value: &$name) -> Result<Self, Unspecified> {
+                let bytes: &[u8] = value.try_into()?;
+                Ok(bytes.to_vec())
+            }
+        }
+
+        impl From<FixedLength<IV_LEN_128_BIT>> for $name {
+            fn from(value: FixedLength<IV_LEN_128_BIT>) -> Self {
+                $name::Iv128(value)
+            }
+        }
+
+        impl From<FixedLength<IV_LEN_64_BIT>> for $name {
+            fn from(value: FixedLength<IV_LEN_64_BIT>) -> Self {
+                $name::Iv64(value)
+            }
+        }
+
+        impl From<FixedLength<IV_LEN_96_BIT>> for $name {
+            fn from(value: FixedLength<IV_LEN_96_BIT>) -> Self {
+                $name::Nonce96(value)
+            }
+        }
+
+        // The variant is chosen by the length of the slice: 16 bytes for `Iv128`, 12 bytes for
+        // `Nonce96` and 8 bytes for `Iv64`.
+        impl TryFrom<&[u8]> for $name {
+            type Error = Unspecified;
+
+            fn try_from(value: &[u8]) -> Result<Self, Unspecified> {
+                match value.len() {
+                    IV_LEN_128_BIT => Ok($name::Iv128(FixedLength::try_from(value)?)),
+                    IV_LEN_96_BIT => Ok($name::Nonce96(FixedLength::try_from(value)?)),
+                    IV_LEN_64_BIT => Ok($name::Iv64(FixedLength::try_from(value)?)),
+                    _ => Err(Unspecified),
                 }
             }
         }
 
+        impl From<[u8; IV_LEN_128_BIT]> for $name {
+            fn from(value: [u8; IV_LEN_128_BIT]) -> Self {
+                $name::Iv128(FixedLength::from(value))
+            }
+        }
+
+        impl From<[u8; IV_LEN_64_BIT]> for $name {
+            fn from(value: [u8; IV_LEN_64_BIT]) -> Self {
+                $name::Iv64(FixedLength::from(value))
+            }
+        }
+
+        impl From<[u8; IV_LEN_96_BIT]> for $name {
+            fn from(value: [u8; IV_LEN_96_BIT]) -> Self {
+                $name::Nonce96(FixedLength::from(value))
+            }
+        }
+
         impl From<$other> for $name {
             fn from(value: $other) -> Self {
                 match value {
                     $other::Iv128(iv) => $name::Iv128(iv),
+                    $other::Iv64(iv) => $name::Iv64(iv),
+                    $other::Nonce96(nonce) => $name::Nonce96(nonce),
+                    $other::Nonce96Counter32(nonce, counter) => {
+                        $name::Nonce96Counter32(nonce, counter)
+                    }
+                    $other::None => $name::None,
                 }
             }
         }
@@ -278,15 +708,234 @@ macro_rules! define_cipher_context {
 define_cipher_context!(EncryptionContext, DecryptionContext);
 define_cipher_context!(DecryptionContext, EncryptionContext);
 
+// IVs are compared in constant time so that comparing a received context against known ones
+// does not reveal how many leading bytes matched.
+impl PartialEq for DecryptionContext {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Iv128(a), Self::Iv128(b)) => {
+                constant_time::verify_slices_are_equal(a.as_ref(), b.as_ref()).is_ok()
+            }
+            (Self::Iv64(a), Self::Iv64(b)) => {
+                constant_time::verify_slices_are_equal(a.as_ref(), b.as_ref()).is_ok()
+            }
+            (Self::Nonce96(a), Self::Nonce96(b)) => {
+                constant_time::verify_slices_are_equal(a.as_ref(), b.as_ref()).is_ok()
+            }
+            (Self::Nonce96Counter32(a, a_counter), Self::Nonce96Counter32(b, b_counter)) => {
+                let nonces_equal =
+                    constant_time::verify_slices_are_equal(a.as_ref(), b.as_ref()).is_ok();
+                nonces_equal & (a_counter == b_counter)
+            }
+            (Self::None, Self::None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for DecryptionContext {}
+
+impl Hash for DecryptionContext {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        core::mem::discriminant(self).hash(state);
+        match self {
+            Self::Iv128(iv) => iv.as_ref().hash(state),
+            Self::Iv64(iv) => iv.as_ref().hash(state),
+            Self::Nonce96(nonce) => nonce.as_ref().hash(state),
+            Self::Nonce96Counter32(nonce, counter) => {
+                nonce.as_ref().hash(state);
+                counter.hash(state);
+            }
+            Self::None => {}
+        }
+    }
+}
+
+/// Serialized as an enum whose variant holds the IV or nonce bytes, and the counter for
+/// `Nonce96Counter32`.
+///
+/// The IV or nonce is not secret: it is meant to be stored or transmitted alongside the
+/// ciphertext, and is needed to decrypt it. It must still never be reused to encrypt other data
+/// with the same key.
+#[cfg(feature = "serde")]
+impl serde::Serialize for DecryptionContext {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use crate::serialization::Bytes;
+        use serde::ser::SerializeTupleVariant;
+
+        const NAME: &str = "DecryptionContext";
+        match self {
+            Self::Iv128(iv) => {
+                serializer.serialize_newtype_variant(NAME, 0, "Iv128", &Bytes(iv.as_ref()))
+            }
+            Self::Iv64(iv) => {
+                serializer.serialize_newtype_variant(NAME, 1, "Iv64", &Bytes(iv.as_ref()))
+            }
+            Self::Nonce96(nonce) => {
+                serializer.serialize_newtype_variant(NAME, 2, "Nonce96", &Bytes(nonce.as_ref()))
+            }
+            Self::Nonce96Counter32(nonce, counter) => {
+                let mut state =
+                    serializer.serialize_tuple_variant(NAME, 3, "Nonce96Counter32", 2)?;
+                state.serialize_field(&Bytes(nonce.as_ref()))?;
+                state.serialize_field(counter)?;
+                state.end()
+            }
+            Self::None => serializer.serialize_unit_variant(NAME, 4, "None"),
+        }
+    }
+}
+
+/// Rejects unknown variants and IVs or nonces of the wrong length.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for DecryptionContext {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_enum(
+            "DecryptionContext",
+            context_serde::VARIANTS,
+            context_serde::ContextVisitor,
+        )
+    }
+}
+
+#[cfg(feature = "serde")]
+mod context_serde {
+    use super::DecryptionContext;
+    use crate::iv::FixedLength;
+    use crate::serialization::ByteBuf;
+    use core::fmt;
+    use serde::de::{self, EnumAccess, SeqAccess, VariantAccess, Visitor};
+    use serde::{Deserialize, Deserializer};
+
+    pub(super) const VARIANTS: &[&str] = &["Iv128", "Iv64", "Nonce96", "Nonce96Counter32", "None"];
+
+    enum Variant {
+        Iv128,
+        Iv64,
+        Nonce96,
+        Nonce96Counter32,
+        None,
+    }
+
+    struct VariantVisitor;
+
+    impl<'de> Visitor<'de> for VariantVisitor {
+        type Value = Variant;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a DecryptionContext variant")
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            match v {
+                0 => Ok(Variant::Iv128),
+                1 => Ok(Variant::Iv64),
+                2 => Ok(Variant::Nonce96),
+                3 => Ok(Variant::Nonce96Counter32),
+                4 => Ok(Variant::None),
+                _ => Err(E::invalid_value(de::Unexpected::Unsigned(v), &self)),
+            }
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            match v {
+                "Iv128" => Ok(Variant::Iv128),
+                "Iv64" => Ok(Variant::Iv64),
+                "Nonce96" => Ok(Variant::Nonce96),
+                "Nonce96Counter32" => Ok(Variant::Nonce96Counter32),
+                "None" => Ok(Variant::None),
+                _ => Err(E::unknown_variant(v, VARIANTS)),
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Variant {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_identifier(VariantVisitor)
+        }
+    }
+
+    fn fixed_length<const L: usize, E: de::Error>(bytes: &[u8]) -> Result<FixedLength<L>, E> {
+        FixedLength::try_from(bytes)
+            .map_err(|_| E::invalid_length(bytes.len(), &"the length of the IV or nonce"))
+    }
+
+    struct NonceAndCounterVisitor;
+
+    impl<'de> Visitor<'de> for NonceAndCounterVisitor {
+        type Value = DecryptionContext;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a nonce and a counter")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let nonce: ByteBuf = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            let counter: u32 = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+            Ok(DecryptionContext::Nonce96Counter32(
+                fixed_length(&nonce.0)?,
+                counter,
+            ))
+        }
+    }
+
+    pub(super) struct ContextVisitor;
+
+    impl<'de> Visitor<'de> for ContextVisitor {
+        type Value = DecryptionContext;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("enum DecryptionContext")
+        }
+
+        fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+            let (variant, access) = data.variant::<Variant>()?;
+            match variant {
+                Variant::Iv128 => {
+                    let iv: ByteBuf = access.newtype_variant()?;
+                    Ok(DecryptionContext::Iv128(fixed_length(&iv.0)?))
+                }
+                Variant::Iv64 => {
+                    let iv: ByteBuf = access.newtype_variant()?;
+                    Ok(DecryptionContext::Iv64(fixed_length(&iv.0)?))
+                }
+                Variant::Nonce96 => {
+                    let nonce: ByteBuf = access.newtype_variant()?;
+                    Ok(DecryptionContext::Nonce96(fixed_length(&nonce.0)?))
+                }
+                Variant::Nonce96Counter32 => access.tuple_variant(2, NonceAndCounterVisitor),
+                Variant::None => {
+                    access.unit_variant()?;
+                    Ok(DecryptionContext::None)
+                }
+            }
+        }
+    }
+}
+
 #[non_exhaustive]
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 /// Cipher algorithm identifier.
 pub enum AlgorithmId {
     /// AES 128-bit
     Aes128,
 
+    /// AES 192-bit
+    Aes192,
+
     /// AES 256-bit
     Aes256,
+
+    /// ChaCha20
+    Chacha20,
+
+    /// Three-key Triple DES
+    #[cfg(not(feature = "approved-only"))]
+    TripleDes3Key,
 }
 
 /// A cipher algorithm.
@@ -304,6 +953,13 @@ pub static AES_128: Algorithm = Algorithm {
     block_len: AES_BLOCK_LEN,
 };
 
+/// AES 192-bit cipher
+pub static AES_192: Algorithm = Algorithm {
+    id: AlgorithmId::Aes192,
+    key_len: AES_192_KEY_LEN,
+    block_len: AES_BLOCK_LEN,
+};
+
 /// AES 256-bit cipher
 pub static AES_256: Algorithm = Algorithm {
     id: AlgorithmId::Aes256,
@@ -311,44 +967,174 @@ pub static AES_256: Algorithm = Algorithm {
     block_len: AES_BLOCK_LEN,
 };
 
+/// ChaCha20 stream cipher
+pub static CHACHA20: Algorithm = Algorithm {
+    id: AlgorithmId::Chacha20,
+    key_len: CHACHA20_KEY_LEN,
+    block_len: CHACHA20_BLOCK_LEN,
+};
+
+/// Three-key Triple DES (DES-EDE3) block cipher.
+///
+/// Not available with the `approved-only` feature.
+///
+/// ⚠️ Triple DES has a 64-bit block size and is only provided to decrypt or produce data for
+/// legacy systems that require it. Only CBC mode is supported. New protocols should use AES.
+#[cfg(not(feature = "approved-only"))]
+pub static TRIPLE_DES_3KEY_FOR_LEGACY_USE_ONLY: Algorithm = Algorithm {
+    id: AlgorithmId::TripleDes3Key,
+    key_len: TRIPLE_DES_3KEY_KEY_LEN,
+    block_len: TRIPLE_DES_BLOCK_LEN,
+};
+
 impl Algorithm {
-    fn id(&self) -> &AlgorithmId {
+    /// The algorithm identifier.
+    #[inline]
+    #[must_use]
+    pub fn id(&self) -> &AlgorithmId {
         &self.id
     }
 
-    const fn block_len(&self) -> usize {
+    /// The length of the key.
+    #[inline]
+    #[must_use]
+    pub fn key_len(&self) -> usize {
+        self.key_len
+    }
+
+    /// The length of a block. For `CHACHA20`, this is the length of a keystream block.
+    #[inline]
+    #[must_use]
+    pub const fn block_len(&self) -> usize {
         self.block_len
     }
 
+    /// The length of the IV or nonce used by this algorithm with `mode`. Returns `Some(0)` for
+    /// ECB, which uses no IV, and `None` if the algorithm does not support `mode`.
+    #[must_use]
+    pub fn iv_len(&self, mode: OperatingMode) -> Option<usize> {
+        match self.id {
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => match mode {
+                OperatingMode::CBC
+                | OperatingMode::CTR
+                | OperatingMode::CFB128
+                | OperatingMode::CFB8
+                | OperatingMode::OFB => Some(IV_LEN_128_BIT),
+                #[cfg(not(feature = "approved-only"))]
+                OperatingMode::ECB => Some(0),
+                OperatingMode::Stream => None,
+            },
+            AlgorithmId::Chacha20 => match mode {
+                OperatingMode::Stream => Some(CHACHA20_NONCE_LEN),
+                _ => None,
+            },
+            #[cfg(not(feature = "approved-only"))]
+            AlgorithmId::TripleDes3Key => match mode {
+                OperatingMode::CBC => Some(TRIPLE_DES_CBC_IV_LEN),
+                _ => None,
+            },
+        }
+    }
+
     fn new_encryption_context(
         &self,
         mode: OperatingMode,
+    ) -> Result<EncryptionContext, Unspecified> {
+        self.new_encryption_context_with_rng(mode, &SystemRandom::new())
+    }
+
+    fn new_encryption_context_with_rng(
+        &self,
+        mode: OperatingMode,
+        rng: &dyn SecureRandom,
     ) -> Result<EncryptionContext, Unspecified> {
         match self.id {
-            AlgorithmId::Aes128 | AlgorithmId::Aes256 => match mode {
-                OperatingMode::CBC | OperatingMode::CTR => {
-                    Ok(EncryptionContext::Iv128(FixedLength::new()?))
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => match mode {
+                OperatingMode::CBC
+                | OperatingMode::CTR
+                | OperatingMode::CFB128
+                | OperatingMode::CFB8
+                | OperatingMode::OFB => {
+                    Ok(EncryptionContext::Iv128(FixedLength::new_with_rng(rng)?))
                 }
+                #[cfg(not(feature = "approved-only"))]
+                OperatingMode::ECB => Ok(EncryptionContext::None),
+                OperatingMode::Stream => Err(Unspecified),
             },
-        }
+            AlgorithmId::Chacha20 => match mode {
+                OperatingMode::Stream => {
+                    Ok(EncryptionContext::Nonce96(FixedLength::new_with_rng(rng)?))
+                }
+                _ => Err(Unspecified),
+            },
+            #[cfg(not(feature = "approved-only"))]
+            AlgorithmId::TripleDes3Key => match mode {
+                OperatingMode::CBC => Ok(EncryptionContext::Iv64(FixedLength::new_with_rng(rng)?)),
+                _ => Err(Unspecified),
+            },
+        }
     }
 
     fn is_valid_encryption_context(&self, mode: OperatingMode, input: &EncryptionContext) -> bool {
         match self.id {
-            AlgorithmId::Aes128 | AlgorithmId::Aes256 => match mode {
-                OperatingMode::CBC | OperatingMode::CTR => {
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => match mode {
+                OperatingMode::CBC
+                | OperatingMode::CFB128
+                | OperatingMode::CFB8
+                | OperatingMode::OFB => {
                     matches!(input, EncryptionContext::Iv128(_))
                 }
+                OperatingMode::CTR => matches!(
+                    input,
+                    EncryptionContext::Iv128(_) | EncryptionContext::Nonce96Counter32(_, _)
+                ),
+                #[cfg(not(feature = "approved-only"))]
+                OperatingMode::ECB => matches!(input, EncryptionContext::None),
+                OperatingMode::Stream => false,
+            },
+            AlgorithmId::Chacha20 => match mode {
+                OperatingMode::Stream => matches!(
+                    input,
+                    EncryptionContext::Nonce96(_) | EncryptionContext::Nonce96Counter32(_, _)
+                ),
+                _ => false,
+            },
+            #[cfg(not(feature = "approved-only"))]
+            AlgorithmId::TripleDes3Key => match mode {
+                OperatingMode::CBC => matches!(input, EncryptionContext::Iv64(_)),
+                _ => false,
             },
         }
     }
 
     fn is_valid_decryption_context(&self, mode: OperatingMode, input: &DecryptionContext) -> bool {
         match self.id {
-            AlgorithmId::Aes128 | AlgorithmId::Aes256 => match mode {
-                OperatingMode::CBC | OperatingMode::CTR => {
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => match mode {
+                OperatingMode::CBC
+                | OperatingMode::CFB128
+                | OperatingMode::CFB8
+                | OperatingMode::OFB => {
                     matches!(input, DecryptionContext::Iv128(_))
                 }
+                OperatingMode::CTR => matches!(
+                    input,
+                    DecryptionContext::Iv128(_) | DecryptionContext::Nonce96Counter32(_, _)
+                ),
+                #[cfg(not(feature = "approved-only"))]
+                OperatingMode::ECB => matches!(input, DecryptionContext::None),
+                OperatingMode::Stream => false,
+            },
+            AlgorithmId::Chacha20 => match mode {
+                OperatingMode::Stream => matches!(
+                    input,
+                    DecryptionContext::Nonce96(_) | DecryptionContext::Nonce96Counter32(_, _)
+                ),
+                _ => false,
+            },
+            #[cfg(not(feature = "approved-only"))]
+            AlgorithmId::TripleDes3Key => match mode {
+                OperatingMode::CBC => matches!(input, DecryptionContext::Iv64(_)),
+                _ => false,
             },
         }
     }
@@ -363,9 +1149,21 @@ impl Debug for UnboundCipherKey {
     }
 }
 
+impl Clone for UnboundCipherKey {
+    fn clone(&self) -> Self {
+        UnboundCipherKey {
+            algorithm: self.algorithm,
+            key: self.key.clone(),
+            key_bytes: self.key_bytes.clone(),
+        }
+    }
+}
+
+impl ZeroizeOnDrop for UnboundCipherKey {}
+
 impl From<hkdf::Okm<'_, &'static Algorithm>> for UnboundCipherKey {
     fn from(okm: hkdf::Okm<&'static Algorithm>) -> Self {
-        let mut key_bytes = [0; MAX_CIPHER_KEY_LEN];
+        let mut key_bytes = Zeroizing::new([0; MAX_CIPHER_KEY_LEN]);
         let key_bytes = &mut key_bytes[..okm.len().key_len];
         let algorithm = *okm.len();
         okm.fill(key_bytes).unwrap();
@@ -380,9 +1178,12 @@ impl KeyType for &'static Algorithm {
 }
 
 /// A key bound to a particular cipher algorithm.
+///
+/// The key bytes and the expanded key schedule are zeroized when the key is dropped.
 pub struct UnboundCipherKey {
     algorithm: &'static Algorithm,
     key: SymmetricCipherKey,
+    key_bytes: Zeroizing<[u8; MAX_CIPHER_KEY_LEN]>,
 }
 
 impl UnboundCipherKey {
@@ -395,9 +1196,81 @@ impl UnboundCipherKey {
     pub fn new(algorithm: &'static Algorithm, key_bytes: &[u8]) -> Result<Self, Unspecified> {
         let key = match algorithm.id() {
             AlgorithmId::Aes128 => SymmetricCipherKey::aes128(key_bytes),
+            AlgorithmId::Aes192 => SymmetricCipherKey::aes192(key_bytes),
             AlgorithmId::Aes256 => SymmetricCipherKey::aes256(key_bytes),
+            AlgorithmId::Chacha20 => SymmetricCipherKey::chacha20(key_bytes),
+            #[cfg(not(feature = "approved-only"))]
+            AlgorithmId::TripleDes3Key => SymmetricCipherKey::triple_des(key_bytes),
         }?;
-        Ok(UnboundCipherKey { algorithm, key })
+        Ok(Self::from_parts(algorithm, key, key_bytes))
+    }
+
+    /// Constructs an [`UnboundCipherKey`] for encryption, skipping the AES decryption key
+    /// schedule. This roughly halves the cost of constructing an AES key that is only used
+    /// to encrypt, or to decrypt in a mode that only uses the forward cipher, such as CTR.
+    ///
+    /// The key can still be used for decryption in any mode: the decryption key schedule is
+    /// computed when a decrypting key that needs it, such as a CBC `DecryptingKey`, is
+    /// constructed from this key.
+    ///
+    /// # Errors
+    ///
+    /// * [`Unspecified`] if `key_bytes.len()` does not match the
+    ///   length required by `algorithm`.
+    pub fn new_encrypt_only(
+        algorithm: &'static Algorithm,
+        key_bytes: &[u8],
+    ) -> Result<Self, Unspecified> {
+        match algorithm.id() {
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => {
+                if key_bytes.len() != algorithm.key_len() {
+                    return Err(Unspecified);
+                }
+                let key = SymmetricCipherKey::aes(key_bytes, false)?;
+                Ok(Self::from_parts(algorithm, key, key_bytes))
+            }
+            AlgorithmId::Chacha20 => Self::new(algorithm, key_bytes),
+            #[cfg(not(feature = "approved-only"))]
+            AlgorithmId::TripleDes3Key => Self::new(algorithm, key_bytes),
+        }
+    }
+
+    fn from_parts(
+        algorithm: &'static Algorithm,
+        key: SymmetricCipherKey,
+        key_bytes: &[u8],
+    ) -> Self {
+        let mut stored_key_bytes = Zeroizing::new([0u8; MAX_CIPHER_KEY_LEN]);
+        stored_key_bytes[..key_bytes.len()].copy_from_slice(key_bytes);
+        UnboundCipherKey {
+            algorithm,
+            key,
+            key_bytes: stored_key_bytes,
+        }
+    }
+
+    /// Ensures that the AES decryption key schedule is present, computing it if the key was
+    /// constructed with [`new_encrypt_only`](Self::new_encrypt_only).
+    pub(crate) fn with_decrypt_key(mut self) -> Result<Self, Unspecified> {
+        let key_len = self.algorithm.key_len();
+        self.key.add_decrypt_key(&self.key_bytes[..key_len])?;
+        Ok(self)
+    }
+
+    /// Generates a new random key for `algorithm`. The key bytes are never exposed to the
+    /// caller and the intermediate buffer is zeroized.
+    ///
+    /// # Errors
+    ///
+    /// * [`Unspecified`] if `rng` fails.
+    pub fn generate(
+        algorithm: &'static Algorithm,
+        rng: &dyn SecureRandom,
+    ) -> Result<Self, Unspecified> {
+        let mut key_bytes = Zeroizing::new([0u8; MAX_CIPHER_KEY_LEN]);
+        let key_bytes = &mut key_bytes[..algorithm.key_len()];
+        rng.fill(key_bytes)?;
+        Self::new(algorithm, key_bytes)
     }
 
     #[inline]
@@ -406,9 +1279,33 @@ impl UnboundCipherKey {
     pub fn algorithm(&self) -> &'static Algorithm {
         self.algorithm
     }
+
+    #[inline]
+    #[must_use]
+    /// Returns the length of this key in bytes.
+    pub fn key_len(&self) -> usize {
+        self.algorithm.key_len()
+    }
+
+    /// Returns a copy of the raw key material, which is zeroized when dropped.
+    ///
+    /// # ⚠️ Exposes the secret key
+    ///
+    /// Anyone who obtains these bytes can decrypt everything encrypted under this key, and
+    /// forge data for any mode that does not authenticate it. This is only intended for
+    /// exporting the key to secure storage, for example wrapping it for an HSM or a key backup.
+    /// Do not log the bytes or keep them longer than necessary, and do not copy them out of the
+    /// returned buffer, as such copies are not zeroized.
+    #[must_use]
+    pub fn less_safe_key_bytes(&self) -> Zeroizing<Vec<u8>> {
+        Zeroizing::new(self.key_bytes[..self.key_len()].to_vec())
+    }
 }
 
 /// A cipher encryption key that performs block padding.
+///
+/// Dropping the key zeroizes the raw key bytes and the AES or Triple DES encryption key
+/// schedule held by its [`UnboundCipherKey`].
 pub struct PaddedBlockEncryptingKey {
     key: UnboundCipherKey,
     mode: OperatingMode,
@@ -422,6 +1319,7 @@ impl PaddedBlockEncryptingKey {
     // # FIPS
     // Use this function with an `UnboundCipherKey` constructed with one of the following algorithms:
     // * `AES_128`
+    // * `AES_192`
     // * `AES_256`
     //
     /// # Errors
@@ -430,6 +1328,51 @@ impl PaddedBlockEncryptingKey {
         PaddedBlockEncryptingKey::new(key, OperatingMode::CBC, PaddingStrategy::PKCS7)
     }
 
+    /// Constructs a new `PaddedBlockEncryptingKey` cipher with chaining block cipher (CBC) mode.
+    /// Plaintext data is padded following the ISO/IEC 7816-4 scheme.
+    ///
+    // # FIPS
+    // Use this function with an `UnboundCipherKey` constructed with one of the following algorithms:
+    // * `AES_128`
+    // * `AES_192`
+    // * `AES_256`
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing a `PaddedBlockEncryptingKey`.
+    pub fn cbc_iso7816(key: UnboundCipherKey) -> Result<PaddedBlockEncryptingKey, Unspecified> {
+        PaddedBlockEncryptingKey::new(key, OperatingMode::CBC, PaddingStrategy::ISO7816)
+    }
+
+    /// Constructs a new `PaddedBlockEncryptingKey` cipher with chaining block cipher (CBC) mode.
+    /// Plaintext data is padded following the ANSI X9.23 scheme.
+    ///
+    // # FIPS
+    // Use this function with an `UnboundCipherKey` constructed with one of the following algorithms:
+    // * `AES_128`
+    // * `AES_192`
+    // * `AES_256`
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing a `PaddedBlockEncryptingKey`.
+    pub fn cbc_ansi_x923(key: UnboundCipherKey) -> Result<PaddedBlockEncryptingKey, Unspecified> {
+        PaddedBlockEncryptingKey::new(key, OperatingMode::CBC, PaddingStrategy::AnsiX923)
+    }
+
+    /// Constructs a new `PaddedBlockEncryptingKey` cipher with chaining block cipher (CBC) mode.
+    /// Plaintext data is not padded, so it must be a multiple of the block length.
+    ///
+    // # FIPS
+    // Use this function with an `UnboundCipherKey` constructed with one of the following algorithms:
+    // * `AES_128`
+    // * `AES_192`
+    // * `AES_256`
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing a `PaddedBlockEncryptingKey`.
+    pub fn cbc_no_padding(key: UnboundCipherKey) -> Result<PaddedBlockEncryptingKey, Unspecified> {
+        PaddedBlockEncryptingKey::new(key, OperatingMode::CBC, PaddingStrategy::None)
+    }
+
     #[allow(clippy::unnecessary_wraps)]
     fn new(
         key: UnboundCipherKey,
@@ -464,6 +1407,29 @@ impl PaddedBlockEncryptingKey {
         self.less_safe_encrypt(in_out, context)
     }
 
+    /// Pads and encrypts data provided in `in_out` in-place, using an IV generated by `rng`.
+    /// Returns the context needed for decryption.
+    ///
+    /// `encrypt` should be preferred. This is for callers that must generate the IV with a
+    /// particular random number generator, or reproducibly in tests.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `rng` fails, or if encryption fails.
+    pub fn encrypt_with_rng<InOut>(
+        &self,
+        in_out: &mut InOut,
+        rng: &dyn SecureRandom,
+    ) -> Result<DecryptionContext, Unspecified>
+    where
+        InOut: AsMut<[u8]> + for<'a> Extend<&'a u8>,
+    {
+        let context = self
+            .key
+            .algorithm
+            .new_encryption_context_with_rng(self.mode, rng)?;
+        self.less_safe_encrypt(in_out, context)
+    }
+
     /// Pads and encrypts data provided in `in_out` in-place.
     /// Returns a references to the encryted data.
     ///
@@ -489,6 +1455,78 @@ impl PaddedBlockEncryptingKey {
             .add_padding(self.algorithm().block_len(), in_out)?;
         encrypt(&self.key, self.mode, in_out.as_mut(), context)
     }
+
+    /// Pads and encrypts the data provided in `in_out` in-place, and prefixes it with the IV,
+    /// so that `in_out` holds `IV || ciphertext`.
+    ///
+    /// The result can be decrypted with [`PaddedBlockDecryptingKey::decrypt_with_prefixed_iv`].
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if the key's algorithm and mode do not use a 128-bit IV,
+    ///   or if encryption fails.
+    pub fn encrypt_with_prefixed_iv<InOut>(&self, in_out: &mut InOut) -> Result<(), Unspecified>
+    where
+        InOut: AsMut<[u8]> + for<'a> Extend<&'a u8>,
+    {
+        let context = self.key.algorithm.new_encryption_context(self.mode)?;
+        if !matches!(context, EncryptionContext::Iv128(_)) {
+            return Err(Unspecified);
+        }
+        let context = self.less_safe_encrypt(in_out, context)?;
+        prefix_iv(in_out, &context)
+    }
+
+    /// Pads and encrypts `plaintext` into a new `Vec`.
+    /// Returns the ciphertext and the context needed for decryption.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if encryption fails.
+    pub fn encrypt_to_vec(
+        &self,
+        plaintext: &[u8],
+    ) -> Result<(Vec<u8>, DecryptionContext), Unspecified> {
+        let mut in_out = Vec::with_capacity(self.ciphertext_len(plaintext.len()));
+        in_out.extend_from_slice(plaintext);
+        let context = self.encrypt(&mut in_out).map_err(|err| {
+            in_out.zeroize();
+            err
+        })?;
+        Ok((in_out, context))
+    }
+
+    /// Returns the length of the ciphertext for `plaintext_len` bytes of plaintext, which is
+    /// the length of the padded plaintext.
+    #[must_use]
+    pub fn ciphertext_len(&self, plaintext_len: usize) -> usize {
+        let block_len = self.algorithm().block_len();
+        plaintext_len + self.padding.padding_len(block_len, plaintext_len)
+    }
+
+    /// Pads and encrypts `input`, writing the ciphertext to the start of `output`.
+    /// Returns the context needed for decryption and a reference to the ciphertext, which is
+    /// [`ciphertext_len`](Self::ciphertext_len) bytes long.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `output` is shorter than the ciphertext, or if encryption
+    ///   fails.
+    pub fn encrypt_into<'out>(
+        &self,
+        input: &[u8],
+        output: &'out mut [u8],
+    ) -> Result<(DecryptionContext, &'out mut [u8]), Unspecified> {
+        let block_len = self.algorithm().block_len();
+        let padding_size = self.padding.padding_len(block_len, input.len());
+        let out_len = input.len().checked_add(padding_size).ok_or(Unspecified)?;
+        let output = output.get_mut(..out_len).ok_or(Unspecified)?;
+
+        let (plaintext, padding) = output.split_at_mut(input.len());
+        plaintext.copy_from_slice(input);
+        self.padding.write_padding(padding)?;
+
+        let context = self.key.algorithm.new_encryption_context(self.mode)?;
+        let context = encrypt(&self.key, self.mode, output, context)?;
+        Ok((context, output))
+    }
 }
 
 impl Debug for PaddedBlockEncryptingKey {
@@ -501,7 +1539,12 @@ impl Debug for PaddedBlockEncryptingKey {
     }
 }
 
+impl ZeroizeOnDrop for PaddedBlockEncryptingKey {}
+
 /// A cipher decryption key that performs block padding.
+///
+/// Dropping the key zeroizes the raw key bytes together with the encryption and decryption key
+/// schedules held by its [`UnboundCipherKey`].
 pub struct PaddedBlockDecryptingKey {
     key: UnboundCipherKey,
     mode: OperatingMode,
@@ -515,6 +1558,7 @@ impl PaddedBlockDecryptingKey {
     // # FIPS
     // Use this function with an `UnboundCipherKey` constructed with one of the following algorithms:
     // * `AES_128`
+    // * `AES_192`
     // * `AES_256`
     //
     /// # Errors
@@ -523,13 +1567,61 @@ impl PaddedBlockDecryptingKey {
         PaddedBlockDecryptingKey::new(key, OperatingMode::CBC, PaddingStrategy::PKCS7)
     }
 
-    #[allow(clippy::unnecessary_wraps)]
+    /// Constructs a new `PaddedBlockDecryptingKey` cipher with chaining block cipher (CBC) mode.
+    /// Decrypted data is unpadded following the ISO/IEC 7816-4 scheme.
+    ///
+    // # FIPS
+    // Use this function with an `UnboundCipherKey` constructed with one of the following algorithms:
+    // * `AES_128`
+    // * `AES_192`
+    // * `AES_256`
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `PaddedBlockDecryptingKey`.
+    pub fn cbc_iso7816(key: UnboundCipherKey) -> Result<PaddedBlockDecryptingKey, Unspecified> {
+        PaddedBlockDecryptingKey::new(key, OperatingMode::CBC, PaddingStrategy::ISO7816)
+    }
+
+    /// Constructs a new `PaddedBlockDecryptingKey` cipher with chaining block cipher (CBC) mode.
+    /// Decrypted data is unpadded following the ANSI X9.23 scheme.
+    ///
+    // # FIPS
+    // Use this function with an `UnboundCipherKey` constructed with one of the following algorithms:
+    // * `AES_128`
+    // * `AES_192`
+    // * `AES_256`
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `PaddedBlockDecryptingKey`.
+    pub fn cbc_ansi_x923(key: UnboundCipherKey) -> Result<PaddedBlockDecryptingKey, Unspecified> {
+        PaddedBlockDecryptingKey::new(key, OperatingMode::CBC, PaddingStrategy::AnsiX923)
+    }
+
+    /// Constructs a new `PaddedBlockDecryptingKey` cipher with chaining block cipher (CBC) mode.
+    /// Decrypted data is not unpadded, so the ciphertext must be a multiple of the block length.
+    ///
+    // # FIPS
+    // Use this function with an `UnboundCipherKey` constructed with one of the following algorithms:
+    // * `AES_128`
+    // * `AES_192`
+    // * `AES_256`
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `PaddedBlockDecryptingKey`.
+    pub fn cbc_no_padding(key: UnboundCipherKey) -> Result<PaddedBlockDecryptingKey, Unspecified> {
+        PaddedBlockDecryptingKey::new(key, OperatingMode::CBC, PaddingStrategy::None)
+    }
+
     fn new(
         key: UnboundCipherKey,
         mode: OperatingMode,
         padding: PaddingStrategy,
     ) -> Result<PaddedBlockDecryptingKey, Unspecified> {
-        Ok(PaddedBlockDecryptingKey { key, mode, padding })
+        Ok(PaddedBlockDecryptingKey {
+            key: key.with_decrypt_key()?,
+            mode,
+            padding,
+        })
     }
 
     /// Returns the cipher algorithm.
@@ -568,6 +1660,76 @@ impl PaddedBlockDecryptingKey {
         in_out = padding.remove_padding(block_len, in_out)?;
         Ok(in_out)
     }
+
+    /// Decrypts and unpads the data provided in `in_out` in-place, where `in_out` holds
+    /// `IV || ciphertext` as produced by
+    /// [`PaddedBlockEncryptingKey::encrypt_with_prefixed_iv`]. Returns a reference to the
+    /// decrypted data, which follows the IV in `in_out`.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `in_out` is shorter than the IV, or if decryption fails.
+    pub fn decrypt_with_prefixed_iv<'in_out>(
+        &self,
+        in_out: &'in_out mut [u8],
+    ) -> Result<&'in_out mut [u8], Unspecified> {
+        let (context, ciphertext) = split_prefixed_iv(in_out)?;
+        self.decrypt(ciphertext, context)
+    }
+
+    /// Decrypts and unpads `ciphertext` into a new `Vec`.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if decryption fails, including if the ciphertext is not a
+    ///   multiple of the block length or the padding is invalid.
+    pub fn decrypt_to_vec(
+        &self,
+        ciphertext: &[u8],
+        context: DecryptionContext,
+    ) -> Result<Vec<u8>, Unspecified> {
+        decrypt_to_vec(ciphertext, |in_out| self.decrypt(in_out, context))
+    }
+
+    /// Decrypts and unpads `input`, writing the plaintext to the start of `output`, which must
+    /// be at least as long as `input`. Returns a reference to the plaintext.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `output` is shorter than `input`, or if decryption
+    ///   fails.
+    pub fn decrypt_into<'out>(
+        &self,
+        input: &[u8],
+        output: &'out mut [u8],
+        context: DecryptionContext,
+    ) -> Result<&'out mut [u8], Unspecified> {
+        let output = output.get_mut(..input.len()).ok_or(Unspecified)?;
+        output.copy_from_slice(input);
+        self.decrypt(output, context)
+    }
+
+    /// Decrypts data provided in `in_out` in-place without removing the padding.
+    /// Returns a references to the decrypted data, which is the full length of `in_out`.
+    ///
+    /// This is intended for protocols that frame the plaintext themselves and need to see the
+    /// final block as it was decrypted. The padding is not validated.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if decryption fails, including if `in_out` is not a multiple
+    ///   of the cipher block length.
+    pub fn decrypt_unpadded<'in_out>(
+        &self,
+        in_out: &'in_out mut [u8],
+        context: DecryptionContext,
+    ) -> Result<&'in_out mut [u8], Unspecified> {
+        if !self
+            .key
+            .algorithm()
+            .is_valid_decryption_context(self.mode, &context)
+        {
+            return Err(Unspecified);
+        }
+
+        decrypt(&self.key, self.mode, in_out, context)
+    }
 }
 
 impl Debug for PaddedBlockDecryptingKey {
@@ -580,18 +1742,50 @@ impl Debug for PaddedBlockDecryptingKey {
     }
 }
 
+impl ZeroizeOnDrop for PaddedBlockDecryptingKey {}
+
+/// Constructs the decrypting key for the same key, mode and padding, for example to verify
+/// data right after encrypting it. The context returned by
+/// [`PaddedBlockEncryptingKey::encrypt`] is passed to
+/// [`PaddedBlockDecryptingKey::decrypt`] as usual.
+impl TryFrom<&PaddedBlockEncryptingKey> for PaddedBlockDecryptingKey {
+    type Error = Unspecified;
+
+    fn try_from(value: &PaddedBlockEncryptingKey) -> Result<Self, Unspecified> {
+        PaddedBlockDecryptingKey::new(value.key.clone(), value.mode, value.padding)
+    }
+}
+
 /// A cipher encryption key that does not perform block padding.
+///
+/// When dropped, the raw key bytes and the expanded key schedule are zeroized.
 pub struct EncryptingKey {
     key: UnboundCipherKey,
     mode: OperatingMode,
 }
 
 impl EncryptingKey {
+    /// Constructs an `EncryptingKey` operating in cipher block chaining (CBC) mode using the provided key.
+    /// No padding is applied, so the plaintext length must be a multiple of the cipher block length.
+    ///
+    // # FIPS
+    // Use this function with an `UnboundCipherKey` constructed with one of the following algorithms:
+    // * `AES_128`
+    // * `AES_192`
+    // * `AES_256`
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `EncryptingKey`.
+    pub fn cbc(key: UnboundCipherKey) -> Result<EncryptingKey, Unspecified> {
+        EncryptingKey::new(key, OperatingMode::CBC)
+    }
+
     /// Constructs an `EncryptingKey` operating in counter (CTR) mode using the provided key.
     ///
     // # FIPS
     // Use this function with an `UnboundCipherKey` constructed with one of the following algorithms:
     // * `AES_128`
+    // * `AES_192`
     // * `AES_256`
     //
     /// # Errors
@@ -600,8 +1794,69 @@ impl EncryptingKey {
         EncryptingKey::new(key, OperatingMode::CTR)
     }
 
+    /// Constructs an `EncryptingKey` operating in cipher feedback 128-bit mode (CFB128) using the provided key.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `EncryptingKey`.
+    pub fn cfb128(key: UnboundCipherKey) -> Result<EncryptingKey, Unspecified> {
+        EncryptingKey::new(key, OperatingMode::CFB128)
+    }
+
+    /// Constructs an `EncryptingKey` operating in cipher feedback 8-bit mode (CFB8) using the provided key.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `EncryptingKey`.
+    pub fn cfb8(key: UnboundCipherKey) -> Result<EncryptingKey, Unspecified> {
+        EncryptingKey::new(key, OperatingMode::CFB8)
+    }
+
+    /// Constructs an `EncryptingKey` operating in output feedback (OFB) mode using the provided key.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `EncryptingKey`.
+    pub fn ofb(key: UnboundCipherKey) -> Result<EncryptingKey, Unspecified> {
+        EncryptingKey::new(key, OperatingMode::OFB)
+    }
+
+    /// Constructs an `EncryptingKey` operating in electronic codebook (ECB) mode using the provided key.
+    ///
+    /// ⚠️ ECB mode is not semantically secure.
+    ///
+    /// ECB encrypts each block independently, so equal plaintext blocks produce equal ciphertext
+    /// blocks. It is provided only for interoperability with protocols that require the raw block
+    /// cipher and should not be used to encrypt general data. The plaintext length must be a
+    /// multiple of the cipher block length and the context is always [`EncryptionContext::None`].
+    ///
+    /// Not available with the `approved-only` feature.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `EncryptingKey`.
+    #[cfg(not(feature = "approved-only"))]
+    pub fn less_safe_ecb(key: UnboundCipherKey) -> Result<EncryptingKey, Unspecified> {
+        EncryptingKey::new(key, OperatingMode::ECB)
+    }
+
+    /// Constructs an `EncryptingKey` for the ChaCha20 stream cipher using the provided key.
+    ///
+    /// Each encryption uses a 96-bit nonce and starts with a block counter of zero, unless an
+    /// [`EncryptionContext::Nonce96Counter32`] context provides a different initial counter. The
+    /// block counter must not wrap, so at most 2^32 64-byte blocks can be encrypted under a single
+    /// nonce.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `key` was not constructed for [`CHACHA20`].
+    pub fn chacha20(key: UnboundCipherKey) -> Result<EncryptingKey, Unspecified> {
+        if key.algorithm().id() != &AlgorithmId::Chacha20 {
+            return Err(Unspecified);
+        }
+        EncryptingKey::new(key, OperatingMode::Stream)
+    }
+
     #[allow(clippy::unnecessary_wraps)]
-    fn new(key: UnboundCipherKey, mode: OperatingMode) -> Result<EncryptingKey, Unspecified> {
+    pub(crate) fn new(
+        key: UnboundCipherKey,
+        mode: OperatingMode,
+    ) -> Result<EncryptingKey, Unspecified> {
         Ok(EncryptingKey { key, mode })
     }
 
@@ -628,49 +1883,213 @@ impl EncryptingKey {
         self.less_safe_encrypt(in_out, context)
     }
 
-    /// Encrypts the data provided in `in_out` in-place using the provided `CipherContext`.
-    /// Returns a references to the decrypted data.
+    /// Encrypts the data provided in `in_out` in-place, using an IV or nonce generated by
+    /// `rng`. Returns the context needed for decryption.
+    ///
+    /// `encrypt` should be preferred. This is for callers that must generate the IV with a
+    /// particular random number generator, or reproducibly in tests.
     ///
     /// # Errors
-    /// * [`Unspecified`]: Returned if cipher mode requires input to be a multiple of the block length,
-    /// and `in_out.len()` is not. Otherwise returned if encryption fails.
-    pub fn less_safe_encrypt(
+    /// * [`Unspecified`]: Returned if `rng` fails, or if cipher mode requires input to be a
+    ///   multiple of the block length, and `in_out.len()` is not. Otherwise returned if
+    ///   encryption fails.
+    pub fn encrypt_with_rng(
         &self,
         in_out: &mut [u8],
-        context: EncryptionContext,
+        rng: &dyn SecureRandom,
     ) -> Result<DecryptionContext, Unspecified> {
-        if !self
+        let context = self
             .key
-            .algorithm()
-            .is_valid_encryption_context(self.mode, &context)
-        {
-            return Err(Unspecified);
-        }
-        encrypt(&self.key, self.mode, in_out, context)
+            .algorithm
+            .new_encryption_context_with_rng(self.mode, rng)?;
+        self.less_safe_encrypt(in_out, context)
     }
-}
 
-impl Debug for EncryptingKey {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("EncryptingKey")
-            .field("key", &self.key)
+    /// Encrypts the data provided in `in_out` in-place, and prefixes it with the IV, so that
+    /// `in_out` holds `IV || ciphertext`.
+    ///
+    /// The result can be decrypted with [`DecryptingKey::decrypt_with_prefixed_iv`].
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if the key's algorithm and mode do not use a 128-bit IV,
+    ///   or if cipher mode requires input to be a multiple of the block length, and
+    ///   `in_out` is not. Otherwise returned if encryption fails.
+    pub fn encrypt_with_prefixed_iv<InOut>(&self, in_out: &mut InOut) -> Result<(), Unspecified>
+    where
+        InOut: AsMut<[u8]> + for<'a> Extend<&'a u8>,
+    {
+        let context = self.key.algorithm.new_encryption_context(self.mode)?;
+        if !matches!(context, EncryptionContext::Iv128(_)) {
+            return Err(Unspecified);
+        }
+        let context = self.less_safe_encrypt(in_out.as_mut(), context)?;
+        prefix_iv(in_out, &context)
+    }
+
+    /// Encrypts the data in `bufs` in-place, with the same result as encrypting the
+    /// concatenation of the buffers in order. Returns the context needed for decryption.
+    ///
+    /// The buffers can be any mutable byte slices, such as `&mut [u8]` or
+    /// [`IoSliceMut`](std::io::IoSliceMut). In CBC and ECB modes, each buffer must be a multiple
+    /// of the block length. Triple DES is not supported.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if cipher mode requires input to be a multiple of the block
+    ///   length, and the length of one of `bufs` is not. Otherwise returned if encryption
+    ///   fails, in which case some of `bufs` may already have been encrypted.
+    pub fn encrypt_vectored<B: DerefMut<Target = [u8]>>(
+        &self,
+        bufs: &mut [B],
+    ) -> Result<DecryptionContext, Unspecified> {
+        let context = self.key.algorithm.new_encryption_context(self.mode)?;
+        streaming::encrypt_vectored(&self.key, self.mode, &context, bufs)?;
+        Ok(context.into())
+    }
+
+    /// Encrypts each of `records` in-place with its own newly generated IV or nonce.
+    /// Returns the contexts needed for decryption, in the same order as `records`.
+    ///
+    /// The result is the same as calling [`encrypt`](Self::encrypt) for each record, but the
+    /// IVs for all of the records are generated with a single call to the random number
+    /// generator. The records can be any mutable byte slices, such as `&mut [u8]` or
+    /// `Vec<u8>`.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if cipher mode requires input to be a multiple of the block
+    ///   length, and the length of one of `records` is not, or if the IVs cannot be generated.
+    ///   In either case none of `records` are modified. Otherwise returned if encryption
+    ///   fails, in which case all of `records` are zeroed.
+    pub fn encrypt_batch<B: DerefMut<Target = [u8]>>(
+        &self,
+        records: &mut [B],
+    ) -> Result<Vec<DecryptionContext>, Unspecified> {
+        let algorithm = self.key.algorithm();
+        let iv_len = algorithm.iv_len(self.mode).ok_or(Unspecified)?;
+        let block_len = algorithm.block_len();
+        if self.mode.is_block_aligned()
+            && records.iter().any(|record| record.len() % block_len != 0)
+        {
+            return Err(Unspecified);
+        }
+
+        let mut ivs = vec![0u8; iv_len * records.len()];
+        SystemRandom::new().fill(&mut ivs)?;
+
+        let mut contexts = Vec::with_capacity(records.len());
+        for (i, record) in records.iter_mut().enumerate() {
+            let iv = &ivs[i * iv_len..(i + 1) * iv_len];
+            match batch_context(iv).and_then(|context| self.less_safe_encrypt(record, context)) {
+                Ok(context) => contexts.push(context),
+                Err(err) => {
+                    for record in records.iter_mut() {
+                        record.zeroize();
+                    }
+                    return Err(err);
+                }
+            }
+        }
+        Ok(contexts)
+    }
+
+    /// Encrypts `plaintext` into a new `Vec`.
+    /// Returns the ciphertext and the context needed for decryption.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if cipher mode requires input to be a multiple of the block
+    ///   length, and `plaintext.len()` is not. Otherwise returned if encryption fails.
+    pub fn encrypt_to_vec(
+        &self,
+        plaintext: &[u8],
+    ) -> Result<(Vec<u8>, DecryptionContext), Unspecified> {
+        let mut in_out = plaintext.to_vec();
+        let context = self.encrypt(&mut in_out).map_err(|err| {
+            in_out.zeroize();
+            err
+        })?;
+        Ok((in_out, context))
+    }
+
+    /// Encrypts `input`, writing the ciphertext to the start of `output`, which must be at
+    /// least as long as `input`. Returns the context needed for decryption.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `output` is shorter than `input`, or if the cipher mode
+    ///   requires input to be a multiple of the block length and `input.len()` is not.
+    ///   Otherwise returned if encryption fails.
+    pub fn encrypt_into(
+        &self,
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Result<DecryptionContext, Unspecified> {
+        let output = output.get_mut(..input.len()).ok_or(Unspecified)?;
+        output.copy_from_slice(input);
+        self.encrypt(output)
+    }
+
+    /// Encrypts the data provided in `in_out` in-place using the provided `CipherContext`.
+    /// Returns a references to the decrypted data.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if cipher mode requires input to be a multiple of the block length,
+    /// and `in_out.len()` is not. Otherwise returned if encryption fails.
+    pub fn less_safe_encrypt(
+        &self,
+        in_out: &mut [u8],
+        context: EncryptionContext,
+    ) -> Result<DecryptionContext, Unspecified> {
+        if !self
+            .key
+            .algorithm()
+            .is_valid_encryption_context(self.mode, &context)
+        {
+            return Err(Unspecified);
+        }
+        encrypt(&self.key, self.mode, in_out, context)
+    }
+}
+
+impl Debug for EncryptingKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("EncryptingKey")
+            .field("key", &self.key)
             .field("mode", &self.mode)
             .finish()
     }
 }
 
+impl ZeroizeOnDrop for EncryptingKey {}
+
 /// A cipher decryption key that does not perform block padding.
+///
+/// When dropped, the raw key bytes and the expanded key schedules, including the AES decryption
+/// schedule used in CBC mode, are zeroized.
 pub struct DecryptingKey {
     key: UnboundCipherKey,
     mode: OperatingMode,
 }
 
 impl DecryptingKey {
+    /// Constructs a cipher decrypting key operating in cipher block chaining (CBC) mode using the provided key.
+    /// No padding is removed, so the ciphertext length must be a multiple of the cipher block length.
+    ///
+    // # FIPS
+    // Use this function with an `UnboundCipherKey` constructed with one of the following algorithms:
+    // * `AES_128`
+    // * `AES_192`
+    // * `AES_256`
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `DecryptingKey`.
+    pub fn cbc(key: UnboundCipherKey) -> Result<DecryptingKey, Unspecified> {
+        DecryptingKey::new(key, OperatingMode::CBC)
+    }
+
     /// Constructs a cipher decrypting key operating in counter (CTR) mode using the provided key and context.
     ///
     // # FIPS
     // Use this function with an `UnboundCipherKey` constructed with one of the following algorithms:
     // * `AES_128`
+    // * `AES_192`
     // * `AES_256`
     //
     /// # Errors
@@ -679,8 +2098,66 @@ impl DecryptingKey {
         DecryptingKey::new(key, OperatingMode::CTR)
     }
 
-    #[allow(clippy::unnecessary_wraps)]
-    fn new(key: UnboundCipherKey, mode: OperatingMode) -> Result<DecryptingKey, Unspecified> {
+    /// Constructs a cipher decrypting key operating in cipher feedback 128-bit mode (CFB128) using the provided key and context.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error during decryption.
+    pub fn cfb128(key: UnboundCipherKey) -> Result<DecryptingKey, Unspecified> {
+        DecryptingKey::new(key, OperatingMode::CFB128)
+    }
+
+    /// Constructs a cipher decrypting key operating in cipher feedback 8-bit mode (CFB8) using the provided key and context.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error during decryption.
+    pub fn cfb8(key: UnboundCipherKey) -> Result<DecryptingKey, Unspecified> {
+        DecryptingKey::new(key, OperatingMode::CFB8)
+    }
+
+    /// Constructs a cipher decrypting key operating in output feedback (OFB) mode using the provided key and context.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error during decryption.
+    pub fn ofb(key: UnboundCipherKey) -> Result<DecryptingKey, Unspecified> {
+        DecryptingKey::new(key, OperatingMode::OFB)
+    }
+
+    /// Constructs a cipher decrypting key operating in electronic codebook (ECB) mode using the provided key.
+    ///
+    /// ⚠️ ECB mode is not semantically secure.
+    ///
+    /// See [`EncryptingKey::less_safe_ecb`] for the limitations of this mode. The context passed to
+    /// [`DecryptingKey::decrypt`] must be [`DecryptionContext::None`].
+    ///
+    /// Not available with the `approved-only` feature.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `DecryptingKey`.
+    #[cfg(not(feature = "approved-only"))]
+    pub fn less_safe_ecb(key: UnboundCipherKey) -> Result<DecryptingKey, Unspecified> {
+        DecryptingKey::new(key, OperatingMode::ECB)
+    }
+
+    /// Constructs a cipher decrypting key for the ChaCha20 stream cipher using the provided key.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `key` was not constructed for [`CHACHA20`].
+    pub fn chacha20(key: UnboundCipherKey) -> Result<DecryptingKey, Unspecified> {
+        if key.algorithm().id() != &AlgorithmId::Chacha20 {
+            return Err(Unspecified);
+        }
+        DecryptingKey::new(key, OperatingMode::Stream)
+    }
+
+    pub(crate) fn new(
+        key: UnboundCipherKey,
+        mode: OperatingMode,
+    ) -> Result<DecryptingKey, Unspecified> {
+        let key = if mode.is_block_aligned() {
+            key.with_decrypt_key()?
+        } else {
+            key
+        };
         Ok(DecryptingKey { key, mode })
     }
 
@@ -707,8 +2184,115 @@ impl DecryptingKey {
         in_out: &'in_out mut [u8],
         context: DecryptionContext,
     ) -> Result<&'in_out mut [u8], Unspecified> {
+        if !self
+            .key
+            .algorithm()
+            .is_valid_decryption_context(self.mode, &context)
+        {
+            return Err(Unspecified);
+        }
         decrypt(&self.key, self.mode, in_out, context)
     }
+
+    /// Decrypts the data provided in `in_out` in-place, where `in_out` holds
+    /// `IV || ciphertext` as produced by [`EncryptingKey::encrypt_with_prefixed_iv`].
+    /// Returns a reference to the decrypted data, which follows the IV in `in_out`.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `in_out` is shorter than the IV, or if decryption fails.
+    pub fn decrypt_with_prefixed_iv<'in_out>(
+        &self,
+        in_out: &'in_out mut [u8],
+    ) -> Result<&'in_out mut [u8], Unspecified> {
+        let (context, ciphertext) = split_prefixed_iv(in_out)?;
+        self.decrypt(ciphertext, context)
+    }
+
+    /// Decrypts the data in `bufs` in-place, with the same result as decrypting the
+    /// concatenation of the buffers in order.
+    ///
+    /// The buffers can be any mutable byte slices, such as `&mut [u8]` or
+    /// [`IoSliceMut`](std::io::IoSliceMut). In CBC and ECB modes, each buffer must be a multiple
+    /// of the block length. Triple DES is not supported.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if cipher mode requires input to be a multiple of the block
+    ///   length, and the length of one of `bufs` is not. Otherwise returned if decryption
+    ///   fails, in which case some of `bufs` may already have been decrypted.
+    pub fn decrypt_vectored<B: DerefMut<Target = [u8]>>(
+        &self,
+        bufs: &mut [B],
+        context: DecryptionContext,
+    ) -> Result<(), Unspecified> {
+        if !self
+            .key
+            .algorithm()
+            .is_valid_decryption_context(self.mode, &context)
+        {
+            return Err(Unspecified);
+        }
+        streaming::decrypt_vectored(&self.key, self.mode, &context.into(), bufs)
+    }
+
+    /// Decrypts `ciphertext` into a new `Vec`.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if cipher mode requires input to be a multiple of the block
+    ///   length, and `ciphertext.len()` is not. Also returned if decryption fails.
+    pub fn decrypt_to_vec(
+        &self,
+        ciphertext: &[u8],
+        context: DecryptionContext,
+    ) -> Result<Vec<u8>, Unspecified> {
+        decrypt_to_vec(ciphertext, |in_out| self.decrypt(in_out, context))
+    }
+
+    /// Decrypts `input`, writing the plaintext to the start of `output`, which must be at least
+    /// as long as `input`. Returns a reference to the plaintext.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `output` is shorter than `input`, or if the cipher mode
+    ///   requires input to be a multiple of the block length and `input.len()` is not. Also
+    ///   returned if decryption fails.
+    pub fn decrypt_into<'out>(
+        &self,
+        input: &[u8],
+        output: &'out mut [u8],
+        context: DecryptionContext,
+    ) -> Result<&'out mut [u8], Unspecified> {
+        let output = output.get_mut(..input.len()).ok_or(Unspecified)?;
+        output.copy_from_slice(input);
+        self.decrypt(output, context)
+    }
+
+    /// Decrypts the data provided in `in_out` in-place, where `in_out` is the part of a CTR
+    /// mode ciphertext that starts `byte_offset` bytes into it. Returns a reference to the
+    /// decrypted data.
+    ///
+    /// This allows any range of a CTR mode ciphertext to be decrypted without decrypting the
+    /// data before it. `context` is the context returned when the whole ciphertext was
+    /// encrypted.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if the key's mode is not CTR, if `context` is not valid for
+    ///   the key, or if the range extends beyond the end of the counter.
+    pub fn decrypt_range<'in_out>(
+        &self,
+        in_out: &'in_out mut [u8],
+        context: DecryptionContext,
+        byte_offset: u64,
+    ) -> Result<&'in_out mut [u8], Unspecified> {
+        if self.mode != OperatingMode::CTR
+            || !self
+                .key
+                .algorithm()
+                .is_valid_decryption_context(self.mode, &context)
+        {
+            return Err(Unspecified);
+        }
+        aes_ctr_mode_at_offset(&self.key, &context.into(), byte_offset, in_out)?;
+        Ok(in_out)
+    }
 }
 
 impl Debug for DecryptingKey {
@@ -720,6 +2304,74 @@ impl Debug for DecryptingKey {
     }
 }
 
+impl ZeroizeOnDrop for DecryptingKey {}
+
+/// Constructs the decrypting key for the same key and mode, for example to verify data right
+/// after encrypting it. The context returned by [`EncryptingKey::encrypt`] is passed to
+/// [`DecryptingKey::decrypt`] as usual.
+impl TryFrom<&EncryptingKey> for DecryptingKey {
+    type Error = Unspecified;
+
+    fn try_from(value: &EncryptingKey) -> Result<Self, Unspecified> {
+        DecryptingKey::new(value.key.clone(), value.mode)
+    }
+}
+
+/// Moves the encrypted data in `in_out` along to make room for the IV of `context` in front of
+/// it.
+fn prefix_iv<InOut>(in_out: &mut InOut, context: &DecryptionContext) -> Result<(), Unspecified>
+where
+    InOut: AsMut<[u8]> + for<'a> Extend<&'a u8>,
+{
+    let iv: &[u8] = context.try_into()?;
+    in_out.extend(iv);
+    in_out.as_mut().rotate_right(iv.len());
+    Ok(())
+}
+
+/// Splits `IV || ciphertext` into the decryption context and the ciphertext.
+fn split_prefixed_iv(in_out: &mut [u8]) -> Result<(DecryptionContext, &mut [u8]), Unspecified> {
+    if in_out.len() < IV_LEN_128_BIT {
+        return Err(Unspecified);
+    }
+    let (iv, ciphertext) = in_out.split_at_mut(IV_LEN_128_BIT);
+    let iv = FixedLength::try_from(&*iv)?;
+    Ok((DecryptionContext::Iv128(iv), ciphertext))
+}
+
+/// Decrypts a copy of `ciphertext` in place with `decrypt`, returning the plaintext. The copy is
+/// zeroized if decryption fails.
+fn decrypt_to_vec<F>(ciphertext: &[u8], decrypt: F) -> Result<Vec<u8>, Unspecified>
+where
+    F: FnOnce(&mut [u8]) -> Result<&mut [u8], Unspecified>,
+{
+    let mut in_out = ciphertext.to_vec();
+    match decrypt(&mut in_out) {
+        Ok(plaintext) => {
+            let plaintext_len = plaintext.len();
+            in_out[plaintext_len..].zeroize();
+            in_out.truncate(plaintext_len);
+            Ok(in_out)
+        }
+        Err(err) => {
+            in_out.zeroize();
+            Err(err)
+        }
+    }
+}
+
+/// Returns the encryption context holding `iv`, which is one of the IVs generated for
+/// [`EncryptingKey::encrypt_batch`].
+fn batch_context(iv: &[u8]) -> Result<EncryptionContext, Unspecified> {
+    Ok(match iv.len() {
+        0 => EncryptionContext::None,
+        IV_LEN_64_BIT => EncryptionContext::Iv64(iv.try_into()?),
+        IV_LEN_96_BIT => EncryptionContext::Nonce96(iv.try_into()?),
+        IV_LEN_128_BIT => EncryptionContext::Iv128(iv.try_into()?),
+        _ => return Err(Unspecified),
+    })
+}
+
 fn encrypt(
     key: &UnboundCipherKey,
     mode: OperatingMode,
@@ -729,7 +2381,11 @@ fn encrypt(
     let block_len = key.algorithm().block_len();
 
     match mode {
-        OperatingMode::CTR => {}
+        OperatingMode::CTR
+        | OperatingMode::CFB128
+        | OperatingMode::CFB8
+        | OperatingMode::OFB
+        | OperatingMode::Stream => {}
         _ => {
             if (in_out.len() % block_len) != 0 {
                 return Err(Unspecified);
@@ -739,10 +2395,51 @@ fn encrypt(
 
     match mode {
         OperatingMode::CBC => match key.algorithm().id() {
-            AlgorithmId::Aes128 | AlgorithmId::Aes256 => encrypt_aes_cbc_mode(key, context, in_out),
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => {
+                encrypt_aes_cbc_mode(key, context, in_out)
+            }
+            #[cfg(not(feature = "approved-only"))]
+            AlgorithmId::TripleDes3Key => encrypt_triple_des_cbc_mode(key, context, in_out),
+            AlgorithmId::Chacha20 => Err(Unspecified),
         },
         OperatingMode::CTR => match key.algorithm().id() {
-            AlgorithmId::Aes128 | AlgorithmId::Aes256 => encrypt_aes_ctr_mode(key, context, in_out),
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => {
+                encrypt_aes_ctr_mode(key, context, in_out)
+            }
+            AlgorithmId::Chacha20 => Err(Unspecified),
+            #[cfg(not(feature = "approved-only"))]
+            AlgorithmId::TripleDes3Key => Err(Unspecified),
+        },
+        OperatingMode::CFB128 | OperatingMode::CFB8 => match key.algorithm().id() {
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => {
+                encrypt_aes_cfb_mode(key, mode, context, in_out)
+            }
+            AlgorithmId::Chacha20 => Err(Unspecified),
+            #[cfg(not(feature = "approved-only"))]
+            AlgorithmId::TripleDes3Key => Err(Unspecified),
+        },
+        OperatingMode::OFB => match key.algorithm().id() {
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => {
+                encrypt_aes_ofb_mode(key, context, in_out)
+            }
+            AlgorithmId::Chacha20 => Err(Unspecified),
+            #[cfg(not(feature = "approved-only"))]
+            AlgorithmId::TripleDes3Key => Err(Unspecified),
+        },
+        #[cfg(not(feature = "approved-only"))]
+        OperatingMode::ECB => match key.algorithm().id() {
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => {
+                encrypt_aes_ecb_mode(key, context, in_out)
+            }
+            AlgorithmId::Chacha20 => Err(Unspecified),
+            #[cfg(not(feature = "approved-only"))]
+            AlgorithmId::TripleDes3Key => Err(Unspecified),
+        },
+        OperatingMode::Stream => match key.algorithm().id() {
+            AlgorithmId::Chacha20 => encrypt_chacha20_mode(key, context, in_out),
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => Err(Unspecified),
+            #[cfg(not(feature = "approved-only"))]
+            AlgorithmId::TripleDes3Key => Err(Unspecified),
         },
     }
 }
@@ -756,7 +2453,11 @@ fn decrypt<'in_out>(
     let block_len = key.algorithm().block_len();
 
     match mode {
-        OperatingMode::CTR => {}
+        OperatingMode::CTR
+        | OperatingMode::CFB128
+        | OperatingMode::CFB8
+        | OperatingMode::OFB
+        | OperatingMode::Stream => {}
         _ => {
             if (in_out.len() % block_len) != 0 {
                 return Err(Unspecified);
@@ -766,10 +2467,51 @@ fn decrypt<'in_out>(
 
     match mode {
         OperatingMode::CBC => match key.algorithm().id() {
-            AlgorithmId::Aes128 | AlgorithmId::Aes256 => decrypt_aes_cbc_mode(key, context, in_out),
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => {
+                decrypt_aes_cbc_mode(key, context, in_out)
+            }
+            #[cfg(not(feature = "approved-only"))]
+            AlgorithmId::TripleDes3Key => decrypt_triple_des_cbc_mode(key, context, in_out),
+            AlgorithmId::Chacha20 => Err(Unspecified),
         },
         OperatingMode::CTR => match key.algorithm().id() {
-            AlgorithmId::Aes128 | AlgorithmId::Aes256 => decrypt_aes_ctr_mode(key, context, in_out),
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => {
+                decrypt_aes_ctr_mode(key, context, in_out)
+            }
+            AlgorithmId::Chacha20 => Err(Unspecified),
+            #[cfg(not(feature = "approved-only"))]
+            AlgorithmId::TripleDes3Key => Err(Unspecified),
+        },
+        OperatingMode::CFB128 | OperatingMode::CFB8 => match key.algorithm().id() {
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => {
+                decrypt_aes_cfb_mode(key, mode, context, in_out)
+            }
+            AlgorithmId::Chacha20 => Err(Unspecified),
+            #[cfg(not(feature = "approved-only"))]
+            AlgorithmId::TripleDes3Key => Err(Unspecified),
+        },
+        OperatingMode::OFB => match key.algorithm().id() {
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => {
+                decrypt_aes_ofb_mode(key, context, in_out)
+            }
+            AlgorithmId::Chacha20 => Err(Unspecified),
+            #[cfg(not(feature = "approved-only"))]
+            AlgorithmId::TripleDes3Key => Err(Unspecified),
+        },
+        #[cfg(not(feature = "approved-only"))]
+        OperatingMode::ECB => match key.algorithm().id() {
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => {
+                decrypt_aes_ecb_mode(key, context, in_out)
+            }
+            AlgorithmId::Chacha20 => Err(Unspecified),
+            #[cfg(not(feature = "approved-only"))]
+            AlgorithmId::TripleDes3Key => Err(Unspecified),
+        },
+        OperatingMode::Stream => match key.algorithm().id() {
+            AlgorithmId::Chacha20 => decrypt_chacha20_mode(key, context, in_out),
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => Err(Unspecified),
+            #[cfg(not(feature = "approved-only"))]
+            AlgorithmId::TripleDes3Key => Err(Unspecified),
         },
     }
 }
@@ -779,26 +2521,61 @@ fn encrypt_aes_ctr_mode(
     context: EncryptionContext,
     in_out: &mut [u8],
 ) -> Result<DecryptionContext, Unspecified> {
+    #[cfg(feature = "parallel")]
+    {
+        if in_out.len() >= parallel::PARALLEL_CTR_MIN_LEN {
+            parallel::aes_ctr_mode(key, &context, in_out)?;
+            return Ok(context.into());
+        }
+    }
+    aes_ctr_mode_at_offset(key, &context, 0, in_out)?;
+    Ok(context.into())
+}
+
+/// Applies the CTR mode keystream of `context`, starting `offset` bytes into it, to `in_out`.
+fn aes_ctr_mode_at_offset(
+    key: &UnboundCipherKey,
+    context: &EncryptionContext,
+    offset: u64,
+    in_out: &mut [u8],
+) -> Result<(), Unspecified> {
     #[allow(clippy::match_wildcard_for_single_variants)]
     let key = match &key.key {
-        SymmetricCipherKey::Aes128 { enc_key, .. } | SymmetricCipherKey::Aes256 { enc_key, .. } => {
-            enc_key
-        }
+        SymmetricCipherKey::Aes128 { enc_key, .. }
+        | SymmetricCipherKey::Aes192 { enc_key, .. }
+        | SymmetricCipherKey::Aes256 { enc_key, .. } => enc_key,
         _ => return Err(Unspecified),
     };
 
-    let mut iv = {
-        let mut iv = [0u8; AES_CTR_IV_LEN];
-        iv.copy_from_slice((&context).try_into()?);
-        iv
-    };
+    let block_len = AES_BLOCK_LEN as u64;
+    let context = context.advanced_by_blocks(offset / block_len)?;
+    let (mut iv, blocks_available) = aes_ctr_counter_block(&context)?;
 
-    let mut buffer = [0u8; AES_BLOCK_LEN];
+    let skipped_len = (offset % block_len) as usize;
+    let len = in_out.len().checked_add(skipped_len).ok_or(Unspecified)?;
+    if aes_ctr_blocks(len) > blocks_available {
+        return Err(Unspecified);
+    }
 
-    aes_ctr128_encrypt(key, &mut iv, &mut buffer, in_out);
-    iv.zeroize();
+    let mut buffer = Zeroizing::new([0u8; AES_BLOCK_LEN]);
+    let mut num = 0;
 
-    Ok(context.into())
+    // Generate and discard the keystream for the start of a partial block, leaving the rest
+    // of the block's keystream in `buffer`.
+    if skipped_len != 0 {
+        let mut skipped = Zeroizing::new([0u8; AES_BLOCK_LEN]);
+        aes_ctr128_encrypt(
+            key,
+            &mut *iv,
+            &mut *buffer,
+            &mut num,
+            &mut skipped[..skipped_len],
+        );
+    }
+
+    aes_ctr128_encrypt(key, &mut *iv, &mut *buffer, &mut num, in_out);
+
+    Ok(())
 }
 
 fn decrypt_aes_ctr_mode<'in_out>(
@@ -817,20 +2594,16 @@ fn encrypt_aes_cbc_mode(
 ) -> Result<DecryptionContext, Unspecified> {
     #[allow(clippy::match_wildcard_for_single_variants)]
     let key = match &key.key {
-        SymmetricCipherKey::Aes128 { enc_key, .. } | SymmetricCipherKey::Aes256 { enc_key, .. } => {
-            enc_key
-        }
+        SymmetricCipherKey::Aes128 { enc_key, .. }
+        | SymmetricCipherKey::Aes192 { enc_key, .. }
+        | SymmetricCipherKey::Aes256 { enc_key, .. } => enc_key,
         _ => return Err(Unspecified),
     };
 
-    let mut iv = {
-        let mut iv = [0u8; AES_CBC_IV_LEN];
-        iv.copy_from_slice((&context).try_into()?);
-        iv
-    };
+    let mut iv = Zeroizing::new([0u8; AES_CBC_IV_LEN]);
+    iv.copy_from_slice((&context).try_into()?);
 
-    aes_cbc_encrypt(key, &mut iv, in_out);
-    iv.zeroize();
+    aes_cbc_encrypt(key, &mut *iv, in_out);
 
     Ok(context.into())
 }
@@ -843,59 +2616,326 @@ fn decrypt_aes_cbc_mode<'in_out>(
 ) -> Result<&'in_out mut [u8], Unspecified> {
     #[allow(clippy::match_wildcard_for_single_variants)]
     let key = match &key.key {
-        SymmetricCipherKey::Aes128 { dec_key, .. } | SymmetricCipherKey::Aes256 { dec_key, .. } => {
-            dec_key
+        SymmetricCipherKey::Aes128 {
+            dec_key: Some(dec_key),
+            ..
         }
+        | SymmetricCipherKey::Aes192 {
+            dec_key: Some(dec_key),
+            ..
+        }
+        | SymmetricCipherKey::Aes256 {
+            dec_key: Some(dec_key),
+            ..
+        } => dec_key,
         _ => return Err(Unspecified),
     };
 
-    let mut iv = {
-        let mut iv = [0u8; AES_CBC_IV_LEN];
-        iv.copy_from_slice((&context).try_into()?);
-        iv
-    };
+    let mut iv = Zeroizing::new([0u8; AES_CBC_IV_LEN]);
+    iv.copy_from_slice((&context).try_into()?);
 
-    aes_cbc_decrypt(key, &mut iv, in_out);
-    iv.zeroize();
+    aes_cbc_decrypt(key, &mut *iv, in_out);
 
     Ok(in_out)
 }
 
-fn aes_ctr128_encrypt(key: &AES_KEY, iv: &mut [u8], block_buffer: &mut [u8], in_out: &mut [u8]) {
-    let mut num = MaybeUninit::<u32>::new(0);
+#[cfg(not(feature = "approved-only"))]
+fn encrypt_triple_des_cbc_mode(
+    key: &UnboundCipherKey,
+    context: EncryptionContext,
+    in_out: &mut [u8],
+) -> Result<DecryptionContext, Unspecified> {
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    let key_schedules = match &key.key {
+        SymmetricCipherKey::TripleDes { key_schedules } => key_schedules,
+        _ => return Err(Unspecified),
+    };
 
-    indicator_check!(unsafe {
-        AES_ctr128_encrypt(
-            in_out.as_ptr(),
-            in_out.as_mut_ptr(),
-            in_out.len(),
-            key,
-            iv.as_mut_ptr(),
-            block_buffer.as_mut_ptr(),
-            num.as_mut_ptr(),
-        );
-    });
+    let mut iv = Zeroizing::new([0u8; TRIPLE_DES_CBC_IV_LEN]);
+    iv.copy_from_slice((&context).try_into()?);
 
-    Zeroize::zeroize(block_buffer);
-}
+    triple_des_cbc(key_schedules, &mut iv, in_out, DES_ENCRYPT);
 
-fn aes_cbc_encrypt(key: &AES_KEY, iv: &mut [u8], in_out: &mut [u8]) {
-    indicator_check!(unsafe {
-        AES_cbc_encrypt(
-            in_out.as_ptr(),
-            in_out.as_mut_ptr(),
-            in_out.len(),
-            key,
-            iv.as_mut_ptr(),
-            AES_ENCRYPT,
-        );
-    });
+    Ok(context.into())
 }
 
-fn aes_cbc_decrypt(key: &AES_KEY, iv: &mut [u8], in_out: &mut [u8]) {
-    indicator_check!(unsafe {
-        AES_cbc_encrypt(
-            in_out.as_ptr(),
+#[allow(clippy::needless_pass_by_value)]
+#[cfg(not(feature = "approved-only"))]
+fn decrypt_triple_des_cbc_mode<'in_out>(
+    key: &UnboundCipherKey,
+    context: DecryptionContext,
+    in_out: &'in_out mut [u8],
+) -> Result<&'in_out mut [u8], Unspecified> {
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    let key_schedules = match &key.key {
+        SymmetricCipherKey::TripleDes { key_schedules } => key_schedules,
+        _ => return Err(Unspecified),
+    };
+
+    let mut iv = Zeroizing::new([0u8; TRIPLE_DES_CBC_IV_LEN]);
+    iv.copy_from_slice((&context).try_into()?);
+
+    triple_des_cbc(key_schedules, &mut iv, in_out, DES_DECRYPT);
+
+    Ok(in_out)
+}
+
+fn encrypt_aes_cfb_mode(
+    unbound_key: &UnboundCipherKey,
+    mode: OperatingMode,
+    context: EncryptionContext,
+    in_out: &mut [u8],
+) -> Result<DecryptionContext, Unspecified> {
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    let key = match &unbound_key.key {
+        SymmetricCipherKey::Aes128 { enc_key, .. }
+        | SymmetricCipherKey::Aes192 { enc_key, .. }
+        | SymmetricCipherKey::Aes256 { enc_key, .. } => enc_key,
+        _ => return Err(Unspecified),
+    };
+
+    let mut iv = Zeroizing::new([0u8; AES_CFB_IV_LEN]);
+    iv.copy_from_slice((&context).try_into()?);
+
+    match mode {
+        OperatingMode::CFB128 => aes_cfb128_encrypt(key, &mut *iv, &mut 0, in_out, AES_ENCRYPT),
+        OperatingMode::CFB8 => aes_cfb8_encrypt(unbound_key, &mut iv, in_out, AES_ENCRYPT)?,
+        _ => return Err(Unspecified),
+    }
+
+    Ok(context.into())
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn decrypt_aes_cfb_mode<'in_out>(
+    unbound_key: &UnboundCipherKey,
+    mode: OperatingMode,
+    context: DecryptionContext,
+    in_out: &'in_out mut [u8],
+) -> Result<&'in_out mut [u8], Unspecified> {
+    // CFB decryption uses the forward cipher, so the encryption key schedule is used here too.
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    let key = match &unbound_key.key {
+        SymmetricCipherKey::Aes128 { enc_key, .. }
+        | SymmetricCipherKey::Aes192 { enc_key, .. }
+        | SymmetricCipherKey::Aes256 { enc_key, .. } => enc_key,
+        _ => return Err(Unspecified),
+    };
+
+    let mut iv = Zeroizing::new([0u8; AES_CFB_IV_LEN]);
+    iv.copy_from_slice((&context).try_into()?);
+
+    match mode {
+        OperatingMode::CFB128 => aes_cfb128_encrypt(key, &mut *iv, &mut 0, in_out, AES_DECRYPT),
+        OperatingMode::CFB8 => aes_cfb8_encrypt(unbound_key, &mut iv, in_out, AES_DECRYPT)?,
+        _ => return Err(Unspecified),
+    }
+
+    Ok(in_out)
+}
+
+fn encrypt_aes_ofb_mode(
+    key: &UnboundCipherKey,
+    context: EncryptionContext,
+    in_out: &mut [u8],
+) -> Result<DecryptionContext, Unspecified> {
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    let key = match &key.key {
+        SymmetricCipherKey::Aes128 { enc_key, .. }
+        | SymmetricCipherKey::Aes192 { enc_key, .. }
+        | SymmetricCipherKey::Aes256 { enc_key, .. } => enc_key,
+        _ => return Err(Unspecified),
+    };
+
+    let mut iv = Zeroizing::new([0u8; AES_OFB_IV_LEN]);
+    iv.copy_from_slice((&context).try_into()?);
+
+    aes_ofb128_encrypt(key, &mut *iv, &mut 0, in_out);
+
+    Ok(context.into())
+}
+
+fn decrypt_aes_ofb_mode<'in_out>(
+    key: &UnboundCipherKey,
+    context: DecryptionContext,
+    in_out: &'in_out mut [u8],
+) -> Result<&'in_out mut [u8], Unspecified> {
+    // it's the same in OFB, just providing a nice named wrapper to match
+    encrypt_aes_ofb_mode(key, context.into(), in_out).map(|_| in_out)
+}
+
+#[cfg(not(feature = "approved-only"))]
+fn encrypt_aes_ecb_mode(
+    key: &UnboundCipherKey,
+    context: EncryptionContext,
+    in_out: &mut [u8],
+) -> Result<DecryptionContext, Unspecified> {
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    let key = match &key.key {
+        SymmetricCipherKey::Aes128 { enc_key, .. }
+        | SymmetricCipherKey::Aes192 { enc_key, .. }
+        | SymmetricCipherKey::Aes256 { enc_key, .. } => enc_key,
+        _ => return Err(Unspecified),
+    };
+
+    if !matches!(context, EncryptionContext::None) {
+        return Err(Unspecified);
+    }
+
+    aes_ecb_encrypt(key, in_out, AES_ENCRYPT);
+
+    Ok(context.into())
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[cfg(not(feature = "approved-only"))]
+fn decrypt_aes_ecb_mode<'in_out>(
+    key: &UnboundCipherKey,
+    context: DecryptionContext,
+    in_out: &'in_out mut [u8],
+) -> Result<&'in_out mut [u8], Unspecified> {
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    let key = match &key.key {
+        SymmetricCipherKey::Aes128 {
+            dec_key: Some(dec_key),
+            ..
+        }
+        | SymmetricCipherKey::Aes192 {
+            dec_key: Some(dec_key),
+            ..
+        }
+        | SymmetricCipherKey::Aes256 {
+            dec_key: Some(dec_key),
+            ..
+        } => dec_key,
+        _ => return Err(Unspecified),
+    };
+
+    if !matches!(context, DecryptionContext::None) {
+        return Err(Unspecified);
+    }
+
+    aes_ecb_encrypt(key, in_out, AES_DECRYPT);
+
+    Ok(in_out)
+}
+
+fn encrypt_chacha20_mode(
+    key: &UnboundCipherKey,
+    context: EncryptionContext,
+    in_out: &mut [u8],
+) -> Result<DecryptionContext, Unspecified> {
+    let key = match &key.key {
+        SymmetricCipherKey::ChaCha20 { raw_key } => raw_key,
+        _ => return Err(Unspecified),
+    };
+
+    let counter = chacha20_initial_counter(&context);
+    if in_out.len() as u64 > CHACHA20_MAX_INPUT_LEN - u64::from(counter) * CHACHA20_BLOCK_LEN as u64
+    {
+        return Err(Unspecified);
+    }
+
+    let mut nonce = Zeroizing::new([0u8; CHACHA20_NONCE_LEN]);
+    nonce.copy_from_slice(context.iv_or_nonce()?);
+
+    key.encrypt_in_place(&nonce, in_out, counter);
+
+    Ok(context.into())
+}
+
+/// Returns the ChaCha20 block counter that processing starts from for `context`.
+fn chacha20_initial_counter(context: &EncryptionContext) -> u32 {
+    match context {
+        EncryptionContext::Nonce96Counter32(_, counter) => *counter,
+        _ => 0,
+    }
+}
+
+fn decrypt_chacha20_mode<'in_out>(
+    key: &UnboundCipherKey,
+    context: DecryptionContext,
+    in_out: &'in_out mut [u8],
+) -> Result<&'in_out mut [u8], Unspecified> {
+    // it's the same for a stream cipher, just providing a nice named wrapper to match
+    encrypt_chacha20_mode(key, context.into(), in_out).map(|_| in_out)
+}
+
+/// Returns the number of blocks of keystream needed for `len` bytes in CTR mode.
+fn aes_ctr_blocks(len: usize) -> u128 {
+    let blocks = len / AES_BLOCK_LEN + usize::from(len % AES_BLOCK_LEN != 0);
+    blocks as u128
+}
+
+/// Returns the number of blocks that can be processed in CTR mode starting from `iv` before the
+/// 128-bit big-endian counter incremented by `AES_ctr128_encrypt` wraps around and repeats a
+/// counter block. The result saturates at `u128::MAX` for a counter of zero.
+fn aes_ctr_blocks_available(iv: &[u8; AES_CTR_IV_LEN]) -> u128 {
+    (u128::MAX - u128::from_be_bytes(*iv)).saturating_add(1)
+}
+
+/// Returns the initial counter block for CTR mode with `context`, and the number of blocks that
+/// can be processed from it.
+///
+/// The counter block of a `Nonce96Counter32` context is the nonce followed by the big-endian
+/// counter. Only the counter may be incremented, so the blocks available stop where it would
+/// wrap around, before `AES_ctr128_encrypt` would carry into the nonce.
+fn aes_ctr_counter_block(
+    context: &EncryptionContext,
+) -> Result<(Zeroizing<[u8; AES_CTR_IV_LEN]>, u128), Unspecified> {
+    let mut iv = Zeroizing::new([0u8; AES_CTR_IV_LEN]);
+    match context {
+        EncryptionContext::Iv128(counter_block) => {
+            iv.copy_from_slice(counter_block.as_ref());
+            let blocks_available = aes_ctr_blocks_available(&iv);
+            Ok((iv, blocks_available))
+        }
+        EncryptionContext::Nonce96Counter32(nonce, counter) => {
+            iv[..IV_LEN_96_BIT].copy_from_slice(nonce.as_ref());
+            iv[IV_LEN_96_BIT..].copy_from_slice(&counter.to_be_bytes());
+            Ok((iv, (1u128 << 32) - u128::from(*counter)))
+        }
+        _ => Err(Unspecified),
+    }
+}
+
+fn aes_ctr128_encrypt(
+    key: &AES_KEY,
+    iv: &mut [u8],
+    block_buffer: &mut [u8],
+    num: &mut u32,
+    in_out: &mut [u8],
+) {
+    indicator_check!(unsafe {
+        AES_ctr128_encrypt(
+            in_out.as_ptr(),
+            in_out.as_mut_ptr(),
+            in_out.len(),
+            key,
+            iv.as_mut_ptr(),
+            block_buffer.as_mut_ptr(),
+            num,
+        );
+    });
+}
+
+fn aes_cbc_encrypt(key: &AES_KEY, iv: &mut [u8], in_out: &mut [u8]) {
+    indicator_check!(unsafe {
+        AES_cbc_encrypt(
+            in_out.as_ptr(),
+            in_out.as_mut_ptr(),
+            in_out.len(),
+            key,
+            iv.as_mut_ptr(),
+            AES_ENCRYPT,
+        );
+    });
+}
+
+fn aes_cbc_decrypt(key: &AES_KEY, iv: &mut [u8], in_out: &mut [u8]) {
+    indicator_check!(unsafe {
+        AES_cbc_encrypt(
+            in_out.as_ptr(),
             in_out.as_mut_ptr(),
             in_out.len(),
             key,
@@ -905,166 +2945,1547 @@ fn aes_cbc_decrypt(key: &AES_KEY, iv: &mut [u8], in_out: &mut [u8]) {
     });
 }
 
+#[cfg(not(feature = "approved-only"))]
+fn triple_des_cbc(
+    key_schedules: &[DES_key_schedule; 3],
+    iv: &mut [u8; TRIPLE_DES_CBC_IV_LEN],
+    in_out: &mut [u8],
+    mode: i32,
+) {
+    let mut ivec = DES_cblock { bytes: *iv };
+    unsafe {
+        DES_ede3_cbc_encrypt(
+            in_out.as_ptr(),
+            in_out.as_mut_ptr(),
+            in_out.len(),
+            &key_schedules[0],
+            &key_schedules[1],
+            &key_schedules[2],
+            &mut ivec,
+            mode,
+        );
+    }
+    *iv = ivec.bytes;
+    ivec.bytes.zeroize();
+}
+
+fn aes_ofb128_encrypt(key: &AES_KEY, iv: &mut [u8], num: &mut i32, in_out: &mut [u8]) {
+    indicator_check!(unsafe {
+        AES_ofb128_encrypt(
+            in_out.as_ptr(),
+            in_out.as_mut_ptr(),
+            in_out.len(),
+            key,
+            iv.as_mut_ptr(),
+            num,
+        );
+    });
+}
+
+#[cfg(not(feature = "approved-only"))]
+fn aes_ecb_encrypt(key: &AES_KEY, in_out: &mut [u8], mode: i32) {
+    for block in in_out.chunks_exact_mut(AES_BLOCK_LEN) {
+        indicator_check!(unsafe {
+            AES_ecb_encrypt(block.as_ptr(), block.as_mut_ptr(), key, mode);
+        });
+    }
+}
+
+fn aes_cfb8_encrypt(
+    key: &UnboundCipherKey,
+    iv: &mut [u8; AES_CFB_IV_LEN],
+    in_out: &mut [u8],
+    mode: i32,
+) -> Result<(), Unspecified> {
+    let cipher = match key.algorithm().id() {
+        AlgorithmId::Aes128 => unsafe { EVP_aes_128_cfb8() },
+        AlgorithmId::Aes192 => unsafe { EVP_aes_192_cfb8() },
+        AlgorithmId::Aes256 => unsafe { EVP_aes_256_cfb8() },
+        _ => return Err(Unspecified),
+    };
+    let in_out_len = c_int::try_from(in_out.len()).map_err(|_| Unspecified)?;
+    let encrypt = mode == AES_ENCRYPT;
+
+    let ctx = LcPtr::new(unsafe { EVP_CIPHER_CTX_new() })?;
+    if 1 != unsafe {
+        EVP_CipherInit_ex(
+            *ctx,
+            cipher,
+            null_mut(),
+            key.key_bytes.as_ptr(),
+            iv.as_ptr(),
+            c_int::from(encrypt),
+        )
+    } {
+        return Err(Unspecified);
+    }
+
+    // The feedback register ends with the last ciphertext bytes, which are the input when
+    // decrypting and the output when encrypting.
+    if !encrypt {
+        cfb8_feedback(iv, in_out);
+    }
+    let mut out_len: c_int = 0;
+    if 1 != indicator_check!(unsafe {
+        EVP_CipherUpdate(
+            *ctx,
+            in_out.as_mut_ptr(),
+            &mut out_len,
+            in_out.as_ptr(),
+            in_out_len,
+        )
+    }) {
+        return Err(Unspecified);
+    }
+    if encrypt {
+        cfb8_feedback(iv, in_out);
+    }
+
+    Ok(())
+}
+
+/// Shifts `ciphertext` into the CFB8 feedback register `iv`.
+fn cfb8_feedback(iv: &mut [u8; AES_CFB_IV_LEN], ciphertext: &[u8]) {
+    if ciphertext.len() >= AES_CFB_IV_LEN {
+        iv.copy_from_slice(&ciphertext[ciphertext.len() - AES_CFB_IV_LEN..]);
+    } else {
+        iv.copy_within(ciphertext.len().., 0);
+        iv[AES_CFB_IV_LEN - ciphertext.len()..].copy_from_slice(ciphertext);
+    }
+}
+
+fn aes_cfb128_encrypt(key: &AES_KEY, iv: &mut [u8], num: &mut i32, in_out: &mut [u8], mode: i32) {
+    indicator_check!(unsafe {
+        AES_cfb128_encrypt(
+            in_out.as_ptr(),
+            in_out.as_mut_ptr(),
+            in_out.len(),
+            key,
+            iv.as_mut_ptr(),
+            num,
+            mode,
+        );
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::hex;
     use crate::test::from_hex;
 
-    #[cfg(feature = "fips")]
-    mod fips;
+    #[cfg(feature = "fips")]
+    mod fips;
+    mod kat;
+
+    #[test]
+    fn test_debug() {
+        {
+            let aes_128_key_bytes = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+            let cipher_key = UnboundCipherKey::new(&AES_128, aes_128_key_bytes.as_slice()).unwrap();
+            assert_eq!("UnboundCipherKey { algorithm: Algorithm { id: Aes128, key_len: 16, block_len: 16 } }", format!("{cipher_key:?}"));
+        }
+
+        {
+            let aes_256_key_bytes =
+                from_hex("000102030405060708090a0b0c0d0e0f000102030405060708090a0b0c0d0e0f")
+                    .unwrap();
+            let cipher_key = UnboundCipherKey::new(&AES_256, aes_256_key_bytes.as_slice()).unwrap();
+            assert_eq!("UnboundCipherKey { algorithm: Algorithm { id: Aes256, key_len: 32, block_len: 16 } }", format!("{cipher_key:?}"));
+        }
+
+        {
+            let key_bytes = &[0u8; 16];
+            let key = PaddedBlockEncryptingKey::cbc_pkcs7(
+                UnboundCipherKey::new(&AES_128, key_bytes).unwrap(),
+            )
+            .unwrap();
+            assert_eq!("PaddedBlockEncryptingKey { key: UnboundCipherKey { algorithm: Algorithm { id: Aes128, key_len: 16, block_len: 16 } }, mode: CBC, padding: PKCS7 }", format!("{key:?}"));
+            let mut data = vec![0u8; 16];
+            let context = key.encrypt(&mut data).unwrap();
+            let iv: &[u8] = (&context).try_into().unwrap();
+            assert_eq!("Iv128", format!("{context:?}"));
+            assert!(!format!("{context:?}").contains(&hex::encode(iv)));
+            let key = PaddedBlockDecryptingKey::cbc_pkcs7(
+                UnboundCipherKey::new(&AES_128, key_bytes).unwrap(),
+            )
+            .unwrap();
+            assert_eq!("PaddedBlockDecryptingKey { key: UnboundCipherKey { algorithm: Algorithm { id: Aes128, key_len: 16, block_len: 16 } }, mode: CBC, padding: PKCS7 }", format!("{key:?}"));
+        }
+
+        {
+            let key_bytes = &[0u8; 16];
+            let key =
+                EncryptingKey::ctr(UnboundCipherKey::new(&AES_128, key_bytes).unwrap()).unwrap();
+            assert_eq!("EncryptingKey { key: UnboundCipherKey { algorithm: Algorithm { id: Aes128, key_len: 16, block_len: 16 } }, mode: CTR }", format!("{key:?}"));
+            let mut data = vec![0u8; 16];
+            let context = key.encrypt(&mut data).unwrap();
+            let iv: &[u8] = (&context).try_into().unwrap();
+            assert_eq!("Iv128", format!("{context:?}"));
+            assert!(!format!("{context:?}").contains(&hex::encode(iv)));
+            let key =
+                DecryptingKey::ctr(UnboundCipherKey::new(&AES_128, key_bytes).unwrap()).unwrap();
+            assert_eq!("DecryptingKey { key: UnboundCipherKey { algorithm: Algorithm { id: Aes128, key_len: 16, block_len: 16 } }, mode: CTR }", format!("{key:?}"));
+        }
+
+        {
+            let key_bytes = &[0u8; 32];
+            let key = EncryptingKey::chacha20(UnboundCipherKey::new(&CHACHA20, key_bytes).unwrap())
+                .unwrap();
+            assert_eq!("EncryptingKey { key: UnboundCipherKey { algorithm: Algorithm { id: Chacha20, key_len: 32, block_len: 64 } }, mode: Stream }", format!("{key:?}"));
+            let mut data = vec![0u8; 16];
+            let context = key.encrypt(&mut data).unwrap();
+            let iv: &[u8] = (&context).try_into().unwrap();
+            assert_eq!("Nonce96", format!("{context:?}"));
+            assert!(!format!("{context:?}").contains(&hex::encode(iv)));
+        }
+    }
+
+    fn helper_test_cipher_n_bytes(
+        key: &[u8],
+        alg: &'static Algorithm,
+        mode: OperatingMode,
+        n: usize,
+    ) {
+        let mut input: Vec<u8> = Vec::with_capacity(n);
+        for i in 0..n {
+            let byte: u8 = i.try_into().unwrap();
+            input.push(byte);
+        }
+
+        let cipher_key = UnboundCipherKey::new(alg, key).unwrap();
+        let encrypting_key = EncryptingKey::new(cipher_key, mode).unwrap();
+
+        let mut in_out = input.clone();
+        let decrypt_iv = encrypting_key.encrypt(&mut in_out).unwrap();
+
+        if n > 5 {
+            // There's no more than a 1 in 2^48 chance that this will fail randomly
+            assert_ne!(input.as_slice(), in_out);
+        }
+
+        let cipher_key2 = UnboundCipherKey::new(alg, key).unwrap();
+        let decrypting_key = DecryptingKey::new(cipher_key2, mode).unwrap();
+
+        let plaintext = decrypting_key.decrypt(&mut in_out, decrypt_iv).unwrap();
+        assert_eq!(input.as_slice(), plaintext);
+    }
+
+    fn helper_test_padded_cipher_n_bytes(
+        key: &[u8],
+        alg: &'static Algorithm,
+        mode: OperatingMode,
+        padding: PaddingStrategy,
+        n: usize,
+    ) {
+        let mut input: Vec<u8> = Vec::with_capacity(n);
+        for i in 0..n {
+            let byte: u8 = i.try_into().unwrap();
+            input.push(byte);
+        }
+
+        let cipher_key = UnboundCipherKey::new(alg, key).unwrap();
+        let encrypting_key = PaddedBlockEncryptingKey::new(cipher_key, mode, padding).unwrap();
+
+        let mut in_out = input.clone();
+        let decrypt_iv = encrypting_key.encrypt(&mut in_out).unwrap();
+
+        if n > 5 {
+            // There's no more than a 1 in 2^48 chance that this will fail randomly
+            assert_ne!(input.as_slice(), in_out);
+        }
+
+        let cipher_key2 = UnboundCipherKey::new(alg, key).unwrap();
+        let decrypting_key = PaddedBlockDecryptingKey::new(cipher_key2, mode, padding).unwrap();
+
+        let plaintext = decrypting_key.decrypt(&mut in_out, decrypt_iv).unwrap();
+        assert_eq!(input.as_slice(), plaintext);
+    }
+
+    #[test]
+    fn test_aes_128_cbc() {
+        let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+        for i in 0..=50 {
+            helper_test_padded_cipher_n_bytes(
+                key.as_slice(),
+                &AES_128,
+                OperatingMode::CBC,
+                PaddingStrategy::PKCS7,
+                i,
+            );
+        }
+    }
+
+    #[test]
+    fn test_aes_256_cbc() {
+        let key =
+            from_hex("000102030405060708090a0b0c0d0e0f000102030405060708090a0b0c0d0e0f").unwrap();
+        for i in 0..=50 {
+            helper_test_padded_cipher_n_bytes(
+                key.as_slice(),
+                &AES_256,
+                OperatingMode::CBC,
+                PaddingStrategy::PKCS7,
+                i,
+            );
+        }
+    }
+
+    #[test]
+    fn test_aes_192_cbc() {
+        let key = from_hex("000102030405060708090a0b0c0d0e0f0001020304050607").unwrap();
+        for i in 0..=50 {
+            helper_test_padded_cipher_n_bytes(
+                key.as_slice(),
+                &AES_192,
+                OperatingMode::CBC,
+                PaddingStrategy::PKCS7,
+                i,
+            );
+        }
+    }
+
+    #[test]
+    fn test_aes_cbc_iso7816() {
+        for key in [
+            from_hex("000102030405060708090a0b0c0d0e0f").unwrap(),
+            from_hex("000102030405060708090a0b0c0d0e0f000102030405060708090a0b0c0d0e0f").unwrap(),
+        ] {
+            let alg = if key.len() == 16 { &AES_128 } else { &AES_256 };
+            for i in 0..=50 {
+                helper_test_padded_cipher_n_bytes(
+                    key.as_slice(),
+                    alg,
+                    OperatingMode::CBC,
+                    PaddingStrategy::ISO7816,
+                    i,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_aes_cbc_ansi_x923() {
+        let key = from_hex("000102030405060708090a0b0c0d0e0f0001020304050607").unwrap();
+        for i in 0..=50 {
+            helper_test_padded_cipher_n_bytes(
+                key.as_slice(),
+                &AES_192,
+                OperatingMode::CBC,
+                PaddingStrategy::AnsiX923,
+                i,
+            );
+        }
+    }
+
+    #[test]
+    fn test_aes_cbc_no_padding() {
+        let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+        for i in (0..=64).step_by(16) {
+            helper_test_padded_cipher_n_bytes(
+                key.as_slice(),
+                &AES_128,
+                OperatingMode::CBC,
+                PaddingStrategy::None,
+                i,
+            );
+        }
+
+        let encrypting_key = PaddedBlockEncryptingKey::cbc_no_padding(
+            UnboundCipherKey::new(&AES_128, &key).unwrap(),
+        )
+        .unwrap();
+        let mut in_out = vec![0u8; 17];
+        assert!(encrypting_key.encrypt(&mut in_out).is_err());
+    }
+
+    #[test]
+    fn test_prefixed_iv() {
+        let key = [0x42u8; 16];
+        let key = || UnboundCipherKey::new(&AES_128, &key).unwrap();
+        let plaintext: Vec<u8> = (0..40).collect();
+
+        let encrypting_key = PaddedBlockEncryptingKey::cbc_pkcs7(key()).unwrap();
+        let decrypting_key = PaddedBlockDecryptingKey::cbc_pkcs7(key()).unwrap();
+        let mut in_out = plaintext.clone();
+        encrypting_key
+            .encrypt_with_prefixed_iv(&mut in_out)
+            .unwrap();
+        assert_eq!(16 + 48, in_out.len());
+
+        // The same as decrypting the ciphertext after the IV with a context made from the IV.
+        let mut expected = in_out[16..].to_vec();
+        let context = DecryptionContext::Iv128(FixedLength::try_from(&in_out[..16]).unwrap());
+        assert_eq!(
+            plaintext,
+            decrypting_key.decrypt(&mut expected, context).unwrap()
+        );
+        assert_eq!(
+            plaintext,
+            decrypting_key
+                .decrypt_with_prefixed_iv(&mut in_out)
+                .unwrap()
+        );
+
+        let encrypting_key = EncryptingKey::ctr(key()).unwrap();
+        let decrypting_key = DecryptingKey::ctr(key()).unwrap();
+        for len in [0, 1, 40] {
+            let mut in_out = plaintext[..len].to_vec();
+            encrypting_key
+                .encrypt_with_prefixed_iv(&mut in_out)
+                .unwrap();
+            assert_eq!(16 + len, in_out.len());
+
+            // The same as composing the IV and ciphertext by hand.
+            let iv: [u8; 16] = in_out[..16].try_into().unwrap();
+            let mut composed = plaintext[..len].to_vec();
+            encrypting_key
+                .less_safe_encrypt(&mut composed, EncryptionContext::Iv128(iv.into()))
+                .unwrap();
+            assert_eq!(composed, in_out[16..]);
+
+            assert_eq!(
+                plaintext[..len],
+                *decrypting_key
+                    .decrypt_with_prefixed_iv(&mut in_out)
+                    .unwrap()
+            );
+        }
+
+        assert!(decrypting_key
+            .decrypt_with_prefixed_iv(&mut [0u8; 15])
+            .is_err());
+        assert!(PaddedBlockDecryptingKey::cbc_pkcs7(key())
+            .unwrap()
+            .decrypt_with_prefixed_iv(&mut [0u8; 15])
+            .is_err());
+
+        // Only keys that use a 128-bit IV are supported, and `in_out` is left as it was.
+        #[cfg(not(feature = "approved-only"))]
+        {
+            let ecb_key = EncryptingKey::less_safe_ecb(key()).unwrap();
+            let mut in_out = vec![0u8; 16];
+            assert!(ecb_key.encrypt_with_prefixed_iv(&mut in_out).is_err());
+            assert_eq!(vec![0u8; 16], in_out);
+        }
+    }
+
+    #[test]
+    fn test_to_vec() {
+        let key = [0x42u8; 16];
+        let key = || UnboundCipherKey::new(&AES_128, &key).unwrap();
+        let context_copy = |context: &DecryptionContext| {
+            let iv: &[u8] = context.try_into().unwrap();
+            DecryptionContext::Iv128(FixedLength::try_from(iv).unwrap())
+        };
+
+        let encrypting_key = PaddedBlockEncryptingKey::cbc_pkcs7(key()).unwrap();
+        let decrypting_key = PaddedBlockDecryptingKey::cbc_pkcs7(key()).unwrap();
+        for len in [0, 1, 16, 33] {
+            let plaintext: Vec<u8> = (0..len).collect();
+            let (ciphertext, context) = encrypting_key.encrypt_to_vec(&plaintext).unwrap();
+            // An empty plaintext is padded to a full block.
+            assert_eq!(usize::from(len) / 16 * 16 + 16, ciphertext.len());
+            assert_eq!(
+                plaintext,
+                decrypting_key
+                    .decrypt_to_vec(&ciphertext, context_copy(&context))
+                    .unwrap()
+            );
+            assert!(decrypting_key
+                .decrypt_to_vec(&ciphertext[1..], context)
+                .is_err());
+        }
+
+        let encrypting_key = EncryptingKey::ctr(key()).unwrap();
+        let decrypting_key = DecryptingKey::ctr(key()).unwrap();
+        for len in [0, 1, 33] {
+            let plaintext: Vec<u8> = (0..len).collect();
+            let (ciphertext, context) = encrypting_key.encrypt_to_vec(&plaintext).unwrap();
+            assert_eq!(plaintext.len(), ciphertext.len());
+            assert_eq!(
+                plaintext,
+                decrypting_key.decrypt_to_vec(&ciphertext, context).unwrap()
+            );
+        }
+
+        let encrypting_key = EncryptingKey::cbc(key()).unwrap();
+        let decrypting_key = DecryptingKey::cbc(key()).unwrap();
+        let (ciphertext, context) = encrypting_key.encrypt_to_vec(&[0u8; 32]).unwrap();
+        assert!(encrypting_key.encrypt_to_vec(&[0u8; 17]).is_err());
+        assert!(decrypting_key
+            .decrypt_to_vec(&ciphertext[..17], context)
+            .is_err());
+    }
+
+    #[test]
+    fn test_encrypt_into() {
+        let key = [0x42u8; 16];
+        let key = || UnboundCipherKey::new(&AES_128, &key).unwrap();
+        let input: Vec<u8> = (0..37).collect();
+
+        let encrypting_key = EncryptingKey::ctr(key()).unwrap();
+        let decrypting_key = DecryptingKey::ctr(key()).unwrap();
+        assert!(encrypting_key.encrypt_into(&input, &mut [0u8; 36]).is_err());
+
+        // Only the start of a longer output is written.
+        let mut output = [0xaau8; 40];
+        let context = encrypting_key.encrypt_into(&input, &mut output).unwrap();
+        let iv: &[u8] = (&context).try_into().unwrap();
+        let context = || DecryptionContext::Iv128(FixedLength::try_from(iv).unwrap());
+        assert_eq!([0xaau8; 3], output[37..]);
+        let ciphertext = &output[..37];
+        assert_ne!(input, ciphertext);
+
+        let mut decrypted = [0u8; 37];
+        assert!(decrypting_key
+            .decrypt_into(ciphertext, &mut decrypted[..36], context())
+            .is_err());
+        let decrypted = decrypting_key
+            .decrypt_into(ciphertext, &mut decrypted, context())
+            .unwrap();
+        assert_eq!(input, decrypted);
+
+        let encrypting_key = EncryptingKey::cbc(key()).unwrap();
+        assert!(encrypting_key
+            .encrypt_into(&input[..17], &mut [0u8; 32])
+            .is_err());
+    }
+
+    #[test]
+    fn test_padded_encrypt_into() {
+        let key = [0x42u8; 16];
+        let key = || UnboundCipherKey::new(&AES_128, &key).unwrap();
+        let input: Vec<u8> = (0..20).collect();
+
+        for (encrypting_key, decrypting_key) in [
+            (
+                PaddedBlockEncryptingKey::cbc_pkcs7 as fn(_) -> _,
+                PaddedBlockDecryptingKey::cbc_pkcs7 as fn(_) -> _,
+            ),
+            (
+                PaddedBlockEncryptingKey::cbc_iso7816,
+                PaddedBlockDecryptingKey::cbc_iso7816,
+            ),
+            (
+                PaddedBlockEncryptingKey::cbc_ansi_x923,
+                PaddedBlockDecryptingKey::cbc_ansi_x923,
+            ),
+        ] {
+            let encrypting_key = encrypting_key(key()).unwrap();
+            let decrypting_key = decrypting_key(key()).unwrap();
+            assert_eq!(32, encrypting_key.ciphertext_len(input.len()));
+            assert_eq!(48, encrypting_key.ciphertext_len(32));
+            assert!(encrypting_key.encrypt_into(&input, &mut [0u8; 31]).is_err());
+
+            // The padding is written in full, whatever the output held before.
+            let mut output = [0xffu8; 32];
+            let (context, ciphertext) = encrypting_key.encrypt_into(&input, &mut output).unwrap();
+            assert_eq!(32, ciphertext.len());
+            let iv: &[u8] = (&context).try_into().unwrap();
+            let context = || DecryptionContext::Iv128(FixedLength::try_from(iv).unwrap());
+
+            let mut in_out = ciphertext.to_vec();
+            let mut decrypted = [0u8; 32];
+            assert!(decrypting_key
+                .decrypt_into(&in_out, &mut decrypted[..31], context())
+                .is_err());
+            assert_eq!(
+                input,
+                decrypting_key
+                    .decrypt_into(&in_out, &mut decrypted, context())
+                    .unwrap()
+            );
+            assert_eq!(
+                input,
+                decrypting_key.decrypt(&mut in_out, context()).unwrap()
+            );
+        }
+
+        let encrypting_key = PaddedBlockEncryptingKey::cbc_no_padding(key()).unwrap();
+        assert_eq!(32, encrypting_key.ciphertext_len(32));
+        let mut output = [0u8; 32];
+        let (_, ciphertext) = encrypting_key
+            .encrypt_into(&[0u8; 32], &mut output)
+            .unwrap();
+        assert_eq!(32, ciphertext.len());
+        assert!(encrypting_key.encrypt_into(&input, &mut [0u8; 32]).is_err());
+    }
+
+    #[test]
+    fn test_no_padding() {
+        let mut in_out = vec![1u8; 16];
+        PaddingStrategy::None.add_padding(16, &mut in_out).unwrap();
+        assert_eq!(vec![1u8; 16], in_out);
+        assert_eq!(
+            16,
+            PaddingStrategy::None
+                .remove_padding(16, &mut in_out)
+                .unwrap()
+                .len()
+        );
+        assert!(PaddingStrategy::None
+            .remove_padding(16, &mut [])
+            .unwrap()
+            .is_empty());
+        assert!(PaddingStrategy::None
+            .remove_padding(16, &mut in_out[..15])
+            .is_err());
+    }
+
+    #[test]
+    fn test_ansi_x923_padding() {
+        let mut in_out = vec![1u8, 2, 3];
+        PaddingStrategy::AnsiX923
+            .add_padding(16, &mut in_out)
+            .unwrap();
+        assert_eq!(16, in_out.len());
+        assert_eq!([1u8, 2, 3], in_out[..3]);
+        assert!(in_out[3..15].iter().all(|b| *b == 0));
+        assert_eq!(13, in_out[15]);
+        assert_eq!(
+            3,
+            PaddingStrategy::AnsiX923
+                .remove_padding(16, &mut in_out)
+                .unwrap()
+                .len()
+        );
+
+        let mut in_out = vec![0u8; 16];
+        PaddingStrategy::AnsiX923
+            .add_padding(16, &mut in_out)
+            .unwrap();
+        assert_eq!(32, in_out.len());
+        assert_eq!(16, in_out[31]);
+
+        // A non-zero padding byte.
+        let mut block = [0u8; 16];
+        block[15] = 4;
+        block[13] = 1;
+        assert!(PaddingStrategy::AnsiX923
+            .remove_padding(16, &mut block)
+            .is_err());
+        // A length of zero or longer than the block.
+        block[15] = 0;
+        assert!(PaddingStrategy::AnsiX923
+            .remove_padding(16, &mut block)
+            .is_err());
+        block[15] = 17;
+        assert!(PaddingStrategy::AnsiX923
+            .remove_padding(16, &mut block)
+            .is_err());
+    }
+
+    #[test]
+    fn test_ansi_x923_padding_removal_matches_reference() {
+        fn reference(block_len: usize, in_out: &[u8]) -> Option<usize> {
+            let padding = *in_out.last()? as usize;
+            if in_out.len() < block_len || padding == 0 || padding > block_len {
+                return None;
+            }
+            let final_len = in_out.len() - padding;
+            if in_out[final_len..in_out.len() - 1]
+                .iter()
+                .any(|item| *item != 0)
+            {
+                return None;
+            }
+            Some(final_len)
+        }
+
+        for block_len in [8, 16] {
+            for padding in 0..=255u8 {
+                let mut base = vec![0x5au8; 2 * block_len];
+                for item in base.iter_mut().rev().take(padding as usize) {
+                    *item = 0;
+                }
+                base[2 * block_len - 1] = padding;
+                // Corrupt each byte of the last block in turn, as well as leaving it intact.
+                for corrupt in 0..=block_len {
+                    let mut in_out = base.clone();
+                    if corrupt < block_len {
+                        in_out[2 * block_len - 1 - corrupt] ^= 0x01;
+                    }
+                    let expected = reference(block_len, &in_out);
+                    let actual = PaddingStrategy::AnsiX923
+                        .remove_padding(block_len, &mut in_out)
+                        .map(|unpadded| unpadded.len());
+                    assert_eq!(expected, actual.ok(), "{block_len} {padding} {corrupt}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_padding_every_remainder() {
+        for block_len in [8, 16] {
+            for data_len in block_len..2 * block_len {
+                let padding_size = 2 * block_len - data_len;
+                #[allow(clippy::cast_possible_truncation)]
+                let padding_len = padding_size as u8;
+                for (strategy, expected_padding) in [
+                    (PaddingStrategy::PKCS7, vec![padding_len; padding_size]),
+                    (PaddingStrategy::ISO7816, {
+                        let mut padding = vec![0u8; padding_size];
+                        padding[0] = 0x80;
+                        padding
+                    }),
+                    (PaddingStrategy::AnsiX923, {
+                        let mut padding = vec![0u8; padding_size];
+                        padding[padding_size - 1] = padding_len;
+                        padding
+                    }),
+                ] {
+                    let mut in_out = Vec::with_capacity(2 * block_len);
+                    in_out.resize(data_len, 0x5a);
+                    let ptr = in_out.as_ptr();
+                    strategy.add_padding(block_len, &mut in_out).unwrap();
+
+                    assert_eq!(2 * block_len, in_out.len());
+                    assert_eq!(vec![0x5au8; data_len], in_out[..data_len]);
+                    assert_eq!(expected_padding, in_out[data_len..]);
+                    // No reallocation when the buffer already has room for the padding.
+                    assert_eq!(ptr, in_out.as_ptr());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pkcs7_padding_removal_matches_reference() {
+        fn reference(block_len: usize, in_out: &[u8]) -> Option<usize> {
+            let padding = *in_out.last()? as usize;
+            if in_out.len() < block_len || padding == 0 || padding > block_len {
+                return None;
+            }
+            let final_len = in_out.len() - padding;
+            if in_out[final_len..]
+                .iter()
+                .any(|item| *item as usize != padding)
+            {
+                return None;
+            }
+            Some(final_len)
+        }
+
+        for block_len in [8, 16] {
+            for padding in 0..=255u8 {
+                let mut base = vec![0x5au8; 2 * block_len];
+                for item in base.iter_mut().rev().take(padding as usize) {
+                    *item = padding;
+                }
+                // Corrupt each byte of the last block in turn, as well as leaving it intact.
+                for corrupt in 0..=block_len {
+                    let mut in_out = base.clone();
+                    if corrupt < block_len {
+                        in_out[2 * block_len - 1 - corrupt] ^= 0x01;
+                    }
+                    let expected = reference(block_len, &in_out);
+                    let actual = PaddingStrategy::PKCS7
+                        .remove_padding(block_len, &mut in_out)
+                        .map(|unpadded| unpadded.len());
+                    assert_eq!(expected, actual.ok(), "{block_len} {padding} {corrupt}");
+                }
+            }
+        }
+
+        assert!(PaddingStrategy::PKCS7
+            .remove_padding(16, &mut [1u8; 15])
+            .is_err());
+        assert!(PaddingStrategy::PKCS7.remove_padding(16, &mut []).is_err());
+    }
+
+    #[test]
+    fn test_iso7816_padding() {
+        let mut in_out = vec![1u8, 2, 3];
+        PaddingStrategy::ISO7816
+            .add_padding(16, &mut in_out)
+            .unwrap();
+        assert_eq!(16, in_out.len());
+        assert_eq!([1u8, 2, 3, 0x80], in_out[..4]);
+        assert!(in_out[4..].iter().all(|b| *b == 0));
+
+        let mut in_out = vec![0u8; 16];
+        PaddingStrategy::ISO7816
+            .add_padding(16, &mut in_out)
+            .unwrap();
+        assert_eq!(32, in_out.len());
+        assert_eq!(0x80, in_out[16]);
+
+        let mut block = [0u8; 16];
+        block[5] = 0x80;
+        assert_eq!(
+            5,
+            PaddingStrategy::ISO7816
+                .remove_padding(16, &mut block)
+                .unwrap()
+                .len()
+        );
+
+        // No marker.
+        assert!(PaddingStrategy::ISO7816
+            .remove_padding(16, &mut [0u8; 16])
+            .is_err());
+        // A non-zero byte after the marker.
+        block[10] = 1;
+        assert!(PaddingStrategy::ISO7816
+            .remove_padding(16, &mut block)
+            .is_err());
+        // The marker is only searched for within the last block.
+        let mut two_blocks = [0u8; 32];
+        two_blocks[15] = 0x80;
+        assert!(PaddingStrategy::ISO7816
+            .remove_padding(16, &mut two_blocks)
+            .is_err());
+        // Shorter than a block.
+        assert!(PaddingStrategy::ISO7816
+            .remove_padding(16, &mut [0x80u8; 15])
+            .is_err());
+    }
+
+    #[test]
+    fn test_iso7816_padding_removal_matches_reference() {
+        fn reference(block_len: usize, in_out: &[u8]) -> Option<usize> {
+            if in_out.len() < block_len {
+                return None;
+            }
+            let last_block = &in_out[in_out.len() - block_len..];
+            let marker = last_block.iter().rposition(|item| *item != 0)?;
+            if last_block[marker] != 0x80 {
+                return None;
+            }
+            Some(in_out.len() - block_len + marker)
+        }
+
+        for block_len in [8, 16] {
+            for data_len in 0..block_len {
+                let mut base = vec![0x5au8; block_len + data_len];
+                PaddingStrategy::ISO7816
+                    .add_padding(block_len, &mut base)
+                    .unwrap();
+                // Set each byte of the last block to each of a few values in turn, as well as
+                // leaving it intact.
+                for corrupt in 0..=block_len {
+                    for value in [0x00u8, 0x01, 0x80, 0x81] {
+                        let mut in_out = base.clone();
+                        if corrupt < block_len {
+                            let index = in_out.len() - 1 - corrupt;
+                            in_out[index] = value;
+                        }
+                        let expected = reference(block_len, &in_out);
+                        let actual = PaddingStrategy::ISO7816
+                            .remove_padding(block_len, &mut in_out)
+                            .map(|unpadded| unpadded.len());
+                        assert_eq!(
+                            expected,
+                            actual.ok(),
+                            "{block_len} {data_len} {corrupt} {value}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_remove_padding_zeroizes_padding() {
+        let plaintext = [0x5au8; 21];
+        for strategy in [
+            PaddingStrategy::PKCS7,
+            PaddingStrategy::ISO7816,
+            PaddingStrategy::AnsiX923,
+        ] {
+            let mut in_out = plaintext.to_vec();
+            strategy.add_padding(16, &mut in_out).unwrap();
+            assert_eq!(
+                &plaintext[..],
+                strategy.remove_padding(16, &mut in_out).unwrap()
+            );
+            assert!(in_out[plaintext.len()..].iter().all(|b| *b == 0));
+
+            // The padding left in the caller's buffer by a decrypting key is zeroized too.
+            let key = || UnboundCipherKey::new(&AES_128, &[0x42u8; 16]).unwrap();
+            let encrypting_key =
+                PaddedBlockEncryptingKey::new(key(), OperatingMode::CBC, strategy).unwrap();
+            let decrypting_key =
+                PaddedBlockDecryptingKey::new(key(), OperatingMode::CBC, strategy).unwrap();
+            let mut in_out = plaintext.to_vec();
+            let context = encrypting_key.encrypt(&mut in_out).unwrap();
+            let decrypted_len = decrypting_key.decrypt(&mut in_out, context).unwrap().len();
+            assert_eq!(plaintext.len(), decrypted_len);
+            assert_eq!(plaintext, in_out[..decrypted_len]);
+            assert!(in_out[decrypted_len..].iter().all(|b| *b == 0));
+        }
+    }
+
+    #[test]
+    fn test_aes_192_ctr() {
+        let key = from_hex("000102030405060708090a0b0c0d0e0f0001020304050607").unwrap();
+        for i in 0..=50 {
+            helper_test_cipher_n_bytes(key.as_slice(), &AES_192, OperatingMode::CTR, i);
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "approved-only"))]
+    fn test_triple_des_cbc() {
+        let key = from_hex("0123456789abcdef23456789abcdef01456789abcdef0123").unwrap();
+        for i in 0..=50 {
+            helper_test_padded_cipher_n_bytes(
+                key.as_slice(),
+                &TRIPLE_DES_3KEY_FOR_LEGACY_USE_ONLY,
+                OperatingMode::CBC,
+                PaddingStrategy::PKCS7,
+                i,
+            );
+        }
+        for i in (0..=48).step_by(8) {
+            helper_test_cipher_n_bytes(
+                key.as_slice(),
+                &TRIPLE_DES_3KEY_FOR_LEGACY_USE_ONLY,
+                OperatingMode::CBC,
+                i,
+            );
+        }
+    }
+
+    #[test]
+    fn test_aes_ctr_counter_wrap() {
+        let key = [0x42u8; 16];
+        let key = || UnboundCipherKey::new(&AES_128, &key).unwrap();
+
+        let last_iv = [0xffu8; 16];
+        assert_eq!(1, aes_ctr_blocks_available(&last_iv));
+        assert_eq!(u128::MAX, aes_ctr_blocks_available(&[0u8; 16]));
+        assert_eq!(0, aes_ctr_blocks(0));
+        assert_eq!(1, aes_ctr_blocks(16));
+        assert_eq!(2, aes_ctr_blocks(17));
+
+        let encrypting_key = EncryptingKey::ctr(key()).unwrap();
+        let decrypting_key = DecryptingKey::ctr(key()).unwrap();
+        let context = |iv: [u8; 16]| EncryptionContext::Iv128(FixedLength::from(iv));
+
+        // The last counter value can be used for a single block, but not for two.
+        let mut in_out = [0u8; 16];
+        assert!(encrypting_key
+            .less_safe_encrypt(&mut in_out, context(last_iv))
+            .is_ok());
+        let mut in_out = [0u8; 17];
+        assert!(encrypting_key
+            .less_safe_encrypt(&mut in_out, context(last_iv))
+            .is_err());
+        assert_eq!([0u8; 17], in_out);
+        assert!(decrypting_key
+            .decrypt(&mut in_out, context(last_iv).into())
+            .is_err());
+
+        let mut iv = last_iv;
+        iv[15] = 0xfe;
+        let mut in_out = [0u8; 32];
+        assert!(encrypting_key
+            .less_safe_encrypt(&mut in_out, context(iv))
+            .is_ok());
+        let mut in_out = [0u8; 33];
+        assert!(encrypting_key
+            .less_safe_encrypt(&mut in_out, context(iv))
+            .is_err());
+    }
+
+    #[test]
+    fn test_aes_ctr_nonce_counter() {
+        let key = [0x42u8; 16];
+        let key = || UnboundCipherKey::new(&AES_128, &key).unwrap();
+        let encrypting_key = EncryptingKey::ctr(key()).unwrap();
+        let decrypting_key = DecryptingKey::ctr(key()).unwrap();
+        let nonce = [0x24u8; 12];
+        let counter_block = |counter: u32| {
+            let mut iv = [0u8; 16];
+            iv[..12].copy_from_slice(&nonce);
+            iv[12..].copy_from_slice(&counter.to_be_bytes());
+            EncryptionContext::Iv128(FixedLength::from(iv))
+        };
+
+        // The counter block is the nonce followed by the big-endian counter.
+        let plaintext: Vec<u8> = (0..100).collect();
+        for counter in [0, 1, 7, 0x0102_0304] {
+            let mut expected = plaintext.clone();
+            encrypting_key
+                .less_safe_encrypt(&mut expected, counter_block(counter))
+                .unwrap();
+            let mut in_out = plaintext.clone();
+            let context = encrypting_key
+                .less_safe_encrypt(
+                    &mut in_out,
+                    EncryptionContext::ctr_nonce_counter(&nonce, counter),
+                )
+                .unwrap();
+            assert_eq!(expected, in_out);
+            assert_eq!(
+                DecryptionContext::ctr_nonce_counter(&nonce, counter),
+                context
+            );
+            assert_eq!(
+                plaintext,
+                decrypting_key.decrypt(&mut in_out, context).unwrap()
+            );
+        }
+
+        // Two counter values are left before the counter would wrap into the nonce, so 32
+        // bytes can be processed but 33 cannot, although the same counter block as an `Iv128`
+        // carries into the nonce and allows it.
+        let context = || EncryptionContext::ctr_nonce_counter(&nonce, u32::MAX - 1);
+        let mut in_out = [0u8; 32];
+        assert!(encrypting_key
+            .less_safe_encrypt(&mut in_out, context())
+            .is_ok());
+        let mut in_out = [0u8; 33];
+        assert!(encrypting_key
+            .less_safe_encrypt(&mut in_out, context())
+            .is_err());
+        assert_eq!([0u8; 33], in_out);
+        assert!(decrypting_key
+            .decrypt(&mut in_out, context().into())
+            .is_err());
+        assert!(encrypting_key
+            .less_safe_encrypt(&mut in_out, counter_block(u32::MAX - 1))
+            .is_ok());
+
+        let last = || EncryptionContext::ctr_nonce_counter(&nonce, u32::MAX);
+        let mut in_out = [0u8; 17];
+        assert!(encrypting_key
+            .less_safe_encrypt(&mut in_out[..16], last())
+            .is_ok());
+        assert!(encrypting_key
+            .less_safe_encrypt(&mut in_out, last())
+            .is_err());
+
+        // The same limit applies to ranges and to streaming.
+        assert!(decrypting_key
+            .decrypt_range(&mut in_out[..16], context().into(), 16)
+            .is_ok());
+        assert!(decrypting_key
+            .decrypt_range(&mut in_out, context().into(), 16)
+            .is_err());
+        assert!(decrypting_key
+            .decrypt_range(&mut in_out[..0], context().into(), 32)
+            .is_err());
+
+        let mut streaming_key =
+            StreamingEncryptingKey::less_safe_new(EncryptingKey::ctr(key()).unwrap(), context())
+                .unwrap();
+        let mut output = [0u8; 32];
+        assert!(streaming_key.update(&[0u8; 20], &mut output).is_ok());
+        assert!(streaming_key.update(&[0u8; 12], &mut output).is_ok());
+        assert!(streaming_key.update(&[0u8; 1], &mut output).is_err());
+
+        // Other modes do not accept the context.
+        let mut in_out = [0u8; 16];
+        assert!(EncryptingKey::cbc(key())
+            .unwrap()
+            .less_safe_encrypt(&mut in_out, context())
+            .is_err());
+    }
 
     #[test]
-    fn test_debug() {
-        {
-            let aes_128_key_bytes = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
-            let cipher_key = UnboundCipherKey::new(&AES_128, aes_128_key_bytes.as_slice()).unwrap();
-            assert_eq!("UnboundCipherKey { algorithm: Algorithm { id: Aes128, key_len: 16, block_len: 16 } }", format!("{cipher_key:?}"));
+    fn test_decrypt_range() {
+        let key = [0x42u8; 16];
+        let key = || UnboundCipherKey::new(&AES_128, &key).unwrap();
+        let plaintext: Vec<u8> = (0..1000).map(|i| (i * 7) as u8).collect();
+
+        let mut ciphertext = plaintext.clone();
+        let context = EncryptingKey::ctr(key())
+            .unwrap()
+            .encrypt(&mut ciphertext)
+            .unwrap();
+        let iv: &[u8] = (&context).try_into().unwrap();
+        let context = || DecryptionContext::Iv128(FixedLength::try_from(iv).unwrap());
+
+        let decrypting_key = DecryptingKey::ctr(key()).unwrap();
+        let mut in_out = Vec::with_capacity(plaintext.len());
+        for offset in 0..=plaintext.len() {
+            for end in offset..=plaintext.len() {
+                in_out.clear();
+                in_out.extend_from_slice(&ciphertext[offset..end]);
+                let decrypted = decrypting_key
+                    .decrypt_range(&mut in_out, context(), offset as u64)
+                    .unwrap();
+                assert_eq!(&plaintext[offset..end], decrypted);
+            }
         }
 
-        {
-            let aes_256_key_bytes =
-                from_hex("000102030405060708090a0b0c0d0e0f000102030405060708090a0b0c0d0e0f")
-                    .unwrap();
-            let cipher_key = UnboundCipherKey::new(&AES_256, aes_256_key_bytes.as_slice()).unwrap();
-            assert_eq!("UnboundCipherKey { algorithm: Algorithm { id: Aes256, key_len: 32, block_len: 16 } }", format!("{cipher_key:?}"));
+        let mut in_out = [0u8; 16];
+        let cbc_key = DecryptingKey::cbc(key()).unwrap();
+        assert!(cbc_key.decrypt_range(&mut in_out, context(), 0).is_err());
+
+        // The range may end at, but not extend past, the end of the last counter block.
+        let last = || DecryptionContext::Iv128(FixedLength::from([0xffu8; 16]));
+        assert!(decrypting_key
+            .decrypt_range(&mut in_out[..1], last(), 15)
+            .is_ok());
+        assert!(decrypting_key
+            .decrypt_range(&mut in_out[..2], last(), 15)
+            .is_err());
+        assert!(decrypting_key
+            .decrypt_range(&mut in_out[..0], last(), 16)
+            .is_err());
+    }
+
+    #[test]
+    fn test_advanced_by_blocks() {
+        let mut iv = [0u8; 16];
+        iv[8..].copy_from_slice(&[0xff; 8]);
+        let context = DecryptionContext::Iv128(FixedLength::from(iv));
+
+        let mut expected = [0u8; 16];
+        expected[7] = 1;
+        expected[15] = 2;
+        assert_eq!(
+            DecryptionContext::Iv128(FixedLength::from(expected)),
+            context.advanced_by_blocks(3).unwrap()
+        );
+        assert_eq!(context, context.advanced_by_blocks(0).unwrap());
+
+        // The last counter value can be reached, but not advanced past.
+        let last = EncryptionContext::Iv128(FixedLength::from([0xffu8; 16]));
+        assert!(last.advanced_by_blocks(0).is_ok());
+        assert!(last.advanced_by_blocks(1).is_err());
+
+        // Only the 32-bit counter of a nonce and counter context is advanced.
+        let nonce = [0x24u8; 12];
+        assert_eq!(
+            DecryptionContext::ctr_nonce_counter(&nonce, 8),
+            DecryptionContext::ctr_nonce_counter(&nonce, 5)
+                .advanced_by_blocks(3)
+                .unwrap()
+        );
+        let last = DecryptionContext::ctr_nonce_counter(&nonce, u32::MAX);
+        assert!(last.advanced_by_blocks(0).is_ok());
+        assert!(last.advanced_by_blocks(1).is_err());
+        assert!(DecryptionContext::ctr_nonce_counter(&nonce, 0)
+            .advanced_by_blocks(1 << 32)
+            .is_err());
+
+        let nonce = DecryptionContext::Nonce96(FixedLength::from([0u8; 12]));
+        assert!(nonce.advanced_by_blocks(1).is_err());
+        assert!(DecryptionContext::None.advanced_by_blocks(1).is_err());
+    }
+
+    #[test]
+    fn test_debug_redacts_key_material() {
+        let key_hex = "4f2a9c61d0e7b3588a15c7e2f94b06d3";
+        let key = from_hex(key_hex).unwrap();
+        let unbound = || UnboundCipherKey::new(&AES_128, &key).unwrap();
+
+        let outputs = [
+            format!("{:?}", unbound()),
+            format!("{:?}", EncryptingKey::cbc(unbound()).unwrap()),
+            format!("{:?}", DecryptingKey::cbc(unbound()).unwrap()),
+            format!(
+                "{:?}",
+                PaddedBlockEncryptingKey::cbc_pkcs7(unbound()).unwrap()
+            ),
+            format!(
+                "{:?}",
+                PaddedBlockDecryptingKey::cbc_pkcs7(unbound()).unwrap()
+            ),
+        ];
+        for output in &outputs {
+            assert!(output.contains("Aes128"), "{output}");
+            assert!(!output.to_lowercase().contains(key_hex), "{output}");
         }
+        assert!(outputs[1].contains("CBC"));
+        assert!(outputs[3].contains("PKCS7"));
+
+        let iv = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+        let context = DecryptionContext::Iv128(FixedLength::try_from(iv.as_slice()).unwrap());
+        assert_eq!("Iv128", format!("{context:?}"));
+        let context = EncryptionContext::Iv128(FixedLength::try_from(iv.as_slice()).unwrap());
+        assert_eq!("Iv128", format!("{context:?}"));
+        assert_eq!("None", format!("{:?}", DecryptionContext::None));
+    }
 
-        {
-            let key_bytes = &[0u8; 16];
-            let key = PaddedBlockEncryptingKey::cbc_pkcs7(
-                UnboundCipherKey::new(&AES_128, key_bytes).unwrap(),
-            )
-            .unwrap();
-            assert_eq!("PaddedBlockEncryptingKey { key: UnboundCipherKey { algorithm: Algorithm { id: Aes128, key_len: 16, block_len: 16 } }, mode: CBC, padding: PKCS7 }", format!("{key:?}"));
-            let mut data = vec![0u8; 16];
-            let context = key.encrypt(&mut data).unwrap();
-            assert_eq!("Iv128", format!("{context:?}"));
-            let key = PaddedBlockDecryptingKey::cbc_pkcs7(
-                UnboundCipherKey::new(&AES_128, key_bytes).unwrap(),
-            )
-            .unwrap();
-            assert_eq!("PaddedBlockDecryptingKey { key: UnboundCipherKey { algorithm: Algorithm { id: Aes128, key_len: 16, block_len: 16 } }, mode: CBC, padding: PKCS7 }", format!("{key:?}"));
+    #[test]
+    fn test_generate() {
+        let rng = SystemRandom::new();
+        for algorithm in [&AES_128, &AES_192, &AES_256, &CHACHA20] {
+            let first = UnboundCipherKey::generate(algorithm, &rng).unwrap();
+            let second = UnboundCipherKey::generate(algorithm, &rng).unwrap();
+            assert_eq!(algorithm, first.algorithm());
+            assert_eq!(algorithm.key_len(), first.less_safe_key_bytes().len());
+            assert_ne!(first.less_safe_key_bytes(), second.less_safe_key_bytes());
         }
 
-        {
-            let key_bytes = &[0u8; 16];
-            let key =
-                EncryptingKey::ctr(UnboundCipherKey::new(&AES_128, key_bytes).unwrap()).unwrap();
-            assert_eq!("EncryptingKey { key: UnboundCipherKey { algorithm: Algorithm { id: Aes128, key_len: 16, block_len: 16 } }, mode: CTR }", format!("{key:?}"));
-            let mut data = vec![0u8; 16];
-            let context = key.encrypt(&mut data).unwrap();
-            assert_eq!("Iv128", format!("{context:?}"));
-            let key =
-                DecryptingKey::ctr(UnboundCipherKey::new(&AES_128, key_bytes).unwrap()).unwrap();
-            assert_eq!("DecryptingKey { key: UnboundCipherKey { algorithm: Algorithm { id: Aes128, key_len: 16, block_len: 16 } }, mode: CTR }", format!("{key:?}"));
+        let key = UnboundCipherKey::generate(&AES_256, &rng).unwrap();
+        let encrypting_key = PaddedBlockEncryptingKey::cbc_pkcs7(key.clone()).unwrap();
+        let decrypting_key = PaddedBlockDecryptingKey::cbc_pkcs7(key).unwrap();
+        let mut in_out = b"generated key".to_vec();
+        let context = encrypting_key.encrypt(&mut in_out).unwrap();
+        assert_eq!(
+            b"generated key",
+            decrypting_key.decrypt(&mut in_out, context).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_key_bytes_export() {
+        fn assert_zeroize_on_drop<T: ZeroizeOnDrop>(_: &T) {}
+
+        for (algorithm, key_len) in [
+            (&AES_128, 16),
+            (&AES_192, 24),
+            (&AES_256, 32),
+            (&CHACHA20, 32),
+        ] {
+            let key_bytes: Vec<u8> = (1..=key_len).collect();
+            let key = UnboundCipherKey::new(algorithm, &key_bytes).unwrap();
+            assert_zeroize_on_drop(&key);
+            assert_eq!(key_bytes, *key.clone().less_safe_key_bytes());
+
+            let exported: Zeroizing<Vec<u8>> = key.less_safe_key_bytes();
+            assert_eq!(key_bytes, *exported);
+
+            let imported = UnboundCipherKey::new(algorithm, &exported).unwrap();
+            assert_eq!(key_bytes, *imported.less_safe_key_bytes());
         }
     }
 
-    fn helper_test_cipher_n_bytes(
-        key: &[u8],
-        alg: &'static Algorithm,
-        mode: OperatingMode,
-        n: usize,
-    ) {
-        let mut input: Vec<u8> = Vec::with_capacity(n);
-        for i in 0..n {
-            let byte: u8 = i.try_into().unwrap();
-            input.push(byte);
+    #[test]
+    fn test_context_zeroize() {
+        fn assert_zeroize_on_drop<T: ZeroizeOnDrop>(_: &T) {}
+
+        let mut context = DecryptionContext::Nonce96Counter32(FixedLength::from([0xab; 12]), 7);
+        assert_zeroize_on_drop(&context);
+        context.zeroize();
+        match &context {
+            DecryptionContext::Nonce96Counter32(nonce, counter) => {
+                assert_eq!(&[0u8; 12], nonce.as_bytes());
+                assert_eq!(0, *counter);
+            }
+            _ => unreachable!(),
         }
 
-        let cipher_key = UnboundCipherKey::new(alg, key).unwrap();
-        let encrypting_key = EncryptingKey::new(cipher_key, mode).unwrap();
+        let mut context = EncryptionContext::Iv64(FixedLength::from([0xab; 8]));
+        assert_zeroize_on_drop(&context);
+        context.zeroize();
+        let bytes: &[u8] = (&context).try_into().unwrap();
+        assert_eq!(&[0u8; 8], bytes);
+
+        // The IV is zeroed in place when the context is dropped. The context is dropped in
+        // storage that outlives it, so the IV's memory can still be read afterwards.
+        let mut slot = core::mem::MaybeUninit::new(DecryptionContext::Iv128(FixedLength::from(
+            [0xab; IV_LEN_128_BIT],
+        )));
+        let iv_ptr = match unsafe { slot.assume_init_ref() } {
+            DecryptionContext::Iv128(iv) => iv.as_bytes().as_ptr(),
+            _ => unreachable!(),
+        };
+        unsafe { core::ptr::drop_in_place(slot.as_mut_ptr()) };
+        for i in 0..IV_LEN_128_BIT {
+            assert_eq!(0, unsafe { core::ptr::read_volatile(iv_ptr.add(i)) });
+        }
+    }
 
-        let mut in_out = input.clone();
-        let decrypt_iv = encrypting_key.encrypt(&mut in_out).unwrap();
+    #[test]
+    fn test_context_nonce_counter_to_bytes() {
+        let context = EncryptionContext::ctr_nonce_counter(&[0xab; IV_LEN_96_BIT], 7);
+        assert!(<&[u8]>::try_from(&context).is_err());
+        assert!(<[u8; IV_LEN_96_BIT]>::try_from(&context).is_err());
+        assert!(Vec::<u8>::try_from(&context).is_err());
+
+        let context = DecryptionContext::Nonce96(FixedLength::from([0xab; IV_LEN_96_BIT]));
+        let bytes: [u8; IV_LEN_96_BIT] = (&context).try_into().unwrap();
+        assert_eq!([0xab; IV_LEN_96_BIT], bytes);
+    }
 
-        if n > 5 {
-            // There's no more than a 1 in 2^48 chance that this will fail randomly
-            assert_ne!(input.as_slice(), in_out);
+    #[test]
+    fn test_algorithm_accessors() {
+        for (algorithm, id, key_len) in [
+            (&AES_128, AlgorithmId::Aes128, 16),
+            (&AES_256, AlgorithmId::Aes256, 32),
+        ] {
+            assert_eq!(&id, algorithm.id());
+            assert_eq!(key_len, algorithm.key_len());
+            assert_eq!(16, algorithm.block_len());
+            for mode in [
+                OperatingMode::CBC,
+                OperatingMode::CTR,
+                OperatingMode::CFB128,
+                OperatingMode::CFB8,
+                OperatingMode::OFB,
+            ] {
+                assert_eq!(Some(16), algorithm.iv_len(mode));
+            }
+            #[cfg(not(feature = "approved-only"))]
+            assert_eq!(Some(0), algorithm.iv_len(OperatingMode::ECB));
+            assert_eq!(None, algorithm.iv_len(OperatingMode::Stream));
         }
+        assert_eq!(Some(12), CHACHA20.iv_len(OperatingMode::Stream));
+        #[cfg(not(feature = "approved-only"))]
+        assert_eq!(
+            Some(8),
+            TRIPLE_DES_3KEY_FOR_LEGACY_USE_ONLY.iv_len(OperatingMode::CBC)
+        );
 
-        let cipher_key2 = UnboundCipherKey::new(alg, key).unwrap();
-        let decrypting_key = DecryptingKey::new(cipher_key2, mode).unwrap();
+        // The lengths agree with the contexts generated for encryption.
+        for algorithm in [
+            &AES_128,
+            &AES_192,
+            &AES_256,
+            &CHACHA20,
+            #[cfg(not(feature = "approved-only"))]
+            &TRIPLE_DES_3KEY_FOR_LEGACY_USE_ONLY,
+        ] {
+            for mode in [
+                OperatingMode::CBC,
+                OperatingMode::CTR,
+                OperatingMode::CFB128,
+                OperatingMode::CFB8,
+                OperatingMode::OFB,
+                #[cfg(not(feature = "approved-only"))]
+                OperatingMode::ECB,
+                OperatingMode::Stream,
+            ] {
+                match (
+                    algorithm.iv_len(mode),
+                    algorithm.new_encryption_context(mode),
+                ) {
+                    (None, context) => assert!(context.is_err()),
+                    (Some(0), context) => {
+                        assert!(matches!(context, Ok(EncryptionContext::None)));
+                    }
+                    (Some(iv_len), context) => {
+                        let context = context.unwrap();
+                        let iv: &[u8] = (&context).try_into().unwrap();
+                        assert_eq!(iv_len, iv.len());
+                    }
+                }
+            }
+        }
 
-        let plaintext = decrypting_key.decrypt(&mut in_out, decrypt_iv).unwrap();
-        assert_eq!(input.as_slice(), plaintext);
+        let ids: std::collections::HashSet<AlgorithmId> = [&AES_128, &AES_256, &AES_128]
+            .iter()
+            .map(|algorithm| *algorithm.id())
+            .collect();
+        assert_eq!(2, ids.len());
     }
 
-    fn helper_test_padded_cipher_n_bytes(
-        key: &[u8],
-        alg: &'static Algorithm,
-        mode: OperatingMode,
-        padding: PaddingStrategy,
-        n: usize,
-    ) {
-        let mut input: Vec<u8> = Vec::with_capacity(n);
-        for i in 0..n {
-            let byte: u8 = i.try_into().unwrap();
-            input.push(byte);
+    #[test]
+    #[cfg(not(feature = "approved-only"))]
+    fn test_triple_des_context() {
+        let key = from_hex("0123456789abcdef23456789abcdef01456789abcdef0123").unwrap();
+        let algorithm = &TRIPLE_DES_3KEY_FOR_LEGACY_USE_ONLY;
+        assert!(UnboundCipherKey::new(algorithm, &key[..16]).is_err());
+
+        for mode in [
+            OperatingMode::CTR,
+            OperatingMode::CFB128,
+            OperatingMode::OFB,
+            OperatingMode::ECB,
+        ] {
+            assert!(algorithm.new_encryption_context(mode).is_err());
         }
+        assert!(matches!(
+            algorithm.new_encryption_context(OperatingMode::CBC),
+            Ok(EncryptionContext::Iv64(_))
+        ));
+
+        let encrypting_key =
+            EncryptingKey::cbc(UnboundCipherKey::new(algorithm, &key).unwrap()).unwrap();
+        let mut in_out = [0u8; 16];
+        let context = EncryptionContext::Iv128(FixedLength::from([0u8; 16]));
+        assert!(encrypting_key
+            .less_safe_encrypt(&mut in_out, context)
+            .is_err());
+        let mut in_out = [0u8; 12];
+        assert!(encrypting_key.encrypt(&mut in_out).is_err());
+    }
 
-        let cipher_key = UnboundCipherKey::new(alg, key).unwrap();
-        let encrypting_key = PaddedBlockEncryptingKey::new(cipher_key, mode, padding).unwrap();
+    #[test]
+    fn test_aes_128_ctr() {
+        let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+        for i in 0..=50 {
+            helper_test_cipher_n_bytes(key.as_slice(), &AES_128, OperatingMode::CTR, i);
+        }
+    }
 
-        let mut in_out = input.clone();
-        let decrypt_iv = encrypting_key.encrypt(&mut in_out).unwrap();
+    #[test]
+    fn test_aes_256_ctr() {
+        let key =
+            from_hex("000102030405060708090a0b0c0d0e0f000102030405060708090a0b0c0d0e0f").unwrap();
+        for i in 0..=50 {
+            helper_test_cipher_n_bytes(key.as_slice(), &AES_256, OperatingMode::CTR, i);
+        }
+    }
 
-        if n > 5 {
-            // There's no more than a 1 in 2^48 chance that this will fail randomly
-            assert_ne!(input.as_slice(), in_out);
+    #[test]
+    fn test_aes_128_cfb128() {
+        let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+        for i in 0..=50 {
+            helper_test_cipher_n_bytes(key.as_slice(), &AES_128, OperatingMode::CFB128, i);
         }
+    }
 
-        let cipher_key2 = UnboundCipherKey::new(alg, key).unwrap();
-        let decrypting_key = PaddedBlockDecryptingKey::new(cipher_key2, mode, padding).unwrap();
+    #[test]
+    fn test_aes_256_cfb128() {
+        let key =
+            from_hex("000102030405060708090a0b0c0d0e0f000102030405060708090a0b0c0d0e0f").unwrap();
+        for i in 0..=50 {
+            helper_test_cipher_n_bytes(key.as_slice(), &AES_256, OperatingMode::CFB128, i);
+        }
+    }
 
-        let plaintext = decrypting_key.decrypt(&mut in_out, decrypt_iv).unwrap();
-        assert_eq!(input.as_slice(), plaintext);
+    #[test]
+    #[cfg(not(feature = "approved-only"))]
+    fn test_aes_ecb() {
+        let key =
+            from_hex("000102030405060708090a0b0c0d0e0f000102030405060708090a0b0c0d0e0f").unwrap();
+        for (alg, key_len) in [(&AES_128, 16), (&AES_192, 24), (&AES_256, 32)] {
+            for i in [0, 16, 32, 48, 64] {
+                helper_test_cipher_n_bytes(&key[..key_len], alg, OperatingMode::ECB, i);
+            }
+        }
     }
 
     #[test]
-    fn test_aes_128_cbc() {
+    #[cfg(not(feature = "approved-only"))]
+    fn test_ecb_context() {
+        let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+        let encrypting_key =
+            EncryptingKey::less_safe_ecb(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+        let mut in_out = [0u8; 16];
+        assert!(encrypting_key
+            .less_safe_encrypt(
+                &mut in_out,
+                EncryptionContext::Iv128(FixedLength::from([0u8; 16]))
+            )
+            .is_err());
+
+        let decrypting_key =
+            DecryptingKey::less_safe_ecb(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+        assert!(decrypting_key
+            .decrypt(
+                &mut in_out,
+                DecryptionContext::Iv128(FixedLength::from([0u8; 16]))
+            )
+            .is_err());
+        assert!(decrypting_key
+            .decrypt(&mut in_out, DecryptionContext::None)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_aes_128_cfb8() {
         let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
         for i in 0..=50 {
-            helper_test_padded_cipher_n_bytes(
-                key.as_slice(),
-                &AES_128,
-                OperatingMode::CBC,
-                PaddingStrategy::PKCS7,
-                i,
-            );
+            helper_test_cipher_n_bytes(key.as_slice(), &AES_128, OperatingMode::CFB8, i);
         }
     }
 
     #[test]
-    fn test_aes_256_cbc() {
+    fn test_aes_256_cfb8() {
         let key =
             from_hex("000102030405060708090a0b0c0d0e0f000102030405060708090a0b0c0d0e0f").unwrap();
         for i in 0..=50 {
-            helper_test_padded_cipher_n_bytes(
-                key.as_slice(),
-                &AES_256,
-                OperatingMode::CBC,
-                PaddingStrategy::PKCS7,
-                i,
-            );
+            helper_test_cipher_n_bytes(key.as_slice(), &AES_256, OperatingMode::CFB8, i);
         }
     }
 
     #[test]
-    fn test_aes_128_ctr() {
+    fn test_aes_128_ofb() {
         let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
         for i in 0..=50 {
-            helper_test_cipher_n_bytes(key.as_slice(), &AES_128, OperatingMode::CTR, i);
+            helper_test_cipher_n_bytes(key.as_slice(), &AES_128, OperatingMode::OFB, i);
         }
     }
 
     #[test]
-    fn test_aes_256_ctr() {
+    fn test_aes_192_ofb() {
+        let key = from_hex("000102030405060708090a0b0c0d0e0f0001020304050607").unwrap();
+        for i in 0..=50 {
+            helper_test_cipher_n_bytes(key.as_slice(), &AES_192, OperatingMode::OFB, i);
+        }
+    }
+
+    #[test]
+    fn test_aes_256_ofb() {
         let key =
             from_hex("000102030405060708090a0b0c0d0e0f000102030405060708090a0b0c0d0e0f").unwrap();
         for i in 0..=50 {
-            helper_test_cipher_n_bytes(key.as_slice(), &AES_256, OperatingMode::CTR, i);
+            helper_test_cipher_n_bytes(key.as_slice(), &AES_256, OperatingMode::OFB, i);
+        }
+    }
+
+    #[test]
+    fn test_chacha20() {
+        let key =
+            from_hex("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f").unwrap();
+        for i in 0..=130 {
+            helper_test_cipher_n_bytes(key.as_slice(), &CHACHA20, OperatingMode::Stream, i);
+        }
+    }
+
+    #[test]
+    fn test_chacha20_mismatched_key_and_context() {
+        let chacha_key = [0u8; CHACHA20_KEY_LEN];
+        let aes_key = [0u8; AES_128_KEY_LEN];
+
+        assert!(
+            EncryptingKey::chacha20(UnboundCipherKey::new(&AES_128, &aes_key).unwrap()).is_err()
+        );
+        assert!(
+            DecryptingKey::chacha20(UnboundCipherKey::new(&AES_128, &aes_key).unwrap()).is_err()
+        );
+
+        // A ChaCha20 key can't be used with a block cipher mode.
+        let key =
+            EncryptingKey::ctr(UnboundCipherKey::new(&CHACHA20, &chacha_key).unwrap()).unwrap();
+        assert!(key.encrypt(&mut [0u8; 16]).is_err());
+
+        // A 96-bit nonce isn't a valid AES-CTR context, nor a 128-bit IV a valid ChaCha20 context.
+        let key = DecryptingKey::ctr(UnboundCipherKey::new(&AES_128, &aes_key).unwrap()).unwrap();
+        let nonce = DecryptionContext::Nonce96(FixedLength::from([0u8; IV_LEN_96_BIT]));
+        assert!(key.decrypt(&mut [0u8; 16], nonce).is_err());
+        let key = DecryptingKey::chacha20(UnboundCipherKey::new(&CHACHA20, &chacha_key).unwrap())
+            .unwrap();
+        let iv = DecryptionContext::Iv128(FixedLength::from([0u8; IV_LEN_128_BIT]));
+        assert!(key.decrypt(&mut [0u8; 16], iv).is_err());
+    }
+
+    #[test]
+    fn test_counter_nonce() {
+        use crate::iv::CounterBuilder;
+
+        let key_bytes = [0x42u8; 32];
+        let key =
+            EncryptingKey::chacha20(UnboundCipherKey::new(&CHACHA20, &key_bytes).unwrap()).unwrap();
+        let mut nonces = CounterBuilder::<IV_LEN_96_BIT>::new()
+            .identifier(&[0xA1, 0xB2, 0xC3, 0xD4])
+            .counter(41)
+            .limit(2)
+            .build()
+            .unwrap();
+
+        for counter in [41u8, 42] {
+            let mut expected_nonce = [0u8; IV_LEN_96_BIT];
+            expected_nonce[..4].copy_from_slice(&[0xA1, 0xB2, 0xC3, 0xD4]);
+            expected_nonce[11] = counter;
+
+            let mut in_out = [0u8; 32];
+            let context = key
+                .less_safe_encrypt(&mut in_out, nonces.advance().unwrap().into())
+                .unwrap();
+            let nonce: &[u8] = (&context).try_into().unwrap();
+            assert_eq!(&expected_nonce, nonce);
+
+            let mut expected = [0u8; 32];
+            key.less_safe_encrypt(
+                &mut expected,
+                EncryptionContext::Nonce96(expected_nonce.into()),
+            )
+            .unwrap();
+            assert_eq!(expected, in_out);
         }
+        assert!(nonces.advance().is_err());
     }
 
     macro_rules! padded_cipher_kat {
@@ -1256,4 +4677,122 @@ mod tests {
         "a39c1fdf77ea3e1f18178c0ec237c70a",
         "f1af484830a149ee0387b854d65fe87ca0e62efc1c8e6909d4b9ab8666470453"
     );
+
+    cipher_kat!(
+        test_sp800_38a_cfb128_aes128,
+        &AES_128,
+        OperatingMode::CFB128,
+        "2b7e151628aed2a6abf7158809cf4f3c",
+        "000102030405060708090a0b0c0d0e0f",
+        "6bc1bee22e409f96e93d7e117393172a",
+        "3b3fd92eb72dad20333449f8e83cfb4a"
+    );
+
+    cipher_kat!(
+        test_openssl_aes_128_cfb128_15_bytes,
+        &AES_128,
+        OperatingMode::CFB128,
+        "9aeefe39b0cc47f831a1d61a87422583",
+        "9b5fba9cab3eab33a1ac21332ab59ece",
+        "fa305d52dc424c60b09b4d79bc717b",
+        "30cc28bce8cb0f706739461c71520e"
+    );
+
+    cipher_kat!(
+        test_openssl_aes_256_cfb128_15_bytes,
+        &AES_256,
+        OperatingMode::CFB128,
+        "b064131a2b9d0e89e462399de04ae409c96cb5d4c13bb68270f01b6f189c59fe",
+        "f46a1d6f5d7c1e2dce5297ea22a49084",
+        "3d7a4894739cc0cd30d6c449128e63",
+        "f1043eb1bdd91deecc696987046ff2"
+    );
+
+    // NIST SP 800-38A, F.2.3 CBC-AES192.Encrypt
+    cipher_kat!(
+        test_sp800_38a_cbc_aes192,
+        &AES_192,
+        OperatingMode::CBC,
+        "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+        "000102030405060708090a0b0c0d0e0f",
+        "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+        "4f021db243bc633d7178183a9fa071e8b4d9ada9ad7dedf4e5e738763f69145a571b242012fb7ae07fa9baac3df102e008b0e27988598881d920a9e64f5615cd"
+    );
+
+    // NIST SP 800-38A, F.5.3 CTR-AES192.Encrypt
+    cipher_kat!(
+        test_sp800_38a_ctr_aes192,
+        &AES_192,
+        OperatingMode::CTR,
+        "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+        "f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff",
+        "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+        "1abc932417521ca24f2b0459fe7e6e0b090339ec0aa6faefd5ccc2c6f4ce8e941e36b26bd1ebc670d1bd1d665620abf74f78a7f6d29809585a97daec58c6b050"
+    );
+
+    // NIST SP 800-38A, F.4.1 OFB-AES128.Encrypt
+    cipher_kat!(
+        test_sp800_38a_ofb_aes128,
+        &AES_128,
+        OperatingMode::OFB,
+        "2b7e151628aed2a6abf7158809cf4f3c",
+        "000102030405060708090a0b0c0d0e0f",
+        "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+        "3b3fd92eb72dad20333449f8e83cfb4a7789508d16918f03f53c52dac54ed8259740051e9c5fecf64344f7a82260edcc304c6528f659c77866a510d9c1d6ae5e"
+    );
+
+    // NIST SP 800-38A, F.4.3 OFB-AES192.Encrypt
+    cipher_kat!(
+        test_sp800_38a_ofb_aes192,
+        &AES_192,
+        OperatingMode::OFB,
+        "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+        "000102030405060708090a0b0c0d0e0f",
+        "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+        "cdc80d6fddf18cab34c25909c99a4174fcc28b8d4c63837c09e81700c11004018d9a9aeac0f6596f559c6d4daf59a5f26d9f200857ca6c3e9cac524bd9acc92a"
+    );
+
+    // NIST SP 800-38A, F.4.5 OFB-AES256.Encrypt
+    cipher_kat!(
+        test_sp800_38a_ofb_aes256,
+        &AES_256,
+        OperatingMode::OFB,
+        "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+        "000102030405060708090a0b0c0d0e0f",
+        "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+        "dc7e84bfda79164b7ecd8486985d38604febdc6740d20b3ac88f6ad82a4fb08d71ab47a086e86eedf39d1c5bba97c4080126141d67f37be8538f5a8be740e484"
+    );
+
+    // NIST SP 800-38A, F.3.7 CFB8-AES128.Encrypt
+    cipher_kat!(
+        test_sp800_38a_cfb8_aes128,
+        &AES_128,
+        OperatingMode::CFB8,
+        "2b7e151628aed2a6abf7158809cf4f3c",
+        "000102030405060708090a0b0c0d0e0f",
+        "6bc1bee22e409f96e93d7e117393172aae2d",
+        "3b79424c9c0dd436bace9e0ed4586a4f32b9"
+    );
+
+    // NIST SP 800-38A, F.3.9 CFB8-AES192.Encrypt
+    cipher_kat!(
+        test_sp800_38a_cfb8_aes192,
+        &AES_192,
+        OperatingMode::CFB8,
+        "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+        "000102030405060708090a0b0c0d0e0f",
+        "6bc1bee22e409f96e93d7e117393172aae2d",
+        "cda2521ef0a905ca44cd057cbf0d47a0678a"
+    );
+
+    // NIST SP 800-38A, F.3.11 CFB8-AES256.Encrypt
+    cipher_kat!(
+        test_sp800_38a_cfb8_aes256,
+        &AES_256,
+        OperatingMode::CFB8,
+        "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+        "000102030405060708090a0b0c0d0e0f",
+        "6bc1bee22e409f96e93d7e117393172aae2d",
+        "dc1f1a8520a64db55fcc8ac554844e889700"
+    );
 }
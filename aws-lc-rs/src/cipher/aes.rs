@@ -7,7 +7,7 @@ use crate::{
     cipher::block::{Block, BLOCK_LEN},
     fips::indicator_check,
 };
-use aws_lc::{AES_ecb_encrypt, AES_ENCRYPT, AES_KEY};
+use aws_lc::{AES_ecb_encrypt, AES_DECRYPT, AES_ENCRYPT, AES_KEY};
 use core::mem::MaybeUninit;
 
 /// Length of an AES-128 key in bytes.
@@ -32,3 +32,20 @@ pub(crate) fn encrypt_block_aes(aes_key: &AES_KEY, block: Block) -> Block {
         Block::from(&cipher_text.assume_init())
     }
 }
+
+#[inline]
+pub(crate) fn decrypt_block_aes(aes_key: &AES_KEY, block: Block) -> Block {
+    unsafe {
+        let mut plain_text = MaybeUninit::<[u8; BLOCK_LEN]>::uninit();
+        let cipher_bytes = block.as_ref();
+
+        indicator_check!(AES_ecb_encrypt(
+            cipher_bytes.as_ptr(),
+            plain_text.as_mut_ptr().cast(),
+            aes_key,
+            AES_DECRYPT,
+        ));
+
+        Block::from(&plain_text.assume_init())
+    }
+}
@@ -0,0 +1,521 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use super::{
+    Algorithm, CipherError, DecryptingKey, DecryptionContext, EncryptingKey, EncryptionContext,
+    OperatingMode, PaddedBlockDecryptingKey, PaddedBlockEncryptingKey, PaddingStrategy,
+    UnboundCipherKey,
+};
+use core::fmt;
+
+/// Constructs cipher keys from a runtime configuration.
+///
+/// The algorithm, operating mode and padding are validated together when the key is built,
+/// rather than by choosing among the constructors of the padded and unpadded key types. A key
+/// can be built from raw key bytes, or from an existing [`UnboundCipherKey`] using
+/// [`build_encryptor_with_key`](Builder::build_encryptor_with_key) and
+/// [`build_decryptor_with_key`](Builder::build_decryptor_with_key).
+///
+/// ```rust
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use aws_lc_rs::cipher::{Builder, OperatingMode, PaddingStrategy, AES_128};
+///
+/// let key_bytes = [0x42u8; 16];
+/// let mut in_out = Vec::from(&b"This is a secret message!"[..]);
+///
+/// let builder = Builder::new()
+///     .algorithm(&AES_128)
+///     .mode(OperatingMode::CBC)
+///     .padding(Some(PaddingStrategy::PKCS7));
+///
+/// let context = builder.clone().build_encryptor(&key_bytes)?.encrypt(&mut in_out)?;
+///
+/// let decryptor = builder.context(context).build_decryptor(&key_bytes)?;
+/// assert_eq!(b"This is a secret message!", decryptor.decrypt(&mut in_out)?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct Builder {
+    algorithm: Option<&'static Algorithm>,
+    mode: Option<OperatingMode>,
+    padding: Option<PaddingStrategy>,
+    context: Option<DecryptionContext>,
+}
+
+impl Builder {
+    /// Constructs a `Builder` with nothing configured.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the cipher algorithm. Required.
+    #[must_use]
+    pub fn algorithm(mut self, algorithm: &'static Algorithm) -> Self {
+        self.algorithm = Some(algorithm);
+        self
+    }
+
+    /// Sets the operating mode. Required.
+    #[must_use]
+    pub fn mode(mut self, mode: OperatingMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Sets the block padding, or `None` for no padding. Defaults to `None`.
+    ///
+    /// Padding is only supported in CBC mode.
+    #[must_use]
+    pub fn padding(mut self, padding: Option<PaddingStrategy>) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Sets the context (IV). Required for decryption.
+    ///
+    /// When building an encryptor, the context is used in place of a randomly generated one.
+    /// A context must never be used to encrypt more than one message with the same key.
+    #[must_use]
+    pub fn context<C: Into<DecryptionContext>>(mut self, context: C) -> Self {
+        self.context = Some(context.into());
+        self
+    }
+
+    /// Builds an [`Encryptor`] for `key_bytes`.
+    ///
+    /// # Errors
+    /// [`CipherError`] describing the first invalid or missing setting.
+    pub fn build_encryptor(self, key_bytes: &[u8]) -> Result<Encryptor, CipherError> {
+        let algorithm = self.algorithm.ok_or(CipherError::MissingAlgorithm)?;
+        self.encryptor(algorithm, || {
+            UnboundCipherKey::try_new_detailed(algorithm, key_bytes)
+        })
+    }
+
+    /// Builds an [`Encryptor`] for `key`, which determines the algorithm. Setting an
+    /// algorithm is optional, but if one is set it must be the key's algorithm.
+    ///
+    /// # Errors
+    /// [`CipherError`] describing the first invalid or missing setting.
+    pub fn build_encryptor_with_key(self, key: UnboundCipherKey) -> Result<Encryptor, CipherError> {
+        let algorithm = self.key_algorithm(&key)?;
+        self.encryptor(algorithm, || Ok(key))
+    }
+
+    /// Builds a [`Decryptor`] for `key_bytes`.
+    ///
+    /// # Errors
+    /// [`CipherError`] describing the first invalid or missing setting.
+    pub fn build_decryptor(self, key_bytes: &[u8]) -> Result<Decryptor, CipherError> {
+        let algorithm = self.algorithm.ok_or(CipherError::MissingAlgorithm)?;
+        self.decryptor(algorithm, || {
+            UnboundCipherKey::try_new_detailed(algorithm, key_bytes)
+        })
+    }
+
+    /// Builds a [`Decryptor`] for `key`, which determines the algorithm. Setting an
+    /// algorithm is optional, but if one is set it must be the key's algorithm.
+    ///
+    /// # Errors
+    /// [`CipherError`] describing the first invalid or missing setting.
+    pub fn build_decryptor_with_key(self, key: UnboundCipherKey) -> Result<Decryptor, CipherError> {
+        let algorithm = self.key_algorithm(&key)?;
+        self.decryptor(algorithm, || Ok(key))
+    }
+
+    fn encryptor<F>(self, algorithm: &'static Algorithm, key: F) -> Result<Encryptor, CipherError>
+    where
+        F: FnOnce() -> Result<UnboundCipherKey, CipherError>,
+    {
+        let mode = self.validate()?;
+        let context: Option<EncryptionContext> = self.context.map(EncryptionContext::from);
+        if let Some(context) = &context {
+            if !algorithm.is_valid_encryption_context(mode, context) {
+                return Err(CipherError::InvalidContext);
+            }
+        }
+        let key = key()?;
+        let key = match self.padding {
+            Some(padding) => {
+                EncryptorKey::Padded(PaddedBlockEncryptingKey::new(key, mode, padding)?)
+            }
+            None => EncryptorKey::Unpadded(EncryptingKey::new(key, mode)?),
+        };
+        Ok(Encryptor { key, context })
+    }
+
+    fn decryptor<F>(self, algorithm: &'static Algorithm, key: F) -> Result<Decryptor, CipherError>
+    where
+        F: FnOnce() -> Result<UnboundCipherKey, CipherError>,
+    {
+        let mode = self.validate()?;
+        let context = self.context.ok_or(CipherError::MissingContext)?;
+        if !algorithm.is_valid_decryption_context(mode, &context) {
+            return Err(CipherError::InvalidContext);
+        }
+        let key = key()?;
+        let key = match self.padding {
+            Some(padding) => {
+                DecryptorKey::Padded(PaddedBlockDecryptingKey::new(key, mode, padding)?)
+            }
+            None => DecryptorKey::Unpadded(DecryptingKey::new(key, mode)?),
+        };
+        Ok(Decryptor { key, context })
+    }
+
+    fn key_algorithm(&self, key: &UnboundCipherKey) -> Result<&'static Algorithm, CipherError> {
+        let algorithm = key.algorithm();
+        match self.algorithm {
+            Some(configured) if configured != algorithm => Err(CipherError::AlgorithmMismatch),
+            _ => Ok(algorithm),
+        }
+    }
+
+    fn validate(&self) -> Result<OperatingMode, CipherError> {
+        let mode = self.mode.ok_or(CipherError::MissingMode)?;
+        if self.padding.is_some() && mode != OperatingMode::CBC {
+            return Err(CipherError::PaddingNotSupported(mode));
+        }
+        Ok(mode)
+    }
+}
+
+impl Clone for Builder {
+    fn clone(&self) -> Self {
+        Self {
+            algorithm: self.algorithm,
+            mode: self.mode,
+            padding: self.padding,
+            context: self.context.as_ref().map(|context| match context {
+                DecryptionContext::Iv128(iv) => DecryptionContext::Iv128(iv.as_ref().into()),
+                DecryptionContext::Iv64(iv) => DecryptionContext::Iv64(iv.as_ref().into()),
+                DecryptionContext::Nonce96(nonce) => {
+                    DecryptionContext::Nonce96(nonce.as_ref().into())
+                }
+                DecryptionContext::Nonce96Counter32(nonce, counter) => {
+                    DecryptionContext::Nonce96Counter32(nonce.as_ref().into(), *counter)
+                }
+                DecryptionContext::None => DecryptionContext::None,
+            }),
+        }
+    }
+}
+
+enum EncryptorKey {
+    Padded(PaddedBlockEncryptingKey),
+    Unpadded(EncryptingKey),
+}
+
+enum DecryptorKey {
+    Padded(PaddedBlockDecryptingKey),
+    Unpadded(DecryptingKey),
+}
+
+/// An encryption key built by [`Builder`], with or without padding.
+///
+/// An `Encryptor` encrypts a single message.
+pub struct Encryptor {
+    key: EncryptorKey,
+    context: Option<EncryptionContext>,
+}
+
+impl Encryptor {
+    /// Returns the cipher algorithm.
+    #[must_use]
+    pub fn algorithm(&self) -> &Algorithm {
+        match &self.key {
+            EncryptorKey::Padded(key) => key.algorithm(),
+            EncryptorKey::Unpadded(key) => key.algorithm(),
+        }
+    }
+
+    /// Returns the cipher operating mode.
+    #[must_use]
+    pub fn mode(&self) -> OperatingMode {
+        match &self.key {
+            EncryptorKey::Padded(key) => key.mode(),
+            EncryptorKey::Unpadded(key) => key.mode(),
+        }
+    }
+
+    /// Returns the padding strategy, if any.
+    #[must_use]
+    pub fn padding(&self) -> Option<PaddingStrategy> {
+        match &self.key {
+            EncryptorKey::Padded(key) => Some(key.padding),
+            EncryptorKey::Unpadded(_) => None,
+        }
+    }
+
+    /// Encrypts, and pads if configured, the data provided in `in_out` in-place, using the
+    /// configured context or a randomly generated one.
+    ///
+    /// # Errors
+    /// * [`CipherError::InvalidInputLength`]: Returned if the mode requires the unpadded input
+    ///   to be a multiple of the block length and `in_out` is not.
+    /// * [`CipherError::Unspecified`]: Returned if encryption fails.
+    pub fn encrypt<InOut>(self, in_out: &mut InOut) -> Result<DecryptionContext, CipherError>
+    where
+        InOut: AsMut<[u8]> + for<'a> Extend<&'a u8>,
+    {
+        let context = match self.context {
+            Some(context) => context,
+            None => self.algorithm().new_encryption_context(self.mode())?,
+        };
+        match &self.key {
+            EncryptorKey::Padded(key) => key.less_safe_encrypt_detailed(in_out, context),
+            EncryptorKey::Unpadded(key) => key.less_safe_encrypt_detailed(in_out.as_mut(), context),
+        }
+    }
+}
+
+impl fmt::Debug for Encryptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Encryptor")
+            .field("algorithm", self.algorithm())
+            .field("mode", &self.mode())
+            .field("padding", &self.padding())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A decryption key and context built by [`Builder`], with or without padding.
+///
+/// A `Decryptor` decrypts a single message.
+pub struct Decryptor {
+    key: DecryptorKey,
+    context: DecryptionContext,
+}
+
+impl Decryptor {
+    /// Returns the cipher algorithm.
+    #[must_use]
+    pub fn algorithm(&self) -> &Algorithm {
+        match &self.key {
+            DecryptorKey::Padded(key) => key.algorithm(),
+            DecryptorKey::Unpadded(key) => key.algorithm(),
+        }
+    }
+
+    /// Returns the cipher operating mode.
+    #[must_use]
+    pub fn mode(&self) -> OperatingMode {
+        match &self.key {
+            DecryptorKey::Padded(key) => key.mode(),
+            DecryptorKey::Unpadded(key) => key.mode(),
+        }
+    }
+
+    /// Returns the padding strategy, if any.
+    #[must_use]
+    pub fn padding(&self) -> Option<PaddingStrategy> {
+        match &self.key {
+            DecryptorKey::Padded(key) => Some(key.padding),
+            DecryptorKey::Unpadded(_) => None,
+        }
+    }
+
+    /// Decrypts, and unpads if configured, the data provided in `in_out` in-place.
+    /// Returns a reference to the decrypted data.
+    ///
+    /// # Errors
+    /// * [`CipherError::InvalidInputLength`]: Returned if the mode requires the input to be a
+    ///   multiple of the block length and `in_out` is not.
+    /// * [`CipherError::Unspecified`]: Returned if the padding is invalid, or if decryption
+    ///   fails.
+    pub fn decrypt(self, in_out: &mut [u8]) -> Result<&mut [u8], CipherError> {
+        match &self.key {
+            DecryptorKey::Padded(key) => key.decrypt_detailed(in_out, self.context),
+            DecryptorKey::Unpadded(key) => key.decrypt_detailed(in_out, self.context),
+        }
+    }
+}
+
+impl fmt::Debug for Decryptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Decryptor")
+            .field("algorithm", self.algorithm())
+            .field("mode", &self.mode())
+            .field("padding", &self.padding())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Builder, CipherError};
+    use crate::cipher::{
+        Algorithm, EncryptionContext, OperatingMode, PaddingStrategy, UnboundCipherKey, AES_128,
+        AES_256,
+    };
+    use crate::iv::FixedLength;
+
+    const PLAINTEXT: &[u8] = b"Builder-configured cipher round trip";
+
+    fn round_trip(
+        algorithm: &'static Algorithm,
+        mode: OperatingMode,
+        padding: Option<PaddingStrategy>,
+    ) {
+        let key_bytes = vec![0x5a; algorithm.key_len];
+        let builder = Builder::new()
+            .algorithm(algorithm)
+            .mode(mode)
+            .padding(padding);
+
+        // Unpadded CBC requires whole blocks.
+        let plaintext = match (mode, padding) {
+            (OperatingMode::CBC, None) => &PLAINTEXT[..32],
+            _ => PLAINTEXT,
+        };
+
+        let encryptor = builder.clone().build_encryptor(&key_bytes).unwrap();
+        assert_eq!(algorithm, encryptor.algorithm());
+        assert_eq!(mode, encryptor.mode());
+        assert_eq!(padding, encryptor.padding());
+        let mut in_out = plaintext.to_vec();
+        let context = encryptor.encrypt(&mut in_out).unwrap();
+        assert_ne!(plaintext, in_out.as_slice());
+
+        let decryptor = builder
+            .context(context)
+            .build_decryptor(&key_bytes)
+            .unwrap();
+        assert_eq!(algorithm, decryptor.algorithm());
+        assert_eq!(mode, decryptor.mode());
+        assert_eq!(padding, decryptor.padding());
+        assert_eq!(plaintext, decryptor.decrypt(&mut in_out).unwrap());
+    }
+
+    #[test]
+    fn test_valid_combinations() {
+        for algorithm in [&AES_128, &AES_256] {
+            round_trip(algorithm, OperatingMode::CBC, Some(PaddingStrategy::PKCS7));
+            round_trip(algorithm, OperatingMode::CBC, None);
+            round_trip(algorithm, OperatingMode::CTR, None);
+        }
+    }
+
+    #[test]
+    fn test_configured_encryption_context() {
+        let key_bytes = [0x5a; 16];
+        let iv = [0xa5; 16];
+        let builder = Builder::new().algorithm(&AES_128).mode(OperatingMode::CTR);
+
+        let mut first = PLAINTEXT.to_vec();
+        builder
+            .clone()
+            .context(EncryptionContext::Iv128(FixedLength::from(iv)))
+            .build_encryptor(&key_bytes)
+            .unwrap()
+            .encrypt(&mut first)
+            .unwrap();
+
+        let mut second = PLAINTEXT.to_vec();
+        builder
+            .context(EncryptionContext::Iv128(FixedLength::from(iv)))
+            .build_encryptor(&key_bytes)
+            .unwrap()
+            .encrypt(&mut second)
+            .unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_invalid_combinations() {
+        let key_bytes = [0x5a; 16];
+        let iv = || EncryptionContext::Iv128(FixedLength::from([0u8; 16]));
+
+        assert_eq!(
+            CipherError::MissingAlgorithm,
+            Builder::new()
+                .mode(OperatingMode::CTR)
+                .build_encryptor(&key_bytes)
+                .unwrap_err()
+        );
+        assert_eq!(
+            CipherError::MissingMode,
+            Builder::new()
+                .algorithm(&AES_128)
+                .build_encryptor(&key_bytes)
+                .unwrap_err()
+        );
+        assert_eq!(
+            CipherError::PaddingNotSupported(OperatingMode::CTR),
+            Builder::new()
+                .algorithm(&AES_128)
+                .mode(OperatingMode::CTR)
+                .padding(Some(PaddingStrategy::PKCS7))
+                .build_encryptor(&key_bytes)
+                .unwrap_err()
+        );
+        assert_eq!(
+            CipherError::MissingContext,
+            Builder::new()
+                .algorithm(&AES_128)
+                .mode(OperatingMode::CBC)
+                .padding(Some(PaddingStrategy::PKCS7))
+                .build_decryptor(&key_bytes)
+                .unwrap_err()
+        );
+        assert_eq!(
+            CipherError::InvalidKeyLength,
+            Builder::new()
+                .algorithm(&AES_256)
+                .mode(OperatingMode::CTR)
+                .context(iv())
+                .build_decryptor(&key_bytes)
+                .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_build_with_key() {
+        let key_bytes = [0x5a; 32];
+        let key = || UnboundCipherKey::new(&AES_256, &key_bytes).unwrap();
+        let builder = Builder::new()
+            .mode(OperatingMode::CBC)
+            .padding(Some(PaddingStrategy::PKCS7));
+
+        let encryptor = builder.clone().build_encryptor_with_key(key()).unwrap();
+        assert_eq!(&AES_256, encryptor.algorithm());
+        let mut in_out = PLAINTEXT.to_vec();
+        let context = encryptor.encrypt(&mut in_out).unwrap();
+
+        // A key built from bytes decrypts the same as one built from an `UnboundCipherKey`.
+        let decryptor = builder
+            .algorithm(&AES_256)
+            .context(context)
+            .build_decryptor(&key_bytes)
+            .unwrap();
+        assert_eq!(PLAINTEXT, decryptor.decrypt(&mut in_out).unwrap());
+
+        assert_eq!(
+            CipherError::AlgorithmMismatch,
+            Builder::new()
+                .algorithm(&AES_128)
+                .mode(OperatingMode::CTR)
+                .build_encryptor_with_key(key())
+                .unwrap_err()
+        );
+        assert_eq!(
+            CipherError::MissingMode,
+            Builder::new().build_decryptor_with_key(key()).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!(
+            "padding is not supported in CTR mode",
+            CipherError::PaddingNotSupported(OperatingMode::CTR).to_string()
+        );
+        assert_eq!(
+            "a context (IV) is required for decryption",
+            CipherError::MissingContext.to_string()
+        );
+    }
+}
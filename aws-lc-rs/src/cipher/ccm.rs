@@ -0,0 +1,391 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use crate::cipher::block::BLOCK_LEN;
+use crate::cipher::{Algorithm, AlgorithmId, UnboundCipherKey};
+use crate::error::Unspecified;
+use crate::ptr::LcPtr;
+use aws_lc::{
+    EVP_CIPHER_CTX_ctrl, EVP_CIPHER_CTX_new, EVP_CipherInit_ex, EVP_CipherUpdate,
+    EVP_EncryptFinal_ex, EVP_aes_128_ccm, EVP_aes_192_ccm, EVP_aes_256_ccm, EVP_CIPHER,
+    EVP_CIPHER_CTX, EVP_CTRL_AEAD_GET_TAG, EVP_CTRL_AEAD_SET_IVLEN, EVP_CTRL_AEAD_SET_TAG,
+};
+use core::fmt::Debug;
+use core::ptr::{null, null_mut};
+use std::os::raw::c_int;
+use zeroize::Zeroize;
+
+/// The shortest nonce accepted by AES-CCM, in bytes.
+const MIN_NONCE_LEN: usize = 7;
+
+/// The longest nonce accepted by AES-CCM, in bytes.
+const MAX_NONCE_LEN: usize = 13;
+
+/// The key for AES-CCM authenticated encryption, as specified by NIST SP 800-38C and
+/// RFC 3610.
+///
+/// AES-CCM authenticates the associated data and plaintext with CBC-MAC and encrypts the
+/// plaintext and the tag in CTR mode. The nonce must be between 7 and 13 bytes long, and
+/// the tag 4, 6, 8, 10, 12, 14 or 16 bytes long. A nonce must never be used more than once
+/// with the same key.
+///
+/// Not available with the `fips` feature.
+pub struct CcmKey {
+    key: UnboundCipherKey,
+}
+
+impl CcmKey {
+    /// Constructs a `CcmKey` from `key`.
+    ///
+    /// Only the encryption key schedule is used, so `key` can be constructed with
+    /// [`UnboundCipherKey::new_encrypt_only`].
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `key` is not an AES key.
+    pub fn new(key: UnboundCipherKey) -> Result<Self, Unspecified> {
+        match key.algorithm().id() {
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => {}
+            AlgorithmId::Chacha20 => return Err(Unspecified),
+            #[cfg(not(feature = "approved-only"))]
+            AlgorithmId::TripleDes3Key => return Err(Unspecified),
+        }
+        Ok(CcmKey { key })
+    }
+
+    /// Returns the cipher algorithm.
+    #[must_use]
+    pub fn algorithm(&self) -> &Algorithm {
+        self.key.algorithm()
+    }
+
+    fn cipher(&self) -> *const EVP_CIPHER {
+        unsafe {
+            match self.algorithm().id() {
+                AlgorithmId::Aes192 => EVP_aes_192_ccm(),
+                AlgorithmId::Aes256 => EVP_aes_256_ccm(),
+                _ => EVP_aes_128_ccm(),
+            }
+        }
+    }
+
+    /// Returns a context that is ready to encrypt, or with `tag` to decrypt, a message of
+    /// `len` bytes with `nonce`, after the associated data `aad` has been processed.
+    fn cipher_ctx(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        len: usize,
+        tag_len: usize,
+        tag: Option<&[u8]>,
+    ) -> Result<LcPtr<EVP_CIPHER_CTX>, Unspecified> {
+        let nonce_len = c_int::try_from(nonce.len()).map_err(|_| Unspecified)?;
+        let tag_len = c_int::try_from(tag_len).map_err(|_| Unspecified)?;
+        let aad_len = c_int::try_from(aad.len()).map_err(|_| Unspecified)?;
+        let len = c_int::try_from(len).map_err(|_| Unspecified)?;
+        let key_bytes = &self.key.key_bytes[..self.algorithm().key_len()];
+        let encrypt = c_int::from(tag.is_none());
+
+        let ctx = LcPtr::new(unsafe { EVP_CIPHER_CTX_new() })?;
+        let mut out_len: c_int = 0;
+        if 1 != unsafe {
+            EVP_CipherInit_ex(*ctx, self.cipher(), null_mut(), null(), null(), encrypt)
+        } || 1
+            != unsafe { EVP_CIPHER_CTX_ctrl(*ctx, EVP_CTRL_AEAD_SET_IVLEN, nonce_len, null_mut()) }
+            || 1 != unsafe {
+                // When encrypting, only the tag length is set.
+                EVP_CIPHER_CTX_ctrl(
+                    *ctx,
+                    EVP_CTRL_AEAD_SET_TAG,
+                    tag_len,
+                    tag.map_or(null_mut(), |tag| tag.as_ptr() as *mut _),
+                )
+            }
+            || 1 != unsafe {
+                EVP_CipherInit_ex(
+                    *ctx,
+                    null(),
+                    null_mut(),
+                    key_bytes.as_ptr(),
+                    nonce.as_ptr(),
+                    encrypt,
+                )
+            }
+            || 1 != unsafe {
+                // CCM needs the message length before it processes any input.
+                EVP_CipherUpdate(*ctx, null_mut(), &mut out_len, null(), len)
+            }
+        {
+            return Err(Unspecified);
+        }
+        if !aad.is_empty()
+            && 1 != unsafe {
+                EVP_CipherUpdate(*ctx, null_mut(), &mut out_len, aad.as_ptr(), aad_len)
+            }
+        {
+            return Err(Unspecified);
+        }
+        Ok(ctx)
+    }
+}
+
+impl Debug for CcmKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CcmKey")
+            .field("algorithm", self.algorithm())
+            .finish()
+    }
+}
+
+/// An AES-CCM key for sealing data.
+pub struct CcmEncryptingKey {
+    key: CcmKey,
+}
+
+impl CcmEncryptingKey {
+    /// Constructs a `CcmEncryptingKey` from `key`.
+    #[must_use]
+    pub fn new(key: CcmKey) -> Self {
+        CcmEncryptingKey { key }
+    }
+
+    /// Returns the cipher algorithm.
+    #[must_use]
+    pub fn algorithm(&self) -> &Algorithm {
+        self.key.algorithm()
+    }
+
+    /// Encrypts and authenticates `plaintext`, together with `aad`, using `nonce`. Returns the
+    /// ciphertext followed by a tag of `tag_len` bytes.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `nonce` is not between 7 and 13 bytes long, if `tag_len`
+    ///   is not one of 4, 6, 8, 10, 12, 14 or 16, or if `plaintext` is too long to have its
+    ///   length encoded with the remaining `15 - nonce.len()` bytes.
+    pub fn seal(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        plaintext: &[u8],
+        tag_len: usize,
+    ) -> Result<Vec<u8>, Unspecified> {
+        check_parameters(nonce, tag_len, plaintext.len())?;
+        let ctx = self
+            .key
+            .cipher_ctx(nonce, aad, plaintext.len(), tag_len, None)?;
+
+        let mut output = vec![0u8; plaintext.len() + tag_len];
+        let (ciphertext, tag) = output.split_at_mut(plaintext.len());
+        let mut out_len: c_int = 0;
+        #[allow(clippy::cast_possible_truncation)]
+        if 1 != unsafe {
+            EVP_CipherUpdate(
+                *ctx,
+                ciphertext.as_mut_ptr(),
+                &mut out_len,
+                plaintext.as_ptr(),
+                plaintext.len() as c_int,
+            )
+        } || 1 != unsafe { EVP_EncryptFinal_ex(*ctx, tag.as_mut_ptr(), &mut out_len) }
+            || 1 != unsafe {
+                EVP_CIPHER_CTX_ctrl(
+                    *ctx,
+                    EVP_CTRL_AEAD_GET_TAG,
+                    tag.len() as c_int,
+                    tag.as_mut_ptr().cast(),
+                )
+            }
+        {
+            output.zeroize();
+            return Err(Unspecified);
+        }
+
+        Ok(output)
+    }
+}
+
+impl Debug for CcmEncryptingKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CcmEncryptingKey")
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+/// An AES-CCM key for opening sealed data.
+pub struct CcmDecryptingKey {
+    key: CcmKey,
+}
+
+impl CcmDecryptingKey {
+    /// Constructs a `CcmDecryptingKey` from `key`.
+    #[must_use]
+    pub fn new(key: CcmKey) -> Self {
+        CcmDecryptingKey { key }
+    }
+
+    /// Returns the cipher algorithm.
+    #[must_use]
+    pub fn algorithm(&self) -> &Algorithm {
+        self.key.algorithm()
+    }
+
+    /// Verifies and decrypts `ciphertext_and_tag`, the output of [`CcmEncryptingKey::seal`]
+    /// with the same nonce, associated data and tag length. Returns the plaintext.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `nonce` or `tag_len` is not valid, if
+    ///   `ciphertext_and_tag` is shorter than `tag_len`, or if the tag does not match. A tag
+    ///   truncated to fewer bytes than it was sealed with does not match.
+    pub fn open(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        ciphertext_and_tag: &[u8],
+        tag_len: usize,
+    ) -> Result<Vec<u8>, Unspecified> {
+        let ciphertext_len = ciphertext_and_tag
+            .len()
+            .checked_sub(tag_len)
+            .ok_or(Unspecified)?;
+        check_parameters(nonce, tag_len, ciphertext_len)?;
+        let (ciphertext, tag) = ciphertext_and_tag.split_at(ciphertext_len);
+        let ctx = self
+            .key
+            .cipher_ctx(nonce, aad, ciphertext_len, tag_len, Some(tag))?;
+
+        // The tag is verified by the update, which fails without output if it does not match.
+        let mut plaintext = vec![0u8; ciphertext_len];
+        let mut out_len: c_int = 0;
+        #[allow(clippy::cast_possible_truncation)]
+        if 1 != unsafe {
+            EVP_CipherUpdate(
+                *ctx,
+                plaintext.as_mut_ptr(),
+                &mut out_len,
+                ciphertext.as_ptr(),
+                ciphertext_len as c_int,
+            )
+        } {
+            plaintext.zeroize();
+            return Err(Unspecified);
+        }
+
+        Ok(plaintext)
+    }
+}
+
+impl Debug for CcmDecryptingKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CcmDecryptingKey")
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+/// Checks the nonce and tag lengths, and that a plaintext of `len` bytes can have its length
+/// encoded in the bytes of the first block not taken by the nonce.
+fn check_parameters(nonce: &[u8], tag_len: usize, len: usize) -> Result<(), Unspecified> {
+    if !(MIN_NONCE_LEN..=MAX_NONCE_LEN).contains(&nonce.len())
+        || !(4..=16).contains(&tag_len)
+        || tag_len % 2 != 0
+    {
+        return Err(Unspecified);
+    }
+    let length_bits = 8 * (BLOCK_LEN - 1 - nonce.len());
+    if length_bits < 64 && (len as u64) >> length_bits != 0 {
+        return Err(Unspecified);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cipher::{AES_128, AES_256, CHACHA20};
+
+    fn keys(key_bytes: &[u8]) -> (CcmEncryptingKey, CcmDecryptingKey) {
+        let algorithm = if key_bytes.len() == 16 {
+            &AES_128
+        } else {
+            &AES_256
+        };
+        let key = || CcmKey::new(UnboundCipherKey::new(algorithm, key_bytes).unwrap()).unwrap();
+        (CcmEncryptingKey::new(key()), CcmDecryptingKey::new(key()))
+    }
+
+    #[test]
+    fn test_ccm_round_trip() {
+        for key_bytes in [&[1u8; 16][..], &[2u8; 32]] {
+            let (sealing_key, opening_key) = keys(key_bytes);
+            for nonce_len in MIN_NONCE_LEN..=MAX_NONCE_LEN {
+                let nonce = vec![3u8; nonce_len];
+                for tag_len in (4..=16).step_by(2) {
+                    for len in [0, 1, 15, 16, 17, 40] {
+                        let plaintext: Vec<u8> = (0..len).map(|i| i as u8).collect();
+                        for aad in [&b""[..], b"header"] {
+                            let sealed =
+                                sealing_key.seal(&nonce, aad, &plaintext, tag_len).unwrap();
+                            assert_eq!(len + tag_len, sealed.len());
+                            let opened = opening_key.open(&nonce, aad, &sealed, tag_len).unwrap();
+                            assert_eq!(plaintext, opened);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_ccm_open_rejects_modified_input() {
+        let (sealing_key, opening_key) = keys(&[1u8; 16]);
+        let nonce = [3u8; 12];
+        let sealed = sealing_key.seal(&nonce, b"header", b"message", 8).unwrap();
+
+        for i in 0..sealed.len() {
+            let mut modified = sealed.clone();
+            modified[i] ^= 1;
+            assert!(opening_key.open(&nonce, b"header", &modified, 8).is_err());
+        }
+        assert!(opening_key.open(&nonce, b"headex", &sealed, 8).is_err());
+        assert!(opening_key.open(&nonce, b"", &sealed, 8).is_err());
+        assert!(opening_key.open(&[4u8; 12], b"header", &sealed, 8).is_err());
+        assert!(opening_key
+            .open(&nonce, b"header", &sealed[..7], 8)
+            .is_err());
+    }
+
+    #[test]
+    fn test_ccm_rejects_truncated_tag() {
+        let (sealing_key, opening_key) = keys(&[1u8; 16]);
+        let nonce = [3u8; 13];
+        let sealed = sealing_key.seal(&nonce, b"", b"message", 16).unwrap();
+        assert!(opening_key.open(&nonce, b"", &sealed, 16).is_ok());
+
+        // The tag length is authenticated, so a prefix of the tag is not a valid shorter tag.
+        for tag_len in (4..16).step_by(2) {
+            let truncated = &sealed[..sealed.len() - 16 + tag_len];
+            assert!(opening_key.open(&nonce, b"", truncated, tag_len).is_err());
+        }
+        // Dropping the end of the tag while keeping its length shifts the ciphertext into it.
+        assert!(opening_key
+            .open(&nonce, b"", &sealed[..sealed.len() - 2], 16)
+            .is_err());
+    }
+
+    #[test]
+    fn test_ccm_rejects_invalid_parameters() {
+        let (sealing_key, opening_key) = keys(&[1u8; 16]);
+        for (nonce_len, tag_len) in [(6, 8), (14, 8), (12, 2), (12, 5), (12, 18), (12, 0)] {
+            let nonce = vec![0u8; nonce_len];
+            assert!(sealing_key.seal(&nonce, b"", b"message", tag_len).is_err());
+            assert!(opening_key.open(&nonce, b"", &[0u8; 32], tag_len).is_err());
+        }
+
+        // A 13-byte nonce leaves two bytes for the plaintext length.
+        let nonce = [0u8; 13];
+        assert!(sealing_key.seal(&nonce, b"", &[0u8; 0xffff], 4).is_ok());
+        assert!(sealing_key.seal(&nonce, b"", &[0u8; 0x10000], 4).is_err());
+
+        assert!(CcmKey::new(UnboundCipherKey::new(&CHACHA20, &[0u8; 32]).unwrap()).is_err());
+    }
+}
@@ -21,6 +21,12 @@ impl From<[u8; KEY_LEN]> for ChaCha20Key {
     }
 }
 
+impl Clone for ChaCha20Key {
+    fn clone(&self) -> Self {
+        ChaCha20Key(self.0)
+    }
+}
+
 impl Drop for ChaCha20Key {
     fn drop(&mut self) {
         self.0.zeroize();
@@ -0,0 +1,266 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! AES-CMAC, as specified by NIST SP 800-38B.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::cipher::cmac::{self, Cmac};
+//! use aws_lc_rs::cipher::{UnboundCipherKey, AES_128};
+//!
+//! let key_bytes = [0x42u8; 16];
+//!
+//! let mut cmac = Cmac::new(UnboundCipherKey::new(&AES_128, &key_bytes)?)?;
+//! cmac.update(b"hello, ");
+//! cmac.update(b"world");
+//! let tag = cmac.finalize();
+//!
+//! let key = UnboundCipherKey::new(&AES_128, &key_bytes)?;
+//! cmac::verify(&key, b"hello, world", tag.as_ref())?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::cipher::block::BLOCK_LEN;
+use crate::cipher::{Algorithm, AlgorithmId, UnboundCipherKey};
+use crate::constant_time;
+use crate::error::Unspecified;
+use crate::fips::indicator_check;
+use crate::ptr::LcPtr;
+use aws_lc::{
+    CMAC_CTX_new, CMAC_Final, CMAC_Init, CMAC_Update, EVP_aes_128_cbc, EVP_aes_192_cbc,
+    EVP_aes_256_cbc, CMAC_CTX,
+};
+use core::fmt::Debug;
+use core::ptr::null_mut;
+
+/// The length of an AES-CMAC tag.
+pub const CMAC_TAG_LEN: usize = BLOCK_LEN;
+
+/// An AES-CMAC tag.
+///
+/// For a given tag `t`, use `t.as_ref()` to get the tag value as a byte slice. Use [`verify`]
+/// or [`Cmac::verify`] rather than comparing tags directly.
+#[derive(Clone, Copy, Debug)]
+pub struct Tag(pub(super) [u8; CMAC_TAG_LEN]);
+
+impl AsRef<[u8]> for Tag {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// A context for multi-step (Init-Update-Finish) AES-CMAC computation.
+///
+/// The key schedule and intermediate state live in an AWS-LC `CMAC_CTX`, which clears them
+/// when the context is dropped.
+pub struct Cmac {
+    algorithm: &'static Algorithm,
+    ctx: LcPtr<CMAC_CTX>,
+}
+
+// The `CMAC_CTX` is only accessed through `&mut self` or when the context is consumed.
+unsafe impl Send for Cmac {}
+
+impl Cmac {
+    /// Constructs a new AES-CMAC context with `key`.
+    ///
+    // # FIPS
+    // Use this function with an `UnboundCipherKey` constructed with one of the following algorithms:
+    // * `AES_128`
+    // * `AES_256`
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `key` is not an AES key.
+    pub fn new(key: UnboundCipherKey) -> Result<Cmac, Unspecified> {
+        Self::with_key(&key)
+    }
+
+    pub(super) fn with_key(key: &UnboundCipherKey) -> Result<Cmac, Unspecified> {
+        let algorithm = key.algorithm();
+        let cipher = match algorithm.id() {
+            AlgorithmId::Aes128 => unsafe { EVP_aes_128_cbc() },
+            AlgorithmId::Aes192 => unsafe { EVP_aes_192_cbc() },
+            AlgorithmId::Aes256 => unsafe { EVP_aes_256_cbc() },
+            _ => return Err(Unspecified),
+        };
+        let key_bytes = &key.key_bytes[..algorithm.key_len()];
+
+        let ctx = LcPtr::new(unsafe { CMAC_CTX_new() })?;
+        if 1 != unsafe {
+            CMAC_Init(
+                *ctx,
+                key_bytes.as_ptr().cast(),
+                key_bytes.len(),
+                cipher,
+                null_mut(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+
+        Ok(Cmac { algorithm, ctx })
+    }
+
+    /// Returns the cipher algorithm.
+    #[must_use]
+    pub fn algorithm(&self) -> &Algorithm {
+        self.algorithm
+    }
+
+    /// Updates the CMAC with all the data in `data`. `update` may be called zero or more times
+    /// until `finalize` is called.
+    ///
+    /// # Panics
+    /// Panics if the CMAC cannot be updated
+    #[inline]
+    pub fn update(&mut self, data: &[u8]) {
+        self.try_update(data).expect("CMAC_Update failed");
+    }
+
+    #[inline]
+    pub(super) fn try_update(&mut self, data: &[u8]) -> Result<(), Unspecified> {
+        if 1 != unsafe { CMAC_Update(*self.ctx, data.as_ptr(), data.len()) } {
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
+
+    /// Finalizes the CMAC calculation and returns the tag.
+    ///
+    /// It is not safe to implement CMAC verification by comparing the returned tag to an
+    /// expected value. Use `verify` for verification instead.
+    ///
+    /// # Panics
+    /// Panics if the CMAC cannot be finalized
+    #[inline]
+    #[must_use]
+    pub fn finalize(self) -> Tag {
+        self.try_finalize().expect("CMAC_Final failed")
+    }
+
+    pub(super) fn try_finalize(self) -> Result<Tag, Unspecified> {
+        let mut tag = [0u8; CMAC_TAG_LEN];
+        let mut tag_len = 0usize;
+        if 1 != indicator_check!(unsafe { CMAC_Final(*self.ctx, tag.as_mut_ptr(), &mut tag_len) })
+            || tag_len != CMAC_TAG_LEN
+        {
+            return Err(Unspecified);
+        }
+        Ok(Tag(tag))
+    }
+
+    /// Finalizes the CMAC calculation and verifies, in constant time, that it matches `tag`.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if the tags do not match.
+    pub fn verify(self, tag: &[u8]) -> Result<(), Unspecified> {
+        constant_time::verify_slices_are_equal(self.try_finalize()?.as_ref(), tag)
+    }
+}
+
+impl Debug for Cmac {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Cmac")
+            .field("algorithm", &self.algorithm)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Calculates the AES-CMAC of `data` using `key` and verifies, in constant time, that it
+/// matches `tag`.
+///
+// # FIPS
+// Use this function with an `UnboundCipherKey` constructed with one of the following algorithms:
+// * `AES_128`
+// * `AES_256`
+//
+/// # Errors
+/// * [`Unspecified`]: Returned if `key` is not an AES key, or if the tags do not match.
+pub fn verify(key: &UnboundCipherKey, data: &[u8], tag: &[u8]) -> Result<(), Unspecified> {
+    constant_time::verify_slices_are_equal(&aes_cmac(key, data)?, tag)
+}
+
+/// Computes the AES-CMAC of `data` as specified by NIST SP 800-38B.
+pub(crate) fn aes_cmac(
+    key: &UnboundCipherKey,
+    data: &[u8],
+) -> Result<[u8; BLOCK_LEN], Unspecified> {
+    let mut cmac = Cmac::with_key(key)?;
+    cmac.try_update(data)?;
+    Ok(cmac.try_finalize()?.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{aes_cmac, Cmac};
+    use crate::cipher::{UnboundCipherKey, AES_128, AES_256, CHACHA20};
+    use crate::test::from_hex;
+
+    const MESSAGE: &str = "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51\
+                           30c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710";
+
+    #[test]
+    fn test_sp800_38b_aes_128() {
+        let key = from_hex("2b7e151628aed2a6abf7158809cf4f3c").unwrap();
+        let key = UnboundCipherKey::new(&AES_128, &key).unwrap();
+        let message = from_hex(MESSAGE).unwrap();
+        for (len, expected) in [
+            (0, "bb1d6929e95937287fa37d129b756746"),
+            (16, "070a16b46b4d4144f79bdd9dd04a287c"),
+            (40, "dfa66747de9ae63030ca32611497c827"),
+            (64, "51f0bebf7e3b9d92fc49741779363cfe"),
+        ] {
+            let tag = aes_cmac(&key, &message[..len]).unwrap();
+            assert_eq!(from_hex(expected).unwrap(), tag);
+        }
+    }
+
+    #[test]
+    fn test_sp800_38b_aes_256() {
+        let key =
+            from_hex("603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4").unwrap();
+        let key = UnboundCipherKey::new(&AES_256, &key).unwrap();
+        let message = from_hex(MESSAGE).unwrap();
+        for (len, expected) in [
+            (0, "028962f61b7bf89efc6b551f4667d983"),
+            (16, "28a7023f452e8f82bd4bf28d8c37c35c"),
+            (40, "aaf3d8f1de5640c232f5b169b9c911e6"),
+            (64, "e1992190549f6ed5696a2c056c315410"),
+        ] {
+            let tag = aes_cmac(&key, &message[..len]).unwrap();
+            assert_eq!(from_hex(expected).unwrap(), tag);
+        }
+    }
+
+    #[test]
+    fn test_incremental_matches_one_shot() {
+        let key_bytes = from_hex("2b7e151628aed2a6abf7158809cf4f3c").unwrap();
+        let key = UnboundCipherKey::new(&AES_128, &key_bytes).unwrap();
+        let message = from_hex(MESSAGE).unwrap();
+
+        for len in 0..=message.len() {
+            let expected = aes_cmac(&key, &message[..len]).unwrap();
+            for chunk_len in [1, 7, 15, 16, 17, 64] {
+                let mut cmac =
+                    Cmac::new(UnboundCipherKey::new(&AES_128, &key_bytes).unwrap()).unwrap();
+                for chunk in message[..len].chunks(chunk_len) {
+                    cmac.update(chunk);
+                    cmac.update(&[]);
+                }
+                assert_eq!(expected.as_slice(), cmac.finalize().as_ref());
+            }
+        }
+    }
+
+    #[test]
+    fn test_rejects_chacha20_key() {
+        let key = UnboundCipherKey::new(&CHACHA20, &[0u8; 32]).unwrap();
+        assert!(aes_cmac(&key, b"data").is_err());
+        assert!(Cmac::new(key).is_err());
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Single-block AES operations.
+//!
+//! These apply the AES permutation to one 16-byte block, for building constructions such as
+//! RFC 3394 key wrapping or subkey derivation that are specified in terms of it.
+//!
+//! # ⚠️ Not for encrypting data
+//!
+//! Encrypting data block by block with these functions is ECB mode, which encrypts equal
+//! blocks to equal ciphertext and so reveals patterns in the data. Use the modes of the
+//! [`cipher`](crate::cipher) module, or the [`aead`](crate::aead) module, to encrypt data.
+//!
+//! This module is not available with the `approved-only` feature.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::cipher::ecb::{aes_decrypt_block, aes_encrypt_block};
+//! use aws_lc_rs::cipher::{UnboundCipherKey, AES_128};
+//!
+//! let key = UnboundCipherKey::new(&AES_128, &[0x42u8; 16])?;
+//! let block = [0u8; 16];
+//!
+//! let encrypted = aes_encrypt_block(&key, &block)?;
+//! assert_eq!(block, aes_decrypt_block(&key, &encrypted)?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::cipher::block::{Block, BLOCK_LEN};
+use crate::cipher::key::SymmetricCipherKey;
+use crate::cipher::UnboundCipherKey;
+use crate::error::Unspecified;
+
+/// Encrypts a single block with the AES key `key`.
+///
+/// # Safety note
+///
+/// **This is raw ECB mode and is insecure for encrypting data.** Equal blocks encrypt to
+/// equal ciphertext, so encrypting data block by block reveals its structure. Use it only as
+/// the building block of a construction that calls for the AES permutation.
+///
+/// # Errors
+/// * [`Unspecified`]: Returned if `key` is not an AES key.
+pub fn aes_encrypt_block(
+    key: &UnboundCipherKey,
+    block: &[u8; BLOCK_LEN],
+) -> Result<[u8; BLOCK_LEN], Unspecified> {
+    if !key.key.is_aes() {
+        return Err(Unspecified);
+    }
+    Ok(*key.key.encrypt_block(Block::from(block)).as_ref())
+}
+
+/// Decrypts a single block with the AES key `key`.
+///
+/// # Safety note
+///
+/// **This is raw ECB mode and is insecure for decrypting data.** See [`aes_encrypt_block`].
+///
+/// # Errors
+/// * [`Unspecified`]: Returned if `key` is not an AES key, or if it was constructed with
+///   [`UnboundCipherKey::new_encrypt_only`] and so has no decryption key schedule.
+pub fn aes_decrypt_block(
+    key: &UnboundCipherKey,
+    block: &[u8; BLOCK_LEN],
+) -> Result<[u8; BLOCK_LEN], Unspecified> {
+    match &key.key {
+        SymmetricCipherKey::Aes128 {
+            dec_key: Some(_), ..
+        }
+        | SymmetricCipherKey::Aes192 {
+            dec_key: Some(_), ..
+        }
+        | SymmetricCipherKey::Aes256 {
+            dec_key: Some(_), ..
+        } => Ok(*key.key.decrypt_block(Block::from(block)).as_ref()),
+        _ => Err(Unspecified),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cipher::{AES_128, AES_192, AES_256, CHACHA20};
+    use crate::test::from_hex;
+
+    #[test]
+    fn test_fips_197_vectors() {
+        // FIPS 197 Appendix C.
+        let plaintext: [u8; BLOCK_LEN] = from_hex("00112233445566778899aabbccddeeff")
+            .unwrap()
+            .try_into()
+            .unwrap();
+        for (algorithm, key, ciphertext) in [
+            (
+                &AES_128,
+                "000102030405060708090a0b0c0d0e0f",
+                "69c4e0d86a7b0430d8cdb78070b4c55a",
+            ),
+            (
+                &AES_192,
+                "000102030405060708090a0b0c0d0e0f1011121314151617",
+                "dda97ca4864cdfe06eaf70a0ec0d7191",
+            ),
+            (
+                &AES_256,
+                "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+                "8ea2b7ca516745bfeafc49904b496089",
+            ),
+        ] {
+            let key = UnboundCipherKey::new(algorithm, &from_hex(key).unwrap()).unwrap();
+            let ciphertext: [u8; BLOCK_LEN] = from_hex(ciphertext).unwrap().try_into().unwrap();
+            assert_eq!(ciphertext, aes_encrypt_block(&key, &plaintext).unwrap());
+            assert_eq!(plaintext, aes_decrypt_block(&key, &ciphertext).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_unsupported_keys() {
+        let key = UnboundCipherKey::new(&CHACHA20, &[0u8; 32]).unwrap();
+        assert!(aes_encrypt_block(&key, &[0u8; BLOCK_LEN]).is_err());
+        assert!(aes_decrypt_block(&key, &[0u8; BLOCK_LEN]).is_err());
+
+        let key = UnboundCipherKey::new_encrypt_only(&AES_128, &[0u8; 16]).unwrap();
+        assert!(aes_encrypt_block(&key, &[0u8; BLOCK_LEN]).is_ok());
+        assert!(aes_decrypt_block(&key, &[0u8; BLOCK_LEN]).is_err());
+    }
+}
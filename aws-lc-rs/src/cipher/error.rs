@@ -0,0 +1,414 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use super::{
+    decrypt, encrypt, Algorithm, DecryptingKey, DecryptionContext, EncryptingKey,
+    EncryptionContext, OperatingMode, PaddedBlockDecryptingKey, PaddedBlockEncryptingKey,
+    UnboundCipherKey,
+};
+use crate::error::Unspecified;
+use core::fmt;
+use std::error::Error;
+
+/// The reason a cipher key could not be constructed or a cipher operation was rejected.
+///
+/// This is returned by [`Builder`](super::Builder) and by the `_detailed` variants of the
+/// cipher key functions, which fail in the same situations as the functions returning
+/// [`Unspecified`]. Only errors caused by the caller's configuration or input lengths are
+/// distinguished; failures that depend on the contents of a ciphertext, such as invalid
+/// padding, are all reported as [`CipherError::Unspecified`] so that they cannot be used as an
+/// oracle.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CipherError {
+    /// No algorithm was set on the [`Builder`](super::Builder).
+    MissingAlgorithm,
+
+    /// No operating mode was set on the [`Builder`](super::Builder).
+    MissingMode,
+
+    /// Padding was requested for a mode that does not support it.
+    PaddingNotSupported(OperatingMode),
+
+    /// No context was set when building a decryptor.
+    MissingContext,
+
+    /// The algorithm of the key does not match the algorithm set on the
+    /// [`Builder`](super::Builder).
+    AlgorithmMismatch,
+
+    /// The key length does not match the algorithm.
+    InvalidKeyLength,
+
+    /// The context (IV or nonce) is not valid for the algorithm and mode.
+    InvalidContext,
+
+    /// The input length is not valid for the mode, such as an input that is not a multiple of
+    /// the block length in CBC or ECB mode.
+    InvalidInputLength,
+
+    /// The operation failed, including when decryption or unpadding fails.
+    Unspecified,
+}
+
+impl fmt::Display for CipherError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingAlgorithm => f.write_str("no cipher algorithm was configured"),
+            Self::MissingMode => f.write_str("no operating mode was configured"),
+            Self::PaddingNotSupported(mode) => {
+                write!(f, "padding is not supported in {mode:?} mode")
+            }
+            Self::MissingContext => f.write_str("a context (IV) is required for decryption"),
+            Self::AlgorithmMismatch => {
+                f.write_str("the key algorithm does not match the configured algorithm")
+            }
+            Self::InvalidKeyLength => f.write_str("the key length does not match the algorithm"),
+            Self::InvalidContext => {
+                f.write_str("the context is not valid for the algorithm and mode")
+            }
+            Self::InvalidInputLength => f.write_str("the input length is not valid for the mode"),
+            Self::Unspecified => f.write_str("the cipher operation failed"),
+        }
+    }
+}
+
+impl Error for CipherError {}
+
+impl From<Unspecified> for CipherError {
+    fn from(_: Unspecified) -> Self {
+        Self::Unspecified
+    }
+}
+
+impl From<CipherError> for Unspecified {
+    fn from(_: CipherError) -> Self {
+        Unspecified
+    }
+}
+
+fn check_key_len(algorithm: &Algorithm, key_bytes: &[u8]) -> Result<(), CipherError> {
+    if key_bytes.len() != algorithm.key_len() {
+        return Err(CipherError::InvalidKeyLength);
+    }
+    Ok(())
+}
+
+fn check_encryption_context(
+    algorithm: &Algorithm,
+    mode: OperatingMode,
+    context: &EncryptionContext,
+) -> Result<(), CipherError> {
+    if algorithm.is_valid_encryption_context(mode, context) {
+        Ok(())
+    } else {
+        Err(CipherError::InvalidContext)
+    }
+}
+
+fn check_decryption_context(
+    algorithm: &Algorithm,
+    mode: OperatingMode,
+    context: &DecryptionContext,
+) -> Result<(), CipherError> {
+    if algorithm.is_valid_decryption_context(mode, context) {
+        Ok(())
+    } else {
+        Err(CipherError::InvalidContext)
+    }
+}
+
+fn check_input_len(
+    algorithm: &Algorithm,
+    mode: OperatingMode,
+    len: usize,
+) -> Result<(), CipherError> {
+    if mode.is_block_aligned() && len % algorithm.block_len() != 0 {
+        return Err(CipherError::InvalidInputLength);
+    }
+    Ok(())
+}
+
+impl UnboundCipherKey {
+    /// Constructs an [`UnboundCipherKey`], as [`new`](Self::new) does, reporting why the key
+    /// was rejected.
+    ///
+    /// # Errors
+    /// * [`CipherError::InvalidKeyLength`]: Returned if `key_bytes.len()` does not match the
+    ///   length required by `algorithm`.
+    /// * [`CipherError::Unspecified`]: Returned if the key could not be constructed.
+    pub fn try_new_detailed(
+        algorithm: &'static Algorithm,
+        key_bytes: &[u8],
+    ) -> Result<Self, CipherError> {
+        check_key_len(algorithm, key_bytes)?;
+        Ok(Self::new(algorithm, key_bytes)?)
+    }
+}
+
+impl EncryptingKey {
+    /// Encrypts the data provided in `in_out` in-place using the provided context, as
+    /// [`less_safe_encrypt`](Self::less_safe_encrypt) does, reporting why encryption was
+    /// rejected.
+    ///
+    /// # Errors
+    /// * [`CipherError::InvalidContext`]: Returned if `context` is not valid for the key's
+    ///   algorithm and mode.
+    /// * [`CipherError::InvalidInputLength`]: Returned if cipher mode requires input to be a
+    ///   multiple of the block length, and `in_out.len()` is not.
+    /// * [`CipherError::Unspecified`]: Returned if encryption fails.
+    pub fn less_safe_encrypt_detailed(
+        &self,
+        in_out: &mut [u8],
+        context: EncryptionContext,
+    ) -> Result<DecryptionContext, CipherError> {
+        check_encryption_context(self.algorithm(), self.mode, &context)?;
+        check_input_len(self.algorithm(), self.mode, in_out.len())?;
+        Ok(encrypt(&self.key, self.mode, in_out, context)?)
+    }
+}
+
+impl DecryptingKey {
+    /// Decrypts the data provided in `in_out` in-place, as [`decrypt`](Self::decrypt) does,
+    /// reporting why decryption was rejected.
+    ///
+    /// # Errors
+    /// * [`CipherError::InvalidContext`]: Returned if `context` is not valid for the key's
+    ///   algorithm and mode.
+    /// * [`CipherError::InvalidInputLength`]: Returned if cipher mode requires input to be a
+    ///   multiple of the block length, and `in_out.len()` is not.
+    /// * [`CipherError::Unspecified`]: Returned if decryption fails.
+    pub fn decrypt_detailed<'in_out>(
+        &self,
+        in_out: &'in_out mut [u8],
+        context: DecryptionContext,
+    ) -> Result<&'in_out mut [u8], CipherError> {
+        check_decryption_context(self.algorithm(), self.mode, &context)?;
+        check_input_len(self.algorithm(), self.mode, in_out.len())?;
+        Ok(decrypt(&self.key, self.mode, in_out, context)?)
+    }
+}
+
+impl PaddedBlockEncryptingKey {
+    /// Pads and encrypts data provided in `in_out` in-place using the provided context, as
+    /// [`less_safe_encrypt`](Self::less_safe_encrypt) does, reporting why encryption was
+    /// rejected.
+    ///
+    /// # Errors
+    /// * [`CipherError::InvalidContext`]: Returned if `context` is not valid for the key's
+    ///   algorithm and mode.
+    /// * [`CipherError::Unspecified`]: Returned if encryption fails.
+    pub fn less_safe_encrypt_detailed<InOut>(
+        &self,
+        in_out: &mut InOut,
+        context: EncryptionContext,
+    ) -> Result<DecryptionContext, CipherError>
+    where
+        InOut: AsMut<[u8]> + for<'a> Extend<&'a u8>,
+    {
+        check_encryption_context(self.algorithm(), self.mode, &context)?;
+        Ok(self.less_safe_encrypt(in_out, context)?)
+    }
+}
+
+impl PaddedBlockDecryptingKey {
+    /// Decrypts and unpads data provided in `in_out` in-place, as [`decrypt`](Self::decrypt)
+    /// does, reporting why decryption was rejected.
+    ///
+    /// # Errors
+    /// * [`CipherError::InvalidContext`]: Returned if `context` is not valid for the key's
+    ///   algorithm and mode.
+    /// * [`CipherError::InvalidInputLength`]: Returned if `in_out.len()` is not a multiple of
+    ///   the block length.
+    /// * [`CipherError::Unspecified`]: Returned if decryption fails, including if the padding
+    ///   is invalid.
+    pub fn decrypt_detailed<'in_out>(
+        &self,
+        in_out: &'in_out mut [u8],
+        context: DecryptionContext,
+    ) -> Result<&'in_out mut [u8], CipherError> {
+        check_decryption_context(self.algorithm(), self.mode, &context)?;
+        check_input_len(self.algorithm(), self.mode, in_out.len())?;
+        Ok(self.decrypt(in_out, context)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cipher::{AES_128, AES_256, CHACHA20};
+    use crate::iv::FixedLength;
+
+    fn unbound() -> UnboundCipherKey {
+        UnboundCipherKey::new(&AES_128, &[0x42u8; 16]).unwrap()
+    }
+
+    #[test]
+    fn test_invalid_key_length() {
+        assert_eq!(
+            CipherError::InvalidKeyLength,
+            UnboundCipherKey::try_new_detailed(&AES_128, &[0u8; 32]).unwrap_err()
+        );
+        assert_eq!(
+            CipherError::InvalidKeyLength,
+            UnboundCipherKey::try_new_detailed(&AES_256, &[0u8; 16]).unwrap_err()
+        );
+        assert_eq!(
+            CipherError::InvalidKeyLength,
+            UnboundCipherKey::try_new_detailed(&CHACHA20, &[]).unwrap_err()
+        );
+        let key = UnboundCipherKey::try_new_detailed(&AES_256, &[0u8; 32]).unwrap();
+        assert_eq!(&AES_256, key.algorithm());
+    }
+
+    #[test]
+    fn test_invalid_context() {
+        let encrypting_key = EncryptingKey::cbc(unbound()).unwrap();
+        let mut in_out = [0u8; 16];
+        assert_eq!(
+            CipherError::InvalidContext,
+            encrypting_key
+                .less_safe_encrypt_detailed(&mut in_out, EncryptionContext::None)
+                .unwrap_err()
+        );
+        assert_eq!(
+            CipherError::InvalidContext,
+            encrypting_key
+                .less_safe_encrypt_detailed(&mut in_out, EncryptionContext::from([0u8; 12]))
+                .unwrap_err()
+        );
+
+        let decrypting_key = DecryptingKey::cbc(unbound()).unwrap();
+        assert_eq!(
+            CipherError::InvalidContext,
+            decrypting_key
+                .decrypt_detailed(&mut in_out, DecryptionContext::from([0u8; 8]))
+                .unwrap_err()
+        );
+
+        let encrypting_key = PaddedBlockEncryptingKey::cbc_pkcs7(unbound()).unwrap();
+        let mut in_out = vec![0u8; 5];
+        assert_eq!(
+            CipherError::InvalidContext,
+            encrypting_key
+                .less_safe_encrypt_detailed(&mut in_out, EncryptionContext::None)
+                .unwrap_err()
+        );
+        assert_eq!(vec![0u8; 5], in_out);
+
+        let decrypting_key = PaddedBlockDecryptingKey::cbc_pkcs7(unbound()).unwrap();
+        let mut in_out = [0u8; 16];
+        assert_eq!(
+            CipherError::InvalidContext,
+            decrypting_key
+                .decrypt_detailed(&mut in_out, DecryptionContext::None)
+                .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_invalid_input_length() {
+        let context = || EncryptionContext::Iv128(FixedLength::from([1u8; 16]));
+
+        let encrypting_key = EncryptingKey::cbc(unbound()).unwrap();
+        let mut in_out = [0u8; 17];
+        assert_eq!(
+            CipherError::InvalidInputLength,
+            encrypting_key
+                .less_safe_encrypt_detailed(&mut in_out, context())
+                .unwrap_err()
+        );
+        assert_eq!(
+            CipherError::InvalidInputLength,
+            DecryptingKey::cbc(unbound())
+                .unwrap()
+                .decrypt_detailed(&mut in_out, context().into())
+                .unwrap_err()
+        );
+        assert_eq!(
+            CipherError::InvalidInputLength,
+            PaddedBlockDecryptingKey::cbc_pkcs7(unbound())
+                .unwrap()
+                .decrypt_detailed(&mut in_out, context().into())
+                .unwrap_err()
+        );
+
+        #[cfg(not(feature = "approved-only"))]
+        {
+            let encrypting_key = EncryptingKey::less_safe_ecb(unbound()).unwrap();
+            assert_eq!(
+                CipherError::InvalidInputLength,
+                encrypting_key
+                    .less_safe_encrypt_detailed(&mut in_out, EncryptionContext::None)
+                    .unwrap_err()
+            );
+        }
+
+        // Stream modes accept any length.
+        let encrypting_key = EncryptingKey::ctr(unbound()).unwrap();
+        let decryption_context = encrypting_key
+            .less_safe_encrypt_detailed(&mut in_out, context())
+            .unwrap();
+        DecryptingKey::ctr(unbound())
+            .unwrap()
+            .decrypt_detailed(&mut in_out, decryption_context)
+            .unwrap();
+        assert_eq!([0u8; 17], in_out);
+    }
+
+    #[test]
+    fn test_padding_failures_are_unspecified() {
+        let decrypting_key = PaddedBlockDecryptingKey::cbc_pkcs7(unbound()).unwrap();
+        let context = || DecryptionContext::Iv128(FixedLength::from([1u8; 16]));
+
+        // Every ciphertext with a valid length and context fails the same way, whatever the
+        // reason the padding is rejected.
+        let mut rejected = 0;
+        for last_byte in 0..=255u8 {
+            let mut in_out = [0u8; 32];
+            in_out[31] = last_byte;
+            if let Err(err) = decrypting_key.decrypt_detailed(&mut in_out, context()) {
+                assert_eq!(CipherError::Unspecified, err);
+                rejected += 1;
+            }
+        }
+        assert!(rejected > 0);
+
+        let mut in_out: [u8; 0] = [];
+        assert_eq!(
+            CipherError::Unspecified,
+            decrypting_key
+                .decrypt_detailed(&mut in_out, context())
+                .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let encrypting_key = PaddedBlockEncryptingKey::cbc_pkcs7(unbound()).unwrap();
+        let decrypting_key = PaddedBlockDecryptingKey::cbc_pkcs7(unbound()).unwrap();
+        let mut in_out = b"detailed errors".to_vec();
+        let context = encrypting_key
+            .less_safe_encrypt_detailed(
+                &mut in_out,
+                EncryptionContext::Iv128(FixedLength::from([3u8; 16])),
+            )
+            .unwrap();
+        assert_eq!(
+            b"detailed errors",
+            decrypting_key
+                .decrypt_detailed(&mut in_out, context)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(CipherError::Unspecified, CipherError::from(Unspecified));
+        assert_eq!(Unspecified, Unspecified::from(CipherError::InvalidContext));
+        assert_eq!(
+            "the key length does not match the algorithm",
+            CipherError::InvalidKeyLength.to_string()
+        );
+    }
+}
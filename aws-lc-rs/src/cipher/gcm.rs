@@ -0,0 +1,308 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use crate::aead::{self, Nonce, Tag};
+use crate::cipher::{Algorithm, AlgorithmId, DecryptionContext, EncryptionContext};
+use crate::error::Unspecified;
+use crate::iv::FixedLength;
+use core::fmt::Debug;
+
+/// The number of bytes in an AES-GCM authentication tag.
+pub const GCM_TAG_LEN: usize = 16;
+
+/// The AES-GCM key shared by the encrypting and decrypting key types.
+struct GcmKey {
+    key: aead::UnboundKey,
+    algorithm: &'static Algorithm,
+}
+
+impl GcmKey {
+    fn new(algorithm: &'static Algorithm, key_bytes: &[u8]) -> Result<Self, Unspecified> {
+        let aead_algorithm = match algorithm.id() {
+            AlgorithmId::Aes128 => &aead::AES_128_GCM,
+            AlgorithmId::Aes256 => &aead::AES_256_GCM,
+            _ => return Err(Unspecified),
+        };
+        Ok(GcmKey {
+            key: aead::UnboundKey::new(aead_algorithm, key_bytes)?,
+            algorithm,
+        })
+    }
+}
+
+impl Debug for GcmKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("GcmKey")
+            .field("algorithm", self.algorithm)
+            .finish()
+    }
+}
+
+/// An AES-GCM key for encrypting data in place, with the authentication tag returned
+/// separately from the ciphertext.
+///
+/// This is a compatibility path for protocols that carry the tag apart from the ciphertext.
+/// Prefer the [`aead`](crate::aead) module where the combined interface can be used.
+pub struct GcmEncryptingKey {
+    key: GcmKey,
+}
+
+impl GcmEncryptingKey {
+    /// Constructs a `GcmEncryptingKey` from the raw key bytes for `algorithm`, which must be
+    /// `AES_128` or `AES_256`.
+    ///
+    // # FIPS
+    // Use this function with one of the following algorithms:
+    // * `AES_128`
+    // * `AES_256`
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `algorithm` is not supported or `key_bytes` is not a
+    ///   valid key for it.
+    pub fn new(
+        algorithm: &'static Algorithm,
+        key_bytes: &[u8],
+    ) -> Result<GcmEncryptingKey, Unspecified> {
+        Ok(GcmEncryptingKey {
+            key: GcmKey::new(algorithm, key_bytes)?,
+        })
+    }
+
+    /// Returns the cipher algorithm.
+    #[must_use]
+    pub fn algorithm(&self) -> &Algorithm {
+        self.key.algorithm
+    }
+
+    /// Encrypts the data provided in `in_out` in place, authenticating it together with `aad`,
+    /// using a randomly generated 96-bit nonce. Returns the context holding the nonce and the
+    /// authentication tag, both of which are needed to decrypt the data.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if a nonce cannot be generated or `in_out` is too long.
+    pub fn encrypt(
+        &self,
+        in_out: &mut [u8],
+        aad: &[u8],
+    ) -> Result<(DecryptionContext, Tag), Unspecified> {
+        let context = EncryptionContext::Nonce96(FixedLength::new()?);
+        self.less_safe_encrypt(in_out, aad, context)
+    }
+
+    /// Encrypts the data provided in `in_out` in place, authenticating it together with `aad`,
+    /// using the nonce in `context`. Returns the context needed to decrypt the data and the
+    /// authentication tag.
+    ///
+    /// A nonce must never be used more than once with the same key.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `context` is not a `Nonce96` context or `in_out` is too
+    ///   long.
+    pub fn less_safe_encrypt(
+        &self,
+        in_out: &mut [u8],
+        aad: &[u8],
+        context: EncryptionContext,
+    ) -> Result<(DecryptionContext, Tag), Unspecified> {
+        let nonce = match &context {
+            EncryptionContext::Nonce96(nonce) => Nonce::from(nonce.as_ref()),
+            _ => return Err(Unspecified),
+        };
+        let (_, tag) = self
+            .key
+            .key
+            .seal_in_place_separate_tag(Some(nonce), aad, in_out)?;
+        Ok((context.into(), tag))
+    }
+}
+
+impl Debug for GcmEncryptingKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("GcmEncryptingKey")
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+/// An AES-GCM key for decrypting data in place, with the authentication tag provided
+/// separately from the ciphertext.
+pub struct GcmDecryptingKey {
+    key: GcmKey,
+}
+
+impl GcmDecryptingKey {
+    /// Constructs a `GcmDecryptingKey` from the raw key bytes for `algorithm`, which must be
+    /// `AES_128` or `AES_256`.
+    ///
+    // # FIPS
+    // Use this function with one of the following algorithms:
+    // * `AES_128`
+    // * `AES_256`
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `algorithm` is not supported or `key_bytes` is not a
+    ///   valid key for it.
+    pub fn new(
+        algorithm: &'static Algorithm,
+        key_bytes: &[u8],
+    ) -> Result<GcmDecryptingKey, Unspecified> {
+        Ok(GcmDecryptingKey {
+            key: GcmKey::new(algorithm, key_bytes)?,
+        })
+    }
+
+    /// Returns the cipher algorithm.
+    #[must_use]
+    pub fn algorithm(&self) -> &Algorithm {
+        self.key.algorithm
+    }
+
+    /// Verifies `tag` and decrypts the data provided in `in_out` in place, using the context
+    /// and `aad` that the data was encrypted with. Returns a reference to the decrypted data.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `context` is not a `Nonce96` context, if `tag` is not
+    ///   `GCM_TAG_LEN` bytes long, or if the tag does not match. If the tag does not match,
+    ///   `in_out` is zeroed.
+    pub fn decrypt<'in_out>(
+        &self,
+        in_out: &'in_out mut [u8],
+        aad: &[u8],
+        tag: &[u8],
+        context: DecryptionContext,
+    ) -> Result<&'in_out mut [u8], Unspecified> {
+        let nonce = match context {
+            DecryptionContext::Nonce96(nonce) => Nonce::from(nonce),
+            _ => return Err(Unspecified),
+        };
+        if tag.len() != GCM_TAG_LEN {
+            return Err(Unspecified);
+        }
+        self.key
+            .key
+            .open_in_place_separate_tag(&nonce, aad, in_out, tag)?;
+        Ok(in_out)
+    }
+}
+
+impl Debug for GcmDecryptingKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("GcmDecryptingKey")
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cipher::{AES_128, AES_192, AES_256, CHACHA20};
+
+    #[test]
+    fn test_gcm_round_trip() {
+        for (algorithm, key_len) in [(&AES_128, 16), (&AES_256, 32)] {
+            let key_bytes = vec![0x42u8; key_len];
+            let encrypting_key = GcmEncryptingKey::new(algorithm, &key_bytes).unwrap();
+            let decrypting_key = GcmDecryptingKey::new(algorithm, &key_bytes).unwrap();
+            assert_eq!(algorithm, encrypting_key.algorithm());
+            assert_eq!(algorithm, decrypting_key.algorithm());
+
+            for len in [0, 1, 15, 16, 17, 100] {
+                let plaintext: Vec<u8> = (0..len).map(|i| i as u8).collect();
+                let mut in_out = plaintext.clone();
+                let (context, tag) = encrypting_key.encrypt(&mut in_out, b"header").unwrap();
+                assert!(matches!(context, DecryptionContext::Nonce96(_)));
+                assert_eq!(GCM_TAG_LEN, tag.as_ref().len());
+                if len > 0 {
+                    assert_ne!(plaintext, in_out);
+                }
+                let decrypted = decrypting_key
+                    .decrypt(&mut in_out, b"header", tag.as_ref(), context)
+                    .unwrap();
+                assert_eq!(plaintext.as_slice(), decrypted);
+            }
+        }
+    }
+
+    #[test]
+    fn test_gcm_rejects_unsupported_algorithms() {
+        assert!(GcmEncryptingKey::new(&AES_192, &[0u8; 24]).is_err());
+        assert!(GcmDecryptingKey::new(&AES_192, &[0u8; 24]).is_err());
+        assert!(GcmEncryptingKey::new(&CHACHA20, &[0u8; 32]).is_err());
+        assert!(GcmEncryptingKey::new(&AES_128, &[0u8; 32]).is_err());
+    }
+
+    #[test]
+    fn test_gcm_rejects_invalid_context() {
+        let encrypting_key = GcmEncryptingKey::new(&AES_128, &[1u8; 16]).unwrap();
+        let decrypting_key = GcmDecryptingKey::new(&AES_128, &[1u8; 16]).unwrap();
+        let mut in_out = [0u8; 16];
+        assert!(encrypting_key
+            .less_safe_encrypt(&mut in_out, &[], EncryptionContext::None)
+            .is_err());
+        assert!(encrypting_key
+            .less_safe_encrypt(&mut in_out, &[], EncryptionContext::from([0u8; 16]))
+            .is_err());
+        assert!(decrypting_key
+            .decrypt(
+                &mut in_out,
+                &[],
+                &[0u8; GCM_TAG_LEN],
+                DecryptionContext::None
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_gcm_rejects_forgery() {
+        let key_bytes = [7u8; 32];
+        let encrypting_key = GcmEncryptingKey::new(&AES_256, &key_bytes).unwrap();
+        let decrypting_key = GcmDecryptingKey::new(&AES_256, &key_bytes).unwrap();
+        let nonce = [9u8; 12];
+
+        let mut ciphertext = *b"a message that must not be modified";
+        let (_, tag) = encrypting_key
+            .less_safe_encrypt(&mut ciphertext, b"aad", EncryptionContext::from(nonce))
+            .unwrap();
+        let tag = tag.as_ref().to_vec();
+
+        let attempt = |in_out: &[u8], aad: &[u8], tag: &[u8], nonce: [u8; 12]| {
+            let mut in_out = in_out.to_vec();
+            let result = decrypting_key
+                .decrypt(&mut in_out, aad, tag, DecryptionContext::from(nonce))
+                .map(|plaintext| plaintext.to_vec());
+            if result.is_err() {
+                assert!(in_out.iter().all(|b| *b == 0));
+            }
+            result
+        };
+
+        assert_eq!(
+            b"a message that must not be modified".as_slice(),
+            attempt(&ciphertext, b"aad", &tag, nonce).unwrap()
+        );
+
+        let mut modified = ciphertext;
+        modified[3] ^= 1;
+        assert!(attempt(&modified, b"aad", &tag, nonce).is_err());
+
+        let mut modified_tag = tag.clone();
+        modified_tag[15] ^= 0x80;
+        assert!(attempt(&ciphertext, b"aad", &modified_tag, nonce).is_err());
+
+        assert!(attempt(&ciphertext, b"aae", &tag, nonce).is_err());
+        assert!(attempt(&ciphertext, b"", &tag, nonce).is_err());
+        assert!(attempt(&ciphertext, b"aad", &tag, [8u8; 12]).is_err());
+        assert!(attempt(&ciphertext[..20], b"aad", &tag, nonce).is_err());
+
+        let mut in_out = ciphertext;
+        assert!(decrypting_key
+            .decrypt(
+                &mut in_out,
+                b"aad",
+                &tag[..12],
+                DecryptionContext::from(nonce)
+            )
+            .is_err());
+    }
+}
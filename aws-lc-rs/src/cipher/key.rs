@@ -1,23 +1,43 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
-use crate::cipher::aes::encrypt_block_aes;
+use crate::cipher::aes::{decrypt_block_aes, encrypt_block_aes};
 use crate::cipher::block::Block;
 use crate::cipher::chacha::ChaCha20Key;
-use crate::cipher::{AES_128_KEY_LEN, AES_256_KEY_LEN};
+#[cfg(not(feature = "approved-only"))]
+use crate::cipher::TRIPLE_DES_3KEY_KEY_LEN;
+use crate::cipher::{AES_128_KEY_LEN, AES_192_KEY_LEN, AES_256_KEY_LEN};
 use crate::error::Unspecified;
 use aws_lc::{AES_set_decrypt_key, AES_set_encrypt_key, AES_KEY};
+#[cfg(not(feature = "approved-only"))]
+use aws_lc::{DES_cblock, DES_key_schedule, DES_set_key};
 use core::mem::{size_of, MaybeUninit};
 use core::ptr::copy_nonoverlapping;
 // TODO: Uncomment when MSRV >= 1.64
 // use core::ffi::c_uint;
-use std::os::raw::c_uint;
+use std::os::raw::{c_int, c_uint};
 use zeroize::Zeroize;
 
 pub(crate) enum SymmetricCipherKey {
-    Aes128 { enc_key: AES_KEY, dec_key: AES_KEY },
-    Aes256 { enc_key: AES_KEY, dec_key: AES_KEY },
-    ChaCha20 { raw_key: ChaCha20Key },
+    Aes128 {
+        enc_key: AES_KEY,
+        dec_key: Option<AES_KEY>,
+    },
+    Aes192 {
+        enc_key: AES_KEY,
+        dec_key: Option<AES_KEY>,
+    },
+    Aes256 {
+        enc_key: AES_KEY,
+        dec_key: Option<AES_KEY>,
+    },
+    ChaCha20 {
+        raw_key: ChaCha20Key,
+    },
+    #[cfg(not(feature = "approved-only"))]
+    TripleDes {
+        key_schedules: [DES_key_schedule; 3],
+    },
 }
 
 unsafe impl Send for SymmetricCipherKey {}
@@ -30,19 +50,48 @@ impl Drop for SymmetricCipherKey {
         // Aes128Key, Aes256Key and ChaCha20Key implement Drop separately.
         match self {
             SymmetricCipherKey::Aes128 { enc_key, dec_key }
-            | SymmetricCipherKey::Aes256 { enc_key, dec_key } => unsafe {
-                let enc_bytes: &mut [u8; size_of::<AES_KEY>()] = (enc_key as *mut AES_KEY)
-                    .cast::<[u8; size_of::<AES_KEY>()]>()
-                    .as_mut()
-                    .unwrap();
-                enc_bytes.zeroize();
-                let dec_bytes: &mut [u8; size_of::<AES_KEY>()] = (dec_key as *mut AES_KEY)
-                    .cast::<[u8; size_of::<AES_KEY>()]>()
-                    .as_mut()
-                    .unwrap();
-                dec_bytes.zeroize();
-            },
+            | SymmetricCipherKey::Aes192 { enc_key, dec_key }
+            | SymmetricCipherKey::Aes256 { enc_key, dec_key } => {
+                zeroize_aes_key(enc_key);
+                if let Some(dec_key) = dec_key {
+                    zeroize_aes_key(dec_key);
+                }
+            }
             SymmetricCipherKey::ChaCha20 { .. } => {}
+            #[cfg(not(feature = "approved-only"))]
+            SymmetricCipherKey::TripleDes { key_schedules } => {
+                for key_schedule in key_schedules {
+                    key_schedule.subkeys.zeroize();
+                }
+            }
+        }
+    }
+}
+
+// The key schedules are plain C structs, so copying them yields an independent key that is
+// zeroized separately when dropped.
+impl Clone for SymmetricCipherKey {
+    fn clone(&self) -> Self {
+        match self {
+            SymmetricCipherKey::Aes128 { enc_key, dec_key } => SymmetricCipherKey::Aes128 {
+                enc_key: *enc_key,
+                dec_key: *dec_key,
+            },
+            SymmetricCipherKey::Aes192 { enc_key, dec_key } => SymmetricCipherKey::Aes192 {
+                enc_key: *enc_key,
+                dec_key: *dec_key,
+            },
+            SymmetricCipherKey::Aes256 { enc_key, dec_key } => SymmetricCipherKey::Aes256 {
+                enc_key: *enc_key,
+                dec_key: *dec_key,
+            },
+            SymmetricCipherKey::ChaCha20 { raw_key } => SymmetricCipherKey::ChaCha20 {
+                raw_key: raw_key.clone(),
+            },
+            #[cfg(not(feature = "approved-only"))]
+            SymmetricCipherKey::TripleDes { key_schedules } => SymmetricCipherKey::TripleDes {
+                key_schedules: *key_schedules,
+            },
         }
     }
 }
@@ -52,66 +101,55 @@ impl SymmetricCipherKey {
         if key_bytes.len() != AES_128_KEY_LEN {
             return Err(Unspecified);
         }
+        Self::aes(key_bytes, true)
+    }
 
-        unsafe {
-            let mut enc_key = MaybeUninit::<AES_KEY>::uninit();
-            #[allow(clippy::cast_possible_truncation)]
-            if 0 != AES_set_encrypt_key(
-                key_bytes.as_ptr(),
-                (key_bytes.len() * 8) as c_uint,
-                enc_key.as_mut_ptr(),
-            ) {
-                return Err(Unspecified);
-            }
-            let enc_key = enc_key.assume_init();
-
-            let mut dec_key = MaybeUninit::<AES_KEY>::uninit();
-            #[allow(clippy::cast_possible_truncation)]
-            if 0 != AES_set_decrypt_key(
-                key_bytes.as_ptr(),
-                (key_bytes.len() * 8) as c_uint,
-                dec_key.as_mut_ptr(),
-            ) {
-                return Err(Unspecified);
-            }
-            let dec_key = dec_key.assume_init();
-
-            let mut kb = MaybeUninit::<[u8; AES_128_KEY_LEN]>::uninit();
-            copy_nonoverlapping(key_bytes.as_ptr(), kb.as_mut_ptr().cast(), AES_128_KEY_LEN);
-            Ok(SymmetricCipherKey::Aes128 { enc_key, dec_key })
+    pub(crate) fn aes192(key_bytes: &[u8]) -> Result<Self, Unspecified> {
+        if key_bytes.len() != AES_192_KEY_LEN {
+            return Err(Unspecified);
         }
+        Self::aes(key_bytes, true)
     }
 
     pub(crate) fn aes256(key_bytes: &[u8]) -> Result<Self, Unspecified> {
         if key_bytes.len() != AES_256_KEY_LEN {
             return Err(Unspecified);
         }
-        unsafe {
-            let mut enc_key = MaybeUninit::<AES_KEY>::uninit();
-            #[allow(clippy::cast_possible_truncation)]
-            if 0 != AES_set_encrypt_key(
-                key_bytes.as_ptr(),
-                (key_bytes.len() * 8) as c_uint,
-                enc_key.as_mut_ptr(),
-            ) {
-                return Err(Unspecified);
-            }
-            let enc_key = enc_key.assume_init();
-
-            let mut dec_key = MaybeUninit::<AES_KEY>::uninit();
-            #[allow(clippy::cast_possible_truncation)]
-            if 0 != AES_set_decrypt_key(
-                key_bytes.as_ptr(),
-                (key_bytes.len() * 8) as c_uint,
-                dec_key.as_mut_ptr(),
-            ) {
-                return Err(Unspecified);
-            }
-            let dec_key = dec_key.assume_init();
+        Self::aes(key_bytes, true)
+    }
+
+    /// Constructs an AES key of the length of `key_bytes`. The decryption key schedule is only
+    /// computed if `with_decrypt_key` is set.
+    pub(crate) fn aes(key_bytes: &[u8], with_decrypt_key: bool) -> Result<Self, Unspecified> {
+        let enc_key = aes_key_schedule(key_bytes, AES_set_encrypt_key)?;
+        let dec_key = if with_decrypt_key {
+            Some(aes_key_schedule(key_bytes, AES_set_decrypt_key)?)
+        } else {
+            None
+        };
+        match key_bytes.len() {
+            AES_128_KEY_LEN => Ok(SymmetricCipherKey::Aes128 { enc_key, dec_key }),
+            AES_192_KEY_LEN => Ok(SymmetricCipherKey::Aes192 { enc_key, dec_key }),
+            AES_256_KEY_LEN => Ok(SymmetricCipherKey::Aes256 { enc_key, dec_key }),
+            _ => Err(Unspecified),
+        }
+    }
 
-            let mut kb = MaybeUninit::<[u8; AES_256_KEY_LEN]>::uninit();
-            copy_nonoverlapping(key_bytes.as_ptr(), kb.as_mut_ptr().cast(), AES_256_KEY_LEN);
-            Ok(SymmetricCipherKey::Aes256 { enc_key, dec_key })
+    /// Computes the AES decryption key schedule from `key_bytes` if it is not already present.
+    /// `key_bytes` must be the bytes this key was constructed from.
+    pub(crate) fn add_decrypt_key(&mut self, key_bytes: &[u8]) -> Result<(), Unspecified> {
+        match self {
+            SymmetricCipherKey::Aes128 { dec_key, .. }
+            | SymmetricCipherKey::Aes192 { dec_key, .. }
+            | SymmetricCipherKey::Aes256 { dec_key, .. } => {
+                if dec_key.is_none() {
+                    *dec_key = Some(aes_key_schedule(key_bytes, AES_set_decrypt_key)?);
+                }
+                Ok(())
+            }
+            SymmetricCipherKey::ChaCha20 { .. } => Ok(()),
+            #[cfg(not(feature = "approved-only"))]
+            SymmetricCipherKey::TripleDes { .. } => Ok(()),
         }
     }
 
@@ -128,17 +166,91 @@ impl SymmetricCipherKey {
         }
     }
 
-    #[allow(dead_code)]
+    #[cfg(not(feature = "approved-only"))]
+    pub(crate) fn triple_des(key_bytes: &[u8]) -> Result<Self, Unspecified> {
+        if key_bytes.len() != TRIPLE_DES_3KEY_KEY_LEN {
+            return Err(Unspecified);
+        }
+
+        let mut key_schedules = [DES_key_schedule::default(); 3];
+        for (key_schedule, key_part) in key_schedules.iter_mut().zip(key_bytes.chunks_exact(8)) {
+            let mut cblock = DES_cblock::default();
+            cblock.bytes.copy_from_slice(key_part);
+            unsafe { DES_set_key(&cblock, key_schedule) };
+            cblock.bytes.zeroize();
+        }
+        Ok(SymmetricCipherKey::TripleDes { key_schedules })
+    }
+
+    #[cfg(not(feature = "approved-only"))]
+    pub(crate) fn is_aes(&self) -> bool {
+        matches!(
+            self,
+            SymmetricCipherKey::Aes128 { .. }
+                | SymmetricCipherKey::Aes192 { .. }
+                | SymmetricCipherKey::Aes256 { .. }
+        )
+    }
+
     #[inline]
     pub(crate) fn encrypt_block(&self, block: Block) -> Block {
         match self {
             SymmetricCipherKey::Aes128 { enc_key, .. }
+            | SymmetricCipherKey::Aes192 { enc_key, .. }
             | SymmetricCipherKey::Aes256 { enc_key, .. } => encrypt_block_aes(enc_key, block),
             SymmetricCipherKey::ChaCha20 { .. } => panic!("Unsupported algorithm!"),
+            #[cfg(not(feature = "approved-only"))]
+            SymmetricCipherKey::TripleDes { .. } => panic!("Unsupported algorithm!"),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn decrypt_block(&self, block: Block) -> Block {
+        match self {
+            SymmetricCipherKey::Aes128 {
+                dec_key: Some(dec_key),
+                ..
+            }
+            | SymmetricCipherKey::Aes192 {
+                dec_key: Some(dec_key),
+                ..
+            }
+            | SymmetricCipherKey::Aes256 {
+                dec_key: Some(dec_key),
+                ..
+            } => decrypt_block_aes(dec_key, block),
+            _ => panic!("Unsupported algorithm!"),
         }
     }
 }
 
+type AesSetKeyFn = unsafe extern "C" fn(*const u8, c_uint, *mut AES_KEY) -> c_int;
+
+fn aes_key_schedule(key_bytes: &[u8], set_key: AesSetKeyFn) -> Result<AES_KEY, Unspecified> {
+    let mut key = MaybeUninit::<AES_KEY>::uninit();
+    #[allow(clippy::cast_possible_truncation)]
+    if 0 != unsafe {
+        set_key(
+            key_bytes.as_ptr(),
+            (key_bytes.len() * 8) as c_uint,
+            key.as_mut_ptr(),
+        )
+    } {
+        return Err(Unspecified);
+    }
+    Ok(unsafe { key.assume_init() })
+}
+
+fn zeroize_aes_key(key: &mut AES_KEY) {
+    let bytes: &mut [u8; size_of::<AES_KEY>()] = unsafe {
+        (key as *mut AES_KEY)
+            .cast::<[u8; size_of::<AES_KEY>()]>()
+            .as_mut()
+            .unwrap()
+    };
+    bytes.zeroize();
+}
+
 #[cfg(test)]
 mod tests {
     use crate::cipher::block::{Block, BLOCK_LEN};
@@ -158,6 +270,19 @@ mod tests {
         assert_eq!(expected_result.as_slice(), result.as_ref());
     }
 
+    #[test]
+    fn test_encrypt_block_aes_192() {
+        let key = from_hex("000102030405060708090a0b0c0d0e0f1011121314151617").unwrap();
+        let input = from_hex("00112233445566778899aabbccddeeff").unwrap();
+        let expected_result = from_hex("dda97ca4864cdfe06eaf70a0ec0d7191").unwrap();
+        let input_block: [u8; BLOCK_LEN] = <[u8; BLOCK_LEN]>::try_from(input).unwrap();
+
+        let aes192 = SymmetricCipherKey::aes192(key.as_slice()).unwrap();
+        let result = aes192.encrypt_block(Block::from(&input_block));
+
+        assert_eq!(expected_result.as_slice(), result.as_ref());
+    }
+
     #[test]
     fn test_encrypt_block_aes_256() {
         let key =
@@ -171,4 +296,58 @@ mod tests {
 
         assert_eq!(expected_result.as_slice(), result.as_ref());
     }
+
+    #[test]
+    fn test_decrypt_block_aes_128() {
+        let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+        let input = from_hex("69c4e0d86a7b0430d8cdb78070b4c55a").unwrap();
+        let expected_result = from_hex("00112233445566778899aabbccddeeff").unwrap();
+        let input_block: [u8; BLOCK_LEN] = <[u8; BLOCK_LEN]>::try_from(input).unwrap();
+
+        let aes128 = SymmetricCipherKey::aes128(key.as_slice()).unwrap();
+        let result = aes128.decrypt_block(Block::from(&input_block));
+
+        assert_eq!(expected_result.as_slice(), result.as_ref());
+    }
+
+    #[test]
+    fn test_encrypt_only_aes_key() {
+        let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+        let input = from_hex("69c4e0d86a7b0430d8cdb78070b4c55a").unwrap();
+        let expected_result = from_hex("00112233445566778899aabbccddeeff").unwrap();
+        let input_block: [u8; BLOCK_LEN] = <[u8; BLOCK_LEN]>::try_from(input).unwrap();
+
+        let mut aes128 = SymmetricCipherKey::aes(key.as_slice(), false).unwrap();
+        assert!(matches!(
+            aes128,
+            SymmetricCipherKey::Aes128 { dec_key: None, .. }
+        ));
+        aes128.add_decrypt_key(key.as_slice()).unwrap();
+        let result = aes128.decrypt_block(Block::from(&input_block));
+        assert_eq!(expected_result.as_slice(), result.as_ref());
+
+        assert!(SymmetricCipherKey::aes(&key[..15], false).is_err());
+    }
+
+    #[test]
+    fn test_clone_is_independent() {
+        let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+        let input = from_hex("00112233445566778899aabbccddeeff").unwrap();
+        let expected_result = from_hex("69c4e0d86a7b0430d8cdb78070b4c55a").unwrap();
+        let input_block: [u8; BLOCK_LEN] = <[u8; BLOCK_LEN]>::try_from(input).unwrap();
+
+        let aes128 = SymmetricCipherKey::aes128(key.as_slice()).unwrap();
+        let cloned = aes128.clone();
+        assert_eq!(
+            aes128.encrypt_block(Block::from(&input_block)).as_ref(),
+            cloned.encrypt_block(Block::from(&input_block)).as_ref()
+        );
+
+        // Dropping the original zeroizes its key schedules but not those of the clone.
+        drop(aes128);
+        let result = cloned.encrypt_block(Block::from(&input_block));
+        assert_eq!(expected_result.as_slice(), result.as_ref());
+        let result = cloned.decrypt_block(result);
+        assert_eq!(&input_block, result.as_ref());
+    }
 }
@@ -0,0 +1,130 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Encrypts large AES-CTR inputs on the rayon thread pool.
+//!
+//! Each block of the CTR keystream depends only on its counter, so an input can be split into
+//! chunks that are encrypted independently, each starting from the counter of its first block.
+
+use crate::cipher::{
+    aes_ctr_blocks, aes_ctr_counter_block, aes_ctr_mode_at_offset, EncryptionContext,
+    UnboundCipherKey,
+};
+use crate::error::Unspecified;
+use rayon::prelude::*;
+
+/// Inputs shorter than this are encrypted on the calling thread.
+pub(super) const PARALLEL_CTR_MIN_LEN: usize = 1024 * 1024;
+
+/// The length of the chunks encrypted in parallel, a multiple of the block length.
+const CHUNK_LEN: usize = 256 * 1024;
+
+/// Applies the CTR mode keystream of `context` to `in_out`, with the same result as
+/// `aes_ctr_mode_at_offset` with an offset of zero.
+pub(super) fn aes_ctr_mode(
+    key: &UnboundCipherKey,
+    context: &EncryptionContext,
+    in_out: &mut [u8],
+) -> Result<(), Unspecified> {
+    // Check the whole input against the counter before any chunk is encrypted, so that either
+    // all of `in_out` is encrypted or none of it is.
+    let (_, blocks_available) = aes_ctr_counter_block(context)?;
+    if aes_ctr_blocks(in_out.len()) > blocks_available {
+        return Err(Unspecified);
+    }
+
+    in_out
+        .par_chunks_mut(CHUNK_LEN)
+        .enumerate()
+        .try_for_each(|(index, chunk)| {
+            let offset = u64::try_from(index * CHUNK_LEN).map_err(|_| Unspecified)?;
+            aes_ctr_mode_at_offset(key, context, offset, chunk)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cipher::{EncryptingKey, AES_128, AES_256, AES_BLOCK_LEN};
+    use crate::iv::FixedLength;
+
+    fn serial(key: &UnboundCipherKey, context: &EncryptionContext, input: &[u8]) -> Vec<u8> {
+        let mut in_out = input.to_vec();
+        aes_ctr_mode_at_offset(key, context, 0, &mut in_out).unwrap();
+        in_out
+    }
+
+    #[test]
+    fn test_parallel_matches_serial() {
+        let lengths = [
+            0,
+            1,
+            CHUNK_LEN - 1,
+            CHUNK_LEN,
+            CHUNK_LEN + 1,
+            2 * CHUNK_LEN - AES_BLOCK_LEN - 1,
+            2 * CHUNK_LEN + AES_BLOCK_LEN + 1,
+            PARALLEL_CTR_MIN_LEN - 1,
+            PARALLEL_CTR_MIN_LEN,
+            PARALLEL_CTR_MIN_LEN + 15,
+            5 * CHUNK_LEN + 7,
+        ];
+        let input: Vec<u8> = (0..*lengths.iter().max().unwrap())
+            .map(|i| (i % 251) as u8)
+            .collect();
+
+        // The second counter carries out of its low 64 bits within the first chunk, and the
+        // third is close to the end of the counter space.
+        let counters = [
+            [7u8; 16],
+            (u128::from(u64::MAX) - 3).to_be_bytes(),
+            (u128::MAX - (6 * CHUNK_LEN / AES_BLOCK_LEN) as u128).to_be_bytes(),
+        ];
+        for (algorithm, key_bytes) in [(&AES_128, &[0x42u8; 16][..]), (&AES_256, &[0x24u8; 32])] {
+            let key = UnboundCipherKey::new(algorithm, key_bytes).unwrap();
+            for counter in counters {
+                let context = EncryptionContext::Iv128(FixedLength::from(counter));
+                for len in lengths {
+                    let mut in_out = input[..len].to_vec();
+                    aes_ctr_mode(&key, &context, &mut in_out).unwrap();
+                    assert_eq!(serial(&key, &context, &input[..len]), in_out, "{len}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_encrypting_key_uses_parallel_path() {
+        let input = vec![0x5au8; PARALLEL_CTR_MIN_LEN + 3];
+        let context = || EncryptionContext::Iv128(FixedLength::from([9u8; 16]));
+
+        let key = UnboundCipherKey::new(&AES_128, &[1u8; 16]).unwrap();
+        let expected = serial(&key, &context(), &input);
+
+        let mut in_out = input.clone();
+        EncryptingKey::ctr(key)
+            .unwrap()
+            .less_safe_encrypt(&mut in_out, context())
+            .unwrap();
+        assert_eq!(expected, in_out);
+    }
+
+    #[test]
+    fn test_counter_exhausted() {
+        let key = UnboundCipherKey::new(&AES_128, &[1u8; 16]).unwrap();
+        let blocks = (2 * CHUNK_LEN / AES_BLOCK_LEN) as u128;
+        let contexts = [
+            EncryptionContext::Iv128(FixedLength::from((u128::MAX - blocks).to_be_bytes())),
+            // The 32-bit counter is exhausted at the same length.
+            EncryptionContext::ctr_nonce_counter(&[3u8; 12], u32::MAX - blocks as u32),
+        ];
+        for context in contexts {
+            let mut in_out = vec![0u8; 2 * CHUNK_LEN + AES_BLOCK_LEN];
+            aes_ctr_mode(&key, &context, &mut in_out).unwrap();
+
+            let mut in_out = vec![0u8; 2 * CHUNK_LEN + AES_BLOCK_LEN + 1];
+            assert!(aes_ctr_mode(&key, &context, &mut in_out).is_err());
+            assert!(in_out.iter().all(|b| *b == 0));
+        }
+    }
+}
@@ -0,0 +1,328 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use crate::cipher::{
+    DecryptingKey, DecryptionContext, OperatingMode, PaddedBlockDecryptingKey,
+    StreamingDecryptingKey, UnboundCipherKey, AES_BLOCK_LEN,
+};
+use crate::error::Unspecified;
+use core::fmt::Debug;
+use std::io::{self, ErrorKind, Read};
+use zeroize::{Zeroize, Zeroizing};
+
+/// The number of bytes of ciphertext read from the underlying reader at a time.
+const READ_LEN: usize = 4096;
+
+/// A [`Read`] adapter that decrypts the ciphertext read from an underlying reader.
+///
+/// Decryption happens as `read` is called, so the ciphertext never needs to be held in memory
+/// all at once. In CBC and ECB modes, reaching the end of the underlying reader part way
+/// through a block is an error of kind [`ErrorKind::InvalidData`].
+///
+/// The buffered plaintext is zeroized when dropped.
+pub struct DecryptingReader<R: Read> {
+    inner: R,
+    key: Option<StreamingDecryptingKey>,
+    ciphertext: Zeroizing<Vec<u8>>,
+    plaintext: Zeroizing<Vec<u8>>,
+    plaintext_pos: usize,
+    plaintext_len: usize,
+}
+
+impl<R: Read> DecryptingReader<R> {
+    /// Constructs a `DecryptingReader` that decrypts the ciphertext read from `inner` with
+    /// `key` in the given `mode`, using the context returned when the data was encrypted.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `mode` is not supported by the key's algorithm, or if
+    ///   `context` is not valid for the key's algorithm and mode.
+    pub fn new(
+        inner: R,
+        key: UnboundCipherKey,
+        mode: OperatingMode,
+        context: DecryptionContext,
+    ) -> Result<Self, Unspecified> {
+        let key = StreamingDecryptingKey::new(DecryptingKey::new(key, mode)?, context)?;
+        Ok(Self::with_key(inner, key))
+    }
+
+    /// Constructs a `DecryptingReader` that decrypts the ciphertext read from `inner` with
+    /// `key` and removes its padding, using the context returned when the data was encrypted.
+    ///
+    /// Invalid padding at the end of the ciphertext is an error of kind
+    /// [`ErrorKind::InvalidData`].
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `context` is not valid for the key's algorithm and mode.
+    pub fn new_padded(
+        inner: R,
+        key: PaddedBlockDecryptingKey,
+        context: DecryptionContext,
+    ) -> Result<Self, Unspecified> {
+        let key = StreamingDecryptingKey::new_padded(key, context)?;
+        Ok(Self::with_key(inner, key))
+    }
+
+    fn with_key(inner: R, key: StreamingDecryptingKey) -> Self {
+        DecryptingReader {
+            inner,
+            key: Some(key),
+            ciphertext: Zeroizing::new(vec![0u8; READ_LEN]),
+            plaintext: Zeroizing::new(vec![0u8; READ_LEN + AES_BLOCK_LEN]),
+            plaintext_pos: 0,
+            plaintext_len: 0,
+        }
+    }
+
+    /// Returns a reference to the underlying reader.
+    #[must_use]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// Any ciphertext that was read from it but not yet returned as plaintext is lost.
+    #[must_use]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads and decrypts more ciphertext, returning `false` once the end of the ciphertext has
+    /// been reached and all of the plaintext has been returned.
+    fn fill_plaintext(&mut self) -> io::Result<bool> {
+        let key = match self.key.as_mut() {
+            Some(key) => key,
+            None => return Ok(false),
+        };
+
+        let read_len = loop {
+            match self.inner.read(&mut self.ciphertext) {
+                Ok(read_len) => break read_len,
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        };
+
+        let written_len = if read_len == 0 {
+            // DON'T PANIC: `key` was just found to be `Some`.
+            let key = self.key.take().unwrap();
+            key.finish(&mut self.plaintext)
+                .map_err(|_| io::Error::new(ErrorKind::InvalidData, Unspecified))?
+                .written()
+                .len()
+        } else {
+            key.update(&self.ciphertext[..read_len], &mut self.plaintext)
+                .map_err(|_| io::Error::new(ErrorKind::InvalidData, Unspecified))?
+                .written()
+                .len()
+        };
+        self.ciphertext[..read_len].zeroize();
+
+        self.plaintext_pos = 0;
+        self.plaintext_len = written_len;
+        Ok(written_len != 0 || self.key.is_some())
+    }
+}
+
+impl<R: Read> Read for DecryptingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        // A read of the underlying reader may only complete a partial block, producing no
+        // plaintext, so keep reading until there is some or the ciphertext ends.
+        while self.plaintext_pos == self.plaintext_len {
+            if !self.fill_plaintext()? {
+                return Ok(0);
+            }
+        }
+
+        let available = &self.plaintext[self.plaintext_pos..self.plaintext_len];
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.plaintext[self.plaintext_pos..self.plaintext_pos + len].zeroize();
+        self.plaintext_pos += len;
+        Ok(len)
+    }
+}
+
+impl<R: Read> Debug for DecryptingReader<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DecryptingReader")
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DecryptingReader;
+    use crate::cipher::{
+        DecryptingKey, DecryptionContext, EncryptingKey, EncryptionContext, OperatingMode,
+        PaddedBlockDecryptingKey, PaddedBlockEncryptingKey, UnboundCipherKey, AES_128,
+    };
+    use crate::iv::FixedLength;
+    use std::io::{Cursor, ErrorKind, Read};
+
+    /// A reader that returns at most `max_read` bytes per call.
+    struct ShortReader<'a> {
+        data: &'a [u8],
+        max_read: usize,
+    }
+
+    impl Read for ShortReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let len = self.max_read.min(buf.len()).min(self.data.len());
+            buf[..len].copy_from_slice(&self.data[..len]);
+            self.data = &self.data[len..];
+            Ok(len)
+        }
+    }
+
+    fn encrypt(mode: OperatingMode, plaintext: &[u8]) -> (Vec<u8>, DecryptionContext) {
+        let key = UnboundCipherKey::new(&AES_128, &[0x42u8; 16]).unwrap();
+        let mut ciphertext = plaintext.to_vec();
+        let context = EncryptingKey::new(key, mode)
+            .unwrap()
+            .encrypt(&mut ciphertext)
+            .unwrap();
+        (ciphertext, context)
+    }
+
+    fn reader<R: Read>(
+        inner: R,
+        mode: OperatingMode,
+        context: DecryptionContext,
+    ) -> DecryptingReader<R> {
+        let key = UnboundCipherKey::new(&AES_128, &[0x42u8; 16]).unwrap();
+        DecryptingReader::new(inner, key, mode, context).unwrap()
+    }
+
+    fn copy_context(context: &DecryptionContext) -> DecryptionContext {
+        let iv: &[u8] = context.try_into().unwrap();
+        DecryptionContext::Iv128(iv.try_into().unwrap())
+    }
+
+    #[test]
+    fn test_short_reads() {
+        let plaintext: Vec<u8> = (0..5000).map(|i| i as u8).collect();
+        for (mode, len) in [
+            (OperatingMode::CTR, plaintext.len()),
+            (OperatingMode::CBC, 4992),
+        ] {
+            let (ciphertext, context) = encrypt(mode, &plaintext[..len]);
+            for max_read in [1, 5, 16, 17, 100, 5000] {
+                for out_len in [1, 7, 16, 4096] {
+                    let inner = ShortReader {
+                        data: &ciphertext,
+                        max_read,
+                    };
+                    let mut reader = reader(inner, mode, copy_context(&context));
+                    let mut decrypted = Vec::new();
+                    let mut buf = vec![0u8; out_len];
+                    loop {
+                        assert_eq!(0, reader.read(&mut []).unwrap());
+                        let read_len = reader.read(&mut buf).unwrap();
+                        if read_len == 0 {
+                            break;
+                        }
+                        decrypted.extend_from_slice(&buf[..read_len]);
+                    }
+                    assert_eq!(plaintext[..len], decrypted);
+                    assert_eq!(0, reader.read(&mut buf).unwrap());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_matches_one_shot_decrypt() {
+        let plaintext: Vec<u8> = (0..100).collect();
+        let (ciphertext, context) = encrypt(OperatingMode::CTR, &plaintext);
+
+        let key = UnboundCipherKey::new(&AES_128, &[0x42u8; 16]).unwrap();
+        let mut expected = ciphertext.clone();
+        DecryptingKey::ctr(key)
+            .unwrap()
+            .decrypt(&mut expected, copy_context(&context))
+            .unwrap();
+
+        let mut reader = reader(Cursor::new(ciphertext), OperatingMode::CTR, context);
+        let mut decrypted = Vec::new();
+        reader.read_to_end(&mut decrypted).unwrap();
+        assert_eq!(expected, decrypted);
+        assert_eq!(plaintext, decrypted);
+    }
+
+    #[test]
+    fn test_cbc_partial_block_is_an_error() {
+        let (ciphertext, context) = encrypt(OperatingMode::CBC, &[0u8; 32]);
+        let mut reader = reader(Cursor::new(&ciphertext[..24]), OperatingMode::CBC, context);
+        let mut decrypted = Vec::new();
+        let err = reader.read_to_end(&mut decrypted).unwrap_err();
+        assert_eq!(ErrorKind::InvalidData, err.kind());
+        assert_eq!([0u8; 16], decrypted.as_slice());
+    }
+
+    #[test]
+    fn test_padded() {
+        let key = || UnboundCipherKey::new(&AES_128, &[0x42u8; 16]).unwrap();
+        let iv = [7u8; 16];
+        let mut ciphertext = b"some plaintext".to_vec();
+        PaddedBlockEncryptingKey::cbc_pkcs7(key())
+            .unwrap()
+            .less_safe_encrypt(
+                &mut ciphertext,
+                EncryptionContext::Iv128(FixedLength::from(iv)),
+            )
+            .unwrap();
+
+        let inner = ShortReader {
+            data: &ciphertext,
+            max_read: 5,
+        };
+        let mut reader = DecryptingReader::new_padded(
+            inner,
+            PaddedBlockDecryptingKey::cbc_pkcs7(key()).unwrap(),
+            DecryptionContext::Iv128(FixedLength::from(iv)),
+        )
+        .unwrap();
+        let mut decrypted = Vec::new();
+        reader.read_to_end(&mut decrypted).unwrap();
+        assert_eq!(b"some plaintext", decrypted.as_slice());
+
+        // Flipping a bit of the IV flips the same bit of the last padding byte.
+        let mut modified_iv = iv;
+        modified_iv[15] ^= 0x01;
+        let mut reader = DecryptingReader::new_padded(
+            Cursor::new(&ciphertext),
+            PaddedBlockDecryptingKey::cbc_pkcs7(key()).unwrap(),
+            DecryptionContext::Iv128(FixedLength::from(modified_iv)),
+        )
+        .unwrap();
+        let err = reader.read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(ErrorKind::InvalidData, err.kind());
+    }
+
+    #[test]
+    fn test_into_inner() {
+        let (ciphertext, context) = encrypt(OperatingMode::CTR, &[0u8; 10]);
+        let reader = reader(Cursor::new(ciphertext.clone()), OperatingMode::CTR, context);
+        assert_eq!(&ciphertext, reader.get_ref().get_ref());
+        assert_eq!(ciphertext, reader.into_inner().into_inner());
+    }
+
+    #[test]
+    fn test_invalid_context() {
+        let key = UnboundCipherKey::new(&AES_128, &[0x42u8; 16]).unwrap();
+        let result = DecryptingReader::new(
+            Cursor::new(Vec::new()),
+            key,
+            OperatingMode::CTR,
+            DecryptionContext::None,
+        );
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,398 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Cipher keys that enforce additional safety properties at runtime.
+//!
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::cipher::safe::IvUniquenessEnforcingEncryptingKey;
+//! use aws_lc_rs::cipher::{EncryptingKey, EncryptionContext, UnboundCipherKey, AES_128};
+//! use aws_lc_rs::iv::FixedLength;
+//!
+//! let key = UnboundCipherKey::new(&AES_128, &[0x42u8; 16])?;
+//! let mut key = IvUniquenessEnforcingEncryptingKey::new(EncryptingKey::ctr(key)?)?;
+//!
+//! // The first message is two blocks long, so it uses the counter blocks `iv` and `iv + 1`.
+//! let mut in_out = b"first message, two blocks long".to_vec();
+//! let iv = [7u8; 16];
+//! key.less_safe_encrypt(&mut in_out, EncryptionContext::Iv128(FixedLength::from(iv)))?;
+//!
+//! // Encrypting a second message with the same IV is refused.
+//! let mut in_out = b"second message".to_vec();
+//! assert!(key
+//!     .less_safe_encrypt(&mut in_out, EncryptionContext::Iv128(FixedLength::from(iv)))
+//!     .is_err());
+//!
+//! // So is an IV whose counter blocks overlap those used by the first message.
+//! let mut next_iv = iv;
+//! next_iv[15] += 1;
+//! assert!(key
+//!     .less_safe_encrypt(&mut in_out, EncryptionContext::Iv128(FixedLength::from(next_iv)))
+//!     .is_err());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::cipher::{
+    aes_ctr_blocks, aes_ctr_counter_block, Algorithm, DecryptionContext, EncryptingKey,
+    EncryptionContext, OperatingMode, PaddedBlockEncryptingKey,
+};
+use crate::error::Unspecified;
+use core::fmt::Debug;
+use std::collections::{BTreeMap, HashSet};
+
+enum InnerKey {
+    Unpadded(EncryptingKey),
+    Padded(PaddedBlockEncryptingKey),
+}
+
+/// An encrypting key that refuses to encrypt twice with the same IV or nonce.
+///
+/// Every IV or nonce used is recorded, whether it was generated by [`encrypt`](Self::encrypt)
+/// or provided to [`less_safe_encrypt`](Self::less_safe_encrypt), and any later encryption with
+/// a recorded value fails before the input is modified. The memory used grows with the number
+/// of encryptions, so a key should be replaced periodically when encrypting many messages.
+///
+/// What is recorded depends on the mode:
+/// * CTR: the range of counter blocks used by each encryption. An encryption is refused if
+///   any of its counter blocks has already been used, so an IV of `n + 1` after an
+///   encryption of two or more blocks from `n` is refused too. `Iv128` and `Nonce96Counter32`
+///   contexts share the same 128-bit counter block space.
+/// * ChaCha20: the 96-bit nonce only. For a `Nonce96Counter32` context the initial block
+///   counter is not recorded, so any second use of a nonce is refused, even with a block
+///   counter that would not overlap the first.
+/// * Other modes: the IV.
+pub struct IvUniquenessEnforcingEncryptingKey {
+    key: InnerKey,
+    used: HashSet<Vec<u8>>,
+    // The first and last counter blocks of each CTR encryption, keyed by the first.
+    used_counter_blocks: BTreeMap<u128, u128>,
+}
+
+impl IvUniquenessEnforcingEncryptingKey {
+    /// Constructs an `IvUniquenessEnforcingEncryptingKey` from `key`.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if the key's mode does not use an IV or nonce, as for ECB.
+    pub fn new(key: EncryptingKey) -> Result<Self, Unspecified> {
+        Self::with_inner_key(InnerKey::Unpadded(key))
+    }
+
+    /// Constructs an `IvUniquenessEnforcingEncryptingKey` from a key that pads its input.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if the key's mode does not use an IV or nonce, as for ECB.
+    pub fn new_padded(key: PaddedBlockEncryptingKey) -> Result<Self, Unspecified> {
+        Self::with_inner_key(InnerKey::Padded(key))
+    }
+
+    fn with_inner_key(key: InnerKey) -> Result<Self, Unspecified> {
+        let (algorithm, mode) = match &key {
+            InnerKey::Unpadded(key) => (key.algorithm(), key.mode),
+            InnerKey::Padded(key) => (key.algorithm(), key.mode),
+        };
+        if algorithm.iv_len(mode) == Some(0) {
+            return Err(Unspecified);
+        }
+        Ok(IvUniquenessEnforcingEncryptingKey {
+            key,
+            used: HashSet::new(),
+            used_counter_blocks: BTreeMap::new(),
+        })
+    }
+
+    /// Returns the cipher algorithm.
+    #[must_use]
+    pub fn algorithm(&self) -> &Algorithm {
+        match &self.key {
+            InnerKey::Unpadded(key) => key.algorithm(),
+            InnerKey::Padded(key) => key.algorithm(),
+        }
+    }
+
+    /// Returns the cipher operating mode.
+    #[must_use]
+    pub fn mode(&self) -> OperatingMode {
+        match &self.key {
+            InnerKey::Unpadded(key) => key.mode(),
+            InnerKey::Padded(key) => key.mode(),
+        }
+    }
+
+    /// Returns the number of IVs or nonces that have been used with this key.
+    #[must_use]
+    pub fn used_count(&self) -> usize {
+        self.used.len() + self.used_counter_blocks.len()
+    }
+
+    /// Encrypts the data provided in `in_out` in-place with a newly generated IV or nonce,
+    /// extending it with any padding required by the key. Returns the context needed to
+    /// decrypt the data.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if the generated IV or nonce has already been used, or if
+    ///   encryption fails.
+    pub fn encrypt<InOut>(&mut self, in_out: &mut InOut) -> Result<DecryptionContext, Unspecified>
+    where
+        InOut: AsMut<[u8]> + for<'a> Extend<&'a u8>,
+    {
+        let context = self.algorithm().new_encryption_context(self.mode())?;
+        self.less_safe_encrypt(in_out, context)
+    }
+
+    /// Encrypts the data provided in `in_out` in-place using the provided context, extending
+    /// it with any padding required by the key. Returns the context needed to decrypt the data.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if the IV or nonce in `context` has already been used with
+    ///   this key, or for CTR mode if any of the counter blocks needed for `in_out` have, if
+    ///   `context` does not hold one, or if encryption fails.
+    pub fn less_safe_encrypt<InOut>(
+        &mut self,
+        in_out: &mut InOut,
+        context: EncryptionContext,
+    ) -> Result<DecryptionContext, Unspecified>
+    where
+        InOut: AsMut<[u8]> + for<'a> Extend<&'a u8>,
+    {
+        if self.mode() == OperatingMode::CTR {
+            let (first, last) = self.unused_counter_blocks(&context, in_out.as_mut().len())?;
+            let context = self.encrypt_with_inner_key(in_out, context)?;
+            self.used_counter_blocks.insert(first, last);
+            return Ok(context);
+        }
+
+        let iv = context.iv_or_nonce()?.to_vec();
+        if self.used.contains(&iv) {
+            return Err(Unspecified);
+        }
+        let context = self.encrypt_with_inner_key(in_out, context)?;
+        self.used.insert(iv);
+        Ok(context)
+    }
+
+    /// Returns the first and last counter blocks used to encrypt `len` bytes in CTR mode with
+    /// `context`, if none of them has been used yet.
+    fn unused_counter_blocks(
+        &self,
+        context: &EncryptionContext,
+        len: usize,
+    ) -> Result<(u128, u128), Unspecified> {
+        let (counter_block, blocks_available) = aes_ctr_counter_block(context)?;
+        // An empty input uses no keystream, but its counter block is still recorded so that
+        // the context cannot be used again.
+        let blocks = aes_ctr_blocks(len).max(1);
+        if blocks > blocks_available {
+            return Err(Unspecified);
+        }
+        let first = u128::from_be_bytes(*counter_block);
+        let last = first + (blocks - 1);
+
+        // The used ranges don't overlap, so only the one starting last before `last` can
+        // overlap the new range.
+        if let Some((_, used_last)) = self.used_counter_blocks.range(..=last).next_back() {
+            if *used_last >= first {
+                return Err(Unspecified);
+            }
+        }
+        Ok((first, last))
+    }
+
+    fn encrypt_with_inner_key<InOut>(
+        &self,
+        in_out: &mut InOut,
+        context: EncryptionContext,
+    ) -> Result<DecryptionContext, Unspecified>
+    where
+        InOut: AsMut<[u8]> + for<'a> Extend<&'a u8>,
+    {
+        match &self.key {
+            InnerKey::Unpadded(key) => key.less_safe_encrypt(in_out.as_mut(), context),
+            InnerKey::Padded(key) => key.less_safe_encrypt(in_out, context),
+        }
+    }
+}
+
+impl Debug for IvUniquenessEnforcingEncryptingKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let key: &dyn Debug = match &self.key {
+            InnerKey::Unpadded(key) => key,
+            InnerKey::Padded(key) => key,
+        };
+        f.debug_struct("IvUniquenessEnforcingEncryptingKey")
+            .field("key", key)
+            .field("used_count", &self.used_count())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cipher::{
+        DecryptingKey, PaddedBlockDecryptingKey, UnboundCipherKey, AES_128, CHACHA20,
+    };
+    use crate::iv::FixedLength;
+
+    fn unbound() -> UnboundCipherKey {
+        UnboundCipherKey::new(&AES_128, &[0x42u8; 16]).unwrap()
+    }
+
+    #[test]
+    fn test_rejects_reused_iv() {
+        let mut key =
+            IvUniquenessEnforcingEncryptingKey::new(EncryptingKey::ctr(unbound()).unwrap())
+                .unwrap();
+        let context = || EncryptionContext::Iv128(FixedLength::from([1u8; 16]));
+
+        let mut in_out = vec![0u8; 20];
+        key.less_safe_encrypt(&mut in_out, context()).unwrap();
+        assert_eq!(1, key.used_count());
+
+        let mut in_out = vec![0u8; 20];
+        assert!(key.less_safe_encrypt(&mut in_out, context()).is_err());
+        assert_eq!(vec![0u8; 20], in_out);
+        assert_eq!(1, key.used_count());
+
+        let mut in_out = vec![0u8; 20];
+        key.less_safe_encrypt(
+            &mut in_out,
+            EncryptionContext::Iv128(FixedLength::from([2u8; 16])),
+        )
+        .unwrap();
+        assert_eq!(2, key.used_count());
+    }
+
+    #[test]
+    fn test_failed_encryption_does_not_use_iv() {
+        let mut key =
+            IvUniquenessEnforcingEncryptingKey::new(EncryptingKey::cbc(unbound()).unwrap())
+                .unwrap();
+        let context = || EncryptionContext::Iv128(FixedLength::from([1u8; 16]));
+
+        let mut in_out = vec![0u8; 20];
+        assert!(key.less_safe_encrypt(&mut in_out, context()).is_err());
+        assert_eq!(0, key.used_count());
+
+        let mut in_out = vec![0u8; 32];
+        key.less_safe_encrypt(&mut in_out, context()).unwrap();
+        assert_eq!(1, key.used_count());
+    }
+
+    #[test]
+    fn test_generated_ivs_round_trip() {
+        let mut key = IvUniquenessEnforcingEncryptingKey::new_padded(
+            PaddedBlockEncryptingKey::cbc_pkcs7(unbound()).unwrap(),
+        )
+        .unwrap();
+        let decrypting_key = PaddedBlockDecryptingKey::cbc_pkcs7(unbound()).unwrap();
+        for len in 0..100 {
+            let plaintext: Vec<u8> = (0..len).collect();
+            let mut in_out = plaintext.clone();
+            let context = key.encrypt(&mut in_out).unwrap();
+            assert_eq!(
+                plaintext.as_slice(),
+                decrypting_key.decrypt(&mut in_out, context).unwrap()
+            );
+        }
+        assert_eq!(100, key.used_count());
+
+        let chacha_key = UnboundCipherKey::new(&CHACHA20, &[0x42u8; 32]).unwrap();
+        let mut key =
+            IvUniquenessEnforcingEncryptingKey::new(EncryptingKey::chacha20(chacha_key).unwrap())
+                .unwrap();
+        let mut in_out = b"stream cipher".to_vec();
+        let context = key.encrypt(&mut in_out).unwrap();
+        let chacha_key = UnboundCipherKey::new(&CHACHA20, &[0x42u8; 32]).unwrap();
+        assert_eq!(
+            b"stream cipher",
+            DecryptingKey::chacha20(chacha_key)
+                .unwrap()
+                .decrypt(&mut in_out, context)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rejects_overlapping_ctr_counter_blocks() {
+        let mut key =
+            IvUniquenessEnforcingEncryptingKey::new(EncryptingKey::ctr(unbound()).unwrap())
+                .unwrap();
+        let iv = |counter: u128| EncryptionContext::Iv128(FixedLength::from(counter.to_be_bytes()));
+
+        // Uses the counter blocks 10 to 12.
+        let mut in_out = vec![0u8; 40];
+        key.less_safe_encrypt(&mut in_out, iv(10)).unwrap();
+
+        for counter in [9, 10, 11, 12] {
+            let mut in_out = vec![0u8; 32];
+            assert!(key.less_safe_encrypt(&mut in_out, iv(counter)).is_err());
+            assert_eq!(vec![0u8; 32], in_out);
+        }
+        // Counter blocks 8 and 9 are still unused.
+        let mut in_out = vec![0u8; 32];
+        key.less_safe_encrypt(&mut in_out, iv(8)).unwrap();
+        let mut in_out = vec![0u8; 1];
+        key.less_safe_encrypt(&mut in_out, iv(13)).unwrap();
+        assert_eq!(3, key.used_count());
+
+        // An empty input still uses its counter block.
+        key.less_safe_encrypt(&mut Vec::new(), iv(20)).unwrap();
+        assert!(key.less_safe_encrypt(&mut Vec::new(), iv(20)).is_err());
+        assert!(key.less_safe_encrypt(&mut vec![0u8; 1], iv(20)).is_err());
+
+        // A 96-bit nonce and 32-bit counter address the same counter blocks as an `Iv128`.
+        let nonce = [0u8; 12];
+        let mut in_out = vec![0u8; 16];
+        assert!(key
+            .less_safe_encrypt(
+                &mut in_out,
+                EncryptionContext::ctr_nonce_counter(&nonce, 12)
+            )
+            .is_err());
+        key.less_safe_encrypt(
+            &mut in_out,
+            EncryptionContext::ctr_nonce_counter(&nonce, 14),
+        )
+        .unwrap();
+        assert!(key.less_safe_encrypt(&mut in_out, iv(14)).is_err());
+    }
+
+    #[test]
+    fn test_chacha20_tracks_nonce_only() {
+        let chacha_key = UnboundCipherKey::new(&CHACHA20, &[0x42u8; 32]).unwrap();
+        let mut key =
+            IvUniquenessEnforcingEncryptingKey::new(EncryptingKey::chacha20(chacha_key).unwrap())
+                .unwrap();
+        let nonce = [3u8; 12];
+
+        let mut in_out = vec![0u8; 64];
+        key.less_safe_encrypt(
+            &mut in_out,
+            EncryptionContext::Nonce96Counter32(FixedLength::from(nonce), 0),
+        )
+        .unwrap();
+        // The block counter is not tracked, so the nonce can't be used again even with a
+        // counter after the blocks already used.
+        assert!(key
+            .less_safe_encrypt(
+                &mut in_out,
+                EncryptionContext::Nonce96Counter32(FixedLength::from(nonce), 1000),
+            )
+            .is_err());
+        assert!(key
+            .less_safe_encrypt(
+                &mut in_out,
+                EncryptionContext::Nonce96(FixedLength::from(nonce))
+            )
+            .is_err());
+    }
+
+    #[test]
+    #[cfg(not(feature = "approved-only"))]
+    fn test_rejects_modes_without_iv() {
+        let key = EncryptingKey::less_safe_ecb(unbound()).unwrap();
+        assert!(IvUniquenessEnforcingEncryptingKey::new(key).is_err());
+    }
+}
@@ -0,0 +1,331 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use crate::cipher::block::BLOCK_LEN;
+use crate::cipher::cmac::{aes_cmac, Cmac};
+use crate::cipher::key::SymmetricCipherKey;
+use crate::cipher::{aes_ctr128_encrypt, Algorithm, AlgorithmId, UnboundCipherKey};
+use crate::constant_time;
+use crate::error::Unspecified;
+use core::fmt::Debug;
+use zeroize::Zeroize;
+
+/// The number of bytes in the synthetic IV that AES-SIV prepends to the ciphertext.
+pub const SIV_LEN: usize = 16;
+
+/// The maximum number of associated data components, as specified by RFC 5297.
+const MAX_ASSOCIATED_DATA: usize = 126;
+
+/// The key for AES-SIV authenticated encryption, as specified by RFC 5297.
+///
+/// An AES-SIV key consists of a MAC key, used to compute the synthetic IV with S2V, and a
+/// CTR key, used to encrypt the plaintext.
+pub struct SivKey {
+    mac_key: UnboundCipherKey,
+    ctr_key: UnboundCipherKey,
+}
+
+impl SivKey {
+    /// Constructs a `SivKey` from the MAC key and the CTR key.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if the keys are not both AES keys of the same length.
+    pub fn new(mac_key: UnboundCipherKey, ctr_key: UnboundCipherKey) -> Result<Self, Unspecified> {
+        match (mac_key.algorithm().id(), ctr_key.algorithm().id()) {
+            (AlgorithmId::Aes128, AlgorithmId::Aes128)
+            | (AlgorithmId::Aes192, AlgorithmId::Aes192)
+            | (AlgorithmId::Aes256, AlgorithmId::Aes256) => {}
+            _ => return Err(Unspecified),
+        }
+        Ok(SivKey { mac_key, ctr_key })
+    }
+
+    /// Returns the cipher algorithm of the MAC and CTR keys.
+    #[must_use]
+    pub fn algorithm(&self) -> &Algorithm {
+        self.mac_key.algorithm()
+    }
+
+    /// Computes the synthetic IV over `associated_data` and `plaintext` with S2V.
+    fn s2v(
+        &self,
+        associated_data: &[&[u8]],
+        plaintext: &[u8],
+    ) -> Result<[u8; SIV_LEN], Unspecified> {
+        if associated_data.len() > MAX_ASSOCIATED_DATA {
+            return Err(Unspecified);
+        }
+
+        let mut d = aes_cmac(&self.mac_key, &[0u8; BLOCK_LEN])?;
+        for component in associated_data {
+            let mut mac = aes_cmac(&self.mac_key, component)?;
+            d = double(&d);
+            xor_in_place(&mut d, &mac);
+            mac.zeroize();
+        }
+
+        let siv = if plaintext.len() >= BLOCK_LEN {
+            // The last block of the plaintext is combined with `d` before it is authenticated.
+            let (head, last) = plaintext.split_at(plaintext.len() - BLOCK_LEN);
+            let mut t = d;
+            xor_in_place(&mut t, last);
+            let siv = Cmac::with_key(&self.mac_key).and_then(|mut cmac| {
+                cmac.try_update(head)?;
+                cmac.try_update(&t)?;
+                cmac.try_finalize()
+            });
+            t.zeroize();
+            siv.map(|tag| tag.0)
+        } else {
+            let mut t = double(&d);
+            xor_in_place(&mut t[..plaintext.len()], plaintext);
+            t[plaintext.len()] ^= 0x80;
+            let siv = aes_cmac(&self.mac_key, &t);
+            t.zeroize();
+            siv
+        };
+        d.zeroize();
+
+        siv
+    }
+
+    /// Encrypts or decrypts `in_out` in CTR mode, using `siv` as the initial counter block.
+    fn ctr(&self, siv: &[u8; SIV_LEN], in_out: &mut [u8]) -> Result<(), Unspecified> {
+        #[allow(clippy::match_wildcard_for_single_variants)]
+        let key = match &self.ctr_key.key {
+            SymmetricCipherKey::Aes128 { enc_key, .. }
+            | SymmetricCipherKey::Aes192 { enc_key, .. }
+            | SymmetricCipherKey::Aes256 { enc_key, .. } => enc_key,
+            _ => return Err(Unspecified),
+        };
+
+        // The 31st and 63rd bits (counting from the right) of the counter are cleared so that
+        // implementations can use a 32-bit or 64-bit counter.
+        let mut counter = *siv;
+        counter[8] &= 0x7f;
+        counter[12] &= 0x7f;
+
+        let mut block_buffer = [0u8; BLOCK_LEN];
+        aes_ctr128_encrypt(key, &mut counter, &mut block_buffer, &mut 0, in_out);
+        counter.zeroize();
+        block_buffer.zeroize();
+
+        Ok(())
+    }
+}
+
+impl Debug for SivKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SivKey")
+            .field("algorithm", self.algorithm())
+            .finish()
+    }
+}
+
+/// An AES-SIV key for sealing data.
+pub struct SivEncryptingKey {
+    key: SivKey,
+}
+
+impl SivEncryptingKey {
+    /// Constructs a `SivEncryptingKey` from `key`.
+    #[must_use]
+    pub fn new(key: SivKey) -> Self {
+        SivEncryptingKey { key }
+    }
+
+    /// Returns the cipher algorithm.
+    #[must_use]
+    pub fn algorithm(&self) -> &Algorithm {
+        self.key.algorithm()
+    }
+
+    /// Encrypts and authenticates the plaintext in `in_out` in-place, together with each of the
+    /// `associated_data` components. On success `in_out` holds the synthetic IV followed by the
+    /// ciphertext, and is `SIV_LEN` bytes longer than the plaintext.
+    ///
+    /// AES-SIV is deterministic: sealing the same plaintext and associated data twice produces
+    /// the same output. To encrypt with a nonce, pass it as the last associated data component.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there are more than 126 associated data components.
+    pub fn seal<InOut>(
+        &self,
+        associated_data: &[&[u8]],
+        in_out: &mut InOut,
+    ) -> Result<(), Unspecified>
+    where
+        InOut: AsMut<[u8]> + for<'in_out> Extend<&'in_out u8>,
+    {
+        let mut siv = self.key.s2v(associated_data, in_out.as_mut())?;
+        self.key.ctr(&siv, in_out.as_mut())?;
+
+        in_out.extend(siv.iter());
+        in_out.as_mut().rotate_right(SIV_LEN);
+        siv.zeroize();
+
+        Ok(())
+    }
+}
+
+impl Debug for SivEncryptingKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SivEncryptingKey")
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+/// An AES-SIV key for opening sealed data.
+pub struct SivDecryptingKey {
+    key: SivKey,
+}
+
+impl SivDecryptingKey {
+    /// Constructs a `SivDecryptingKey` from `key`.
+    #[must_use]
+    pub fn new(key: SivKey) -> Self {
+        SivDecryptingKey { key }
+    }
+
+    /// Returns the cipher algorithm.
+    #[must_use]
+    pub fn algorithm(&self) -> &Algorithm {
+        self.key.algorithm()
+    }
+
+    /// Verifies and decrypts `in_out` in-place. `in_out` must hold the synthetic IV followed by
+    /// the ciphertext, as produced by [`SivEncryptingKey::seal`] with the same associated data.
+    /// Returns the subslice of `in_out` containing the plaintext.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `in_out` is shorter than `SIV_LEN`, if there are more than
+    ///   126 associated data components, or if the synthetic IV does not match. On failure the
+    ///   decrypted data is zeroed.
+    pub fn open<'in_out>(
+        &self,
+        associated_data: &[&[u8]],
+        in_out: &'in_out mut [u8],
+    ) -> Result<&'in_out mut [u8], Unspecified> {
+        if in_out.len() < SIV_LEN {
+            return Err(Unspecified);
+        }
+        let (siv, ciphertext) = in_out.split_at_mut(SIV_LEN);
+        let siv: [u8; SIV_LEN] = (&*siv).try_into().map_err(|_| Unspecified)?;
+
+        self.key.ctr(&siv, ciphertext)?;
+        let expected = self.key.s2v(associated_data, ciphertext);
+        let verified = match &expected {
+            Ok(expected) => constant_time::verify_slices_are_equal(expected, &siv),
+            Err(_) => Err(Unspecified),
+        };
+        if verified.is_err() {
+            ciphertext.fill(0);
+            return Err(Unspecified);
+        }
+
+        Ok(ciphertext)
+    }
+}
+
+impl Debug for SivDecryptingKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SivDecryptingKey")
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+// Multiplication by `x` in GF(2^128) using the polynomial x^128 + x^7 + x^2 + x + 1, the `dbl`
+// operation of RFC 5297.
+fn double(input: &[u8; BLOCK_LEN]) -> [u8; BLOCK_LEN] {
+    let mut output = [0u8; BLOCK_LEN];
+    let mut carry = 0u8;
+    for i in (0..BLOCK_LEN).rev() {
+        output[i] = (input[i] << 1) | carry;
+        carry = input[i] >> 7;
+    }
+    // Constant-time conditional reduction.
+    output[BLOCK_LEN - 1] ^= 0x87 & 0u8.wrapping_sub(carry);
+    output
+}
+
+fn xor_in_place(a: &mut [u8], b: &[u8]) {
+    for (a, b) in a.iter_mut().zip(b) {
+        *a ^= b;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cipher::{AES_128, AES_256, CHACHA20};
+
+    fn siv_key(algorithm: &'static Algorithm, mac_key: &[u8], ctr_key: &[u8]) -> SivKey {
+        SivKey::new(
+            UnboundCipherKey::new(algorithm, mac_key).unwrap(),
+            UnboundCipherKey::new(algorithm, ctr_key).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_siv_key_validation() {
+        assert!(SivKey::new(
+            UnboundCipherKey::new(&AES_128, &[1u8; 16]).unwrap(),
+            UnboundCipherKey::new(&AES_256, &[2u8; 32]).unwrap(),
+        )
+        .is_err());
+        assert!(SivKey::new(
+            UnboundCipherKey::new(&CHACHA20, &[1u8; 32]).unwrap(),
+            UnboundCipherKey::new(&CHACHA20, &[2u8; 32]).unwrap(),
+        )
+        .is_err());
+        assert_eq!(
+            &AES_256,
+            siv_key(&AES_256, &[1u8; 32], &[2u8; 32]).algorithm()
+        );
+    }
+
+    #[test]
+    fn test_siv_round_trip() {
+        let sealing_key = SivEncryptingKey::new(siv_key(&AES_128, &[1u8; 16], &[2u8; 16]));
+        let opening_key = SivDecryptingKey::new(siv_key(&AES_128, &[1u8; 16], &[2u8; 16]));
+
+        for len in 0..50 {
+            let plaintext: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            for associated_data in [&[][..], &[&b"header"[..]][..], &[&b""[..], &b"a"[..]][..]] {
+                let mut in_out = plaintext.clone();
+                sealing_key.seal(associated_data, &mut in_out).unwrap();
+                assert_eq!(len + SIV_LEN, in_out.len());
+
+                let mut resealed = plaintext.clone();
+                sealing_key.seal(associated_data, &mut resealed).unwrap();
+                assert_eq!(in_out, resealed);
+
+                let opened = opening_key.open(associated_data, &mut in_out).unwrap();
+                assert_eq!(plaintext.as_slice(), opened);
+            }
+        }
+    }
+
+    #[test]
+    fn test_siv_open_rejects_modified_input() {
+        let sealing_key = SivEncryptingKey::new(siv_key(&AES_128, &[1u8; 16], &[2u8; 16]));
+        let opening_key = SivDecryptingKey::new(siv_key(&AES_128, &[1u8; 16], &[2u8; 16]));
+
+        let mut sealed = vec![0x42u8; 20];
+        sealing_key.seal(&[b"header"], &mut sealed).unwrap();
+
+        for i in 0..sealed.len() {
+            let mut modified = sealed.clone();
+            modified[i] ^= 1;
+            assert!(opening_key.open(&[b"header"], &mut modified).is_err());
+            assert!(modified[SIV_LEN..].iter().all(|b| *b == 0));
+        }
+
+        assert!(opening_key.open(&[b"header"], &mut [0u8; 15]).is_err());
+        let too_many = [&b""[..]; MAX_ASSOCIATED_DATA + 1];
+        assert!(sealing_key.seal(&too_many, &mut vec![0u8; 4]).is_err());
+    }
+}
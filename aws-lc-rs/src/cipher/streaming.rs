@@ -0,0 +1,1294 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+#[cfg(not(feature = "approved-only"))]
+use crate::cipher::aes_ecb_encrypt;
+use crate::cipher::chacha::ChaCha20Key;
+use crate::cipher::key::SymmetricCipherKey;
+use crate::cipher::{
+    aes_cbc_decrypt, aes_cbc_encrypt, aes_cfb128_encrypt, aes_cfb8_encrypt, aes_ctr128_encrypt,
+    aes_ctr_blocks, aes_ctr_counter_block, aes_ofb128_encrypt, chacha20_initial_counter, Algorithm,
+    DecryptingKey, DecryptionContext, EncryptingKey, EncryptionContext, OperatingMode,
+    PaddedBlockDecryptingKey, PaddedBlockEncryptingKey, PaddingStrategy, UnboundCipherKey,
+    AES_BLOCK_LEN, AES_CBC_IV_LEN, AES_CFB_IV_LEN, AES_CTR_IV_LEN, AES_OFB_IV_LEN,
+    CHACHA20_BLOCK_LEN, CHACHA20_MAX_INPUT_LEN, CHACHA20_NONCE_LEN,
+};
+use crate::error::Unspecified;
+use aws_lc::{AES_DECRYPT, AES_ENCRYPT, AES_KEY};
+use core::fmt::Debug;
+use core::ops::DerefMut;
+use zeroize::Zeroize;
+
+/// The output of a streaming update or finish operation.
+pub struct BufferUpdate<'a> {
+    written: &'a [u8],
+    remainder: &'a mut [u8],
+}
+
+impl<'a> BufferUpdate<'a> {
+    fn new(out_buffer: &'a mut [u8], written_len: usize) -> Self {
+        let (written, remainder) = out_buffer.split_at_mut(written_len);
+        Self { written, remainder }
+    }
+
+    /// Returns the slice of the output buffer that was written by the operation.
+    #[must_use]
+    pub fn written(&self) -> &[u8] {
+        self.written
+    }
+
+    /// Returns the slice of the output buffer that was not written by the operation.
+    #[must_use]
+    pub fn remainder(&self) -> &[u8] {
+        self.remainder
+    }
+
+    /// Returns the mutable slice of the output buffer that was not written by the operation.
+    #[must_use]
+    pub fn remainder_mut(&mut self) -> &mut [u8] {
+        self.remainder
+    }
+}
+
+impl Debug for BufferUpdate<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BufferUpdate")
+            .field("written_len", &self.written.len())
+            .field("remainder_len", &self.remainder.len())
+            .finish()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Direction {
+    Encrypt,
+    Decrypt,
+}
+
+/// The chaining state carried between calls to `update`.
+enum StreamState {
+    Cbc {
+        iv: [u8; AES_CBC_IV_LEN],
+    },
+    #[cfg(not(feature = "approved-only"))]
+    Ecb,
+    Ctr {
+        iv: [u8; AES_CTR_IV_LEN],
+        block_buffer: [u8; AES_BLOCK_LEN],
+        num: u32,
+        // The number of counter blocks left before the counter would wrap around.
+        blocks_available: u128,
+    },
+    Cfb128 {
+        iv: [u8; AES_CFB_IV_LEN],
+        num: i32,
+    },
+    Cfb8 {
+        iv: [u8; AES_CFB_IV_LEN],
+    },
+    Ofb {
+        iv: [u8; AES_OFB_IV_LEN],
+        num: i32,
+    },
+    ChaCha20 {
+        nonce: [u8; CHACHA20_NONCE_LEN],
+        counter: u32,
+        keystream: [u8; CHACHA20_BLOCK_LEN],
+        keystream_used: usize,
+        processed: u64,
+    },
+}
+
+impl StreamState {
+    fn new(mode: OperatingMode, context: &EncryptionContext) -> Result<Self, Unspecified> {
+        let initial_counter = chacha20_initial_counter(context);
+        let iv: &[u8] = match context {
+            EncryptionContext::None => &[],
+            context => context.iv_or_nonce()?,
+        };
+
+        Ok(match mode {
+            OperatingMode::CBC => StreamState::Cbc {
+                iv: iv.try_into().map_err(|_| Unspecified)?,
+            },
+            #[cfg(not(feature = "approved-only"))]
+            OperatingMode::ECB => StreamState::Ecb,
+            OperatingMode::CTR => {
+                let (iv, blocks_available) = aes_ctr_counter_block(context)?;
+                StreamState::Ctr {
+                    iv: *iv,
+                    block_buffer: [0u8; AES_BLOCK_LEN],
+                    num: 0,
+                    blocks_available,
+                }
+            }
+            OperatingMode::CFB128 => StreamState::Cfb128 {
+                iv: iv.try_into().map_err(|_| Unspecified)?,
+                num: 0,
+            },
+            OperatingMode::CFB8 => StreamState::Cfb8 {
+                iv: iv.try_into().map_err(|_| Unspecified)?,
+            },
+            OperatingMode::OFB => StreamState::Ofb {
+                iv: iv.try_into().map_err(|_| Unspecified)?,
+                num: 0,
+            },
+            OperatingMode::Stream => StreamState::ChaCha20 {
+                nonce: iv.try_into().map_err(|_| Unspecified)?,
+                counter: initial_counter,
+                keystream: [0u8; CHACHA20_BLOCK_LEN],
+                keystream_used: CHACHA20_BLOCK_LEN,
+                // Blocks before the initial counter count against the limit.
+                processed: u64::from(initial_counter) * CHACHA20_BLOCK_LEN as u64,
+            },
+        })
+    }
+}
+
+impl Drop for StreamState {
+    fn drop(&mut self) {
+        match self {
+            StreamState::Cbc { iv } | StreamState::Cfb8 { iv } => iv.zeroize(),
+            #[cfg(not(feature = "approved-only"))]
+            StreamState::Ecb => {}
+            StreamState::Ctr {
+                iv, block_buffer, ..
+            } => {
+                iv.zeroize();
+                block_buffer.zeroize();
+            }
+            StreamState::Cfb128 { iv, .. } | StreamState::Ofb { iv, .. } => iv.zeroize(),
+            StreamState::ChaCha20 {
+                nonce, keystream, ..
+            } => {
+                nonce.zeroize();
+                keystream.zeroize();
+            }
+        }
+    }
+}
+
+/// The mode-independent part of a streaming cipher operation.
+struct StreamingCipher {
+    key: UnboundCipherKey,
+    mode: OperatingMode,
+    direction: Direction,
+    padding: Option<PaddingStrategy>,
+    state: StreamState,
+    buffer: [u8; AES_BLOCK_LEN],
+    buffered: usize,
+}
+
+impl StreamingCipher {
+    fn new(
+        key: UnboundCipherKey,
+        mode: OperatingMode,
+        direction: Direction,
+        padding: Option<PaddingStrategy>,
+        context: &EncryptionContext,
+    ) -> Result<Self, Unspecified> {
+        Ok(StreamingCipher {
+            key,
+            mode,
+            direction,
+            padding,
+            state: StreamState::new(mode, context)?,
+            buffer: [0u8; AES_BLOCK_LEN],
+            buffered: 0,
+        })
+    }
+
+    /// Constructs a CTR mode `StreamingCipher` that starts `offset` bytes into the keystream of
+    /// `context`.
+    fn resume_ctr(
+        key: UnboundCipherKey,
+        direction: Direction,
+        context: &EncryptionContext,
+        offset: u64,
+    ) -> Result<Self, Unspecified> {
+        let block_len = AES_BLOCK_LEN as u64;
+        let context = context.advanced_by_blocks(offset / block_len)?;
+        let mut cipher = Self::new(key, OperatingMode::CTR, direction, None, &context)?;
+
+        // Generate and discard the keystream for the start of a partial block, which leaves
+        // the rest of the block's keystream to be used by the next update.
+        let mut skipped = [0u8; AES_BLOCK_LEN];
+        let skipped_len = (offset % block_len) as usize;
+        let result = cipher.process(&mut skipped[..skipped_len]);
+        skipped.zeroize();
+        result.map(|()| cipher)
+    }
+
+    fn update<'a>(
+        &mut self,
+        input: &[u8],
+        output: &'a mut [u8],
+    ) -> Result<BufferUpdate<'a>, Unspecified> {
+        if !self.mode.is_block_aligned() {
+            let out_len = input.len();
+            if output.len() < out_len {
+                return Err(Unspecified);
+            }
+            output[..out_len].copy_from_slice(input);
+            self.process(&mut output[..out_len])?;
+            return Ok(BufferUpdate::new(output, out_len));
+        }
+
+        // Block modes only process whole blocks, so a trailing partial block is held back
+        // until more input arrives. When padding is removed the last whole block is held back
+        // as well, since it can only be unpadded once it is known that no more input follows.
+        let total = self.buffered + input.len();
+        let mut out_len = total - total % AES_BLOCK_LEN;
+        if self.padding.is_some()
+            && self.direction == Direction::Decrypt
+            && out_len == total
+            && out_len != 0
+        {
+            out_len -= AES_BLOCK_LEN;
+        }
+        if out_len == 0 {
+            self.buffer[self.buffered..total].copy_from_slice(input);
+            self.buffered = total;
+            return Ok(BufferUpdate::new(output, 0));
+        }
+        if output.len() < out_len {
+            return Err(Unspecified);
+        }
+
+        let consumed = out_len - self.buffered;
+        output[..self.buffered].copy_from_slice(&self.buffer[..self.buffered]);
+        output[self.buffered..out_len].copy_from_slice(&input[..consumed]);
+        self.process(&mut output[..out_len])?;
+
+        let rest = &input[consumed..];
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffered = rest.len();
+
+        Ok(BufferUpdate::new(output, out_len))
+    }
+
+    /// Processes any held back input, writing it to `output` and returning the number of bytes
+    /// written.
+    fn finish(&mut self, output: &mut [u8]) -> Result<usize, Unspecified> {
+        let padding = match self.padding {
+            Some(padding) => padding,
+            None if self.buffered == 0 => return Ok(0),
+            None => return Err(Unspecified),
+        };
+        if self.direction == Direction::Encrypt {
+            return self.finish_padded_encrypt(padding, output);
+        }
+        if self.buffered != AES_BLOCK_LEN {
+            return Err(Unspecified);
+        }
+
+        let mut block = self.buffer;
+        self.buffer.zeroize();
+        self.buffered = 0;
+        let result = self.process(&mut block).and_then(|()| {
+            let plaintext = padding.remove_padding(AES_BLOCK_LEN, &mut block)?;
+            let out_len = plaintext.len();
+            output
+                .get_mut(..out_len)
+                .ok_or(Unspecified)?
+                .copy_from_slice(plaintext);
+            Ok(out_len)
+        });
+        block.zeroize();
+        result
+    }
+
+    /// Pads the held back partial block and encrypts it, writing it to `output`.
+    fn finish_padded_encrypt(
+        &mut self,
+        padding: PaddingStrategy,
+        output: &mut [u8],
+    ) -> Result<usize, Unspecified> {
+        let out_len = self.buffered + padding.padding_len(AES_BLOCK_LEN, self.buffered);
+        if out_len % AES_BLOCK_LEN != 0 || output.len() < out_len {
+            return Err(Unspecified);
+        }
+
+        let mut block = self.buffer;
+        self.buffer.zeroize();
+        let result = padding
+            .write_padding(&mut block[self.buffered..out_len])
+            .and_then(|()| self.process(&mut block[..out_len]));
+        self.buffered = 0;
+        if result.is_ok() {
+            output[..out_len].copy_from_slice(&block[..out_len]);
+        }
+        block.zeroize();
+        result.map(|()| out_len)
+    }
+
+    fn process(&mut self, in_out: &mut [u8]) -> Result<(), Unspecified> {
+        process(&self.key, self.direction, &mut self.state, in_out)
+    }
+}
+
+/// Encrypts each of `bufs` in turn, carrying the chaining state from one to the next, so that
+/// the result is the same as encrypting their concatenation.
+pub(super) fn encrypt_vectored<B: DerefMut<Target = [u8]>>(
+    key: &UnboundCipherKey,
+    mode: OperatingMode,
+    context: &EncryptionContext,
+    bufs: &mut [B],
+) -> Result<(), Unspecified> {
+    process_vectored(key, mode, Direction::Encrypt, context, bufs)
+}
+
+/// Decrypts each of `bufs` in turn, carrying the chaining state from one to the next, so that
+/// the result is the same as decrypting their concatenation.
+pub(super) fn decrypt_vectored<B: DerefMut<Target = [u8]>>(
+    key: &UnboundCipherKey,
+    mode: OperatingMode,
+    context: &EncryptionContext,
+    bufs: &mut [B],
+) -> Result<(), Unspecified> {
+    process_vectored(key, mode, Direction::Decrypt, context, bufs)
+}
+
+fn process_vectored<B: DerefMut<Target = [u8]>>(
+    key: &UnboundCipherKey,
+    mode: OperatingMode,
+    direction: Direction,
+    context: &EncryptionContext,
+    bufs: &mut [B],
+) -> Result<(), Unspecified> {
+    // Block modes can only process whole blocks, so a block may not span two buffers.
+    if mode.is_block_aligned() && bufs.iter().any(|buf| buf.len() % AES_BLOCK_LEN != 0) {
+        return Err(Unspecified);
+    }
+
+    let mut state = StreamState::new(mode, context)?;
+    for buf in bufs.iter_mut() {
+        process(key, direction, &mut state, buf)?;
+    }
+    Ok(())
+}
+
+fn process(
+    key: &UnboundCipherKey,
+    direction: Direction,
+    state: &mut StreamState,
+    in_out: &mut [u8],
+) -> Result<(), Unspecified> {
+    match &key.key {
+        SymmetricCipherKey::ChaCha20 { raw_key } => process_chacha20(raw_key, state, in_out),
+        SymmetricCipherKey::Aes128 { enc_key, dec_key }
+        | SymmetricCipherKey::Aes192 { enc_key, dec_key }
+        | SymmetricCipherKey::Aes256 { enc_key, dec_key } => {
+            process_aes(key, enc_key, dec_key.as_ref(), direction, state, in_out)
+        }
+        #[cfg(not(feature = "approved-only"))]
+        SymmetricCipherKey::TripleDes { .. } => Err(Unspecified),
+    }
+}
+
+fn process_aes(
+    key: &UnboundCipherKey,
+    enc_key: &AES_KEY,
+    dec_key: Option<&AES_KEY>,
+    direction: Direction,
+    state: &mut StreamState,
+    in_out: &mut [u8],
+) -> Result<(), Unspecified> {
+    let aes_mode = match direction {
+        Direction::Encrypt => AES_ENCRYPT,
+        Direction::Decrypt => AES_DECRYPT,
+    };
+
+    match (state, direction) {
+        (StreamState::Cbc { iv }, Direction::Encrypt) => aes_cbc_encrypt(enc_key, iv, in_out),
+        (StreamState::Cbc { iv }, Direction::Decrypt) => {
+            aes_cbc_decrypt(dec_key.ok_or(Unspecified)?, iv, in_out)
+        }
+        #[cfg(not(feature = "approved-only"))]
+        (StreamState::Ecb, Direction::Encrypt) => aes_ecb_encrypt(enc_key, in_out, aes_mode),
+        #[cfg(not(feature = "approved-only"))]
+        (StreamState::Ecb, Direction::Decrypt) => {
+            aes_ecb_encrypt(dec_key.ok_or(Unspecified)?, in_out, aes_mode)
+        }
+        // The feedback and counter modes only use the forward cipher.
+        (
+            StreamState::Ctr {
+                iv,
+                block_buffer,
+                num,
+                blocks_available,
+            },
+            _,
+        ) => {
+            // Keystream left over in `block_buffer` from a partial block does not need a new
+            // counter block.
+            let leftover = if *num == 0 {
+                0
+            } else {
+                AES_BLOCK_LEN - *num as usize
+            };
+            let blocks = aes_ctr_blocks(in_out.len().saturating_sub(leftover));
+            if blocks > *blocks_available {
+                return Err(Unspecified);
+            }
+            *blocks_available -= blocks;
+            aes_ctr128_encrypt(enc_key, iv, block_buffer, num, in_out);
+        }
+        (StreamState::Cfb128 { iv, num }, _) => {
+            aes_cfb128_encrypt(enc_key, iv, num, in_out, aes_mode);
+        }
+        (StreamState::Cfb8 { iv }, _) => aes_cfb8_encrypt(key, iv, in_out, aes_mode)?,
+        (StreamState::Ofb { iv, num }, _) => aes_ofb128_encrypt(enc_key, iv, num, in_out),
+        (StreamState::ChaCha20 { .. }, _) => return Err(Unspecified),
+    }
+
+    Ok(())
+}
+
+fn process_chacha20(
+    key: &ChaCha20Key,
+    state: &mut StreamState,
+    in_out: &mut [u8],
+) -> Result<(), Unspecified> {
+    let (nonce, counter, keystream, keystream_used, processed) = match state {
+        StreamState::ChaCha20 {
+            nonce,
+            counter,
+            keystream,
+            keystream_used,
+            processed,
+        } => (nonce, counter, keystream, keystream_used, processed),
+        _ => return Err(Unspecified),
+    };
+
+    *processed = processed
+        .checked_add(in_out.len() as u64)
+        .filter(|processed| *processed <= CHACHA20_MAX_INPUT_LEN)
+        .ok_or(Unspecified)?;
+
+    // Use any keystream left over from the previous call first.
+    let leftover = (CHACHA20_BLOCK_LEN - *keystream_used).min(in_out.len());
+    let (head, in_out) = in_out.split_at_mut(leftover);
+    xor_in_place(head, &keystream[*keystream_used..]);
+    *keystream_used += leftover;
+
+    let whole_len = in_out.len() - in_out.len() % CHACHA20_BLOCK_LEN;
+    let (whole, tail) = in_out.split_at_mut(whole_len);
+    if !whole.is_empty() {
+        key.encrypt_in_place(nonce, whole, *counter);
+        #[allow(clippy::cast_possible_truncation)]
+        let blocks = (whole_len / CHACHA20_BLOCK_LEN) as u32;
+        *counter = counter.wrapping_add(blocks);
+    }
+
+    // Generate one more block of keystream for a trailing partial block and keep the unused
+    // part for the next call.
+    if !tail.is_empty() {
+        keystream.fill(0);
+        key.encrypt_in_place(nonce, keystream, *counter);
+        *counter = counter.wrapping_add(1);
+        xor_in_place(tail, &keystream[..]);
+        *keystream_used = tail.len();
+    }
+
+    Ok(())
+}
+
+impl Drop for StreamingCipher {
+    fn drop(&mut self) {
+        self.buffer.zeroize();
+    }
+}
+
+fn xor_in_place(in_out: &mut [u8], keystream: &[u8]) {
+    for (byte, key) in in_out.iter_mut().zip(keystream) {
+        *byte ^= key;
+    }
+}
+
+/// A cipher encryption key that encrypts data incrementally, as it becomes available.
+///
+/// The output of a `StreamingEncryptingKey` is identical to encrypting all of the data at once
+/// with the [`EncryptingKey`] it was constructed from.
+pub struct StreamingEncryptingKey {
+    cipher: StreamingCipher,
+    context: DecryptionContext,
+}
+
+impl StreamingEncryptingKey {
+    /// Constructs a `StreamingEncryptingKey` from `key`, using a randomly generated context.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if a context cannot be generated for the key's mode.
+    pub fn new(key: EncryptingKey) -> Result<StreamingEncryptingKey, Unspecified> {
+        let context = key.algorithm().new_encryption_context(key.mode())?;
+        Self::less_safe_new(key, context)
+    }
+
+    /// Constructs a `StreamingEncryptingKey` from `key` using the provided context.
+    ///
+    /// This is considered less safe because the caller could potentially construct an
+    /// `EncryptionContext` from a previously used initialization vector (IV).
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `context` is not valid for the key's algorithm and mode.
+    pub fn less_safe_new(
+        key: EncryptingKey,
+        context: EncryptionContext,
+    ) -> Result<StreamingEncryptingKey, Unspecified> {
+        let EncryptingKey { key, mode } = key;
+        if !key.algorithm().is_valid_encryption_context(mode, &context) {
+            return Err(Unspecified);
+        }
+        let cipher = StreamingCipher::new(key, mode, Direction::Encrypt, None, &context)?;
+        Ok(StreamingEncryptingKey {
+            cipher,
+            context: context.into(),
+        })
+    }
+
+    /// Constructs a `StreamingEncryptingKey` that pads the data with `key`'s padding strategy,
+    /// using a randomly generated context.
+    ///
+    /// The output is identical to encrypting all of the data at once with `key`. The padding
+    /// is applied to the last partial block when [`finish`](Self::finish) is called.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if a context cannot be generated for the key's mode.
+    pub fn new_padded(
+        key: PaddedBlockEncryptingKey,
+    ) -> Result<StreamingEncryptingKey, Unspecified> {
+        let context = key.algorithm().new_encryption_context(key.mode())?;
+        Self::less_safe_new_padded(key, context)
+    }
+
+    /// Constructs a `StreamingEncryptingKey` that pads the data with `key`'s padding strategy,
+    /// using the provided context.
+    ///
+    /// This is considered less safe for the same reason as
+    /// [`less_safe_new`](Self::less_safe_new).
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `context` is not valid for the key's algorithm and mode.
+    pub fn less_safe_new_padded(
+        key: PaddedBlockEncryptingKey,
+        context: EncryptionContext,
+    ) -> Result<StreamingEncryptingKey, Unspecified> {
+        let PaddedBlockEncryptingKey { key, mode, padding } = key;
+        if !key.algorithm().is_valid_encryption_context(mode, &context) {
+            return Err(Unspecified);
+        }
+        let padding = match padding {
+            PaddingStrategy::None => None,
+            padding => Some(padding),
+        };
+        let cipher = StreamingCipher::new(key, mode, Direction::Encrypt, padding, &context)?;
+        Ok(StreamingEncryptingKey {
+            cipher,
+            context: context.into(),
+        })
+    }
+
+    /// Constructs a CTR mode `StreamingEncryptingKey` from `key` that resumes an encryption
+    /// using `context`, starting `offset` bytes into the stream.
+    ///
+    /// The output is identical to the output from `offset` onwards of a key constructed with
+    /// [`less_safe_new`](Self::less_safe_new) from the same context, and the context returned
+    /// by [`finish`](Self::finish) is `context`, which decrypts the whole stream. This is
+    /// considered less safe for the same reason as `less_safe_new`.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `key` is not a CTR mode key, if `context` is not valid
+    ///   for the key's algorithm, or if `offset` is beyond the end of the counter.
+    pub fn less_safe_resume(
+        key: EncryptingKey,
+        context: EncryptionContext,
+        offset: u64,
+    ) -> Result<StreamingEncryptingKey, Unspecified> {
+        let EncryptingKey { key, mode } = key;
+        if mode != OperatingMode::CTR
+            || !key.algorithm().is_valid_encryption_context(mode, &context)
+        {
+            return Err(Unspecified);
+        }
+        let cipher = StreamingCipher::resume_ctr(key, Direction::Encrypt, &context, offset)?;
+        Ok(StreamingEncryptingKey {
+            cipher,
+            context: context.into(),
+        })
+    }
+
+    /// Returns the cipher algorithm.
+    #[must_use]
+    pub fn algorithm(&self) -> &Algorithm {
+        self.cipher.key.algorithm()
+    }
+
+    /// Returns the cipher operating mode.
+    #[must_use]
+    pub fn mode(&self) -> OperatingMode {
+        self.cipher.mode
+    }
+
+    /// Returns the context needed for decryption.
+    ///
+    /// The context is fixed when the key is constructed, so it can be read before any data is
+    /// encrypted, for example to write the IV ahead of the ciphertext. It is the same context
+    /// that is returned by [`finish`](Self::finish).
+    #[must_use]
+    pub fn context(&self) -> &DecryptionContext {
+        &self.context
+    }
+
+    /// Encrypts `input`, writing the ciphertext that is available so far to `output`.
+    ///
+    /// In CBC and ECB modes a trailing partial block is held back until the next call, so
+    /// `output` must be at least `input.len()` plus one block long. In the other modes
+    /// `output` must be at least `input.len()` bytes long.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `output` is too small, or if the total input length
+    ///   exceeds the limit of the algorithm.
+    pub fn update<'a>(
+        &mut self,
+        input: &[u8],
+        output: &'a mut [u8],
+    ) -> Result<BufferUpdate<'a>, Unspecified> {
+        self.cipher.update(input, output)
+    }
+
+    /// Finishes the encryption, returning the context needed for decryption.
+    ///
+    /// For a key constructed with [`new_padded`](Self::new_padded), the padded last block is
+    /// written to `output`, which must be at least one block long. Otherwise nothing is
+    /// written.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `output` is too small for the last block, or if no
+    ///   padding is applied and the total input length was not a multiple of the block length
+    ///   in CBC or ECB mode.
+    pub fn finish(
+        mut self,
+        output: &mut [u8],
+    ) -> Result<(DecryptionContext, BufferUpdate<'_>), Unspecified> {
+        let out_len = self.cipher.finish(output)?;
+        Ok((self.context, BufferUpdate::new(output, out_len)))
+    }
+}
+
+impl Debug for StreamingEncryptingKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StreamingEncryptingKey")
+            .field("key", &self.cipher.key)
+            .field("mode", &self.cipher.mode)
+            .finish()
+    }
+}
+
+/// A cipher decryption key that decrypts data incrementally, as it becomes available.
+pub struct StreamingDecryptingKey {
+    cipher: StreamingCipher,
+}
+
+impl StreamingDecryptingKey {
+    /// Constructs a `StreamingDecryptingKey` from `key` and the context returned when the data
+    /// was encrypted.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `context` is not valid for the key's algorithm and mode.
+    pub fn new(
+        key: DecryptingKey,
+        context: DecryptionContext,
+    ) -> Result<StreamingDecryptingKey, Unspecified> {
+        let DecryptingKey { key, mode } = key;
+        if !key.algorithm().is_valid_decryption_context(mode, &context) {
+            return Err(Unspecified);
+        }
+        Ok(StreamingDecryptingKey {
+            cipher: StreamingCipher::new(key, mode, Direction::Decrypt, None, &context.into())?,
+        })
+    }
+
+    /// Constructs a CTR mode `StreamingDecryptingKey` from `key` that resumes a decryption
+    /// using the context returned when the data was encrypted, starting `offset` bytes into
+    /// the stream.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `key` is not a CTR mode key, if `context` is not valid
+    ///   for the key's algorithm, or if `offset` is beyond the end of the counter.
+    pub fn resume(
+        key: DecryptingKey,
+        context: DecryptionContext,
+        offset: u64,
+    ) -> Result<StreamingDecryptingKey, Unspecified> {
+        let DecryptingKey { key, mode } = key;
+        if mode != OperatingMode::CTR
+            || !key.algorithm().is_valid_decryption_context(mode, &context)
+        {
+            return Err(Unspecified);
+        }
+        Ok(StreamingDecryptingKey {
+            cipher: StreamingCipher::resume_ctr(key, Direction::Decrypt, &context.into(), offset)?,
+        })
+    }
+
+    /// Constructs a `StreamingDecryptingKey` that removes the padding applied by `key`'s padding
+    /// strategy, from `key` and the context returned when the data was encrypted.
+    ///
+    /// The output is identical to decrypting all of the data at once with `key`. The last
+    /// block of ciphertext is held back until [`finish`](Self::finish) is called, which
+    /// removes the padding and writes the remaining plaintext.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `context` is not valid for the key's algorithm and mode.
+    pub fn new_padded(
+        key: PaddedBlockDecryptingKey,
+        context: DecryptionContext,
+    ) -> Result<StreamingDecryptingKey, Unspecified> {
+        let PaddedBlockDecryptingKey { key, mode, padding } = key;
+        if !key.algorithm().is_valid_decryption_context(mode, &context) {
+            return Err(Unspecified);
+        }
+        // Without padding there is nothing to remove, so the last block need not be held back.
+        let padding = match padding {
+            PaddingStrategy::None => None,
+            padding => Some(padding),
+        };
+        let cipher = StreamingCipher::new(key, mode, Direction::Decrypt, padding, &context.into())?;
+        Ok(StreamingDecryptingKey { cipher })
+    }
+
+    /// Returns the cipher algorithm.
+    #[must_use]
+    pub fn algorithm(&self) -> &Algorithm {
+        self.cipher.key.algorithm()
+    }
+
+    /// Returns the cipher operating mode.
+    #[must_use]
+    pub fn mode(&self) -> OperatingMode {
+        self.cipher.mode
+    }
+
+    /// Decrypts `input`, writing the plaintext that is available so far to `output`.
+    ///
+    /// In CBC and ECB modes a trailing partial block is held back until the next call, so
+    /// `output` must be at least `input.len()` plus one block long. In the other modes
+    /// `output` must be at least `input.len()` bytes long.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `output` is too small, or if the total input length
+    ///   exceeds the limit of the algorithm.
+    pub fn update<'a>(
+        &mut self,
+        input: &[u8],
+        output: &'a mut [u8],
+    ) -> Result<BufferUpdate<'a>, Unspecified> {
+        self.cipher.update(input, output)
+    }
+
+    /// Finishes the decryption.
+    ///
+    /// For a key constructed with [`new_padded`](Self::new_padded), the held back last block
+    /// is decrypted and unpadded, and the remaining plaintext is written to `output`, which
+    /// must be at least one block long. Otherwise nothing is written to `output`.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if the total input length was not a multiple of the block
+    ///   length in CBC or ECB mode, if the padding is invalid, or if `output` is too small.
+    pub fn finish(mut self, output: &mut [u8]) -> Result<BufferUpdate<'_>, Unspecified> {
+        let out_len = self.cipher.finish(output)?;
+        Ok(BufferUpdate::new(output, out_len))
+    }
+}
+
+impl Debug for StreamingDecryptingKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StreamingDecryptingKey")
+            .field("key", &self.cipher.key)
+            .field("mode", &self.cipher.mode)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cipher::{PaddedBlockEncryptingKey, AES_128, AES_192, AES_256, CHACHA20};
+    use crate::iv::FixedLength;
+    use std::io::IoSliceMut;
+
+    const CHUNK_SIZES: [usize; 8] = [1, 3, 15, 16, 17, 31, 64, 65];
+
+    fn context(mode: OperatingMode) -> EncryptionContext {
+        match mode {
+            #[cfg(not(feature = "approved-only"))]
+            OperatingMode::ECB => EncryptionContext::None,
+            OperatingMode::Stream => EncryptionContext::Nonce96(FixedLength::from([7u8; 12])),
+            _ => EncryptionContext::Iv128(FixedLength::from([7u8; 16])),
+        }
+    }
+
+    fn stream_encrypt(key: EncryptingKey, input: &[u8], chunk_size: usize) -> Vec<u8> {
+        let mode = key.mode();
+        let mut key = StreamingEncryptingKey::less_safe_new(key, context(mode)).unwrap();
+        let mut ciphertext = Vec::new();
+        let mut output = vec![0u8; chunk_size + AES_BLOCK_LEN];
+        for chunk in input.chunks(chunk_size) {
+            let update = key.update(chunk, &mut output).unwrap();
+            ciphertext.extend_from_slice(update.written());
+        }
+        let (_, update) = key.finish(&mut output).unwrap();
+        assert!(update.written().is_empty());
+        ciphertext
+    }
+
+    fn stream_decrypt(key: DecryptingKey, input: &[u8], chunk_size: usize) -> Vec<u8> {
+        let mode = key.mode();
+        let mut key = StreamingDecryptingKey::new(key, context(mode).into()).unwrap();
+        let mut plaintext = Vec::new();
+        let mut output = vec![0u8; chunk_size + AES_BLOCK_LEN];
+        for chunk in input.chunks(chunk_size) {
+            let update = key.update(chunk, &mut output).unwrap();
+            plaintext.extend_from_slice(update.written());
+        }
+        let update = key.finish(&mut output).unwrap();
+        assert!(update.written().is_empty());
+        plaintext
+    }
+
+    fn helper_test_streaming(alg: &'static Algorithm, mode: OperatingMode, lengths: &[usize]) {
+        let key_bytes = [0x42u8; 32];
+        let key_bytes = &key_bytes[..alg.key_len];
+        for &len in lengths {
+            let input: Vec<u8> = (0..len).map(|i| i as u8).collect();
+
+            let encrypting_key =
+                EncryptingKey::new(UnboundCipherKey::new(alg, key_bytes).unwrap(), mode).unwrap();
+            let mut expected = input.clone();
+            encrypting_key
+                .less_safe_encrypt(&mut expected, context(mode))
+                .unwrap();
+
+            for chunk_size in CHUNK_SIZES {
+                let key = EncryptingKey::new(UnboundCipherKey::new(alg, key_bytes).unwrap(), mode)
+                    .unwrap();
+                assert_eq!(expected, stream_encrypt(key, &input, chunk_size));
+
+                let key = DecryptingKey::new(UnboundCipherKey::new(alg, key_bytes).unwrap(), mode)
+                    .unwrap();
+                assert_eq!(input, stream_decrypt(key, &expected, chunk_size));
+            }
+        }
+    }
+
+    #[test]
+    fn test_streaming_matches_one_shot() {
+        let block_lengths: Vec<usize> = (0..=96).step_by(16).collect();
+        let stream_lengths: Vec<usize> = (0..=100).collect();
+
+        for alg in [&AES_128, &AES_192, &AES_256] {
+            helper_test_streaming(alg, OperatingMode::CBC, &block_lengths);
+            #[cfg(not(feature = "approved-only"))]
+            helper_test_streaming(alg, OperatingMode::ECB, &block_lengths);
+            for mode in [
+                OperatingMode::CTR,
+                OperatingMode::CFB128,
+                OperatingMode::CFB8,
+                OperatingMode::OFB,
+            ] {
+                helper_test_streaming(alg, mode, &stream_lengths);
+            }
+        }
+
+        let chacha_lengths: Vec<usize> = (0..=200).collect();
+        helper_test_streaming(&CHACHA20, OperatingMode::Stream, &chacha_lengths);
+    }
+
+    #[test]
+    fn test_streaming_rejects_partial_block() {
+        let key = EncryptingKey::cbc(UnboundCipherKey::new(&AES_128, &[0u8; 16]).unwrap()).unwrap();
+        let mut key = StreamingEncryptingKey::new(key).unwrap();
+        let mut output = [0u8; 64];
+        assert!(key.update(&[0u8; 20], &mut output).unwrap().written().len() == 16);
+        assert!(key.finish(&mut output).is_err());
+
+        let key = DecryptingKey::cbc(UnboundCipherKey::new(&AES_128, &[0u8; 16]).unwrap()).unwrap();
+        let context = EncryptionContext::Iv128(FixedLength::from([0u8; 16])).into();
+        let mut key = StreamingDecryptingKey::new(key, context).unwrap();
+        assert!(key.update(&[0u8; 33], &mut output[..31]).is_err());
+        assert_eq!(
+            32,
+            key.update(&[0u8; 33], &mut output).unwrap().written().len()
+        );
+        assert!(key.finish(&mut output).is_err());
+    }
+
+    #[test]
+    fn test_streaming_rejects_invalid_context() {
+        let key = DecryptingKey::ctr(UnboundCipherKey::new(&AES_128, &[0u8; 16]).unwrap()).unwrap();
+        assert!(StreamingDecryptingKey::new(key, DecryptionContext::None).is_err());
+
+        let key =
+            EncryptingKey::chacha20(UnboundCipherKey::new(&CHACHA20, &[0u8; 32]).unwrap()).unwrap();
+        let context = EncryptionContext::Iv128(FixedLength::from([0u8; 16]));
+        assert!(StreamingEncryptingKey::less_safe_new(key, context).is_err());
+    }
+
+    #[test]
+    fn test_streaming_padded_decrypt_matches_one_shot() {
+        // A simple linear congruential generator gives reproducible chunk boundaries.
+        let mut seed: u32 = 0x1234_5678;
+        let mut next_chunk_len = move || {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (seed >> 16) as usize % 40
+        };
+
+        for (encrypting_key, decrypting_key) in [
+            (
+                PaddedBlockEncryptingKey::cbc_pkcs7 as fn(_) -> _,
+                PaddedBlockDecryptingKey::cbc_pkcs7 as fn(_) -> _,
+            ),
+            (
+                PaddedBlockEncryptingKey::cbc_iso7816,
+                PaddedBlockDecryptingKey::cbc_iso7816,
+            ),
+            (
+                PaddedBlockEncryptingKey::cbc_ansi_x923,
+                PaddedBlockDecryptingKey::cbc_ansi_x923,
+            ),
+            (
+                PaddedBlockEncryptingKey::cbc_no_padding,
+                PaddedBlockDecryptingKey::cbc_no_padding,
+            ),
+        ] {
+            for len in 0..=100 {
+                let key = || UnboundCipherKey::new(&AES_128, &[0x42u8; 16]).unwrap();
+                let encrypting_key = encrypting_key(key()).unwrap();
+                if encrypting_key.padding == PaddingStrategy::None && len % AES_BLOCK_LEN != 0 {
+                    continue;
+                }
+                let plaintext: Vec<u8> = (0..len).map(|i| i as u8).collect();
+                let mut ciphertext = plaintext.clone();
+                let context = encrypting_key.encrypt(&mut ciphertext).unwrap();
+
+                let mut key =
+                    StreamingDecryptingKey::new_padded(decrypting_key(key()).unwrap(), context)
+                        .unwrap();
+                let mut decrypted = Vec::new();
+                let mut output = [0u8; 40 + AES_BLOCK_LEN];
+                let mut remaining = ciphertext.as_slice();
+                while !remaining.is_empty() {
+                    let (chunk, rest) = remaining.split_at(next_chunk_len().min(remaining.len()));
+                    let update = key.update(chunk, &mut output).unwrap();
+                    decrypted.extend_from_slice(update.written());
+                    remaining = rest;
+                }
+                let update = key.finish(&mut output).unwrap();
+                decrypted.extend_from_slice(update.written());
+                assert_eq!(plaintext, decrypted);
+            }
+        }
+    }
+
+    #[test]
+    fn test_streaming_padded_encrypt_matches_one_shot() {
+        let key = || UnboundCipherKey::new(&AES_128, &[0x42u8; 16]).unwrap();
+        for encrypting_key in [
+            PaddedBlockEncryptingKey::cbc_pkcs7 as fn(_) -> _,
+            PaddedBlockEncryptingKey::cbc_iso7816,
+            PaddedBlockEncryptingKey::cbc_ansi_x923,
+        ] {
+            for len in 0..=50 {
+                let plaintext: Vec<u8> = (0..len).map(|i| i as u8).collect();
+                let mut expected = plaintext.clone();
+                encrypting_key(key())
+                    .unwrap()
+                    .less_safe_encrypt(&mut expected, context(OperatingMode::CBC))
+                    .unwrap();
+
+                for chunk_size in CHUNK_SIZES {
+                    let mut key = StreamingEncryptingKey::less_safe_new_padded(
+                        encrypting_key(key()).unwrap(),
+                        context(OperatingMode::CBC),
+                    )
+                    .unwrap();
+                    let mut ciphertext = Vec::new();
+                    let mut output = vec![0u8; chunk_size + AES_BLOCK_LEN];
+                    for chunk in plaintext.chunks(chunk_size) {
+                        let update = key.update(chunk, &mut output).unwrap();
+                        ciphertext.extend_from_slice(update.written());
+                    }
+                    let (_, update) = key.finish(&mut output).unwrap();
+                    assert_eq!(AES_BLOCK_LEN, update.written().len());
+                    ciphertext.extend_from_slice(update.written());
+                    assert_eq!(expected, ciphertext);
+                }
+            }
+        }
+
+        // `output` too small for the padded last block.
+        let key = PaddedBlockEncryptingKey::cbc_pkcs7(key()).unwrap();
+        let mut key = StreamingEncryptingKey::new_padded(key).unwrap();
+        let mut output = [0u8; 32];
+        assert!(key.update(&[0u8; 5], &mut output).is_ok());
+        assert!(key.finish(&mut output[..AES_BLOCK_LEN - 1]).is_err());
+    }
+
+    #[test]
+    fn test_streaming_padded_decrypt_rejects_invalid_input() {
+        let unbound_key = || UnboundCipherKey::new(&AES_128, &[0x42u8; 16]).unwrap();
+        let iv = [7u8; 16];
+        let mut ciphertext = b"some plaintext".to_vec();
+        PaddedBlockEncryptingKey::cbc_pkcs7(unbound_key())
+            .unwrap()
+            .less_safe_encrypt(
+                &mut ciphertext,
+                EncryptionContext::Iv128(FixedLength::from(iv)),
+            )
+            .unwrap();
+        let decrypting_key = || {
+            let key = PaddedBlockDecryptingKey::cbc_pkcs7(unbound_key()).unwrap();
+            let context = DecryptionContext::Iv128(FixedLength::from(iv));
+            StreamingDecryptingKey::new_padded(key, context).unwrap()
+        };
+        let mut output = [0u8; 64];
+
+        // No ciphertext at all.
+        let key = decrypting_key();
+        assert!(key.finish(&mut output).is_err());
+
+        // A trailing partial block.
+        let mut key = decrypting_key();
+        assert!(key.update(&ciphertext[..10], &mut output).is_ok());
+        assert!(key.finish(&mut output).is_err());
+
+        // Invalid padding: flipping a bit of the IV flips the same bit of the last padding byte.
+        let mut modified_iv = iv;
+        modified_iv[15] ^= 0x01;
+        let key = PaddedBlockDecryptingKey::cbc_pkcs7(unbound_key()).unwrap();
+        let context = DecryptionContext::Iv128(FixedLength::from(modified_iv));
+        let mut key = StreamingDecryptingKey::new_padded(key, context).unwrap();
+        assert!(key
+            .update(&ciphertext, &mut output)
+            .unwrap()
+            .written()
+            .is_empty());
+        assert!(key.finish(&mut output).is_err());
+
+        // `output` too small for the unpadded plaintext.
+        let mut key = decrypting_key();
+        assert!(key.update(&ciphertext, &mut output).is_ok());
+        assert!(key.finish(&mut output[..13]).is_err());
+
+        let mut key = decrypting_key();
+        assert!(key.update(&ciphertext, &mut output).is_ok());
+        assert_eq!(
+            b"some plaintext",
+            key.finish(&mut output).unwrap().written()
+        );
+    }
+
+    #[test]
+    fn test_streaming_ctr_counter_wrap() {
+        let key = EncryptingKey::ctr(UnboundCipherKey::new(&AES_128, &[0u8; 16]).unwrap()).unwrap();
+        let context = EncryptionContext::Iv128(FixedLength::from([0xffu8; 16]));
+        let mut key = StreamingEncryptingKey::less_safe_new(key, context).unwrap();
+        let mut output = [0u8; 32];
+
+        // Only a single counter block is left, so only 16 bytes can be encrypted in total.
+        assert_eq!(
+            10,
+            key.update(&[0u8; 10], &mut output).unwrap().written().len()
+        );
+        assert_eq!(
+            6,
+            key.update(&[0u8; 6], &mut output).unwrap().written().len()
+        );
+        assert!(key.update(&[], &mut output).is_ok());
+        assert!(key.update(&[0u8; 1], &mut output).is_err());
+    }
+
+    fn encryption_context(context: &DecryptionContext) -> EncryptionContext {
+        match context {
+            DecryptionContext::Iv128(iv) => EncryptionContext::Iv128(iv.as_bytes().into()),
+            DecryptionContext::Nonce96(nonce) => {
+                EncryptionContext::Nonce96(nonce.as_bytes().into())
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_vectored_matches_one_shot() {
+        let plaintext: Vec<u8> = (0..100).collect();
+        for (alg, key_len, modes) in [
+            (
+                &AES_128,
+                16,
+                &[
+                    OperatingMode::CBC,
+                    OperatingMode::CTR,
+                    OperatingMode::CFB128,
+                    OperatingMode::CFB8,
+                    OperatingMode::OFB,
+                ][..],
+            ),
+            (&CHACHA20, 32, &[OperatingMode::Stream][..]),
+        ] {
+            let key_bytes = vec![0x42u8; key_len];
+            let unbound_key = || UnboundCipherKey::new(alg, &key_bytes).unwrap();
+            for mode in modes {
+                let splits: &[&[usize]] = if *mode == OperatingMode::CBC {
+                    &[&[0, 16, 48, 32], &[32, 32, 32], &[96]]
+                } else {
+                    &[&[0, 1, 17, 82], &[15, 15, 15], &[64, 0, 3], &[100]]
+                };
+                for split in splits {
+                    let mut bufs: Vec<Vec<u8>> = split
+                        .iter()
+                        .scan(0, |start, len| {
+                            let buf = plaintext[*start..*start + len].to_vec();
+                            *start += len;
+                            Some(buf)
+                        })
+                        .collect();
+                    let concatenated = bufs.concat();
+
+                    let encrypting_key = EncryptingKey::new(unbound_key(), *mode).unwrap();
+                    let context = encrypting_key.encrypt_vectored(&mut bufs).unwrap();
+                    let mut expected = concatenated.clone();
+                    encrypting_key
+                        .less_safe_encrypt(&mut expected, encryption_context(&context))
+                        .unwrap();
+                    assert_eq!(expected, bufs.concat());
+
+                    let decrypting_key = DecryptingKey::new(unbound_key(), *mode).unwrap();
+                    decrypting_key.decrypt_vectored(&mut bufs, context).unwrap();
+                    assert_eq!(concatenated, bufs.concat());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_vectored_io_slices() {
+        let unbound_key = || UnboundCipherKey::new(&AES_128, &[0x42u8; 16]).unwrap();
+        let (mut a, mut b) = ([1u8; 7], [2u8; 30]);
+        let context = {
+            let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+            EncryptingKey::ctr(unbound_key())
+                .unwrap()
+                .encrypt_vectored(&mut bufs)
+                .unwrap()
+        };
+        let mut in_out = [a.as_slice(), b.as_slice()].concat();
+        DecryptingKey::ctr(unbound_key())
+            .unwrap()
+            .decrypt(&mut in_out, context)
+            .unwrap();
+        assert_eq!([[1u8; 7].as_slice(), [2u8; 30].as_slice()].concat(), in_out);
+    }
+
+    #[test]
+    fn test_vectored_rejects_partial_blocks() {
+        let unbound_key = || UnboundCipherKey::new(&AES_128, &[0x42u8; 16]).unwrap();
+        let (mut a, mut b) = ([0u8; 17], [0u8; 15]);
+        let mut bufs: [&mut [u8]; 2] = [&mut a, &mut b];
+        assert!(EncryptingKey::cbc(unbound_key())
+            .unwrap()
+            .encrypt_vectored(&mut bufs)
+            .is_err());
+        assert_eq!([0u8; 17], a);
+
+        #[cfg(not(feature = "approved-only"))]
+        {
+            let mut bufs: [&mut [u8]; 2] = [&mut a, &mut b];
+            assert!(DecryptingKey::less_safe_ecb(unbound_key())
+                .unwrap()
+                .decrypt_vectored(&mut bufs, DecryptionContext::None)
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_streaming_ctr_resume() {
+        let key_bytes = [0x42u8; 16];
+        let unbound_key = || UnboundCipherKey::new(&AES_128, &key_bytes).unwrap();
+        let plaintext: Vec<u8> = (0..100).collect();
+
+        let mut ciphertext = plaintext.clone();
+        EncryptingKey::ctr(unbound_key())
+            .unwrap()
+            .less_safe_encrypt(&mut ciphertext, context(OperatingMode::CTR))
+            .unwrap();
+
+        let mut output = [0u8; 100];
+        for offset in [0, 1, 15, 16, 17, 50, 99, 100] {
+            let key = EncryptingKey::ctr(unbound_key()).unwrap();
+            let mut key =
+                StreamingEncryptingKey::less_safe_resume(key, context(OperatingMode::CTR), offset)
+                    .unwrap();
+            let offset = offset as usize;
+            let update = key.update(&plaintext[offset..], &mut output).unwrap();
+            assert_eq!(&ciphertext[offset..], update.written());
+            let (decryption_context, _) = key.finish(&mut output).unwrap();
+            assert_eq!(
+                DecryptionContext::from(context(OperatingMode::CTR)),
+                decryption_context
+            );
+
+            let key = DecryptingKey::ctr(unbound_key()).unwrap();
+            let mut key = StreamingDecryptingKey::resume(
+                key,
+                context(OperatingMode::CTR).into(),
+                offset as u64,
+            )
+            .unwrap();
+            let update = key.update(&ciphertext[offset..], &mut output).unwrap();
+            assert_eq!(&plaintext[offset..], update.written());
+        }
+    }
+
+    #[test]
+    fn test_streaming_resume_rejects_invalid_input() {
+        let unbound_key = || UnboundCipherKey::new(&AES_128, &[0u8; 16]).unwrap();
+
+        let key = EncryptingKey::cbc(unbound_key()).unwrap();
+        let context = EncryptionContext::Iv128(FixedLength::from([0u8; 16]));
+        assert!(StreamingEncryptingKey::less_safe_resume(key, context, 16).is_err());
+
+        // An offset into the last counter block is allowed, but not one past it.
+        let last = || DecryptionContext::Iv128(FixedLength::from([0xffu8; 16]));
+        let key = DecryptingKey::ctr(unbound_key()).unwrap();
+        let mut key = StreamingDecryptingKey::resume(key, last(), 15).unwrap();
+        let mut output = [0u8; 2];
+        assert!(key.update(&[0u8; 1], &mut output).is_ok());
+        assert!(key.update(&[0u8; 1], &mut output).is_err());
+        let key = DecryptingKey::ctr(unbound_key()).unwrap();
+        assert!(StreamingDecryptingKey::resume(key, last(), 16).is_err());
+    }
+
+    // Encrypts 64 GiB, so it is not run by default.
+    #[test]
+    #[ignore]
+    fn test_streaming_ctr_counter_wrap_after_2_32_minus_1_blocks() {
+        let mut iv = [0xffu8; 16];
+        iv[12..].copy_from_slice(&1u32.to_be_bytes());
+        let key = EncryptingKey::ctr(UnboundCipherKey::new(&AES_128, &[0u8; 16]).unwrap()).unwrap();
+        let context = EncryptionContext::Iv128(FixedLength::from(iv));
+        let mut key = StreamingEncryptingKey::less_safe_new(key, context).unwrap();
+
+        const CHUNK_BLOCKS: usize = 1 << 16;
+        let input = vec![0u8; CHUNK_BLOCKS * AES_BLOCK_LEN];
+        let mut output = vec![0u8; input.len()];
+        let mut remaining_blocks = (1usize << 32) - 1;
+        while remaining_blocks > 0 {
+            let blocks = remaining_blocks.min(CHUNK_BLOCKS);
+            key.update(&input[..blocks * AES_BLOCK_LEN], &mut output)
+                .unwrap();
+            remaining_blocks -= blocks;
+        }
+        assert!(key.update(&[0u8; 1], &mut output).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Known-answer tests read from NIST CAVP response (`.rsp`) files.
+//!
+//! Each vector has `COUNT`, `KEY`, `IV`, `PLAINTEXT` and `CIPHERTEXT` fields and is in an
+//! `[ENCRYPT]` or `[DECRYPT]` section. The key size is taken from the length of `KEY`.
+//!
+//! A subset of the vectors is embedded and always run. With the `nist-vectors` feature, the
+//! CBC and CTR response files in the `test-vectors` directory of this crate are also run. That
+//! directory is not checked in; it can be populated by extracting the CAVP AES known-answer
+//! and multi-block message test archives into it. Monte Carlo (`MCT`) files are skipped, and
+//! CTR mode files must be named with a `CTR` prefix.
+
+use crate::cipher::{
+    Algorithm, DecryptingKey, EncryptingKey, EncryptionContext, OperatingMode, UnboundCipherKey,
+    AES_128, AES_192, AES_256,
+};
+use crate::error::Unspecified;
+use crate::iv::FixedLength;
+use crate::{test, test_file};
+
+fn algorithm(key: &[u8]) -> Result<&'static Algorithm, Unspecified> {
+    match key.len() {
+        16 => Ok(&AES_128),
+        24 => Ok(&AES_192),
+        32 => Ok(&AES_256),
+        _ => Err(Unspecified),
+    }
+}
+
+/// Runs every vector in `file` with the cipher in `mode`.
+fn run_cavp(mode: OperatingMode, file: test::File) {
+    test::run(file, |section, test_case| {
+        let _ = test_case.consume_usize("COUNT");
+        let key_bytes = test_case.consume_bytes("KEY");
+        let iv: FixedLength<16> = test_case.consume_bytes("IV").as_slice().try_into()?;
+        let plaintext = test_case.consume_bytes("PLAINTEXT");
+        let ciphertext = test_case.consume_bytes("CIPHERTEXT");
+        let key = UnboundCipherKey::new(algorithm(&key_bytes)?, &key_bytes)?;
+
+        match section {
+            "ENCRYPT" => {
+                let mut in_out = plaintext;
+                EncryptingKey::new(key, mode)?
+                    .less_safe_encrypt(&mut in_out, EncryptionContext::Iv128(iv))?;
+                assert_eq!(ciphertext, in_out);
+            }
+            "DECRYPT" => {
+                let mut in_out = ciphertext;
+                let decrypted = DecryptingKey::new(key, mode)?
+                    .decrypt(&mut in_out, EncryptionContext::Iv128(iv).into())?;
+                assert_eq!(plaintext.as_slice(), decrypted);
+            }
+            _ => panic!("Unexpected section: {section}"),
+        }
+        Ok(())
+    });
+}
+
+#[test]
+fn test_aes_cbc_cavp() {
+    run_cavp(
+        OperatingMode::CBC,
+        test_file!("../data/aes_cbc_cavp_tests.rsp"),
+    );
+}
+
+#[test]
+fn test_aes_ctr_cavp() {
+    run_cavp(
+        OperatingMode::CTR,
+        test_file!("../data/aes_ctr_cavp_tests.rsp"),
+    );
+}
+
+#[cfg(feature = "nist-vectors")]
+#[test]
+fn test_nist_cavp_vectors() {
+    use std::path::Path;
+
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("test-vectors");
+    let mut paths: Vec<_> = std::fs::read_dir(&dir)
+        .unwrap_or_else(|err| panic!("Unable to read {}: {err}", dir.display()))
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    paths.sort();
+
+    let mut file_count = 0;
+    for path in paths {
+        // DON'T PANIC: `read_dir` only returns entries with a file name.
+        let file_name = path.file_name().unwrap().to_string_lossy();
+        if !file_name.ends_with(".rsp") || file_name.contains("MCT") {
+            continue;
+        }
+        let mode = if file_name.starts_with("CBC") {
+            OperatingMode::CBC
+        } else if file_name.starts_with("CTR") {
+            OperatingMode::CTR
+        } else {
+            continue;
+        };
+        let contents = std::fs::read_to_string(&path).unwrap();
+        run_cavp(
+            mode,
+            test::File {
+                file_name: &file_name,
+                contents: &contents,
+            },
+        );
+        file_count += 1;
+    }
+    assert_ne!(0, file_count, "No CBC or CTR files in {}", dir.display());
+}
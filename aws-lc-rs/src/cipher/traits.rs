@@ -0,0 +1,182 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Traits implemented by the cipher key types, for code that is generic over the mode.
+//!
+//! [`EncryptInPlace`] is implemented by [`EncryptingKey`] and [`PaddedBlockEncryptingKey`], and
+//! [`DecryptInPlace`] by [`DecryptingKey`] and [`PaddedBlockDecryptingKey`].
+//!
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::cipher::traits::{DecryptInPlace, EncryptInPlace};
+//! use aws_lc_rs::cipher::{
+//!     DecryptingKey, EncryptingKey, PaddedBlockDecryptingKey, PaddedBlockEncryptingKey,
+//!     UnboundCipherKey, AES_128,
+//! };
+//!
+//! fn round_trip(
+//!     encrypting_key: &impl EncryptInPlace,
+//!     decrypting_key: &impl DecryptInPlace,
+//!     message: &[u8],
+//! ) -> Result<Vec<u8>, Box<dyn Error>> {
+//!     let mut in_out = message.to_vec();
+//!     let context = encrypting_key.encrypt_in_place(&mut in_out)?;
+//!     Ok(decrypting_key.decrypt_in_place(&mut in_out, context)?.to_vec())
+//! }
+//!
+//! let key_bytes = [0x42u8; 16];
+//! let key = || UnboundCipherKey::new(&AES_128, &key_bytes);
+//!
+//! let message = b"This is a secret message!";
+//! let cbc = round_trip(
+//!     &PaddedBlockEncryptingKey::cbc_pkcs7(key()?)?,
+//!     &PaddedBlockDecryptingKey::cbc_pkcs7(key()?)?,
+//!     message,
+//! )?;
+//! let ctr = round_trip(&EncryptingKey::ctr(key()?)?, &DecryptingKey::ctr(key()?)?, message)?;
+//! assert_eq!(cbc, ctr);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{
+    DecryptingKey, DecryptionContext, EncryptingKey, PaddedBlockDecryptingKey,
+    PaddedBlockEncryptingKey,
+};
+use crate::error::Unspecified;
+use crate::sealed::Sealed;
+
+/// A key that encrypts data in place, generating a new context for each call.
+pub trait EncryptInPlace: Sealed {
+    /// Encrypts the data provided in `in_out` in-place, extending it with any padding required
+    /// by the key. Returns the context needed to decrypt the data.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if encryption fails, including if the key does not pad and
+    ///   `in_out` is not a valid length for its mode.
+    fn encrypt_in_place<InOut>(&self, in_out: &mut InOut) -> Result<DecryptionContext, Unspecified>
+    where
+        InOut: AsMut<[u8]> + for<'a> Extend<&'a u8>;
+}
+
+/// A key that decrypts data in place.
+pub trait DecryptInPlace: Sealed {
+    /// Decrypts the data provided in `in_out` in-place, removing any padding applied by the
+    /// key. Returns a reference to the decrypted data.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if decryption fails.
+    fn decrypt_in_place<'in_out>(
+        &self,
+        in_out: &'in_out mut [u8],
+        context: DecryptionContext,
+    ) -> Result<&'in_out mut [u8], Unspecified>;
+}
+
+impl Sealed for EncryptingKey {}
+
+impl EncryptInPlace for EncryptingKey {
+    fn encrypt_in_place<InOut>(&self, in_out: &mut InOut) -> Result<DecryptionContext, Unspecified>
+    where
+        InOut: AsMut<[u8]> + for<'a> Extend<&'a u8>,
+    {
+        self.encrypt(in_out.as_mut())
+    }
+}
+
+impl Sealed for PaddedBlockEncryptingKey {}
+
+impl EncryptInPlace for PaddedBlockEncryptingKey {
+    fn encrypt_in_place<InOut>(&self, in_out: &mut InOut) -> Result<DecryptionContext, Unspecified>
+    where
+        InOut: AsMut<[u8]> + for<'a> Extend<&'a u8>,
+    {
+        self.encrypt(in_out)
+    }
+}
+
+impl Sealed for DecryptingKey {}
+
+impl DecryptInPlace for DecryptingKey {
+    fn decrypt_in_place<'in_out>(
+        &self,
+        in_out: &'in_out mut [u8],
+        context: DecryptionContext,
+    ) -> Result<&'in_out mut [u8], Unspecified> {
+        self.decrypt(in_out, context)
+    }
+}
+
+impl Sealed for PaddedBlockDecryptingKey {}
+
+impl DecryptInPlace for PaddedBlockDecryptingKey {
+    fn decrypt_in_place<'in_out>(
+        &self,
+        in_out: &'in_out mut [u8],
+        context: DecryptionContext,
+    ) -> Result<&'in_out mut [u8], Unspecified> {
+        self.decrypt(in_out, context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DecryptInPlace, EncryptInPlace};
+    use crate::cipher::{
+        DecryptingKey, EncryptingKey, OperatingMode, PaddedBlockDecryptingKey,
+        PaddedBlockEncryptingKey, UnboundCipherKey, AES_128, AES_256,
+    };
+
+    fn round_trip<E: EncryptInPlace, D: DecryptInPlace>(
+        encrypting_key: &E,
+        decrypting_key: &D,
+        plaintext: &[u8],
+    ) -> Vec<u8> {
+        let mut in_out = plaintext.to_vec();
+        let context = encrypting_key.encrypt_in_place(&mut in_out).unwrap();
+        assert_ne!(plaintext, in_out.as_slice());
+        decrypting_key
+            .decrypt_in_place(&mut in_out, context)
+            .unwrap()
+            .to_vec()
+    }
+
+    #[test]
+    fn test_generic_round_trip() {
+        let key_bytes = [0x42u8; 32];
+        let key = || UnboundCipherKey::new(&AES_256, &key_bytes).unwrap();
+        let plaintext: Vec<u8> = (0..48u8).collect();
+
+        for mode in [
+            OperatingMode::CBC,
+            OperatingMode::CTR,
+            OperatingMode::CFB128,
+            OperatingMode::OFB,
+        ] {
+            let encrypting_key = EncryptingKey::new(key(), mode).unwrap();
+            let decrypting_key = DecryptingKey::new(key(), mode).unwrap();
+            assert_eq!(
+                plaintext,
+                round_trip(&encrypting_key, &decrypting_key, &plaintext)
+            );
+        }
+
+        let encrypting_key = PaddedBlockEncryptingKey::cbc_pkcs7(key()).unwrap();
+        let decrypting_key = PaddedBlockDecryptingKey::cbc_pkcs7(key()).unwrap();
+        for len in [0, 1, 15, 16, 17, 48] {
+            assert_eq!(
+                plaintext[..len],
+                round_trip(&encrypting_key, &decrypting_key, &plaintext[..len])
+            );
+        }
+    }
+
+    #[test]
+    fn test_unpadded_key_rejects_partial_block() {
+        let key = UnboundCipherKey::new(&AES_128, &[0u8; 16]).unwrap();
+        let encrypting_key = EncryptingKey::cbc(key).unwrap();
+        let mut in_out = vec![0u8; 20];
+        assert!(encrypting_key.encrypt_in_place(&mut in_out).is_err());
+    }
+}
@@ -0,0 +1,483 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Cipher keys bound to an algorithm and operating mode at the type level.
+//!
+//! [`TypedEncryptingKey`] and [`TypedDecryptingKey`] wrap the dynamic key types of the
+//! [`cipher`](crate::cipher) module. Their algorithm and mode are zero-sized marker type
+//! parameters, so using an AES-256-CTR key where an AES-128-CBC key is expected fails to compile,
+//! and constructors take the key as an array of exactly the length the algorithm requires.
+//!
+//! Typed keys convert into the corresponding dynamic key types with `From`, and dynamic keys
+//! convert into typed keys with `TryFrom` after their algorithm and mode are checked.
+//!
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::cipher::typed::{Aes256, Ctr, TypedDecryptingKey, TypedEncryptingKey};
+//! use aws_lc_rs::cipher::EncryptingKey;
+//!
+//! let key_bytes = [0x42u8; 32];
+//! let mut in_out = Vec::from(&b"This is a secret message!"[..]);
+//!
+//! let encrypting_key = TypedEncryptingKey::<Aes256, Ctr>::new(&key_bytes)?;
+//! let context = encrypting_key.encrypt(&mut in_out)?;
+//!
+//! let decrypting_key = TypedDecryptingKey::<Aes256, Ctr>::new(&key_bytes)?;
+//! assert_eq!(b"This is a secret message!", decrypting_key.decrypt(&mut in_out, context)?);
+//!
+//! // The dynamic API remains available.
+//! let dynamic: EncryptingKey = encrypting_key.into();
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{
+    Algorithm, DecryptingKey, DecryptionContext, EncryptingKey, EncryptionContext, OperatingMode,
+    PaddedBlockDecryptingKey, PaddedBlockEncryptingKey, PaddingStrategy, UnboundCipherKey, AES_128,
+    AES_128_KEY_LEN, AES_192, AES_192_KEY_LEN, AES_256, AES_256_KEY_LEN,
+};
+use crate::error::Unspecified;
+use crate::sealed::Sealed;
+use core::fmt::Debug;
+use core::marker::PhantomData;
+use zeroize::ZeroizeOnDrop;
+
+/// A cipher algorithm marker type.
+pub trait KeyAlgorithm: Sealed + 'static {
+    /// The key, as an array of the length required by the algorithm.
+    type KeyBytes: AsRef<[u8]>;
+
+    /// The dynamic algorithm this marker stands for.
+    fn algorithm() -> &'static Algorithm;
+}
+
+/// A cipher operating mode marker type.
+pub trait Mode: Sealed + 'static {
+    /// The dynamic encrypting key type for this mode.
+    type EncryptingKey: Debug;
+
+    /// The dynamic decrypting key type for this mode.
+    type DecryptingKey: Debug;
+
+    /// The dynamic operating mode this marker stands for.
+    fn mode() -> OperatingMode;
+
+    #[doc(hidden)]
+    fn encrypting_key(key: UnboundCipherKey) -> Result<Self::EncryptingKey, Unspecified>;
+
+    #[doc(hidden)]
+    fn decrypting_key(key: UnboundCipherKey) -> Result<Self::DecryptingKey, Unspecified>;
+}
+
+/// AES with a 128-bit key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Aes128;
+
+impl Sealed for Aes128 {}
+
+impl KeyAlgorithm for Aes128 {
+    type KeyBytes = [u8; AES_128_KEY_LEN];
+
+    fn algorithm() -> &'static Algorithm {
+        &AES_128
+    }
+}
+
+/// AES with a 192-bit key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Aes192;
+
+impl Sealed for Aes192 {}
+
+impl KeyAlgorithm for Aes192 {
+    type KeyBytes = [u8; AES_192_KEY_LEN];
+
+    fn algorithm() -> &'static Algorithm {
+        &AES_192
+    }
+}
+
+/// AES with a 256-bit key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Aes256;
+
+impl Sealed for Aes256 {}
+
+impl KeyAlgorithm for Aes256 {
+    type KeyBytes = [u8; AES_256_KEY_LEN];
+
+    fn algorithm() -> &'static Algorithm {
+        &AES_256
+    }
+}
+
+/// Counter (CTR) mode, without padding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ctr;
+
+impl Sealed for Ctr {}
+
+impl Mode for Ctr {
+    type EncryptingKey = EncryptingKey;
+    type DecryptingKey = DecryptingKey;
+
+    fn mode() -> OperatingMode {
+        OperatingMode::CTR
+    }
+
+    fn encrypting_key(key: UnboundCipherKey) -> Result<EncryptingKey, Unspecified> {
+        EncryptingKey::ctr(key)
+    }
+
+    fn decrypting_key(key: UnboundCipherKey) -> Result<DecryptingKey, Unspecified> {
+        DecryptingKey::ctr(key)
+    }
+}
+
+/// Cipher block chaining (CBC) mode with PKCS#7 padding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CbcPkcs7;
+
+impl Sealed for CbcPkcs7 {}
+
+impl Mode for CbcPkcs7 {
+    type EncryptingKey = PaddedBlockEncryptingKey;
+    type DecryptingKey = PaddedBlockDecryptingKey;
+
+    fn mode() -> OperatingMode {
+        OperatingMode::CBC
+    }
+
+    fn encrypting_key(key: UnboundCipherKey) -> Result<PaddedBlockEncryptingKey, Unspecified> {
+        PaddedBlockEncryptingKey::cbc_pkcs7(key)
+    }
+
+    fn decrypting_key(key: UnboundCipherKey) -> Result<PaddedBlockDecryptingKey, Unspecified> {
+        PaddedBlockDecryptingKey::cbc_pkcs7(key)
+    }
+}
+
+/// An encrypting key bound to the algorithm `A` and mode `M` at the type level.
+///
+/// Dropping it drops the wrapped [`EncryptingKey`] or [`PaddedBlockEncryptingKey`], which
+/// zeroizes the key bytes and key schedule.
+pub struct TypedEncryptingKey<A: KeyAlgorithm, M: Mode> {
+    key: M::EncryptingKey,
+    algorithm: PhantomData<A>,
+}
+
+/// A decrypting key bound to the algorithm `A` and mode `M` at the type level.
+///
+/// Dropping it drops the wrapped [`DecryptingKey`] or [`PaddedBlockDecryptingKey`], which
+/// zeroizes the key bytes and both key schedules.
+pub struct TypedDecryptingKey<A: KeyAlgorithm, M: Mode> {
+    key: M::DecryptingKey,
+    algorithm: PhantomData<A>,
+}
+
+impl<A: KeyAlgorithm, M: Mode> TypedEncryptingKey<A, M> {
+    /// Constructs a `TypedEncryptingKey` from key bytes of the length required by `A`.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the key.
+    pub fn new(key_bytes: &A::KeyBytes) -> Result<Self, Unspecified> {
+        Self::try_from(UnboundCipherKey::new(A::algorithm(), key_bytes.as_ref())?)
+    }
+
+    /// Returns the cipher algorithm.
+    #[must_use]
+    pub fn algorithm(&self) -> &'static Algorithm {
+        A::algorithm()
+    }
+
+    /// Returns the cipher operating mode.
+    #[must_use]
+    pub fn mode(&self) -> OperatingMode {
+        M::mode()
+    }
+}
+
+impl<A: KeyAlgorithm, M: Mode> TypedDecryptingKey<A, M> {
+    /// Constructs a `TypedDecryptingKey` from key bytes of the length required by `A`.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the key.
+    pub fn new(key_bytes: &A::KeyBytes) -> Result<Self, Unspecified> {
+        Self::try_from(UnboundCipherKey::new(A::algorithm(), key_bytes.as_ref())?)
+    }
+
+    /// Returns the cipher algorithm.
+    #[must_use]
+    pub fn algorithm(&self) -> &'static Algorithm {
+        A::algorithm()
+    }
+
+    /// Returns the cipher operating mode.
+    #[must_use]
+    pub fn mode(&self) -> OperatingMode {
+        M::mode()
+    }
+}
+
+impl<A: KeyAlgorithm> TypedEncryptingKey<A, Ctr> {
+    /// Encrypts the data provided in `in_out` in-place.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if encryption fails.
+    pub fn encrypt(&self, in_out: &mut [u8]) -> Result<DecryptionContext, Unspecified> {
+        self.key.encrypt(in_out)
+    }
+
+    /// Encrypts the data provided in `in_out` in-place using the provided `context`.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `context` is not valid for the algorithm and mode, or if
+    ///   encryption fails.
+    pub fn less_safe_encrypt(
+        &self,
+        in_out: &mut [u8],
+        context: EncryptionContext,
+    ) -> Result<DecryptionContext, Unspecified> {
+        self.key.less_safe_encrypt(in_out, context)
+    }
+}
+
+impl<A: KeyAlgorithm> TypedEncryptingKey<A, CbcPkcs7> {
+    /// Pads and encrypts data provided in `in_out` in-place.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if encryption fails.
+    pub fn encrypt<InOut>(&self, in_out: &mut InOut) -> Result<DecryptionContext, Unspecified>
+    where
+        InOut: AsMut<[u8]> + for<'a> Extend<&'a u8>,
+    {
+        self.key.encrypt(in_out)
+    }
+
+    /// Pads and encrypts data provided in `in_out` in-place using the provided `context`.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `context` is not valid for the algorithm and mode, or if
+    ///   encryption fails.
+    pub fn less_safe_encrypt<InOut>(
+        &self,
+        in_out: &mut InOut,
+        context: EncryptionContext,
+    ) -> Result<DecryptionContext, Unspecified>
+    where
+        InOut: AsMut<[u8]> + for<'a> Extend<&'a u8>,
+    {
+        self.key.less_safe_encrypt(in_out, context)
+    }
+}
+
+impl<A: KeyAlgorithm> TypedDecryptingKey<A, Ctr> {
+    /// Decrypts the data provided in `in_out` in-place.
+    /// Returns a references to the decrypted data.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if decryption fails.
+    pub fn decrypt<'in_out>(
+        &self,
+        in_out: &'in_out mut [u8],
+        context: DecryptionContext,
+    ) -> Result<&'in_out mut [u8], Unspecified> {
+        self.key.decrypt(in_out, context)
+    }
+}
+
+impl<A: KeyAlgorithm> TypedDecryptingKey<A, CbcPkcs7> {
+    /// Decrypts and unpads data provided in `in_out` in-place.
+    /// Returns a references to the decrypted data.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if the padding is invalid or if decryption fails.
+    pub fn decrypt<'in_out>(
+        &self,
+        in_out: &'in_out mut [u8],
+        context: DecryptionContext,
+    ) -> Result<&'in_out mut [u8], Unspecified> {
+        self.key.decrypt(in_out, context)
+    }
+}
+
+impl<A: KeyAlgorithm, M: Mode> TryFrom<UnboundCipherKey> for TypedEncryptingKey<A, M> {
+    type Error = Unspecified;
+
+    fn try_from(key: UnboundCipherKey) -> Result<Self, Unspecified> {
+        if key.algorithm() != A::algorithm() {
+            return Err(Unspecified);
+        }
+        Ok(Self {
+            key: M::encrypting_key(key)?,
+            algorithm: PhantomData,
+        })
+    }
+}
+
+impl<A: KeyAlgorithm, M: Mode> TryFrom<UnboundCipherKey> for TypedDecryptingKey<A, M> {
+    type Error = Unspecified;
+
+    fn try_from(key: UnboundCipherKey) -> Result<Self, Unspecified> {
+        if key.algorithm() != A::algorithm() {
+            return Err(Unspecified);
+        }
+        Ok(Self {
+            key: M::decrypting_key(key)?,
+            algorithm: PhantomData,
+        })
+    }
+}
+
+macro_rules! typed_conversions {
+    ($typed:ident, $mode:ident, $dynamic:ident, $op_mode:expr $(, $padding:expr)?) => {
+        impl<A: KeyAlgorithm> From<$typed<A, $mode>> for $dynamic {
+            fn from(key: $typed<A, $mode>) -> Self {
+                key.key
+            }
+        }
+
+        impl<A: KeyAlgorithm> TryFrom<$dynamic> for $typed<A, $mode> {
+            type Error = Unspecified;
+
+            fn try_from(key: $dynamic) -> Result<Self, Unspecified> {
+                if key.algorithm() != A::algorithm() || key.mode() != $op_mode {
+                    return Err(Unspecified);
+                }
+                $(
+                    if key.padding != $padding {
+                        return Err(Unspecified);
+                    }
+                )?
+                Ok(Self {
+                    key,
+                    algorithm: PhantomData,
+                })
+            }
+        }
+    };
+}
+
+typed_conversions!(TypedEncryptingKey, Ctr, EncryptingKey, OperatingMode::CTR);
+typed_conversions!(TypedDecryptingKey, Ctr, DecryptingKey, OperatingMode::CTR);
+typed_conversions!(
+    TypedEncryptingKey,
+    CbcPkcs7,
+    PaddedBlockEncryptingKey,
+    OperatingMode::CBC,
+    PaddingStrategy::PKCS7
+);
+typed_conversions!(
+    TypedDecryptingKey,
+    CbcPkcs7,
+    PaddedBlockDecryptingKey,
+    OperatingMode::CBC,
+    PaddingStrategy::PKCS7
+);
+
+impl<A: KeyAlgorithm, M: Mode> Debug for TypedEncryptingKey<A, M> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("TypedEncryptingKey")
+            .field(&self.key)
+            .finish()
+    }
+}
+
+impl<A: KeyAlgorithm, M: Mode> Debug for TypedDecryptingKey<A, M> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("TypedDecryptingKey")
+            .field(&self.key)
+            .finish()
+    }
+}
+
+impl<A: KeyAlgorithm, M: Mode> ZeroizeOnDrop for TypedEncryptingKey<A, M> {}
+
+impl<A: KeyAlgorithm, M: Mode> ZeroizeOnDrop for TypedDecryptingKey<A, M> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Aes128, Aes256, CbcPkcs7, Ctr, TypedDecryptingKey, TypedEncryptingKey};
+    use crate::cipher::{
+        DecryptingKey, EncryptingKey, EncryptionContext, OperatingMode, PaddedBlockEncryptingKey,
+        UnboundCipherKey, AES_128, AES_256,
+    };
+    use crate::iv::FixedLength;
+    use crate::test::from_hex;
+
+    #[test]
+    fn test_typed_cbc_kat() {
+        let key: [u8; 16] = from_hex("000102030405060708090a0b0c0d0e0f")
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let context = EncryptionContext::Iv128(FixedLength::from([0u8; 16]));
+        let plaintext = from_hex("00112233445566778899aabbccddeeff").unwrap();
+
+        let encrypting_key = TypedEncryptingKey::<Aes128, CbcPkcs7>::new(&key).unwrap();
+        assert_eq!(&AES_128, encrypting_key.algorithm());
+        assert_eq!(OperatingMode::CBC, encrypting_key.mode());
+        let mut in_out = plaintext.clone();
+        let context = encrypting_key
+            .less_safe_encrypt(&mut in_out, context)
+            .unwrap();
+        assert_eq!(
+            from_hex("69c4e0d86a7b0430d8cdb78070b4c55a9e978e6d16b086570ef794ef97984232").unwrap(),
+            in_out
+        );
+
+        let decrypting_key = TypedDecryptingKey::<Aes128, CbcPkcs7>::new(&key).unwrap();
+        assert_eq!(
+            plaintext.as_slice(),
+            decrypting_key.decrypt(&mut in_out, context).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_typed_ctr_round_trip() {
+        let key = [0x5au8; 32];
+        let encrypting_key = TypedEncryptingKey::<Aes256, Ctr>::new(&key).unwrap();
+        let decrypting_key = TypedDecryptingKey::<Aes256, Ctr>::new(&key).unwrap();
+        assert_eq!(&AES_256, decrypting_key.algorithm());
+        assert_eq!(OperatingMode::CTR, decrypting_key.mode());
+
+        let mut in_out = b"typed round trip".to_vec();
+        let context = encrypting_key.encrypt(&mut in_out).unwrap();
+        assert_eq!(
+            b"typed round trip",
+            decrypting_key.decrypt(&mut in_out, context).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_dynamic_conversions() {
+        let key = [0x5au8; 16];
+
+        let typed = TypedEncryptingKey::<Aes128, Ctr>::new(&key).unwrap();
+        let dynamic: EncryptingKey = typed.into();
+        assert_eq!(OperatingMode::CTR, dynamic.mode());
+        assert!(TypedEncryptingKey::<Aes128, Ctr>::try_from(dynamic).is_ok());
+
+        let dynamic = EncryptingKey::ctr(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+        assert!(TypedEncryptingKey::<Aes256, Ctr>::try_from(dynamic).is_err());
+
+        let dynamic = DecryptingKey::ctr(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+        assert!(TypedDecryptingKey::<Aes128, Ctr>::try_from(dynamic).is_ok());
+
+        let dynamic = EncryptingKey::new(
+            UnboundCipherKey::new(&AES_128, &key).unwrap(),
+            OperatingMode::CBC,
+        )
+        .unwrap();
+        assert!(TypedEncryptingKey::<Aes128, Ctr>::try_from(dynamic).is_err());
+
+        let dynamic =
+            PaddedBlockEncryptingKey::cbc_pkcs7(UnboundCipherKey::new(&AES_128, &key).unwrap())
+                .unwrap();
+        assert!(TypedEncryptingKey::<Aes128, CbcPkcs7>::try_from(dynamic).is_ok());
+
+        let unbound = UnboundCipherKey::new(&AES_256, &[0u8; 32]).unwrap();
+        assert!(TypedDecryptingKey::<Aes128, CbcPkcs7>::try_from(unbound).is_err());
+    }
+}
@@ -0,0 +1,235 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use crate::cipher::{
+    DecryptionContext, EncryptingKey, OperatingMode, PaddedBlockEncryptingKey,
+    StreamingEncryptingKey, UnboundCipherKey, AES_BLOCK_LEN,
+};
+use crate::error::Unspecified;
+use core::fmt::Debug;
+use std::io::{self, ErrorKind, Write};
+
+/// The number of bytes of plaintext encrypted at a time.
+const WRITE_LEN: usize = 4096;
+
+/// A [`Write`] adapter that encrypts the plaintext written to it, writing the ciphertext to an
+/// underlying writer.
+///
+/// Encryption happens as `write` is called, so the plaintext never needs to be held in memory
+/// all at once. In CBC and ECB modes a trailing partial block is held back until more
+/// plaintext is written, and the last block is only written by [`finish`](Self::finish).
+/// A writer that is dropped without calling `finish` may not have written all of the
+/// ciphertext.
+pub struct EncryptingWriter<W: Write> {
+    inner: W,
+    key: StreamingEncryptingKey,
+    ciphertext: Vec<u8>,
+}
+
+impl<W: Write> EncryptingWriter<W> {
+    /// Constructs an `EncryptingWriter` that encrypts the plaintext written to it with `key` in
+    /// the given `mode`, using a randomly generated context.
+    ///
+    /// No padding is applied, so in CBC and ECB modes the total length of the plaintext must
+    /// be a multiple of the block length.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `mode` is not supported by the key's algorithm, or if a
+    ///   context cannot be generated for it.
+    pub fn new(inner: W, key: UnboundCipherKey, mode: OperatingMode) -> Result<Self, Unspecified> {
+        let key = StreamingEncryptingKey::new(EncryptingKey::new(key, mode)?)?;
+        Ok(Self::with_key(inner, key))
+    }
+
+    /// Constructs an `EncryptingWriter` that encrypts the plaintext written to it with `key`
+    /// and pads it, using a randomly generated context.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if a context cannot be generated for the key's mode.
+    pub fn new_padded(inner: W, key: PaddedBlockEncryptingKey) -> Result<Self, Unspecified> {
+        let key = StreamingEncryptingKey::new_padded(key)?;
+        Ok(Self::with_key(inner, key))
+    }
+
+    fn with_key(inner: W, key: StreamingEncryptingKey) -> Self {
+        EncryptingWriter {
+            inner,
+            key,
+            ciphertext: vec![0u8; WRITE_LEN + AES_BLOCK_LEN],
+        }
+    }
+
+    /// Returns the context needed for decryption.
+    ///
+    /// This is the same context that is returned by [`finish`](Self::finish), so it can be
+    /// written ahead of the ciphertext.
+    #[must_use]
+    pub fn context(&self) -> &DecryptionContext {
+        self.key.context()
+    }
+
+    /// Returns a reference to the underlying writer.
+    #[must_use]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Finishes the encryption, writing any remaining ciphertext including the padding and
+    /// flushing the underlying writer. Returns the underlying writer and the context needed
+    /// for decryption.
+    ///
+    /// # Errors
+    /// Returns an error of kind [`ErrorKind::InvalidInput`] if no padding is applied and the
+    /// total length of the plaintext was not a multiple of the block length in CBC or ECB
+    /// mode. Errors from the underlying writer are returned as they are.
+    pub fn finish(self) -> io::Result<(W, DecryptionContext)> {
+        let EncryptingWriter {
+            mut inner,
+            key,
+            mut ciphertext,
+        } = self;
+        let (context, update) = key
+            .finish(&mut ciphertext)
+            .map_err(|_| io::Error::new(ErrorKind::InvalidInput, Unspecified))?;
+        inner.write_all(update.written())?;
+        inner.flush()?;
+        Ok((inner, context))
+    }
+}
+
+impl<W: Write> Write for EncryptingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len().min(WRITE_LEN);
+        let update = self
+            .key
+            .update(&buf[..len], &mut self.ciphertext)
+            .map_err(|_| io::Error::new(ErrorKind::InvalidInput, Unspecified))?;
+        self.inner.write_all(update.written())?;
+        Ok(len)
+    }
+
+    /// Flushes the underlying writer.
+    ///
+    /// A partial block held back in CBC or ECB mode is not written, since it can only be
+    /// encrypted once more plaintext is written or [`finish`](EncryptingWriter::finish) is
+    /// called.
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: Write> Debug for EncryptingWriter<W> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("EncryptingWriter")
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EncryptingWriter;
+    use crate::cipher::{
+        DecryptingReader, EncryptingKey, EncryptionContext, OperatingMode,
+        PaddedBlockDecryptingKey, PaddedBlockEncryptingKey, UnboundCipherKey, AES_128,
+    };
+    use std::io::{Cursor, ErrorKind, Read, Write};
+
+    fn key() -> UnboundCipherKey {
+        UnboundCipherKey::new(&AES_128, &[0x42u8; 16]).unwrap()
+    }
+
+    #[test]
+    fn test_ctr_round_trip() {
+        let plaintext: Vec<u8> = (0..10_000).map(|i| i as u8).collect();
+        for chunk_size in [1, 15, 16, 17, 4096, 10_000] {
+            let mut writer =
+                EncryptingWriter::new(Cursor::new(Vec::new()), key(), OperatingMode::CTR).unwrap();
+            for chunk in plaintext.chunks(chunk_size) {
+                writer.write_all(chunk).unwrap();
+            }
+            writer.flush().unwrap();
+            let (ciphertext, context) = writer.finish().unwrap();
+            let ciphertext = ciphertext.into_inner();
+            assert_eq!(plaintext.len(), ciphertext.len());
+
+            let context_iv: &[u8] = (&context).try_into().unwrap();
+            let mut expected = plaintext.clone();
+            EncryptingKey::ctr(key())
+                .unwrap()
+                .less_safe_encrypt(
+                    &mut expected,
+                    EncryptionContext::Iv128(context_iv.try_into().unwrap()),
+                )
+                .unwrap();
+            assert_eq!(expected, ciphertext);
+
+            let mut reader =
+                DecryptingReader::new(Cursor::new(ciphertext), key(), OperatingMode::CTR, context)
+                    .unwrap();
+            let mut decrypted = Vec::new();
+            reader.read_to_end(&mut decrypted).unwrap();
+            assert_eq!(plaintext, decrypted);
+        }
+    }
+
+    #[test]
+    fn test_cbc_pkcs7_round_trip() {
+        for len in [0, 1, 15, 16, 17, 4095, 4096, 4097, 10_000] {
+            let plaintext: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            let mut writer = EncryptingWriter::new_padded(
+                Cursor::new(Vec::new()),
+                PaddedBlockEncryptingKey::cbc_pkcs7(key()).unwrap(),
+            )
+            .unwrap();
+            for chunk in plaintext.chunks(7) {
+                writer.write_all(chunk).unwrap();
+            }
+            let (ciphertext, context) = writer.finish().unwrap();
+            let ciphertext = ciphertext.into_inner();
+
+            let context_iv: &[u8] = (&context).try_into().unwrap();
+            let mut expected = plaintext.clone();
+            PaddedBlockEncryptingKey::cbc_pkcs7(key())
+                .unwrap()
+                .less_safe_encrypt(
+                    &mut expected,
+                    EncryptionContext::Iv128(context_iv.try_into().unwrap()),
+                )
+                .unwrap();
+            assert_eq!(expected, ciphertext);
+
+            let mut reader = DecryptingReader::new_padded(
+                Cursor::new(ciphertext),
+                PaddedBlockDecryptingKey::cbc_pkcs7(key()).unwrap(),
+                context,
+            )
+            .unwrap();
+            let mut decrypted = Vec::new();
+            reader.read_to_end(&mut decrypted).unwrap();
+            assert_eq!(plaintext, decrypted);
+        }
+    }
+
+    #[test]
+    fn test_context_matches_finish() {
+        let writer =
+            EncryptingWriter::new(Cursor::new(Vec::new()), key(), OperatingMode::CBC).unwrap();
+        let expected: &[u8] = writer.context().try_into().unwrap();
+        let expected = expected.to_vec();
+        assert!(writer.get_ref().get_ref().is_empty());
+        let (_, context) = writer.finish().unwrap();
+        let iv: &[u8] = (&context).try_into().unwrap();
+        assert_eq!(expected, iv);
+    }
+
+    #[test]
+    fn test_cbc_partial_block_is_an_error() {
+        let mut writer =
+            EncryptingWriter::new(Cursor::new(Vec::new()), key(), OperatingMode::CBC).unwrap();
+        writer.write_all(&[0u8; 20]).unwrap();
+        assert_eq!(16, writer.get_ref().get_ref().len());
+        let err = writer.finish().unwrap_err();
+        assert_eq!(ErrorKind::InvalidInput, err.kind());
+    }
+}
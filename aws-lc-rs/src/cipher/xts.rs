@@ -0,0 +1,422 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use crate::cipher::block::{Block, BLOCK_LEN};
+use crate::cipher::key::SymmetricCipherKey;
+use crate::cipher::{Algorithm, AlgorithmId, UnboundCipherKey};
+use crate::error::Unspecified;
+use core::fmt::Debug;
+use zeroize::Zeroize;
+
+/// The number of bytes in an AES-XTS tweak.
+pub const XTS_TWEAK_LEN: usize = 16;
+
+/// The maximum number of blocks in a single data unit, as specified by IEEE 1619.
+const XTS_MAX_BLOCKS: usize = 1 << 20;
+
+#[derive(Clone, Copy)]
+enum Direction {
+    Encrypt,
+    Decrypt,
+}
+
+/// The data key and tweak key of an AES-XTS key.
+struct XtsKeyPair {
+    data_key: UnboundCipherKey,
+    tweak_key: UnboundCipherKey,
+}
+
+impl XtsKeyPair {
+    fn new(data_key: UnboundCipherKey, tweak_key: UnboundCipherKey) -> Result<Self, Unspecified> {
+        match (data_key.algorithm().id(), tweak_key.algorithm().id()) {
+            (AlgorithmId::Aes128, AlgorithmId::Aes128)
+            | (AlgorithmId::Aes256, AlgorithmId::Aes256) => {}
+            _ => return Err(Unspecified),
+        }
+
+        // IEEE 1619 requires the two keys to be distinct. Equal keys always encrypt the zero
+        // block to the same value, while distinct keys do so with negligible probability.
+        let data_check = data_key.key.encrypt_block(Block::zero());
+        let tweak_check = tweak_key.key.encrypt_block(Block::zero());
+        if data_check.as_ref() == tweak_check.as_ref() {
+            return Err(Unspecified);
+        }
+
+        Ok(XtsKeyPair {
+            data_key,
+            tweak_key,
+        })
+    }
+
+    fn from_double_length_key(
+        algorithm: &'static Algorithm,
+        key_bytes: &[u8],
+    ) -> Result<Self, Unspecified> {
+        if key_bytes.len() % 2 != 0 {
+            return Err(Unspecified);
+        }
+        let (data_key, tweak_key) = key_bytes.split_at(key_bytes.len() / 2);
+        XtsKeyPair::new(
+            UnboundCipherKey::new(algorithm, data_key)?,
+            UnboundCipherKey::new(algorithm, tweak_key)?,
+        )
+    }
+
+    fn algorithm(&self) -> &Algorithm {
+        self.data_key.algorithm()
+    }
+
+    fn crypt(
+        &self,
+        direction: Direction,
+        tweak: &[u8; XTS_TWEAK_LEN],
+        in_out: &mut [u8],
+    ) -> Result<(), Unspecified> {
+        let len = in_out.len();
+        if !(BLOCK_LEN..=XTS_MAX_BLOCKS * BLOCK_LEN).contains(&len) {
+            return Err(Unspecified);
+        }
+
+        let data_key = &self.data_key.key;
+        let mut tweak = *self
+            .tweak_key
+            .key
+            .encrypt_block(Block::from(tweak))
+            .as_ref();
+
+        // When the data unit ends with a partial block, the last full block is processed
+        // together with it using ciphertext stealing.
+        let partial_len = len % BLOCK_LEN;
+        let full_blocks = len / BLOCK_LEN - usize::from(partial_len != 0);
+        let (head, tail) = in_out.split_at_mut(full_blocks * BLOCK_LEN);
+
+        for block in head.chunks_exact_mut(BLOCK_LEN) {
+            xts_block(data_key, direction, &tweak, block);
+            xts_tweak_next(&mut tweak);
+        }
+
+        if partial_len != 0 {
+            let (last_block, partial) = tail.split_at_mut(BLOCK_LEN);
+            match direction {
+                Direction::Encrypt => {
+                    xts_block(data_key, direction, &tweak, last_block);
+                    xts_tweak_next(&mut tweak);
+                    last_block[..partial_len].swap_with_slice(partial);
+                    xts_block(data_key, direction, &tweak, last_block);
+                }
+                Direction::Decrypt => {
+                    let mut next_tweak = tweak;
+                    xts_tweak_next(&mut next_tweak);
+                    xts_block(data_key, direction, &next_tweak, last_block);
+                    last_block[..partial_len].swap_with_slice(partial);
+                    xts_block(data_key, direction, &tweak, last_block);
+                    next_tweak.zeroize();
+                }
+            }
+        }
+
+        tweak.zeroize();
+
+        Ok(())
+    }
+}
+
+/// An AES-XTS key for encrypting data units, such as disk sectors, in place.
+///
+/// XTS provides confidentiality only. It does not detect modification of the ciphertext.
+pub struct XtsEncryptingKey {
+    key: XtsKeyPair,
+}
+
+impl XtsEncryptingKey {
+    /// Constructs an `XtsEncryptingKey` from a data key and a tweak key.
+    ///
+    /// Both keys must be constructed for the same algorithm, either `AES_128` or `AES_256`,
+    /// and must not be equal.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if the keys are not a valid AES-XTS key pair.
+    pub fn new(
+        data_key: UnboundCipherKey,
+        tweak_key: UnboundCipherKey,
+    ) -> Result<XtsEncryptingKey, Unspecified> {
+        Ok(XtsEncryptingKey {
+            key: XtsKeyPair::new(data_key, tweak_key)?,
+        })
+    }
+
+    /// Constructs an `XtsEncryptingKey` from a double-length key, as used by IEEE 1619.
+    ///
+    /// The first half of `key_bytes` is the data key and the second half is the tweak key,
+    /// so `key_bytes` is 32 bytes long for `AES_128` and 64 bytes long for `AES_256`.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `key_bytes` is not a valid AES-XTS key for `algorithm`.
+    pub fn from_double_length_key(
+        algorithm: &'static Algorithm,
+        key_bytes: &[u8],
+    ) -> Result<XtsEncryptingKey, Unspecified> {
+        Ok(XtsEncryptingKey {
+            key: XtsKeyPair::from_double_length_key(algorithm, key_bytes)?,
+        })
+    }
+
+    /// Returns the cipher algorithm of the data and tweak keys.
+    #[must_use]
+    pub fn algorithm(&self) -> &Algorithm {
+        self.key.algorithm()
+    }
+
+    /// Encrypts the data unit `in_out` in place.
+    ///
+    /// `tweak` identifies the data unit and is typically the little-endian encoding of the
+    /// sector number. Data units do not need to be a multiple of the block length.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `in_out` is shorter than one block or longer than
+    ///   2^20 blocks.
+    pub fn encrypt(
+        &self,
+        tweak: &[u8; XTS_TWEAK_LEN],
+        in_out: &mut [u8],
+    ) -> Result<(), Unspecified> {
+        self.key.crypt(Direction::Encrypt, tweak, in_out)
+    }
+}
+
+impl Debug for XtsEncryptingKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("XtsEncryptingKey")
+            .field("algorithm", self.algorithm())
+            .finish()
+    }
+}
+
+/// An AES-XTS key for decrypting data units, such as disk sectors, in place.
+pub struct XtsDecryptingKey {
+    key: XtsKeyPair,
+}
+
+impl XtsDecryptingKey {
+    /// Constructs an `XtsDecryptingKey` from a data key and a tweak key.
+    ///
+    /// Both keys must be constructed for the same algorithm, either `AES_128` or `AES_256`,
+    /// and must not be equal.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if the keys are not a valid AES-XTS key pair.
+    pub fn new(
+        data_key: UnboundCipherKey,
+        tweak_key: UnboundCipherKey,
+    ) -> Result<XtsDecryptingKey, Unspecified> {
+        Ok(XtsDecryptingKey {
+            key: XtsKeyPair::new(data_key.with_decrypt_key()?, tweak_key)?,
+        })
+    }
+
+    /// Constructs an `XtsDecryptingKey` from a double-length key, as used by IEEE 1619.
+    ///
+    /// The first half of `key_bytes` is the data key and the second half is the tweak key,
+    /// so `key_bytes` is 32 bytes long for `AES_128` and 64 bytes long for `AES_256`.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `key_bytes` is not a valid AES-XTS key for `algorithm`.
+    pub fn from_double_length_key(
+        algorithm: &'static Algorithm,
+        key_bytes: &[u8],
+    ) -> Result<XtsDecryptingKey, Unspecified> {
+        Ok(XtsDecryptingKey {
+            key: XtsKeyPair::from_double_length_key(algorithm, key_bytes)?,
+        })
+    }
+
+    /// Returns the cipher algorithm of the data and tweak keys.
+    #[must_use]
+    pub fn algorithm(&self) -> &Algorithm {
+        self.key.algorithm()
+    }
+
+    /// Decrypts the data unit `in_out` in place.
+    ///
+    /// `tweak` must be the value used when the data unit was encrypted.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `in_out` is shorter than one block or longer than
+    ///   2^20 blocks.
+    pub fn decrypt(
+        &self,
+        tweak: &[u8; XTS_TWEAK_LEN],
+        in_out: &mut [u8],
+    ) -> Result<(), Unspecified> {
+        self.key.crypt(Direction::Decrypt, tweak, in_out)
+    }
+}
+
+impl Debug for XtsDecryptingKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("XtsDecryptingKey")
+            .field("algorithm", self.algorithm())
+            .finish()
+    }
+}
+
+fn xts_block(
+    key: &SymmetricCipherKey,
+    direction: Direction,
+    tweak: &[u8; BLOCK_LEN],
+    block: &mut [u8],
+) {
+    let mut buf = [0u8; BLOCK_LEN];
+    buf.copy_from_slice(block);
+    xor_in_place(&mut buf, tweak);
+
+    let mut out = match direction {
+        Direction::Encrypt => *key.encrypt_block(Block::from(&buf)).as_ref(),
+        Direction::Decrypt => *key.decrypt_block(Block::from(&buf)).as_ref(),
+    };
+    xor_in_place(&mut out, tweak);
+    block.copy_from_slice(&out);
+
+    buf.zeroize();
+    out.zeroize();
+}
+
+// Multiplication by the primitive element `x` in GF(2^128) using the polynomial
+// x^128 + x^7 + x^2 + x + 1, with the little-endian byte order of IEEE 1619.
+fn xts_tweak_next(tweak: &mut [u8; BLOCK_LEN]) {
+    let mut carry = 0u8;
+    for byte in tweak.iter_mut() {
+        let next_carry = *byte >> 7;
+        *byte = (*byte << 1) | carry;
+        carry = next_carry;
+    }
+    // Constant-time conditional reduction.
+    tweak[0] ^= 0x87 & 0u8.wrapping_sub(carry);
+}
+
+fn xor_in_place(a: &mut [u8; BLOCK_LEN], b: &[u8; BLOCK_LEN]) {
+    for (a, b) in a.iter_mut().zip(b) {
+        *a ^= b;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cipher::{AES_128, AES_192, AES_256, CHACHA20};
+    use crate::rand;
+    use crate::test::from_hex;
+
+    fn key_pair(alg: &'static Algorithm, data_key: &[u8], tweak_key: &[u8]) -> XtsKeyPair {
+        XtsKeyPair::new(
+            UnboundCipherKey::new(alg, data_key).unwrap(),
+            UnboundCipherKey::new(alg, tweak_key).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_xts_tweak_next() {
+        let mut tweak = [0u8; BLOCK_LEN];
+        tweak[15] = 0x80;
+        xts_tweak_next(&mut tweak);
+        let mut expected = [0u8; BLOCK_LEN];
+        expected[0] = 0x87;
+        assert_eq!(expected, tweak);
+
+        // Compare against a 128-bit integer model, including tweaks that overflow.
+        let mut tweak = [0xffu8; BLOCK_LEN];
+        for i in 0..1000 {
+            if i > 0 {
+                rand::fill(&mut tweak).unwrap();
+            }
+            let value = u128::from_le_bytes(tweak);
+            let reduction = if value >> 127 == 1 { 0x87 } else { 0 };
+            let expected = ((value << 1) ^ reduction).to_le_bytes();
+            xts_tweak_next(&mut tweak);
+            assert_eq!(expected, tweak);
+        }
+    }
+
+    #[test]
+    fn test_xts_key_validation() {
+        let key_128 = [0x11u8; 16];
+        let other_128 = [0x22u8; 16];
+        let key_256 = [0x11u8; 32];
+
+        for (alg, data_key, tweak_key) in [
+            (&AES_128, &key_128[..], &key_128[..]),
+            (&AES_192, &[0x11u8; 24][..], &[0x22u8; 24][..]),
+            (&CHACHA20, &key_256[..], &[0x22u8; 32][..]),
+        ] {
+            assert!(XtsKeyPair::new(
+                UnboundCipherKey::new(alg, data_key).unwrap(),
+                UnboundCipherKey::new(alg, tweak_key).unwrap(),
+            )
+            .is_err());
+        }
+
+        assert!(XtsKeyPair::new(
+            UnboundCipherKey::new(&AES_128, &key_128).unwrap(),
+            UnboundCipherKey::new(&AES_256, &key_256).unwrap(),
+        )
+        .is_err());
+
+        assert!(XtsKeyPair::new(
+            UnboundCipherKey::new(&AES_128, &key_128).unwrap(),
+            UnboundCipherKey::new(&AES_128, &other_128).unwrap(),
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_xts_double_length_key() {
+        let key_bytes =
+            from_hex("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f").unwrap();
+        assert!(XtsKeyPair::from_double_length_key(&AES_128, &key_bytes).is_ok());
+        assert!(XtsKeyPair::from_double_length_key(&AES_256, &key_bytes).is_err());
+        assert!(XtsKeyPair::from_double_length_key(&AES_128, &key_bytes[..31]).is_err());
+        assert!(XtsKeyPair::from_double_length_key(&AES_128, &[0x11u8; 32]).is_err());
+    }
+
+    #[test]
+    fn test_xts_data_unit_length() {
+        let key = key_pair(&AES_128, &[0x11u8; 16], &[0x22u8; 16]);
+        let tweak = [0u8; XTS_TWEAK_LEN];
+
+        for len in [0, 1, 15] {
+            let mut in_out = vec![0u8; len];
+            assert!(key.crypt(Direction::Encrypt, &tweak, &mut in_out).is_err());
+            assert!(key.crypt(Direction::Decrypt, &tweak, &mut in_out).is_err());
+        }
+
+        let mut in_out = vec![0u8; XTS_MAX_BLOCKS * BLOCK_LEN + 1];
+        assert!(key.crypt(Direction::Encrypt, &tweak, &mut in_out).is_err());
+        in_out.truncate(XTS_MAX_BLOCKS * BLOCK_LEN);
+        assert!(key.crypt(Direction::Encrypt, &tweak, &mut in_out).is_ok());
+    }
+
+    #[test]
+    fn test_xts_round_trip() {
+        let data_key =
+            from_hex("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f").unwrap();
+        let tweak_key =
+            from_hex("202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f").unwrap();
+
+        for (alg, key_len) in [(&AES_128, 16), (&AES_256, 32)] {
+            let key = key_pair(alg, &data_key[..key_len], &tweak_key[..key_len]);
+            for len in 16..=100 {
+                let mut tweak = [0xffu8; XTS_TWEAK_LEN];
+                if len % 2 == 0 {
+                    rand::fill(&mut tweak).unwrap();
+                }
+                let input: Vec<u8> = (0..len).map(|i| i as u8).collect();
+                let mut in_out = input.clone();
+                key.crypt(Direction::Encrypt, &tweak, &mut in_out).unwrap();
+                assert_ne!(input, in_out);
+                key.crypt(Direction::Decrypt, &tweak, &mut in_out).unwrap();
+                assert_eq!(input, in_out);
+            }
+        }
+    }
+}
@@ -0,0 +1,268 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Runtime CPU capability introspection.
+//!
+//! [`features`] reports the CPU capabilities relevant to the cryptographic implementations, and
+//! which implementation variant *AWS-LC* selects for AES, GHASH (AES-GCM) and SHA-2 on this
+//! machine.
+//!
+//! Whether *AWS-LC* was built with assembly and whether it uses AES hardware acceleration are
+//! reported by *AWS-LC* itself. The remaining capability flags are detected at runtime by the Rust
+//! standard library; *AWS-LC* uses an accelerated path when it was built with assembly and the
+//! corresponding capability is present.
+//!
+//! # Example
+//!
+//! ```rust
+//! use aws_lc_rs::cpu::{self, Implementation};
+//!
+//! let features = cpu::features();
+//! if features.aes_implementation() == Implementation::Hardware {
+//!     println!("aes: {}, gcm: {}", features.aes_implementation(), features.gcm_implementation());
+//! }
+//! ```
+
+use aws_lc::{CRYPTO_has_asm, EVP_has_aes_hardware};
+use core::fmt;
+
+/// The implementation variant selected for an algorithm.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Implementation {
+    /// Dedicated CPU instructions, e.g. AES-NI, PCLMULQDQ, SHA-NI or the ARMv8 Cryptography
+    /// Extensions.
+    Hardware,
+
+    /// Assembly using general purpose vector instructions, e.g. SSSE3 or NEON.
+    Assembly,
+
+    /// Portable C.
+    Generic,
+}
+
+impl fmt::Display for Implementation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Implementation::Hardware => "hw",
+            Implementation::Assembly => "asm",
+            Implementation::Generic => "generic",
+        })
+    }
+}
+
+/// CPU capabilities relevant to the cryptographic implementations.
+///
+/// Capabilities that do not exist on the current architecture are always `false`.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CpuFeatures {
+    asm: bool,
+    aes_hardware: bool,
+    ssse3: bool,
+    aes_ni: bool,
+    pclmulqdq: bool,
+    sha_ni: bool,
+    avx: bool,
+    avx2: bool,
+    avx512f: bool,
+    neon: bool,
+    arm_aes: bool,
+    arm_pmull: bool,
+    arm_sha2: bool,
+}
+
+/// Returns the CPU capabilities of this machine and the implementations *AWS-LC* selects.
+#[must_use]
+pub fn features() -> CpuFeatures {
+    crate::init();
+    // Only mutated on architectures with capabilities to detect.
+    #[allow(unused_mut)]
+    let mut features = CpuFeatures {
+        asm: 1 == unsafe { CRYPTO_has_asm() },
+        aes_hardware: 1 == unsafe { EVP_has_aes_hardware() },
+        ssse3: false,
+        aes_ni: false,
+        pclmulqdq: false,
+        sha_ni: false,
+        avx: false,
+        avx2: false,
+        avx512f: false,
+        neon: false,
+        arm_aes: false,
+        arm_pmull: false,
+        arm_sha2: false,
+    };
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        features.ssse3 = std::arch::is_x86_feature_detected!("ssse3");
+        features.aes_ni = std::arch::is_x86_feature_detected!("aes");
+        features.pclmulqdq = std::arch::is_x86_feature_detected!("pclmulqdq");
+        features.sha_ni = std::arch::is_x86_feature_detected!("sha");
+        features.avx = std::arch::is_x86_feature_detected!("avx");
+        features.avx2 = std::arch::is_x86_feature_detected!("avx2");
+        features.avx512f = std::arch::is_x86_feature_detected!("avx512f");
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        features.neon = std::arch::is_aarch64_feature_detected!("neon");
+        features.arm_aes = std::arch::is_aarch64_feature_detected!("aes");
+        features.arm_pmull = std::arch::is_aarch64_feature_detected!("pmull");
+        features.arm_sha2 = std::arch::is_aarch64_feature_detected!("sha2");
+    }
+
+    features
+}
+
+impl CpuFeatures {
+    /// Whether *AWS-LC* was built with assembly implementations.
+    #[must_use]
+    pub fn asm(&self) -> bool {
+        self.asm
+    }
+
+    /// Whether *AWS-LC* uses AES hardware acceleration (AES-NI and PCLMULQDQ on x86, or the
+    /// AES and PMULL instructions on ARMv8).
+    #[must_use]
+    pub fn aes_hardware(&self) -> bool {
+        self.aes_hardware
+    }
+
+    /// x86: SSSE3.
+    #[must_use]
+    pub fn ssse3(&self) -> bool {
+        self.ssse3
+    }
+
+    /// x86: AES-NI.
+    #[must_use]
+    pub fn aes_ni(&self) -> bool {
+        self.aes_ni
+    }
+
+    /// x86: PCLMULQDQ carry-less multiplication.
+    #[must_use]
+    pub fn pclmulqdq(&self) -> bool {
+        self.pclmulqdq
+    }
+
+    /// x86: SHA extensions (SHA-NI).
+    #[must_use]
+    pub fn sha_ni(&self) -> bool {
+        self.sha_ni
+    }
+
+    /// x86: AVX.
+    #[must_use]
+    pub fn avx(&self) -> bool {
+        self.avx
+    }
+
+    /// x86: AVX2.
+    #[must_use]
+    pub fn avx2(&self) -> bool {
+        self.avx2
+    }
+
+    /// x86: AVX-512 Foundation.
+    #[must_use]
+    pub fn avx512f(&self) -> bool {
+        self.avx512f
+    }
+
+    /// ARMv8: Advanced SIMD (NEON).
+    #[must_use]
+    pub fn neon(&self) -> bool {
+        self.neon
+    }
+
+    /// ARMv8 Cryptography Extensions: AES instructions.
+    #[must_use]
+    pub fn arm_aes(&self) -> bool {
+        self.arm_aes
+    }
+
+    /// ARMv8 Cryptography Extensions: PMULL polynomial multiplication.
+    #[must_use]
+    pub fn arm_pmull(&self) -> bool {
+        self.arm_pmull
+    }
+
+    /// ARMv8 Cryptography Extensions: SHA-256 instructions.
+    #[must_use]
+    pub fn arm_sha2(&self) -> bool {
+        self.arm_sha2
+    }
+
+    /// The AES block cipher implementation.
+    #[must_use]
+    pub fn aes_implementation(&self) -> Implementation {
+        if self.aes_hardware {
+            Implementation::Hardware
+        } else if self.asm && (self.ssse3 || self.neon) {
+            Implementation::Assembly
+        } else {
+            Implementation::Generic
+        }
+    }
+
+    /// The GHASH implementation used by AES-GCM.
+    #[must_use]
+    pub fn gcm_implementation(&self) -> Implementation {
+        if !self.asm {
+            Implementation::Generic
+        } else if self.pclmulqdq || self.arm_pmull {
+            Implementation::Hardware
+        } else if self.ssse3 || self.neon {
+            Implementation::Assembly
+        } else {
+            Implementation::Generic
+        }
+    }
+
+    /// The SHA-256 implementation.
+    #[must_use]
+    pub fn sha_implementation(&self) -> Implementation {
+        if !self.asm {
+            Implementation::Generic
+        } else if self.sha_ni || self.arm_sha2 {
+            Implementation::Hardware
+        } else {
+            Implementation::Assembly
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cpu::{features, Implementation};
+
+    #[test]
+    fn test_features_consistent() {
+        let first = features();
+        assert_eq!(first, features());
+
+        if first.aes_hardware() {
+            assert_eq!(Implementation::Hardware, first.aes_implementation());
+        }
+        if !first.asm() {
+            assert_eq!(Implementation::Generic, first.aes_implementation());
+            assert_eq!(Implementation::Generic, first.gcm_implementation());
+            assert_eq!(Implementation::Generic, first.sha_implementation());
+        }
+
+        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+        assert!(!(first.ssse3() || first.aes_ni() || first.pclmulqdq() || first.avx2()));
+        #[cfg(not(target_arch = "aarch64"))]
+        assert!(!(first.neon() || first.arm_aes() || first.arm_pmull() || first.arm_sha2()));
+    }
+
+    #[test]
+    fn test_implementation_display() {
+        assert_eq!("hw", Implementation::Hardware.to_string());
+        assert_eq!("asm", Implementation::Assembly.to_string());
+        assert_eq!("generic", Implementation::Generic.to_string());
+    }
+}
@@ -33,6 +33,7 @@ use crate::{debug, derive_debug_via_id};
 
 pub(crate) mod digest_ctx;
 mod sha;
+pub mod typed;
 use crate::error::Unspecified;
 use crate::ptr::ConstPointer;
 use aws_lc::{
@@ -204,6 +205,25 @@ pub fn digest(algorithm: &'static Algorithm, data: &[u8]) -> Digest {
     }
 }
 
+/// Returns the digest of `data` as a [`typed::Output`] bound to the algorithm `A`.
+///
+/// ```
+/// use aws_lc_rs::digest;
+/// use aws_lc_rs::digest::typed::Sha256;
+///
+/// let output = digest::digest_typed::<Sha256>(b"hello, world");
+/// let bytes: &[u8; 32] = output.as_bytes();
+///
+/// assert_eq!(digest::digest(&digest::SHA256, b"hello, world").as_ref(), bytes);
+/// ```
+#[inline]
+#[must_use]
+pub fn digest_typed<A: typed::DigestAlgorithm>(data: &[u8]) -> typed::Output<A> {
+    let mut value = A::zeroed();
+    (A::algorithm().one_shot_hash)(data, value.as_mut());
+    typed::Output::new(value)
+}
+
 /// A calculated digest value.
 ///
 /// Use [`Self::as_ref`] to get the value as a `&[u8]`.
@@ -240,6 +260,45 @@ impl core::fmt::Debug for Digest {
     }
 }
 
+/// Serialized as the algorithm name (e.g. `"SHA256"`) and the digest value.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Digest {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::serialization::serialize_with_algorithm(
+            "Digest",
+            self.algorithm.id.name(),
+            self.as_ref(),
+            serializer,
+        )
+    }
+}
+
+/// Rejects unknown algorithms and values whose length differs from the algorithm's output length.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Digest {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let (name, value) =
+            crate::serialization::deserialize_with_algorithm("Digest", deserializer)?;
+        let algorithm = AlgorithmID::from_name(&name)
+            .ok_or_else(|| D::Error::custom("unknown digest algorithm"))?;
+        if value.len() != algorithm.output_len {
+            return Err(D::Error::invalid_length(
+                value.len(),
+                &"the output length of the digest algorithm",
+            ));
+        }
+        let mut digest_msg = [0u8; MAX_OUTPUT_LEN];
+        digest_msg[..value.len()].copy_from_slice(&value);
+        Ok(Digest {
+            digest_msg,
+            digest_len: value.len(),
+            algorithm,
+        })
+    }
+}
+
 /// A digest algorithm.
 pub struct Algorithm {
     /// The length of a finalized digest.
@@ -317,6 +376,38 @@ pub(crate) enum AlgorithmID {
     SHA3_512,
 }
 
+#[cfg(feature = "serde")]
+impl AlgorithmID {
+    fn name(&self) -> &'static str {
+        match self {
+            AlgorithmID::SHA1 => "SHA1",
+            AlgorithmID::SHA224 => "SHA224",
+            AlgorithmID::SHA256 => "SHA256",
+            AlgorithmID::SHA384 => "SHA384",
+            AlgorithmID::SHA512 => "SHA512",
+            AlgorithmID::SHA512_256 => "SHA512_256",
+            AlgorithmID::SHA3_256 => "SHA3_256",
+            AlgorithmID::SHA3_384 => "SHA3_384",
+            AlgorithmID::SHA3_512 => "SHA3_512",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<&'static Algorithm> {
+        Some(match name {
+            "SHA1" => &SHA1_FOR_LEGACY_USE_ONLY,
+            "SHA224" => &SHA224,
+            "SHA256" => &SHA256,
+            "SHA384" => &SHA384,
+            "SHA512" => &SHA512,
+            "SHA512_256" => &SHA512_256,
+            "SHA3_256" => &SHA3_256,
+            "SHA3_384" => &SHA3_384,
+            "SHA3_512" => &SHA3_512,
+            _ => return None,
+        })
+    }
+}
+
 impl PartialEq for Algorithm {
     fn eq(&self, other: &Self) -> bool {
         self.id == other.id
@@ -0,0 +1,210 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Digest outputs whose algorithm and length are part of their type.
+//!
+//! [`Output<A>`] holds the value as an array of the length required by the digest algorithm `A`,
+//! so it can be stored in a fixed-size field without a fallible conversion, and outputs of
+//! different algorithms with the same length (e.g. SHA-256 and SHA-512/256) are distinct types.
+//! Typed outputs are computed with [`digest_typed`](super::digest_typed) and convert to and from
+//! [`Digest`].
+//!
+//! ```rust
+//! use aws_lc_rs::digest;
+//! use aws_lc_rs::digest::typed::{Output, Sha256};
+//!
+//! let output: Output<Sha256> = digest::digest_typed(b"hello, world");
+//! let bytes: [u8; 32] = output.into_bytes();
+//!
+//! assert_eq!(digest::digest(&digest::SHA256, b"hello, world").as_ref(), &bytes);
+//! ```
+
+use super::sha::{SHA3_256_OUTPUT_LEN, SHA3_384_OUTPUT_LEN, SHA3_512_OUTPUT_LEN};
+use super::{
+    Algorithm, Digest, MAX_OUTPUT_LEN, SHA1_FOR_LEGACY_USE_ONLY, SHA1_OUTPUT_LEN, SHA224,
+    SHA224_OUTPUT_LEN, SHA256, SHA256_OUTPUT_LEN, SHA384, SHA384_OUTPUT_LEN, SHA3_256, SHA3_384,
+    SHA3_512, SHA512, SHA512_256, SHA512_256_OUTPUT_LEN, SHA512_OUTPUT_LEN,
+};
+use crate::debug;
+use crate::error::Unspecified;
+use crate::sealed::Sealed;
+use core::fmt::Debug;
+use core::hash::Hash;
+use core::marker::PhantomData;
+
+/// A digest algorithm marker type.
+pub trait DigestAlgorithm: Sealed + Debug + 'static {
+    /// The output, as an array of the length produced by the algorithm.
+    type Bytes: AsRef<[u8]> + AsMut<[u8]> + Copy + Eq + Hash;
+
+    /// The dynamic algorithm this marker stands for.
+    fn algorithm() -> &'static Algorithm;
+
+    #[doc(hidden)]
+    fn zeroed() -> Self::Bytes;
+}
+
+macro_rules! digest_algorithm {
+    ($name:ident, $algorithm:ident, $output_len:ident, $doc:literal) => {
+        #[doc = $doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct $name;
+
+        impl Sealed for $name {}
+
+        impl DigestAlgorithm for $name {
+            type Bytes = [u8; $output_len];
+
+            fn algorithm() -> &'static Algorithm {
+                &$algorithm
+            }
+
+            fn zeroed() -> Self::Bytes {
+                [0u8; $output_len]
+            }
+        }
+    };
+}
+
+digest_algorithm!(
+    Sha1ForLegacyUseOnly,
+    SHA1_FOR_LEGACY_USE_ONLY,
+    SHA1_OUTPUT_LEN,
+    "SHA-1. Deprecated."
+);
+digest_algorithm!(Sha224, SHA224, SHA224_OUTPUT_LEN, "SHA-224.");
+digest_algorithm!(Sha256, SHA256, SHA256_OUTPUT_LEN, "SHA-256.");
+digest_algorithm!(Sha384, SHA384, SHA384_OUTPUT_LEN, "SHA-384.");
+digest_algorithm!(Sha512, SHA512, SHA512_OUTPUT_LEN, "SHA-512.");
+digest_algorithm!(
+    Sha512_256,
+    SHA512_256,
+    SHA512_256_OUTPUT_LEN,
+    "SHA-512/256."
+);
+digest_algorithm!(Sha3_256, SHA3_256, SHA3_256_OUTPUT_LEN, "SHA3-256.");
+digest_algorithm!(Sha3_384, SHA3_384, SHA3_384_OUTPUT_LEN, "SHA3-384.");
+digest_algorithm!(Sha3_512, SHA3_512, SHA3_512_OUTPUT_LEN, "SHA3-512.");
+
+/// A digest value calculated with the algorithm `A`.
+pub struct Output<A: DigestAlgorithm> {
+    value: A::Bytes,
+    algorithm: PhantomData<A>,
+}
+
+impl<A: DigestAlgorithm> Output<A> {
+    /// Constructs an `Output` from a previously calculated digest value.
+    #[must_use]
+    pub fn new(value: A::Bytes) -> Self {
+        Self {
+            value,
+            algorithm: PhantomData,
+        }
+    }
+
+    /// The algorithm that was used to calculate the digest value.
+    #[must_use]
+    pub fn algorithm(&self) -> &'static Algorithm {
+        A::algorithm()
+    }
+
+    /// Provides the digest value.
+    #[must_use]
+    pub fn as_bytes(&self) -> &A::Bytes {
+        &self.value
+    }
+
+    /// Returns the digest value.
+    #[must_use]
+    pub fn into_bytes(self) -> A::Bytes {
+        self.value
+    }
+}
+
+impl<A: DigestAlgorithm> AsRef<[u8]> for Output<A> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.value.as_ref()
+    }
+}
+
+impl<A: DigestAlgorithm> Clone for Output<A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A: DigestAlgorithm> Copy for Output<A> {}
+
+impl<A: DigestAlgorithm> PartialEq for Output<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<A: DigestAlgorithm> Eq for Output<A> {}
+
+impl<A: DigestAlgorithm> Hash for Output<A> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl<A: DigestAlgorithm> Debug for Output<A> {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(fmt, "{:?}:", A::algorithm())?;
+        debug::write_hex_bytes(fmt, self.as_ref())
+    }
+}
+
+impl<A: DigestAlgorithm> From<Output<A>> for Digest {
+    fn from(output: Output<A>) -> Self {
+        let value = output.as_ref();
+        let mut digest_msg = [0u8; MAX_OUTPUT_LEN];
+        digest_msg[..value.len()].copy_from_slice(value);
+        Digest {
+            digest_msg,
+            digest_len: value.len(),
+            algorithm: A::algorithm(),
+        }
+    }
+}
+
+impl<A: DigestAlgorithm> TryFrom<Digest> for Output<A> {
+    type Error = Unspecified;
+
+    /// # Errors
+    /// `error::Unspecified` if `digest` was not calculated with the algorithm `A`.
+    fn try_from(digest: Digest) -> Result<Self, Unspecified> {
+        if digest.algorithm() != A::algorithm() {
+            return Err(Unspecified);
+        }
+        let mut value = A::zeroed();
+        value.as_mut().copy_from_slice(digest.as_ref());
+        Ok(Self::new(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Output, Sha256, Sha384, Sha3_256, Sha512_256};
+    use crate::digest;
+
+    #[test]
+    fn test_typed_digest_conversions() {
+        let output: Output<Sha256> = digest::digest_typed(b"hello, world");
+        let digest = digest::digest(&digest::SHA256, b"hello, world");
+        assert_eq!(digest.as_ref(), output.as_ref());
+        assert_eq!(&digest::SHA256, output.algorithm());
+        assert_eq!(format!("{digest:?}"), format!("{output:?}"));
+
+        let converted = digest::Digest::from(output);
+        assert_eq!(digest.algorithm(), converted.algorithm());
+        assert_eq!(digest.as_ref(), converted.as_ref());
+        assert_eq!(output, Output::<Sha256>::try_from(converted).unwrap());
+
+        assert!(Output::<Sha512_256>::try_from(digest).is_err());
+        assert!(Output::<Sha3_256>::try_from(digest).is_err());
+        assert!(Output::<Sha384>::try_from(digest).is_err());
+    }
+}
@@ -172,6 +172,41 @@ impl AsRef<[u8]> for PublicKey {
     }
 }
 
+impl PublicKey {
+    /// Constructs a public key from its affine coordinates, as big-endian integers.
+    ///
+    /// Each coordinate may be shorter than the field size, in which case it is zero-padded.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if a coordinate is longer than the field size or the point is not on
+    /// the curve of `algorithm`.
+    pub fn from_affine_coordinates(
+        algorithm: &'static EcdsaSigningAlgorithm,
+        x: &[u8],
+        y: &[u8],
+    ) -> Result<Self, KeyRejected> {
+        let mut buffer = [0u8; PUBLIC_KEY_MAX_LEN];
+        let len = ec_point_from_affine_coordinates(
+            algorithm.id.nid(),
+            algorithm.id.private_key_size(),
+            x,
+            y,
+            &mut buffer,
+        )?;
+        Ok(PublicKey {
+            algorithm,
+            octets: buffer[..len].into(),
+        })
+    }
+
+    /// The affine coordinates `(x, y)` of the public key, as big-endian integers zero-padded to
+    /// the field size.
+    #[must_use]
+    pub fn to_affine_coordinates(&self) -> (Vec<u8>, Vec<u8>) {
+        affine_coordinates_from_uncompressed(&self.octets)
+    }
+}
+
 unsafe impl Send for PublicKey {}
 unsafe impl Sync for PublicKey {}
 
@@ -548,6 +583,39 @@ pub(crate) fn ec_point_from_bytes(
     Ok(ec_point)
 }
 
+/// Encodes the affine coordinates `x` and `y`, big-endian integers of at most `field_len` bytes,
+/// as an uncompressed point after checking that it is on the curve `nid`.
+pub(crate) fn ec_point_from_affine_coordinates(
+    nid: i32,
+    field_len: usize,
+    x: &[u8],
+    y: &[u8],
+    buffer: &mut [u8; PUBLIC_KEY_MAX_LEN],
+) -> Result<usize, KeyRejected> {
+    if x.len() > field_len || y.len() > field_len {
+        return Err(KeyRejected::invalid_encoding());
+    }
+    let out_len = 1 + 2 * field_len;
+    buffer[..out_len].fill(0);
+    buffer[0] = 0x04;
+    buffer[1 + field_len - x.len()..=field_len].copy_from_slice(x);
+    buffer[out_len - y.len()..out_len].copy_from_slice(y);
+
+    let ec_group = ec_group_from_nid(nid)?;
+    ec_point_from_bytes(&ec_group, &buffer[..out_len])
+        .map_err(|Unspecified| KeyRejected::invalid_encoding())?;
+    Ok(out_len)
+}
+
+/// Splits an uncompressed point into its affine coordinates.
+pub(crate) fn affine_coordinates_from_uncompressed(octets: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let field_len = (octets.len() - 1) / 2;
+    (
+        octets[1..=field_len].to_vec(),
+        octets[1 + field_len..].to_vec(),
+    )
+}
+
 #[inline]
 unsafe fn ec_point_to_bytes(
     ec_group: &ConstPointer<EC_GROUP>,
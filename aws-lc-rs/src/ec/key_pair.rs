@@ -7,6 +7,7 @@ use core::fmt;
 use core::fmt::{Debug, Formatter};
 use core::mem::MaybeUninit;
 use core::ptr::{null, null_mut};
+use zeroize::ZeroizeOnDrop;
 
 use aws_lc::{EVP_DigestSign, EVP_DigestSignInit, EVP_PKEY_get0_EC_KEY, EVP_PKEY};
 
@@ -27,6 +28,8 @@ use crate::signature::{KeyPair, Signature};
 use crate::{digest, ec};
 
 /// An ECDSA key pair, used for signing.
+///
+/// The private key is freed and zeroized by *AWS-LC* when dropped.
 #[allow(clippy::module_name_repetitions)]
 pub struct EcdsaKeyPair {
     algorithm: &'static EcdsaSigningAlgorithm,
@@ -40,6 +43,8 @@ impl Debug for EcdsaKeyPair {
     }
 }
 
+impl ZeroizeOnDrop for EcdsaKeyPair {}
+
 unsafe impl Send for EcdsaKeyPair {}
 
 unsafe impl Sync for EcdsaKeyPair {}
@@ -10,7 +10,7 @@ use core::ptr::null_mut;
 
 #[cfg(feature = "ring-sig-verify")]
 use untrusted::Input;
-use zeroize::Zeroize;
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 use aws_lc::{
     ED25519_keypair_from_seed, ED25519_sign, ED25519_verify, EVP_PKEY_CTX_new_id,
@@ -79,6 +79,8 @@ impl VerificationAlgorithm for EdDSAParameters {
 }
 
 /// An Ed25519 key pair, for signing.
+///
+/// The private key is zeroized when dropped.
 #[allow(clippy::module_name_repetitions)]
 pub struct Ed25519KeyPair {
     private_key: Box<[u8; ED25519_PRIVATE_KEY_LEN]>,
@@ -100,6 +102,8 @@ impl Drop for Ed25519KeyPair {
     }
 }
 
+impl ZeroizeOnDrop for Ed25519KeyPair {}
+
 #[derive(Clone)]
 #[allow(clippy::module_name_repetitions)]
 /// The seed value for the `EdDSA` signature scheme using Curve25519
@@ -135,6 +139,26 @@ impl AsRef<[u8]> for PublicKey {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for PublicKey {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::serialization::serialize_bytes(&self.0, serializer)
+    }
+}
+
+/// Rejects values that are not exactly `ED25519_PUBLIC_KEY_LEN` bytes.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PublicKey {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let value = crate::serialization::deserialize_bytes(deserializer)?;
+        let key = <[u8; ED25519_PUBLIC_KEY_LEN]>::try_from(value.as_slice())
+            .map_err(|_| D::Error::invalid_length(value.len(), &"an Ed25519 public key"))?;
+        Ok(PublicKey(key))
+    }
+}
+
 impl Debug for PublicKey {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.write_str(&format!("PublicKey(\"{}\")", hex::encode(self.0)))
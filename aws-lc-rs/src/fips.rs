@@ -1,6 +1,100 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
+//! FIPS 140-3 service indicator and self-tests.
+//!
+//! [`self_test`] runs the known-answer self-tests on demand, for example at application startup
+//! or on a schedule.
+//!
+//! When built with the `fips` feature, *AWS-LC* tracks whether each cryptographic service it
+//! performs is approved. [`approved`] exposes this "service indicator" so that applications can
+//! determine whether an operation used an approved algorithm in an approved manner.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM};
+//! use aws_lc_rs::fips::{self, FipsIndicator};
+//!
+//! let key = LessSafeKey::new(UnboundKey::new(&AES_256_GCM, &[0u8; 32])?);
+//! let mut in_out = b"hello".to_vec();
+//!
+//! let (result, indicator) = fips::approved(|| {
+//!     key.seal_in_place_append_tag(
+//!         Nonce::assume_unique_for_key([0u8; 12]),
+//!         Aad::empty(),
+//!         &mut in_out,
+//!     )
+//! });
+//! result?;
+//!
+//! if cfg!(feature = "fips") {
+//!     assert_eq!(FipsIndicator::Approved, indicator);
+//! } else {
+//!     assert_eq!(FipsIndicator::NonApproved, indicator);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+mod self_test;
+
+pub use self_test::{self_test, SelfTestFailure, SelfTestFamily, SelfTestReport, SelfTestResult};
+
+/// The service indicator status of an operation.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FipsIndicator {
+    /// The operation used an approved algorithm in an approved manner.
+    Approved,
+
+    /// The operation was not approved, or the library was not built with the `fips` feature.
+    NonApproved,
+}
+
+impl FipsIndicator {
+    /// Returns `true` if the operation was approved.
+    #[must_use]
+    pub fn is_approved(&self) -> bool {
+        matches!(self, FipsIndicator::Approved)
+    }
+}
+
+/// Performs `operation` and reports whether it was an approved FIPS service.
+///
+/// `operation` should perform exactly one cryptographic service, such as a single
+/// `seal_in_place_append_tag`, `digest`, `hmac::sign`, signature, or key derivation call. An
+/// operation is reported as approved if *AWS-LC* marked any service performed by `operation` as
+/// approved, so combining an approved and a non-approved service in a single closure is reported
+/// as [`FipsIndicator::Approved`].
+///
+/// Without the `fips` feature every operation is reported as [`FipsIndicator::NonApproved`].
+///
+/// The indicator is tracked per thread; `operation` must not move its work to another thread.
+pub fn approved<R, F>(operation: F) -> (R, FipsIndicator)
+where
+    F: FnOnce() -> R,
+{
+    #[cfg(feature = "fips")]
+    {
+        let before = service_indicator_before_call();
+        let result = operation();
+        let after = service_indicator_after_call();
+        let indicator = if before == after {
+            FipsIndicator::NonApproved
+        } else {
+            FipsIndicator::Approved
+        };
+        (result, indicator)
+    }
+    #[cfg(not(feature = "fips"))]
+    {
+        (operation(), FipsIndicator::NonApproved)
+    }
+}
+
 /// Retrieve the FIPS module service status.
 #[allow(dead_code)] // appease clippy
 #[cfg(all(feature = "fips", debug_assertions))]
@@ -49,13 +143,13 @@ pub(crate) mod indicator {
     }
 }
 
-#[cfg(all(feature = "fips", debug_assertions))]
+#[cfg(feature = "fips")]
 #[inline]
 pub(crate) fn service_indicator_before_call() -> u64 {
     unsafe { aws_lc::FIPS_service_indicator_before_call() }
 }
 
-#[cfg(all(feature = "fips", debug_assertions))]
+#[cfg(feature = "fips")]
 #[inline]
 pub(crate) fn service_indicator_after_call() -> u64 {
     unsafe { aws_lc::FIPS_service_indicator_after_call() }
@@ -155,6 +249,127 @@ pub(crate) use assert_fips_status_indicator;
 
 #[cfg(test)]
 mod tests {
+    use crate::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, CHACHA20_POLY1305};
+    use crate::fips::{approved, FipsIndicator};
+    use crate::{digest, hmac};
+
+    fn seal(key: &LessSafeKey) -> FipsIndicator {
+        let mut in_out = vec![0u8; 32];
+        let (result, indicator) = approved(|| {
+            key.seal_in_place_append_tag(
+                Nonce::assume_unique_for_key([0u8; 12]),
+                Aad::empty(),
+                &mut in_out,
+            )
+        });
+        result.unwrap();
+        indicator
+    }
+
+    #[cfg(feature = "fips")]
+    #[test]
+    fn test_approved() {
+        use crate::hkdf;
+
+        let key = LessSafeKey::new(UnboundKey::new(&AES_256_GCM, &[0u8; 32]).unwrap());
+        assert_eq!(FipsIndicator::Approved, seal(&key));
+
+        let (_, indicator) = approved(|| digest::digest(&digest::SHA256, b"data"));
+        assert_eq!(FipsIndicator::Approved, indicator);
+
+        let key = hmac::Key::new(hmac::HMAC_SHA256, &[0u8; 32]);
+        let (_, indicator) = approved(|| hmac::sign(&key, b"data"));
+        assert!(indicator.is_approved());
+
+        let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, &[0u8; 32]).extract(&[1u8; 32]);
+        let mut okm = [0u8; 32];
+        let (result, indicator) = approved(|| {
+            prk.expand(&[b"info"], hkdf::HKDF_SHA256)
+                .and_then(|okm_material| okm_material.fill(&mut okm))
+        });
+        result.unwrap();
+        assert_eq!(FipsIndicator::Approved, indicator);
+    }
+
+    #[cfg(feature = "fips")]
+    #[test]
+    fn test_approved_cipher() {
+        use crate::cipher::{
+            EncryptingKey, PaddedBlockEncryptingKey, UnboundCipherKey, AES_128, AES_256,
+        };
+
+        let key = UnboundCipherKey::new(&AES_128, &[0u8; 16]).unwrap();
+        let key = PaddedBlockEncryptingKey::cbc_pkcs7(key).unwrap();
+        let mut in_out = vec![0u8; 32];
+        let (result, indicator) = approved(|| key.encrypt(&mut in_out));
+        result.unwrap();
+        assert_eq!(FipsIndicator::Approved, indicator);
+
+        let key = UnboundCipherKey::new(&AES_256, &[0u8; 32]).unwrap();
+        let key = EncryptingKey::ctr(key).unwrap();
+        let mut in_out = [0u8; 32];
+        let (result, indicator) = approved(|| key.encrypt(&mut in_out));
+        result.unwrap();
+        assert_eq!(FipsIndicator::Approved, indicator);
+    }
+
+    #[cfg(feature = "fips")]
+    #[test]
+    fn test_approved_signature() {
+        use crate::rand::SystemRandom;
+        use crate::signature::{
+            EcdsaKeyPair, Ed25519KeyPair, KeyPair, UnparsedPublicKey, ECDSA_P256_SHA256_ASN1,
+            ECDSA_P256_SHA256_ASN1_SIGNING, ED25519,
+        };
+
+        let rng = SystemRandom::new();
+        let message = b"data";
+
+        let key_pair = EcdsaKeyPair::generate(&ECDSA_P256_SHA256_ASN1_SIGNING).unwrap();
+        let (signature, indicator) = approved(|| key_pair.sign(&rng, message));
+        let signature = signature.unwrap();
+        assert_eq!(FipsIndicator::Approved, indicator);
+        let public_key = UnparsedPublicKey::new(&ECDSA_P256_SHA256_ASN1, key_pair.public_key());
+        let (result, indicator) = approved(|| public_key.verify(message, signature.as_ref()));
+        result.unwrap();
+        assert_eq!(FipsIndicator::Approved, indicator);
+
+        // The AWS-LC-FIPS 2.x module does not approve Ed25519.
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&rng).unwrap();
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let (signature, indicator) = approved(|| key_pair.sign(message));
+        assert_eq!(FipsIndicator::NonApproved, indicator);
+        let public_key = UnparsedPublicKey::new(&ED25519, key_pair.public_key());
+        let (result, indicator) = approved(|| public_key.verify(message, signature.as_ref()));
+        result.unwrap();
+        assert_eq!(FipsIndicator::NonApproved, indicator);
+    }
+
+    #[cfg(feature = "fips")]
+    #[test]
+    fn test_non_approved() {
+        let key = LessSafeKey::new(UnboundKey::new(&CHACHA20_POLY1305, &[0u8; 32]).unwrap());
+        assert_eq!(FipsIndicator::NonApproved, seal(&key));
+
+        let ((), indicator) = approved(|| ());
+        assert_eq!(FipsIndicator::NonApproved, indicator);
+    }
+
+    #[cfg(not(feature = "fips"))]
+    #[test]
+    fn test_non_fips_never_approved() {
+        for algorithm in [&AES_256_GCM, &CHACHA20_POLY1305] {
+            let key = LessSafeKey::new(UnboundKey::new(algorithm, &[0u8; 32]).unwrap());
+            assert_eq!(FipsIndicator::NonApproved, seal(&key));
+        }
+
+        let (_, indicator) = approved(|| digest::digest(&digest::SHA256, b"data"));
+        assert!(!indicator.is_approved());
+
+        let key = hmac::Key::new(hmac::HMAC_SHA256, &[0u8; 32]);
+        let (_, indicator) = approved(|| hmac::sign(&key, b"data"));
+        assert!(!indicator.is_approved());
+    }
 
     #[cfg(all(feature = "fips", debug_assertions))]
     #[test]
@@ -0,0 +1,367 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use crate::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_128_GCM};
+use crate::cipher::{EncryptingKey, EncryptionContext, UnboundCipherKey, AES_128};
+use crate::error::Unspecified;
+use crate::{constant_time, digest, hkdf, hmac, pbkdf2, signature};
+use aws_lc::BORINGSSL_self_test;
+use core::fmt;
+use core::num::NonZeroU32;
+use std::error::Error;
+use std::time::{Duration, Instant};
+
+/// A family of algorithms covered by [`self_test`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SelfTestFamily {
+    /// The *AWS-LC* module's own known-answer self-tests.
+    Module,
+    /// Message digests (SHA-256).
+    Digest,
+    /// HMAC (HMAC-SHA256).
+    Hmac,
+    /// Authenticated encryption (AES-128-GCM).
+    Aead,
+    /// Unauthenticated block cipher modes (AES-128-CTR).
+    Cipher,
+    /// HKDF (HKDF-SHA256).
+    Hkdf,
+    /// PBKDF2 (PBKDF2-HMAC-SHA256).
+    Pbkdf2,
+    /// Signature verification (ECDSA P-256 with SHA-256).
+    Signature,
+}
+
+/// The outcome of the self-test for one [`SelfTestFamily`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SelfTestResult {
+    family: SelfTestFamily,
+    passed: bool,
+    duration: Duration,
+}
+
+impl SelfTestResult {
+    /// The algorithm family that was tested.
+    #[must_use]
+    pub fn family(&self) -> SelfTestFamily {
+        self.family
+    }
+
+    /// Whether the self-test passed.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.passed
+    }
+
+    /// How long the self-test took to run.
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+/// The per-family results of a [`self_test`] run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SelfTestReport {
+    results: Vec<SelfTestResult>,
+}
+
+impl SelfTestReport {
+    /// The result of each self-test, in the order they were run.
+    #[must_use]
+    pub fn results(&self) -> &[SelfTestResult] {
+        &self.results
+    }
+
+    /// The result for `family`, if it was tested.
+    #[must_use]
+    pub fn get(&self, family: SelfTestFamily) -> Option<&SelfTestResult> {
+        self.results.iter().find(|result| result.family == family)
+    }
+
+    /// Whether every self-test passed.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(SelfTestResult::passed)
+    }
+
+    /// The total time taken by all self-tests.
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        self.results.iter().map(SelfTestResult::duration).sum()
+    }
+}
+
+/// Returned by [`self_test`] when one or more self-tests fail.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SelfTestFailure {
+    report: SelfTestReport,
+}
+
+impl SelfTestFailure {
+    /// The complete report, including the self-tests that passed.
+    #[must_use]
+    pub fn report(&self) -> &SelfTestReport {
+        &self.report
+    }
+
+    /// The algorithm families whose self-tests failed.
+    pub fn failed(&self) -> impl Iterator<Item = SelfTestFamily> + '_ {
+        self.report
+            .results
+            .iter()
+            .filter(|result| !result.passed)
+            .map(SelfTestResult::family)
+    }
+}
+
+impl fmt::Display for SelfTestFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("self-test failed:")?;
+        for family in self.failed() {
+            write!(f, " {family:?}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for SelfTestFailure {}
+
+/// Runs the cryptographic known-answer self-tests.
+///
+/// Drives the *AWS-LC* module's power-on self-tests, then runs a known-answer test for each
+/// algorithm family exposed by this crate. Every test is run even if an earlier one fails.
+///
+/// # Errors
+/// `SelfTestFailure` if any self-test fails. The contained report identifies which ones.
+pub fn self_test() -> Result<SelfTestReport, SelfTestFailure> {
+    run(&[
+        (SelfTestFamily::Module, module_kat),
+        (SelfTestFamily::Digest, digest_kat),
+        (SelfTestFamily::Hmac, hmac_kat),
+        (SelfTestFamily::Aead, aead_kat),
+        (SelfTestFamily::Cipher, cipher_kat),
+        (SelfTestFamily::Hkdf, hkdf_kat),
+        (SelfTestFamily::Pbkdf2, pbkdf2_kat),
+        (SelfTestFamily::Signature, signature_kat),
+    ])
+}
+
+type Kat = fn() -> Result<(), Unspecified>;
+
+fn run(kats: &[(SelfTestFamily, Kat)]) -> Result<SelfTestReport, SelfTestFailure> {
+    let results = kats
+        .iter()
+        .map(|(family, kat)| {
+            let start = Instant::now();
+            let passed = kat().is_ok();
+            SelfTestResult {
+                family: *family,
+                passed,
+                duration: start.elapsed(),
+            }
+        })
+        .collect();
+    let report = SelfTestReport { results };
+    if report.passed() {
+        Ok(report)
+    } else {
+        Err(SelfTestFailure { report })
+    }
+}
+
+fn module_kat() -> Result<(), Unspecified> {
+    crate::init();
+    if 1 == unsafe { BORINGSSL_self_test() } {
+        Ok(())
+    } else {
+        Err(Unspecified)
+    }
+}
+
+// FIPS 180-2 Appendix B.1
+fn digest_kat() -> Result<(), Unspecified> {
+    const EXPECTED: [u8; 32] = [
+        0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae, 0x22,
+        0x23, 0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61, 0xf2, 0x00,
+        0x15, 0xad,
+    ];
+    let actual = digest::digest(&digest::SHA256, b"abc");
+    constant_time::verify_slices_are_equal(actual.as_ref(), &EXPECTED)
+}
+
+// RFC 4231 Test Case 2
+fn hmac_kat() -> Result<(), Unspecified> {
+    const EXPECTED: [u8; 32] = [
+        0x5b, 0xdc, 0xc1, 0x46, 0xbf, 0x60, 0x75, 0x4e, 0x6a, 0x04, 0x24, 0x26, 0x08, 0x95, 0x75,
+        0xc7, 0x5a, 0x00, 0x3f, 0x08, 0x9d, 0x27, 0x39, 0x83, 0x9d, 0xec, 0x58, 0xb9, 0x64, 0xec,
+        0x38, 0x43,
+    ];
+    let key = hmac::Key::new(hmac::HMAC_SHA256, b"Jefe");
+    hmac::verify(&key, b"what do ya want for nothing?", &EXPECTED)
+}
+
+// GCM specification Test Case 2
+fn aead_kat() -> Result<(), Unspecified> {
+    const CIPHERTEXT: [u8; 16] = [
+        0x03, 0x88, 0xda, 0xce, 0x60, 0xb6, 0xa3, 0x92, 0xf3, 0x28, 0xc2, 0xb9, 0x71, 0xb2, 0xfe,
+        0x78,
+    ];
+    const TAG: [u8; 16] = [
+        0xab, 0x6e, 0x47, 0xd4, 0x2c, 0xec, 0x13, 0xbd, 0xf5, 0x3a, 0x67, 0xb2, 0x12, 0x57, 0xbd,
+        0xdf,
+    ];
+    let key = LessSafeKey::new(UnboundKey::new(&AES_128_GCM, &[0u8; 16])?);
+
+    let mut in_out = [0u8; 16];
+    let tag = key.seal_in_place_separate_tag(
+        Nonce::assume_unique_for_key([0u8; 12]),
+        Aad::empty(),
+        &mut in_out,
+    )?;
+    constant_time::verify_slices_are_equal(&in_out, &CIPHERTEXT)?;
+    constant_time::verify_slices_are_equal(tag.as_ref(), &TAG)?;
+
+    let mut in_out = [0u8; 32];
+    in_out[..16].copy_from_slice(&CIPHERTEXT);
+    in_out[16..].copy_from_slice(&TAG);
+    let plaintext = key.open_in_place(
+        Nonce::assume_unique_for_key([0u8; 12]),
+        Aad::empty(),
+        &mut in_out,
+    )?;
+    constant_time::verify_slices_are_equal(plaintext, &[0u8; 16])
+}
+
+// NIST SP 800-38A F.5.1
+fn cipher_kat() -> Result<(), Unspecified> {
+    const KEY: [u8; 16] = [
+        0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f,
+        0x3c,
+    ];
+    const IV: [u8; 16] = [
+        0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9, 0xfa, 0xfb, 0xfc, 0xfd, 0xfe,
+        0xff,
+    ];
+    const PLAINTEXT: [u8; 16] = [
+        0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17,
+        0x2a,
+    ];
+    const CIPHERTEXT: [u8; 16] = [
+        0x87, 0x4d, 0x61, 0x91, 0xb6, 0x20, 0xe3, 0x26, 0x1b, 0xef, 0x68, 0x64, 0x99, 0x0d, 0xb6,
+        0xce,
+    ];
+    let key = EncryptingKey::ctr(UnboundCipherKey::new(&AES_128, &KEY)?)?;
+    let mut in_out = PLAINTEXT;
+    key.less_safe_encrypt(&mut in_out, EncryptionContext::Iv128(IV.into()))?;
+    constant_time::verify_slices_are_equal(&in_out, &CIPHERTEXT)
+}
+
+// RFC 5869 Test Case 1, truncated to the first 32 bytes of output.
+fn hkdf_kat() -> Result<(), Unspecified> {
+    const SALT: [u8; 13] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c,
+    ];
+    const INFO: [u8; 10] = [0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9];
+    const EXPECTED: [u8; 32] = [
+        0x3c, 0xb2, 0x5f, 0x25, 0xfa, 0xac, 0xd5, 0x7a, 0x90, 0x43, 0x4f, 0x64, 0xd0, 0x36, 0x2f,
+        0x2a, 0x2d, 0x2d, 0x0a, 0x90, 0xcf, 0x1a, 0x5a, 0x4c, 0x5d, 0xb0, 0x2d, 0x56, 0xec, 0xc4,
+        0xc5, 0xbf,
+    ];
+    let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, &SALT).extract(&[0x0b; 22]);
+    let mut okm = [0u8; 32];
+    prk.expand(&[&INFO], hkdf::HKDF_SHA256)?.fill(&mut okm)?;
+    constant_time::verify_slices_are_equal(&okm, &EXPECTED)
+}
+
+// P = "password", S = "salt", c = 1, dkLen = 32
+fn pbkdf2_kat() -> Result<(), Unspecified> {
+    const EXPECTED: [u8; 32] = [
+        0x12, 0x0f, 0xb6, 0xcf, 0xfc, 0xf8, 0xb3, 0x2c, 0x43, 0xe7, 0x22, 0x52, 0x56, 0xc4, 0xf8,
+        0x37, 0xa8, 0x65, 0x48, 0xc9, 0x2c, 0xcc, 0x35, 0x48, 0x08, 0x05, 0x98, 0x7c, 0xb7, 0x0b,
+        0xe1, 0x7b,
+    ];
+    let iterations = NonZeroU32::new(1).ok_or(Unspecified)?;
+    pbkdf2::verify(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        b"salt",
+        b"password",
+        &EXPECTED,
+    )
+}
+
+fn signature_kat() -> Result<(), Unspecified> {
+    const PUBLIC_KEY: [u8; 65] = [
+        0x04, 0x60, 0xfe, 0xd4, 0xba, 0x25, 0x5a, 0x9d, 0x31, 0xc9, 0x61, 0xeb, 0x74, 0xc6, 0x35,
+        0x6d, 0x68, 0xc0, 0x49, 0xb8, 0x92, 0x3b, 0x61, 0xfa, 0x6c, 0xe6, 0x69, 0x62, 0x2e, 0x60,
+        0xf2, 0x9f, 0xb6, 0x79, 0x03, 0xfe, 0x10, 0x08, 0xb8, 0xbc, 0x99, 0xa4, 0x1a, 0xe9, 0xe9,
+        0x56, 0x28, 0xbc, 0x64, 0xf2, 0xf1, 0xb2, 0x0c, 0x2d, 0x7e, 0x9f, 0x51, 0x77, 0xa3, 0xc2,
+        0x94, 0xd4, 0x46, 0x22, 0x99,
+    ];
+    const SIGNATURE: [u8; 64] = [
+        0x2f, 0xfc, 0x2e, 0x53, 0xa1, 0xf3, 0x43, 0xd3, 0x45, 0xe1, 0x43, 0xce, 0x37, 0x6a, 0x31,
+        0x16, 0x02, 0xf7, 0x55, 0xf8, 0x1b, 0x17, 0x4b, 0xae, 0x4f, 0x96, 0x4e, 0x4f, 0xfc, 0xe0,
+        0xf0, 0xf0, 0xe6, 0xe2, 0x61, 0x9e, 0x99, 0x83, 0xdd, 0xed, 0x13, 0x9b, 0xa0, 0x0d, 0x7a,
+        0xd0, 0xa3, 0xf2, 0x57, 0xdd, 0x69, 0xf7, 0x71, 0x00, 0xa4, 0x98, 0x34, 0x08, 0xa2, 0xee,
+        0x6a, 0xc3, 0x73, 0x77,
+    ];
+    let public_key =
+        signature::UnparsedPublicKey::new(&signature::ECDSA_P256_SHA256_FIXED, &PUBLIC_KEY);
+    public_key.verify(b"sample", &SIGNATURE)?;
+
+    let mut tampered = SIGNATURE;
+    tampered[0] ^= 0x01;
+    match public_key.verify(b"sample", &tampered) {
+        Ok(()) => Err(Unspecified),
+        Err(_) => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_test() {
+        let report = self_test().unwrap();
+        assert!(report.passed());
+        let families: Vec<SelfTestFamily> = report
+            .results()
+            .iter()
+            .map(SelfTestResult::family)
+            .collect();
+        assert_eq!(
+            vec![
+                SelfTestFamily::Module,
+                SelfTestFamily::Digest,
+                SelfTestFamily::Hmac,
+                SelfTestFamily::Aead,
+                SelfTestFamily::Cipher,
+                SelfTestFamily::Hkdf,
+                SelfTestFamily::Pbkdf2,
+                SelfTestFamily::Signature,
+            ],
+            families
+        );
+        assert!(report.get(SelfTestFamily::Aead).unwrap().passed());
+    }
+
+    #[test]
+    fn test_failure_reported() {
+        let failure = run(&[
+            (SelfTestFamily::Digest, digest_kat),
+            (SelfTestFamily::Hmac, || Err(Unspecified)),
+            (SelfTestFamily::Aead, aead_kat),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            vec![SelfTestFamily::Hmac],
+            failure.failed().collect::<Vec<_>>()
+        );
+        assert_eq!(3, failure.report().results().len());
+        assert!(failure.report().get(SelfTestFamily::Aead).unwrap().passed());
+        assert_eq!("self-test failed: Hmac", failure.to_string());
+    }
+}
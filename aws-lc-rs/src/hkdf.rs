@@ -43,7 +43,7 @@ use crate::{digest, hmac};
 use alloc::sync::Arc;
 use aws_lc::{HKDF_expand, HKDF};
 use core::fmt;
-use zeroize::Zeroize;
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 /// An HKDF algorithm.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -91,6 +91,8 @@ impl KeyType for Algorithm {
 }
 
 /// A salt for HKDF operations.
+///
+/// The salt bytes are zeroized when dropped.
 pub struct Salt {
     algorithm: Algorithm,
     salt_bytes: [u8; MAX_HKDF_SALT_LEN],
@@ -112,6 +114,8 @@ impl Drop for Salt {
     }
 }
 
+impl ZeroizeOnDrop for Salt {}
+
 impl Salt {
     /// Constructs a new `Salt` with the given value based on the given digest
     /// algorithm.
@@ -267,6 +271,16 @@ impl core::fmt::Debug for PrkMode {
     }
 }
 
+impl Drop for PrkMode {
+    fn drop(&mut self) {
+        match self {
+            PrkMode::Expand { key_bytes, .. } => key_bytes.zeroize(),
+            // The secret is zeroized when the last reference to it is dropped.
+            PrkMode::ExtractExpand { salt, .. } => salt.zeroize(),
+        }
+    }
+}
+
 struct ZeroizeBoxSlice<T: Zeroize>(Box<[T]>);
 
 impl<T: Zeroize> core::ops::Deref for ZeroizeBoxSlice<T> {
@@ -290,6 +304,9 @@ impl<T: Zeroize> Drop for ZeroizeBoxSlice<T> {
 }
 
 /// A HKDF PRK (pseudorandom key).
+///
+/// When dropped, the PRK bytes are zeroized, or, for a PRK whose extraction is deferred, the
+/// salt. The input keying material is zeroized when the last clone sharing it is dropped.
 #[derive(Clone)]
 pub struct Prk {
     algorithm: Algorithm,
@@ -306,6 +323,8 @@ impl fmt::Debug for Prk {
     }
 }
 
+impl ZeroizeOnDrop for Prk {}
+
 impl Prk {
     /// Construct a new `Prk` directly with the given value.
     ///
@@ -95,6 +95,7 @@
 //! ```
 //! [RFC 2104]: https://tools.ietf.org/html/rfc2104
 
+use crate::digest::typed::DigestAlgorithm;
 use crate::error::Unspecified;
 use crate::fips::indicator_check;
 use crate::{constant_time, digest, hkdf};
@@ -102,8 +103,10 @@ use aws_lc::{
     HMAC_CTX_cleanup, HMAC_CTX_copy_ex, HMAC_CTX_init, HMAC_Final, HMAC_Init_ex, HMAC_Update,
     HMAC_CTX,
 };
+use core::marker::PhantomData;
 use core::mem::MaybeUninit;
 use core::ptr::null_mut;
+use zeroize::ZeroizeOnDrop;
 // TODO: Uncomment when MSRV >= 1.64
 // use core::ffi::c_uint;
 use std::os::raw::c_uint;
@@ -165,6 +168,43 @@ impl AsRef<[u8]> for Tag {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Tag {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::serialization::serialize_bytes(self.as_ref(), serializer)
+    }
+}
+
+/// Rejects values whose length is not the output length of a supported HMAC algorithm.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Tag {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let value = crate::serialization::deserialize_bytes(deserializer)?;
+        if ![
+            digest::SHA1_OUTPUT_LEN,
+            digest::SHA224_OUTPUT_LEN,
+            digest::SHA256_OUTPUT_LEN,
+            digest::SHA384_OUTPUT_LEN,
+            digest::SHA512_OUTPUT_LEN,
+        ]
+        .contains(&value.len())
+        {
+            return Err(D::Error::invalid_length(
+                value.len(),
+                &"the output length of an HMAC algorithm",
+            ));
+        }
+        let mut msg = [0u8; digest::MAX_OUTPUT_LEN];
+        msg[..value.len()].copy_from_slice(&value);
+        Ok(Tag {
+            msg,
+            msg_len: value.len(),
+        })
+    }
+}
+
 struct LcHmacCtx(HMAC_CTX);
 
 impl LcHmacCtx {
@@ -201,7 +241,88 @@ impl Clone for LcHmacCtx {
     }
 }
 
+/// An HMAC tag whose digest algorithm and length are part of its type.
+///
+/// Returned by [`sign_typed`]. Use [`Self::as_bytes`] or [`Self::into_bytes`] to get the tag
+/// value as an array of the digest algorithm's output length.
+pub struct TypedTag<A: DigestAlgorithm> {
+    value: A::Bytes,
+    algorithm: PhantomData<A>,
+}
+
+impl<A: DigestAlgorithm> TypedTag<A> {
+    /// Provides the tag value.
+    #[must_use]
+    pub fn as_bytes(&self) -> &A::Bytes {
+        &self.value
+    }
+
+    /// Returns the tag value.
+    #[must_use]
+    pub fn into_bytes(self) -> A::Bytes {
+        self.value
+    }
+}
+
+impl<A: DigestAlgorithm> Clone for TypedTag<A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A: DigestAlgorithm> Copy for TypedTag<A> {}
+
+impl<A: DigestAlgorithm> AsRef<[u8]> for TypedTag<A> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.value.as_ref()
+    }
+}
+
+impl<A: DigestAlgorithm> core::fmt::Debug for TypedTag<A> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TypedTag")
+            .field("algorithm", &A::algorithm())
+            .field("value", &self.as_ref())
+            .finish()
+    }
+}
+
+impl<A: DigestAlgorithm> From<TypedTag<A>> for Tag {
+    fn from(tag: TypedTag<A>) -> Self {
+        let value = tag.as_ref();
+        let mut msg = [0u8; digest::MAX_OUTPUT_LEN];
+        msg[..value.len()].copy_from_slice(value);
+        Tag {
+            msg,
+            msg_len: value.len(),
+        }
+    }
+}
+
+impl<A: DigestAlgorithm> TryFrom<Tag> for TypedTag<A> {
+    type Error = Unspecified;
+
+    /// A `Tag` does not record its algorithm, so only its length can be checked.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the length of `tag` is not the output length of `A`.
+    fn try_from(tag: Tag) -> Result<Self, Unspecified> {
+        if tag.msg_len != A::algorithm().output_len {
+            return Err(Unspecified);
+        }
+        let mut value = A::zeroed();
+        value.as_mut().copy_from_slice(tag.as_ref());
+        Ok(Self {
+            value,
+            algorithm: PhantomData,
+        })
+    }
+}
+
 /// A key to use for HMAC signing.
+///
+/// The key state is cleansed by `HMAC_CTX_cleanup` when dropped.
 //
 // # FIPS
 // Use this type with one of the following algorithms:
@@ -229,6 +350,8 @@ impl core::fmt::Debug for Key {
     }
 }
 
+impl ZeroizeOnDrop for Key {}
+
 impl Key {
     /// Generate an HMAC signing key using the given digest algorithm with a
     /// random value generated from `rng`.
@@ -343,6 +466,8 @@ impl From<hkdf::Okm<'_, Algorithm>> for Key {
 /// A context for multi-step (Init-Update-Finish) HMAC signing.
 ///
 /// Use `sign` for single-step HMAC signing.
+///
+/// The key state is cleansed when the context is dropped.
 pub struct Context {
     key: Key,
 }
@@ -365,6 +490,8 @@ impl core::fmt::Debug for Context {
     }
 }
 
+impl ZeroizeOnDrop for Context {}
+
 impl Context {
     /// Constructs a new HMAC signing context using the given digest algorithm
     /// and key.
@@ -461,6 +588,38 @@ pub fn sign(key: &Key, data: &[u8]) -> Tag {
     ctx.sign()
 }
 
+/// Calculates the HMAC of `data` using the key `key` in one step, returning a tag bound to the
+/// digest algorithm `A`.
+///
+/// ```
+/// use aws_lc_rs::digest::typed::Sha256;
+/// use aws_lc_rs::hmac;
+///
+/// let key = hmac::Key::new(hmac::HMAC_SHA256, &[0u8; 32]);
+/// let tag = hmac::sign_typed::<Sha256>(&key, b"hello, world").unwrap();
+/// let bytes: [u8; 32] = tag.into_bytes();
+///
+/// assert!(hmac::verify(&key, b"hello, world", &bytes).is_ok());
+/// ```
+///
+/// # Errors
+/// `error::Unspecified` if `key` is not a key for HMAC with the digest algorithm `A`.
+//
+// # FIPS
+// Use this function with one of the following algorithms:
+// * `Sha1ForLegacyUseOnly`
+// * `Sha224`
+// * `Sha256`
+// * `Sha384`
+// * `Sha512`
+#[inline]
+pub fn sign_typed<A: DigestAlgorithm>(key: &Key, data: &[u8]) -> Result<TypedTag<A>, Unspecified> {
+    if key.algorithm().digest_algorithm() != A::algorithm() {
+        return Err(Unspecified);
+    }
+    TypedTag::try_from(sign(key, data))
+}
+
 /// Calculates the HMAC of `data` using the signing key `key`, and verifies
 /// whether the resultant value equals `tag`, in one step.
 ///
@@ -7,14 +7,26 @@
 //! Initialization Vector (IV) cryptographic primitives
 
 use crate::error::Unspecified;
-use crate::rand;
-use zeroize::Zeroize;
+use crate::{hkdf, rand};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+mod counter;
+
+pub use counter::{Counter, CounterBuilder};
 
 /// Length of a 128-bit IV in bytes.
 pub const IV_LEN_128_BIT: usize = 16;
 
+/// Length of a 96-bit IV in bytes.
+pub const IV_LEN_96_BIT: usize = 12;
+
+/// Length of a 64-bit IV in bytes.
+pub const IV_LEN_64_BIT: usize = 8;
+
 /// An initialization vector that must be unique for the lifetime of the associated key
 /// it is used with.
+///
+/// The IV bytes are zeroized when dropped.
 pub struct FixedLength<const L: usize>([u8; L]);
 
 impl<const L: usize> FixedLength<L> {
@@ -24,6 +36,13 @@ impl<const L: usize> FixedLength<L> {
         L
     }
 
+    /// Returns the IV bytes.
+    #[inline]
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8; L] {
+        &self.0
+    }
+
     /// Constructs a new [`FixedLength`] from pseudo-random bytes.
     ///
     /// # Errors
@@ -34,14 +53,33 @@ impl<const L: usize> FixedLength<L> {
         rand::fill(&mut iv_bytes)?;
         Ok(Self(iv_bytes))
     }
+
+    /// Constructs a new [`FixedLength`] from bytes generated by `rng`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Unspecified`]: Returned if `rng` fails to generate `L` bytes.
+    pub fn new_with_rng(rng: &dyn rand::SecureRandom) -> Result<Self, Unspecified> {
+        let mut iv_bytes = [0u8; L];
+        rng.fill(&mut iv_bytes)?;
+        Ok(Self(iv_bytes))
+    }
+}
+
+impl<const L: usize> Zeroize for FixedLength<L> {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
 }
 
 impl<const L: usize> Drop for FixedLength<L> {
     fn drop(&mut self) {
-        self.0.zeroize();
+        self.zeroize();
     }
 }
 
+impl<const L: usize> ZeroizeOnDrop for FixedLength<L> {}
+
 impl<const L: usize> AsRef<[u8; L]> for FixedLength<L> {
     #[inline]
     fn as_ref(&self) -> &[u8; L] {
@@ -80,6 +118,35 @@ impl<const L: usize> TryFrom<FixedLength<L>> for [u8; L] {
     }
 }
 
+/// The length of a [`FixedLength`] IV, for deriving its value with HKDF.
+///
+/// ```rust
+/// use aws_lc_rs::{hkdf, iv};
+///
+/// # fn main() -> Result<(), aws_lc_rs::error::Unspecified> {
+/// let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, b"salt").extract(b"secret");
+/// let iv: iv::FixedLength<12> = prk.expand(&[b"iv"], iv::IvLength::<12>)?.into();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IvLength<const L: usize>;
+
+impl<const L: usize> hkdf::KeyType for IvLength<L> {
+    #[inline]
+    fn len(&self) -> usize {
+        L
+    }
+}
+
+impl<const L: usize> From<hkdf::Okm<'_, IvLength<L>>> for FixedLength<L> {
+    fn from(okm: hkdf::Okm<IvLength<L>>) -> Self {
+        let mut iv = FixedLength([0u8; L]);
+        okm.fill(&mut iv.0).unwrap();
+        iv
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::iv::FixedLength;
@@ -97,4 +164,12 @@ mod tests {
 
         assert!(TryInto::<[u8; 12]>::try_into(fixed).is_ok());
     }
+
+    #[test]
+    fn test_as_bytes() {
+        let bytes = [0x42u8; 16];
+        let fixed = FixedLength::from(bytes);
+        assert_eq!(&bytes, fixed.as_bytes());
+        assert_eq!(fixed.as_ref(), fixed.as_bytes());
+    }
 }
@@ -0,0 +1,360 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use crate::error::Unspecified;
+use crate::iv::FixedLength;
+
+const MAX_COUNTER_LEN: usize = 8;
+
+/// A sequence of unique `N`-byte values, each a fixed identifier followed by a big-endian
+/// counter.
+///
+/// The counter occupies the last `counter_len` bytes; the identifier fills the remaining
+/// leading bytes. A limit can be set on the number of values generated. The limit is capped at
+/// the number of counter values left before the counter would wrap, so the sequence never
+/// repeats a value. The state needed to resume a sequence is available from
+/// [`Counter::counter`] and [`Counter::generated`] and can be restored with [`CounterBuilder`].
+///
+/// The values can be used as AEAD nonces: `Counter<NONCE_LEN>` implements
+/// `aead::NonceSequence`, and the AEAD `Counter32` and `Counter64` nonce sequences are
+/// implemented with this type.
+///
+/// # ⚠️ Not CTR initial counter blocks
+///
+/// Consecutive values differ only in their low-order bytes. CTR mode increments its initial
+/// counter block for every block it encrypts, so encrypting more than one block under the
+/// value `n` reuses the keystream of the value `n + 1`. Do not use these values as the IV of
+/// [`cipher::EncryptingKey::ctr`](crate::cipher::EncryptingKey::ctr); use a 96-bit value as the
+/// nonce of [`cipher::EncryptionContext::ctr_nonce_counter`](crate::cipher::EncryptionContext::ctr_nonce_counter)
+/// instead, which keeps the block counter separate.
+///
+/// ```rust
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use aws_lc_rs::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_128_GCM, NONCE_LEN};
+/// use aws_lc_rs::iv::CounterBuilder;
+///
+/// let key = LessSafeKey::new(UnboundKey::new(&AES_128_GCM, &[0x42u8; 16])?);
+/// let mut nonces = CounterBuilder::<NONCE_LEN>::new()
+///     .identifier(&[0xA1, 0xB2, 0xC3, 0xD4])
+///     .limit(1 << 32)
+///     .build()?;
+///
+/// let mut in_out = b"message".to_vec();
+/// key.seal_in_place_append_tag(Nonce::from(nonces.advance()?), Aad::empty(), &mut in_out)?;
+/// assert_eq!(1, nonces.counter());
+/// # Ok(())
+/// # }
+/// ```
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug)]
+pub struct Counter<const N: usize> {
+    prefix: [u8; N],
+    counter_len: usize,
+    counter: u64,
+    generated: u64,
+    limit: u64,
+}
+
+/// `CounterBuilder` facilitates the building of a [`Counter`].
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug)]
+pub struct CounterBuilder<const N: usize> {
+    identifier: Option<[u8; N]>,
+    identifier_len: usize,
+    counter_len: usize,
+    counter: u64,
+    generated: u64,
+    limit: u64,
+}
+
+impl<const N: usize> Default for CounterBuilder<N> {
+    fn default() -> Self {
+        CounterBuilder::new()
+    }
+}
+
+impl<const N: usize> CounterBuilder<N> {
+    /// Constructs a `CounterBuilder` with a 64-bit counter starting at zero, an all-zero
+    /// identifier, and no limit other than the number of counter values.
+    #[must_use]
+    pub fn new() -> CounterBuilder<N> {
+        CounterBuilder {
+            identifier: None,
+            identifier_len: 0,
+            counter_len: MAX_COUNTER_LEN,
+            counter: 0,
+            generated: 0,
+            limit: u64::MAX,
+        }
+    }
+
+    /// The number of trailing bytes holding the counter, between 1 and 8. Defaults to 8.
+    #[must_use]
+    pub fn counter_len(mut self, counter_len: usize) -> CounterBuilder<N> {
+        self.counter_len = counter_len;
+        self
+    }
+
+    /// The identifier that prefixes every value. Its length must be `N` minus the counter
+    /// length.
+    #[must_use]
+    pub fn identifier(mut self, identifier: &[u8]) -> CounterBuilder<N> {
+        let mut prefix = [0u8; N];
+        let len = identifier.len().min(N);
+        prefix[..len].copy_from_slice(&identifier[..len]);
+        self.identifier = Some(prefix);
+        self.identifier_len = identifier.len();
+        self
+    }
+
+    /// The starting counter value.
+    #[must_use]
+    pub fn counter(mut self, counter: u64) -> CounterBuilder<N> {
+        self.counter = counter;
+        self
+    }
+
+    /// The number of values already generated, when resuming a persisted sequence.
+    #[must_use]
+    pub fn generated(mut self, generated: u64) -> CounterBuilder<N> {
+        self.generated = generated;
+        self
+    }
+
+    /// The limit for the number of values the `Counter` can produce, including those already
+    /// generated. It is capped at the number of values the counter can take from its starting
+    /// value without wrapping.
+    #[must_use]
+    pub fn limit(mut self, limit: u64) -> CounterBuilder<N> {
+        self.limit = limit;
+        self
+    }
+
+    /// Constructs a new `Counter`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the counter length is not between 1 and `min(N, 8)`, if the
+    /// identifier length does not fill the remaining bytes, or if the starting counter value
+    /// does not fit in the counter length, e.g. because a resumed sequence is exhausted.
+    pub fn build(self) -> Result<Counter<N>, Unspecified> {
+        if self.counter_len == 0 || self.counter_len > MAX_COUNTER_LEN.min(N) {
+            return Err(Unspecified);
+        }
+        let prefix = match self.identifier {
+            Some(prefix) if self.identifier_len == N - self.counter_len => prefix,
+            Some(_) => return Err(Unspecified),
+            None => [0u8; N],
+        };
+        if self.counter & !counter_mask(self.counter_len) != 0 {
+            return Err(Unspecified);
+        }
+        let remaining = counter_end(self.counter_len) - self.counter;
+        Ok(Counter::new(
+            prefix,
+            self.counter_len,
+            self.counter,
+            self.generated,
+            self.limit.min(self.generated.saturating_add(remaining)),
+        ))
+    }
+}
+
+fn counter_mask(counter_len: usize) -> u64 {
+    u64::MAX >> (8 * (MAX_COUNTER_LEN - counter_len))
+}
+
+/// The counter value after the last one that can be generated. For an 8-byte counter the value
+/// `u64::MAX` is never generated, so that the counter always holds the next value.
+fn counter_end(counter_len: usize) -> u64 {
+    counter_mask(counter_len).saturating_add(1)
+}
+
+impl<const N: usize> Counter<N> {
+    /// `prefix` holds the identifier in its leading `N - counter_len` bytes.
+    pub(crate) fn new(
+        prefix: [u8; N],
+        counter_len: usize,
+        counter: u64,
+        generated: u64,
+        limit: u64,
+    ) -> Self {
+        debug_assert!(counter_len > 0 && counter_len <= MAX_COUNTER_LEN.min(N));
+        debug_assert_eq!(0, counter & !counter_mask(counter_len));
+        Self {
+            prefix,
+            counter_len,
+            counter,
+            generated,
+            limit,
+        }
+    }
+
+    /// Returns the next value and increments the counter.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the limit on the number of generated values has been reached, or
+    /// if the counter is exhausted.
+    pub fn advance(&mut self) -> Result<FixedLength<N>, Unspecified> {
+        if self.generated >= self.limit || self.counter >= counter_end(self.counter_len) {
+            return Err(Unspecified);
+        }
+        let mut bytes = self.prefix;
+        bytes[N - self.counter_len..]
+            .copy_from_slice(&self.counter.to_be_bytes()[MAX_COUNTER_LEN - self.counter_len..]);
+        self.generated += 1;
+        self.counter += 1;
+        Ok(FixedLength::from(bytes))
+    }
+
+    /// Provides the identifier.
+    #[must_use]
+    pub fn identifier(&self) -> &[u8] {
+        &self.prefix[..N - self.counter_len]
+    }
+
+    /// Provides the number of trailing bytes holding the counter.
+    #[must_use]
+    pub fn counter_len(&self) -> usize {
+        self.counter_len
+    }
+
+    /// Provides the current counter value, i.e. the counter of the next value.
+    #[must_use]
+    pub fn counter(&self) -> u64 {
+        self.counter
+    }
+
+    /// Provides how many values have been generated.
+    #[must_use]
+    pub fn generated(&self) -> u64 {
+        self.generated
+    }
+
+    /// Provides the limit on the number of values allowed to be generated.
+    #[must_use]
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::iv::CounterBuilder;
+
+    #[test]
+    fn test_counter() {
+        let mut counter = CounterBuilder::<16>::new()
+            .identifier(&[1, 2, 3, 4, 5, 6, 7, 8])
+            .counter(0xFF)
+            .build()
+            .unwrap();
+        assert_eq!(&[1, 2, 3, 4, 5, 6, 7, 8], counter.identifier());
+        assert_eq!(8, counter.counter_len());
+
+        let value = counter.advance().unwrap();
+        assert_eq!(
+            &[1, 2, 3, 4, 5, 6, 7, 8, 0, 0, 0, 0, 0, 0, 0, 0xFF],
+            value.as_ref()
+        );
+        let value = counter.advance().unwrap();
+        assert_eq!(
+            &[1, 2, 3, 4, 5, 6, 7, 8, 0, 0, 0, 0, 0, 0, 1, 0],
+            value.as_ref()
+        );
+        assert_eq!(0x101, counter.counter());
+        assert_eq!(2, counter.generated());
+    }
+
+    #[test]
+    fn test_counter_exhausted() {
+        let mut counter = CounterBuilder::<4>::new()
+            .counter_len(2)
+            .identifier(&[0xAB, 0xCD])
+            .counter(0xFFFE)
+            .build()
+            .unwrap();
+        assert_eq!(2, counter.limit());
+        assert_eq!(
+            &[0xAB, 0xCD, 0xFF, 0xFE],
+            counter.advance().unwrap().as_ref()
+        );
+        assert_eq!(
+            &[0xAB, 0xCD, 0xFF, 0xFF],
+            counter.advance().unwrap().as_ref()
+        );
+        // The counter does not wrap back to zero.
+        assert!(counter.advance().is_err());
+        assert_eq!(0x10000, counter.counter());
+        assert_eq!(2, counter.generated());
+
+        // An exhausted sequence cannot be resumed.
+        assert!(CounterBuilder::<4>::new()
+            .counter_len(2)
+            .identifier(&[0xAB, 0xCD])
+            .counter(counter.counter())
+            .generated(counter.generated())
+            .build()
+            .is_err());
+
+        // A 4-byte counter allows at most 2^32 values, whatever the requested limit.
+        let counter = CounterBuilder::<12>::new().counter_len(4).build().unwrap();
+        assert_eq!(1 << 32, counter.limit());
+        let counter = CounterBuilder::<12>::new()
+            .counter_len(4)
+            .counter(10)
+            .generated(5)
+            .build()
+            .unwrap();
+        assert_eq!(5 + (1 << 32) - 10, counter.limit());
+        let counter = CounterBuilder::<12>::new()
+            .counter_len(4)
+            .limit(3)
+            .build()
+            .unwrap();
+        assert_eq!(3, counter.limit());
+
+        let mut counter = CounterBuilder::<8>::new()
+            .counter(u64::MAX - 1)
+            .build()
+            .unwrap();
+        assert_eq!(1, counter.limit());
+        counter.advance().unwrap();
+        assert!(counter.advance().is_err());
+    }
+
+    #[test]
+    fn test_counter_resume() {
+        let mut counter = CounterBuilder::<12>::new().limit(3).build().unwrap();
+        counter.advance().unwrap();
+        counter.advance().unwrap();
+
+        let mut resumed = CounterBuilder::<12>::new()
+            .counter(counter.counter())
+            .generated(counter.generated())
+            .limit(counter.limit())
+            .build()
+            .unwrap();
+        assert_eq!(
+            counter.advance().unwrap().as_ref(),
+            resumed.advance().unwrap().as_ref()
+        );
+        assert!(resumed.advance().is_err());
+    }
+
+    #[test]
+    fn test_counter_invalid() {
+        assert!(CounterBuilder::<16>::new().counter_len(0).build().is_err());
+        assert!(CounterBuilder::<16>::new().counter_len(9).build().is_err());
+        assert!(CounterBuilder::<4>::new().build().is_err());
+        assert!(CounterBuilder::<16>::new()
+            .identifier(&[0u8; 4])
+            .build()
+            .is_err());
+        assert!(CounterBuilder::<12>::new()
+            .counter_len(4)
+            .counter(1 << 32)
+            .build()
+            .is_err());
+    }
+}
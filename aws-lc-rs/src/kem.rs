@@ -63,7 +63,7 @@ use aws_lc::{
     EVP_PKEY, EVP_PKEY_KEM,
 };
 use core::{cmp::Ordering, ptr::null_mut};
-use zeroize::Zeroize;
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 /// An identifier for a KEM algorithm.
 pub trait AlgorithmIdentifier:
@@ -127,6 +127,8 @@ where
 }
 
 /// A serializable decapulsation key usable with KEMs. This can be randomly generated with `DecapsulationKey::generate`.
+///
+/// The private key is freed and zeroized by *AWS-LC* when dropped.
 pub struct DecapsulationKey<Id = AlgorithmId>
 where
     Id: AlgorithmIdentifier,
@@ -258,6 +260,8 @@ where
     }
 }
 
+impl<Id> ZeroizeOnDrop for DecapsulationKey<Id> where Id: AlgorithmIdentifier {}
+
 use paste::paste;
 generated_encodings!(EncapsulationKeyBytes);
 
@@ -424,6 +428,8 @@ impl<'a> From<&'a [u8]> for Ciphertext<'a> {
 }
 
 /// The cryptographic shared secret output from the KEM encapsulate / decapsulate process.
+///
+/// The secret is zeroized when dropped.
 pub struct SharedSecret(Box<[u8]>);
 
 impl SharedSecret {
@@ -432,12 +438,20 @@ impl SharedSecret {
     }
 }
 
+impl Zeroize for SharedSecret {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
 impl Drop for SharedSecret {
     fn drop(&mut self) {
-        self.0.zeroize();
+        self.zeroize();
     }
 }
 
+impl ZeroizeOnDrop for SharedSecret {}
+
 impl AsRef<[u8]> for SharedSecret {
     fn as_ref(&self) -> &[u8] {
         self.0.as_ref()
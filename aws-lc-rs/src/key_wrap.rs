@@ -38,6 +38,7 @@ use aws_lc::{
     AES_wrap_key_padded, AES_KEY,
 };
 use core::{fmt::Debug, mem::MaybeUninit, ptr::null};
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 mod tests;
 
@@ -48,6 +49,9 @@ pub enum BlockCipherId {
     /// AES Block Cipher with 128-bit key.
     Aes128,
 
+    /// AES Block Cipher with 192-bit key.
+    Aes192,
+
     /// AES Block Cipher with 256-bit key.
     Aes256,
 }
@@ -97,12 +101,21 @@ pub const AES_128: AesBlockCipher = AesBlockCipher {
     key_len: 16,
 };
 
+/// AES Block Cipher with 192-bit key.
+pub const AES_192: AesBlockCipher = AesBlockCipher {
+    id: BlockCipherId::Aes192,
+    key_len: 24,
+};
+
 /// AES Block Cipher with 256-bit key.
 pub const AES_256: AesBlockCipher = AesBlockCipher {
     id: BlockCipherId::Aes256,
     key_len: 32,
 };
 
+/// The length of the initial value (IV) checked by the Key Wrap (KW) algorithm.
+pub const IV_LEN: usize = 8;
+
 /// A Key Wrap (KW) algorithm implementation.
 #[allow(clippy::module_name_repetitions)]
 pub trait KeyWrap: Sealed {
@@ -127,6 +140,31 @@ pub trait KeyWrap: Sealed {
         ciphertext: &[u8],
         output: &'output mut [u8],
     ) -> Result<&'output mut [u8], Unspecified>;
+
+    /// Peforms the key wrap encryption algorithm like [`KeyWrap::wrap`], but using `iv` as the
+    /// initial value in place of the default initial value of RFC 3394, Section 2.2.3.1.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Any error that has occurred performing the operation.
+    fn wrap_with_iv<'output>(
+        self,
+        iv: &[u8; IV_LEN],
+        plaintext: &[u8],
+        output: &'output mut [u8],
+    ) -> Result<&'output mut [u8], Unspecified>;
+
+    /// Peforms the key wrap decryption algorithm like [`KeyWrap::unwrap`], but checking the
+    /// integrity of the plaintext against `iv` in place of the default initial value of
+    /// RFC 3394, Section 2.2.3.1.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Any error that has occurred performing the operation.
+    fn unwrap_with_iv<'output>(
+        self,
+        iv: &[u8; IV_LEN],
+        ciphertext: &[u8],
+        output: &'output mut [u8],
+    ) -> Result<&'output mut [u8], Unspecified>;
 }
 
 /// A Key Wrap with Padding (KWP) algorithm implementation.
@@ -166,6 +204,8 @@ pub type AesKek = KeyEncryptionKey<AesBlockCipher>;
 /// * Specifies a maxiumum plaintext length that can be accepted.
 /// * Allows implementations to specify a subset of valid lengths accepted.
 /// * Allows for the usage of other 128-bit block ciphers other than AES.
+///
+/// The key is zeroized when dropped.
 pub struct KeyEncryptionKey<Cipher: BlockCipher> {
     cipher: &'static Cipher,
     key: Box<[u8]>,
@@ -195,6 +235,14 @@ impl<Cipher: BlockCipher> KeyEncryptionKey<Cipher> {
 
 impl<Cipher: BlockCipher> Sealed for KeyEncryptionKey<Cipher> {}
 
+impl<Cipher: BlockCipher> Drop for KeyEncryptionKey<Cipher> {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+impl<Cipher: BlockCipher> ZeroizeOnDrop for KeyEncryptionKey<Cipher> {}
+
 impl KeyWrap for KeyEncryptionKey<AesBlockCipher> {
     /// Peforms the key wrap encryption algorithm using `KeyEncryptionKey`'s configured block cipher.
     /// It wraps `plaintext` and writes the corresponding ciphertext to `output`.
@@ -211,43 +259,7 @@ impl KeyWrap for KeyEncryptionKey<AesBlockCipher> {
         plaintext: &[u8],
         output: &'output mut [u8],
     ) -> Result<&'output mut [u8], Unspecified> {
-        if output.len() < plaintext.len() + 8 {
-            return Err(Unspecified);
-        }
-
-        let mut aes_key = MaybeUninit::<AES_KEY>::uninit();
-
-        let key_bits: u32 = (self.key.len() * 8).try_into().map_err(|_| Unspecified)?;
-
-        if 0 != unsafe { AES_set_encrypt_key(self.key.as_ptr(), key_bits, aes_key.as_mut_ptr()) } {
-            return Err(Unspecified);
-        }
-
-        let aes_key = unsafe { aes_key.assume_init() };
-
-        // AWS-LC validates the following:
-        // * in_len <= INT_MAX - 8
-        // * in_len >= 16
-        // * in_len % 8 == 0
-        let out_len = indicator_check!(unsafe {
-            AES_wrap_key(
-                &aes_key,
-                null(),
-                output.as_mut_ptr(),
-                plaintext.as_ptr(),
-                plaintext.len(),
-            )
-        });
-
-        if out_len == -1 {
-            return Err(Unspecified);
-        }
-
-        let out_len: usize = out_len.try_into().map_err(|_| Unspecified)?;
-
-        debug_assert_eq!(out_len, plaintext.len() + 8);
-
-        Ok(&mut output[..out_len])
+        aes_wrap_key(&self.key, None, plaintext, output)
     }
 
     /// Peforms the key wrap decryption algorithm using `KeyEncryptionKey`'s configured block cipher.
@@ -265,48 +277,143 @@ impl KeyWrap for KeyEncryptionKey<AesBlockCipher> {
         ciphertext: &[u8],
         output: &'output mut [u8],
     ) -> Result<&'output mut [u8], Unspecified> {
-        if output.len() < ciphertext.len() - 8 {
-            return Err(Unspecified);
-        }
+        aes_unwrap_key(&self.key, None, ciphertext, output)
+    }
 
-        let mut aes_key = MaybeUninit::<AES_KEY>::uninit();
+    /// Peforms the key wrap encryption algorithm using `KeyEncryptionKey`'s configured block cipher
+    /// and `iv` as the initial value.
+    ///
+    /// # Validation
+    /// * `plaintext.len()` must be a multiple of eight
+    /// * `output.len() >= (input.len() + 8)`
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: An error occurred either due to `output` being insufficiently sized, `input` exceeding
+    ///   the allowed input size, or for other unspecified reasons.
+    fn wrap_with_iv<'output>(
+        self,
+        iv: &[u8; IV_LEN],
+        plaintext: &[u8],
+        output: &'output mut [u8],
+    ) -> Result<&'output mut [u8], Unspecified> {
+        aes_wrap_key(&self.key, Some(iv), plaintext, output)
+    }
 
-        if 0 != unsafe {
-            AES_set_decrypt_key(
-                self.key.as_ptr(),
-                (self.key.len() * 8).try_into().map_err(|_| Unspecified)?,
-                aes_key.as_mut_ptr(),
-            )
-        } {
-            return Err(Unspecified);
-        }
+    /// Peforms the key wrap decryption algorithm using `KeyEncryptionKey`'s configured block cipher
+    /// and `iv` as the initial value.
+    ///
+    /// # Validation
+    /// * `ciphertext.len()` must be a multiple of 8
+    /// * `output.len() >= (input.len() - 8)`
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: An error occurred either due to `output` being insufficiently sized, `input` exceeding
+    ///   the allowed input size, the integrity check failing, or for other unspecified reasons.
+    fn unwrap_with_iv<'output>(
+        self,
+        iv: &[u8; IV_LEN],
+        ciphertext: &[u8],
+        output: &'output mut [u8],
+    ) -> Result<&'output mut [u8], Unspecified> {
+        aes_unwrap_key(&self.key, Some(iv), ciphertext, output)
+    }
+}
 
-        let aes_key = unsafe { aes_key.assume_init() };
+fn aes_wrap_key<'output>(
+    key: &[u8],
+    iv: Option<&[u8; IV_LEN]>,
+    plaintext: &[u8],
+    output: &'output mut [u8],
+) -> Result<&'output mut [u8], Unspecified> {
+    if output.len() < plaintext.len() + 8 {
+        return Err(Unspecified);
+    }
 
-        // AWS-LC validates the following:
-        // * in_len < INT_MAX
-        // * in_len > 24
-        // * in_len % 8 == 0
-        let out_len = indicator_check!(unsafe {
-            AES_unwrap_key(
-                &aes_key,
-                null(),
-                output.as_mut_ptr(),
-                ciphertext.as_ptr(),
-                ciphertext.len(),
-            )
-        });
+    let mut aes_key = MaybeUninit::<AES_KEY>::uninit();
 
-        if out_len == -1 {
-            return Err(Unspecified);
-        }
+    let key_bits: u32 = (key.len() * 8).try_into().map_err(|_| Unspecified)?;
+
+    if 0 != unsafe { AES_set_encrypt_key(key.as_ptr(), key_bits, aes_key.as_mut_ptr()) } {
+        return Err(Unspecified);
+    }
+
+    let aes_key = unsafe { aes_key.assume_init() };
+
+    // AWS-LC validates the following:
+    // * in_len <= INT_MAX - 8
+    // * in_len >= 16
+    // * in_len % 8 == 0
+    let out_len = indicator_check!(unsafe {
+        AES_wrap_key(
+            &aes_key,
+            iv.map_or(null(), |iv| iv.as_ptr()),
+            output.as_mut_ptr(),
+            plaintext.as_ptr(),
+            plaintext.len(),
+        )
+    });
+
+    if out_len == -1 {
+        return Err(Unspecified);
+    }
 
-        let out_len: usize = out_len.try_into().map_err(|_| Unspecified)?;
+    let out_len: usize = out_len.try_into().map_err(|_| Unspecified)?;
 
-        debug_assert_eq!(out_len, ciphertext.len() - 8);
+    debug_assert_eq!(out_len, plaintext.len() + 8);
 
-        Ok(&mut output[..out_len])
+    Ok(&mut output[..out_len])
+}
+
+fn aes_unwrap_key<'output>(
+    key: &[u8],
+    iv: Option<&[u8; IV_LEN]>,
+    ciphertext: &[u8],
+    output: &'output mut [u8],
+) -> Result<&'output mut [u8], Unspecified> {
+    let plaintext_len = ciphertext.len().checked_sub(8).ok_or(Unspecified)?;
+    if output.len() < plaintext_len {
+        return Err(Unspecified);
+    }
+
+    let mut aes_key = MaybeUninit::<AES_KEY>::uninit();
+
+    if 0 != unsafe {
+        AES_set_decrypt_key(
+            key.as_ptr(),
+            (key.len() * 8).try_into().map_err(|_| Unspecified)?,
+            aes_key.as_mut_ptr(),
+        )
+    } {
+        return Err(Unspecified);
+    }
+
+    let aes_key = unsafe { aes_key.assume_init() };
+
+    // AWS-LC validates the following:
+    // * in_len < INT_MAX
+    // * in_len > 24
+    // * in_len % 8 == 0
+    let out_len = indicator_check!(unsafe {
+        AES_unwrap_key(
+            &aes_key,
+            iv.map_or(null(), |iv| iv.as_ptr()),
+            output.as_mut_ptr(),
+            ciphertext.as_ptr(),
+            ciphertext.len(),
+        )
+    });
+
+    if out_len == -1 {
+        // AWS-LC writes the plaintext before checking its integrity.
+        output[..plaintext_len].zeroize();
+        return Err(Unspecified);
     }
+
+    let out_len: usize = out_len.try_into().map_err(|_| Unspecified)?;
+
+    debug_assert_eq!(out_len, plaintext_len);
+
+    Ok(&mut output[..out_len])
 }
 
 impl KeyWrapPadded for KeyEncryptionKey<AesBlockCipher> {
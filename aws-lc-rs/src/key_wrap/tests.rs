@@ -8,7 +8,9 @@ mod fips;
 
 use crate::key_wrap::AesKek;
 
-use super::{BlockCipher, BlockCipherId, KeyWrap, KeyWrapPadded, AES_128, AES_256};
+use super::{
+    BlockCipher, BlockCipherId, KeyWrap, KeyWrapPadded, AES_128, AES_192, AES_256, IV_LEN,
+};
 
 macro_rules! block_cipher_test {
     ($name:ident, $alg:expr, $id:expr, $key_len:literal) => {
@@ -22,6 +24,7 @@ macro_rules! block_cipher_test {
 }
 
 block_cipher_test!(aes_128_cipher, &AES_128, BlockCipherId::Aes128, 16);
+block_cipher_test!(aes_192_cipher, &AES_192, BlockCipherId::Aes192, 24);
 block_cipher_test!(aes_256_cipher, &AES_256, BlockCipherId::Aes256, 32);
 
 #[test]
@@ -579,6 +582,160 @@ nist_aes_key_unwrap_test!(
     ]
 );
 
+// RFC 3394, Section 4.2
+nist_aes_key_wrap_test!(
+    rfc3394_aes192_128bit_data,
+    &AES_192,
+    &[
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+    ],
+    &[
+        0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee,
+        0xff,
+    ],
+    &[
+        0x96, 0x77, 0x8b, 0x25, 0xae, 0x6c, 0xa4, 0x35, 0xf9, 0x2b, 0x5b, 0x97, 0xc0, 0x50, 0xae,
+        0xd2, 0x46, 0x8a, 0xb8, 0xa1, 0x7a, 0xd8, 0x4e, 0x5d,
+    ]
+);
+
+// RFC 3394, Section 4.4
+nist_aes_key_wrap_test!(
+    rfc3394_aes192_192bit_data,
+    &AES_192,
+    &[
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+    ],
+    &[
+        0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee,
+        0xff, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+    ],
+    &[
+        0x03, 0x1d, 0x33, 0x26, 0x4e, 0x15, 0xd3, 0x32, 0x68, 0xf2, 0x4e, 0xc2, 0x60, 0x74, 0x3e,
+        0xdc, 0xe1, 0xc6, 0xc7, 0xdd, 0xee, 0x72, 0x5a, 0x93, 0x6b, 0xa8, 0x14, 0x91, 0x5c, 0x67,
+        0x62, 0xd2,
+    ]
+);
+
+// RFC 5649, Section 6
+nist_aes_key_wrap_with_padding_test!(
+    rfc5649_aes192_160bit_data,
+    &AES_192,
+    &[
+        0x58, 0x40, 0xdf, 0x6e, 0x29, 0xb0, 0x2a, 0xf1, 0xab, 0x49, 0x3b, 0x70, 0x5b, 0xf1, 0x6e,
+        0xa1, 0xae, 0x83, 0x38, 0xf4, 0xdc, 0xc1, 0x76, 0xa8,
+    ],
+    &[
+        0xc3, 0x7b, 0x7e, 0x64, 0x92, 0x58, 0x43, 0x40, 0xbe, 0xd1, 0x22, 0x07, 0x80, 0x89, 0x41,
+        0x15, 0x50, 0x68, 0xf7, 0x38,
+    ],
+    &[
+        0x13, 0x8b, 0xde, 0xaa, 0x9b, 0x8f, 0xa7, 0xfc, 0x61, 0xf9, 0x77, 0x42, 0xe7, 0x22, 0x48,
+        0xee, 0x5a, 0xe6, 0xae, 0x53, 0x60, 0xd1, 0xae, 0x6a, 0x5f, 0x54, 0xf3, 0x73, 0xfa, 0x54,
+        0x3b, 0x6a,
+    ]
+);
+
+// RFC 5649, Section 6
+nist_aes_key_wrap_with_padding_test!(
+    rfc5649_aes192_56bit_data,
+    &AES_192,
+    &[
+        0x58, 0x40, 0xdf, 0x6e, 0x29, 0xb0, 0x2a, 0xf1, 0xab, 0x49, 0x3b, 0x70, 0x5b, 0xf1, 0x6e,
+        0xa1, 0xae, 0x83, 0x38, 0xf4, 0xdc, 0xc1, 0x76, 0xa8,
+    ],
+    &[0x46, 0x6f, 0x72, 0x50, 0x61, 0x73, 0x69],
+    &[
+        0xaf, 0xbe, 0xb0, 0xf0, 0x7d, 0xfb, 0xf5, 0x41, 0x92, 0x00, 0xf2, 0xcc, 0xb5, 0x0b, 0xb2,
+        0x4f,
+    ]
+);
+
+macro_rules! nist_aes_key_wrap_with_iv_test {
+    ($name:ident, $alg:expr, $key:expr, $iv:expr, $plaintext:expr, $expect:expr) => {
+        #[test]
+        fn $name() {
+            const K: &[u8] = $key;
+            const IV: &[u8; IV_LEN] = $iv;
+            const P: &[u8] = $plaintext;
+            const C: &[u8] = $expect;
+
+            let kek = AesKek::new($alg, K).expect("key creation successful");
+
+            let mut output = vec![0u8; C.len()];
+
+            let wrapped = Vec::from(
+                kek.wrap_with_iv(IV, P, &mut output)
+                    .expect("wrap successful"),
+            );
+
+            assert_eq!(wrapped, C);
+
+            let kek = AesKek::new($alg, K).expect("key creation successful");
+
+            let mut output = vec![0u8; C.len()];
+
+            let unwrapped = kek
+                .unwrap_with_iv(IV, &wrapped, &mut output)
+                .expect("unwrap successful");
+
+            assert_eq!(unwrapped, P);
+
+            // The integrity check fails with the default initial value, and the output is
+            // cleared.
+            let kek = AesKek::new($alg, K).expect("key creation successful");
+
+            let mut output = vec![0u8; C.len()];
+
+            kek.unwrap(&wrapped, &mut output)
+                .expect_err("unwrap to fail");
+
+            assert!(output.iter().all(|b| *b == 0));
+        }
+    };
+}
+
+nist_aes_key_wrap_with_iv_test!(
+    kw_iv_aes128_128bit_len,
+    &AES_128,
+    &[
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f,
+    ],
+    &[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef],
+    &[
+        0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee,
+        0xff,
+    ],
+    &[
+        0xa0, 0xf7, 0x6f, 0x4b, 0x09, 0xe1, 0xf2, 0x19, 0x1b, 0x8d, 0x94, 0xda, 0x2c, 0xa5, 0x7a,
+        0xdf, 0xd4, 0x5e, 0xe9, 0x73, 0x29, 0x92, 0xa9, 0x8f,
+    ]
+);
+
+nist_aes_key_wrap_with_iv_test!(
+    kw_iv_aes256_256bit_len,
+    &AES_256,
+    &[
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d,
+        0x1e, 0x1f,
+    ],
+    &[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef],
+    &[
+        0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee,
+        0xff, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d,
+        0x0e, 0x0f,
+    ],
+    &[
+        0x55, 0x8e, 0xad, 0xda, 0x00, 0xbd, 0x8a, 0x4b, 0x02, 0x1f, 0xe5, 0xd9, 0x97, 0x73, 0x62,
+        0xb5, 0x2b, 0x6d, 0xd7, 0x8d, 0x91, 0x63, 0x14, 0x88, 0x2f, 0x69, 0x80, 0xf8, 0x45, 0x1d,
+        0x0e, 0x26, 0x17, 0xb4, 0xf5, 0xe5, 0xef, 0xe7, 0x93, 0x2c,
+    ]
+);
+
 macro_rules! wrap_input_output_invalid_test {
     ($name:ident, $input_len:expr, $output_len:expr) => {
         #[test]
@@ -5,13 +5,18 @@
 
 use crate::{
     fips::{assert_fips_status_indicator, FipsServiceStatus},
-    key_wrap::{AesKek, KeyWrap, KeyWrapPadded, AES_128, AES_256},
+    key_wrap::{AesKek, KeyWrap, KeyWrapPadded, AES_128, AES_192, AES_256},
 };
 
 const K_128: &[u8] = &[
     0x60, 0x43, 0xb2, 0x73, 0xe9, 0x71, 0x26, 0x5e, 0x53, 0x8a, 0x6c, 0xcd, 0x5d, 0x5a, 0x11, 0xe4,
 ];
 
+const K_192: &[u8] = &[
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+    0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+];
+
 const K_256: &[u8] = &[
     0x15, 0x52, 0x45, 0x0c, 0x60, 0xf3, 0x10, 0xfb, 0xc8, 0x41, 0x98, 0xe5, 0xfd, 0x70, 0x7d, 0x04,
     0x8f, 0x81, 0xbf, 0x9a, 0xdc, 0x63, 0x90, 0xed, 0xe5, 0xb0, 0x4b, 0x3c, 0xe4, 0x06, 0x54, 0xba,
@@ -95,5 +100,7 @@ macro_rules! nist_aes_key_wrap_with_padding_test {
 
 nist_aes_key_wrap_with_padding_test!(kwp_aes128, &AES_128, K_128, P);
 nist_aes_key_wrap_test!(kw_aes128, &AES_128, K_128, P);
+nist_aes_key_wrap_with_padding_test!(kwp_aes192, &AES_192, K_192, P);
+nist_aes_key_wrap_test!(kw_aes192, &AES_192, K_192, P);
 nist_aes_key_wrap_with_padding_test!(kwp_aes256, &AES_256, K_256, P);
 nist_aes_key_wrap_test!(kw_aes256, &AES_256, K_256, P);
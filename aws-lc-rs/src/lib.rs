@@ -50,7 +50,8 @@
 //! crate for the cryptographic implementations. The *aws-lc-fips-sys* crate provides bindings to
 //! [AWS-LC-FIPS 2.x](https://github.com/aws/aws-lc/tree/fips-2022-11-02), which has completed
 //! FIPS validation testing by an accredited lab and has been submitted to NIST for certification.
-//! The static build of AWS-LC-FIPS is used.
+//! The static build of AWS-LC-FIPS is used. AES-CCM (`cipher::CcmKey` and its sealing and
+//! opening keys) is not available with this feature, as AWS-LC-FIPS 2.x does not provide it.
 //!
 //! | Supported Targets |
 //! | --- |
@@ -62,6 +63,36 @@
 //! made available in the vendor security policy once the validation certificate has been issued. We will also update our release notes
 //! and documentation to reflect any changes in FIPS certification status.
 //!
+//! #### approved-only
+//! Removes the constructors for legacy algorithms that are not approved for new applications:
+//! RSA signature verification with moduli smaller than 2048 bits or with SHA-1, three-key
+//! Triple DES (`cipher::TRIPLE_DES_3KEY_FOR_LEGACY_USE_ONLY` and its key and IV lengths), and
+//! AES in ECB mode (`cipher::OperatingMode::ECB`, the `less_safe_ecb` constructors and the
+//! `cipher::ecb` module). Algorithms removed by this feature can also not be found through the
+//! lookup functions of [`policy::Registry`].
+//!
+//! #### serde
+//! Implements `serde::Serialize` and `serde::Deserialize` for `digest::Digest`, `hmac::Tag`,
+//! `signature::Signature`, the Ed25519 public key (`<Ed25519KeyPair as KeyPair>::PublicKey`),
+//! `agreement::PublicKey` and `cipher::DecryptionContext`. Byte values are encoded as hex
+//! strings in human-readable formats and deserialized values are validated against the length
+//! required by their algorithm.
+//!
+//! #### parallel
+//! Encrypts and decrypts large AES-CTR inputs on the [rayon](https://crates.io/crates/rayon)
+//! thread pool, with output identical to the single-threaded implementation. Inputs smaller than
+//! 1 MiB are still processed on the calling thread. This adds a requirement on `rayon`.
+//!
+//! #### test-utils
+//! Documents the `test` module as a supported API: hex helpers, deterministic `SecureRandom`
+//! implementations and a runner for the `key = value` test vector file format, for use in
+//! downstream known-answer tests.
+//!
+//! #### nist-vectors
+//! Runs the AES CBC and CTR known-answer tests against the full NIST CAVP response files in the
+//! `test-vectors` directory of this crate, which is not checked in. A subset of these vectors is
+//! always tested.
+//!
 //! #### asan
 //! Performs an "address sanitizer" build. This can be used to help detect memory leaks. See the
 //! ["Address Sanitizer" section](https://doc.rust-lang.org/beta/unstable-book/compiler-flags/sanitizer.html#addresssanitizer)
@@ -112,6 +143,7 @@ extern crate core;
 pub mod aead;
 pub mod agreement;
 pub mod constant_time;
+pub mod cpu;
 pub mod digest;
 pub mod error;
 pub mod hkdf;
@@ -119,10 +151,13 @@ pub mod hmac;
 #[cfg(feature = "ring-io")]
 pub mod io;
 pub mod key_wrap;
+pub mod password;
 pub mod pbkdf2;
 pub mod pkcs8;
+pub mod policy;
 pub mod rand;
 pub mod signature;
+#[cfg_attr(not(feature = "test-utils"), doc(hidden))]
 pub mod test;
 
 mod bn;
@@ -136,15 +171,19 @@ mod ed25519;
 pub mod encoding;
 mod endian;
 mod evp_pkey;
-mod fips;
+pub mod fips;
 mod hex;
 pub mod iv;
 #[allow(clippy::module_name_repetitions)]
 pub mod kem;
 mod ptr;
 pub mod rsa;
+#[cfg(feature = "serde")]
+mod serialization;
 pub mod tls_prf;
+pub mod tr31;
 pub mod unstable;
+pub mod vrf;
 
 pub(crate) use debug::derive_debug_via_id;
 // TODO: Uncomment when MSRV >= 1.64
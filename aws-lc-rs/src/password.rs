@@ -0,0 +1,271 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Password-based encryption.
+//!
+//! [`seal`] derives a key from a password with PBKDF2-HMAC-SHA256 using a random salt,
+//! encrypts the plaintext with AES-256-GCM under a random nonce, and returns a single
+//! self-describing blob. [`open`] reverses the operation using only the password and the blob.
+//!
+//! # Format
+//!
+//! The version 1 format is stable and will remain readable by all future versions of this module.
+//! All integers are big-endian.
+//!
+//! | Offset | Length | Field                                   |
+//! |--------|--------|-----------------------------------------|
+//! | 0      | 1      | Format version (`0x01`)                 |
+//! | 1      | 4      | PBKDF2-HMAC-SHA256 iteration count      |
+//! | 5      | 16     | PBKDF2 salt                             |
+//! | 21     | 12     | AES-256-GCM nonce                       |
+//! | 33     | *n*    | Ciphertext                              |
+//! | 33 + *n* | 16   | AES-256-GCM tag                         |
+//!
+//! The 33-byte header is authenticated as the AEAD additional data, so modifying any of the
+//! parameters causes [`open`] to fail.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::password::{self, Params};
+//! use std::num::NonZeroU32;
+//!
+//! let params = Params::new(NonZeroU32::new(100_000).unwrap());
+//! let sealed = password::seal(b"hunter2", b"attack at dawn", &params)?;
+//!
+//! let plaintext = password::open(b"hunter2", &sealed)?;
+//! assert_eq!(b"attack at dawn", plaintext.as_slice());
+//!
+//! assert!(password::open(b"hunter3", &sealed).is_err());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use crate::error::Unspecified;
+use crate::{pbkdf2, rand};
+use core::num::NonZeroU32;
+use zeroize::Zeroize;
+
+/// The format version produced by [`seal`].
+pub const VERSION_1: u8 = 1;
+
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+const HEADER_LEN: usize = 1 + 4 + SALT_LEN + NONCE_LEN;
+const TAG_LEN: usize = 16;
+
+/// The number of PBKDF2 iterations used by [`Params::default`].
+pub const DEFAULT_ITERATIONS: u32 = 600_000;
+
+/// Parameters used by [`seal`] when deriving the encryption key from a password.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Params {
+    iterations: NonZeroU32,
+}
+
+impl Params {
+    /// Constructs `Params` with the given PBKDF2 iteration count.
+    #[must_use]
+    pub const fn new(iterations: NonZeroU32) -> Self {
+        Self { iterations }
+    }
+
+    /// Returns the PBKDF2 iteration count.
+    #[must_use]
+    pub fn iterations(&self) -> NonZeroU32 {
+        self.iterations
+    }
+}
+
+impl Default for Params {
+    /// Returns parameters using [`DEFAULT_ITERATIONS`].
+    fn default() -> Self {
+        // DEFAULT_ITERATIONS is a non-zero constant.
+        Self::new(NonZeroU32::new(DEFAULT_ITERATIONS).unwrap())
+    }
+}
+
+/// Encrypts `plaintext` under a key derived from `password`.
+///
+/// A fresh random salt and nonce are generated for every call. The returned blob uses the
+/// [version 1 format](self#format).
+///
+/// # Errors
+/// `error::Unspecified` if random generation or encryption fails.
+pub fn seal(password: &[u8], plaintext: &[u8], params: &Params) -> Result<Vec<u8>, Unspecified> {
+    let mut salt = [0u8; SALT_LEN];
+    rand::fill(&mut salt)?;
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::fill(&mut nonce)?;
+    seal_v1(password, plaintext, params.iterations, &salt, nonce)
+}
+
+/// Decrypts a blob produced by [`seal`] using `password`.
+///
+/// The iteration count is read from the blob; callers accepting blobs from untrusted sources
+/// should be aware that the cost of this function is controlled by that value.
+///
+/// # Errors
+/// `error::Unspecified` if the blob is truncated, has an unknown version, was modified, or if
+/// `password` is incorrect.
+pub fn open(password: &[u8], blob: &[u8]) -> Result<Vec<u8>, Unspecified> {
+    match blob.first() {
+        Some(&VERSION_1) => open_v1(password, blob),
+        _ => Err(Unspecified),
+    }
+}
+
+fn seal_v1(
+    password: &[u8],
+    plaintext: &[u8],
+    iterations: NonZeroU32,
+    salt: &[u8; SALT_LEN],
+    nonce: [u8; NONCE_LEN],
+) -> Result<Vec<u8>, Unspecified> {
+    let mut out = Vec::with_capacity(HEADER_LEN + plaintext.len() + TAG_LEN);
+    out.push(VERSION_1);
+    out.extend_from_slice(&iterations.get().to_be_bytes());
+    out.extend_from_slice(salt);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(plaintext);
+
+    let key = derive_key(password, iterations, salt)?;
+    let (header, in_out) = out.split_at_mut(HEADER_LEN);
+    let tag = key.seal_in_place_separate_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::from(&*header),
+        in_out,
+    )?;
+    out.extend_from_slice(tag.as_ref());
+    Ok(out)
+}
+
+fn open_v1(password: &[u8], blob: &[u8]) -> Result<Vec<u8>, Unspecified> {
+    if blob.len() < HEADER_LEN + TAG_LEN {
+        return Err(Unspecified);
+    }
+    let (header, ciphertext) = blob.split_at(HEADER_LEN);
+
+    let iterations = u32::from_be_bytes(header[1..5].try_into()?);
+    let iterations = NonZeroU32::new(iterations).ok_or(Unspecified)?;
+    let salt: &[u8; SALT_LEN] = header[5..5 + SALT_LEN].try_into()?;
+    let nonce = Nonce::try_assume_unique_for_key(&header[5 + SALT_LEN..])?;
+
+    let key = derive_key(password, iterations, salt)?;
+    let mut in_out = ciphertext.to_vec();
+    let plaintext_len = match key.open_in_place(nonce, Aad::from(header), &mut in_out) {
+        Ok(plaintext) => plaintext.len(),
+        Err(err) => {
+            in_out.zeroize();
+            return Err(err);
+        }
+    };
+    in_out.truncate(plaintext_len);
+    Ok(in_out)
+}
+
+fn derive_key(
+    password: &[u8],
+    iterations: NonZeroU32,
+    salt: &[u8; SALT_LEN],
+) -> Result<LessSafeKey, Unspecified> {
+    let mut key_bytes = [0u8; KEY_LEN];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        password,
+        &mut key_bytes,
+    );
+    let key = UnboundKey::new(&AES_256_GCM, &key_bytes);
+    key_bytes.zeroize();
+    Ok(LessSafeKey::new(key?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::from_hex;
+
+    const PASSWORD: &[u8] = b"correct horse battery staple";
+    const PLAINTEXT: &[u8] = b"aws-lc-rs password sealing v1";
+
+    // Produced by version 1 of this module; must remain decryptable.
+    const GOLDEN_V1: &str = "01000003e82e5c7a0f9b1d34e6a8c0f2147d9b3e51c1a4e2f6093b7d58e04f1a62\
+                             2627b87124ace9119d176d3f1a7ed559193cd61bc4bb175e8d6b601b616358496a\
+                             2bc69c5aac13afbae8807bb2";
+
+    fn test_params() -> Params {
+        Params::new(NonZeroU32::new(1000).unwrap())
+    }
+
+    #[test]
+    fn test_golden_v1() {
+        let blob = from_hex(GOLDEN_V1).unwrap();
+        assert_eq!(PLAINTEXT, open(PASSWORD, &blob).unwrap().as_slice());
+
+        let salt: [u8; SALT_LEN] = blob[5..21].try_into().unwrap();
+        let nonce: [u8; NONCE_LEN] = blob[21..33].try_into().unwrap();
+        let sealed = seal_v1(
+            PASSWORD,
+            PLAINTEXT,
+            test_params().iterations(),
+            &salt,
+            nonce,
+        )
+        .unwrap();
+        assert_eq!(blob, sealed);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for len in [0, 1, 15, 16, 17, 100] {
+            let plaintext = vec![0x5a; len];
+            let sealed = seal(PASSWORD, &plaintext, &test_params()).unwrap();
+            assert_eq!(HEADER_LEN + len + TAG_LEN, sealed.len());
+            assert_eq!(VERSION_1, sealed[0]);
+            assert_eq!(plaintext, open(PASSWORD, &sealed).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_wrong_password() {
+        let sealed = seal(PASSWORD, PLAINTEXT, &test_params()).unwrap();
+        assert_eq!(Err(Unspecified), open(b"incorrect password", &sealed));
+        assert_eq!(Err(Unspecified), open(b"", &sealed));
+    }
+
+    #[test]
+    fn test_truncated() {
+        let blob = from_hex(GOLDEN_V1).unwrap();
+        for len in 0..blob.len() {
+            assert_eq!(Err(Unspecified), open(PASSWORD, &blob[..len]));
+        }
+    }
+
+    #[test]
+    fn test_tampered() {
+        let blob = from_hex(GOLDEN_V1).unwrap();
+        // Skip the high bytes of the iteration count; flipping them makes the derivation very slow.
+        for i in (0..blob.len()).filter(|i| !(1..3).contains(i)) {
+            let mut tampered = blob.clone();
+            tampered[i] ^= 0x01;
+            assert_eq!(Err(Unspecified), open(PASSWORD, &tampered));
+        }
+    }
+
+    #[test]
+    fn test_zero_iterations_rejected() {
+        let mut blob = from_hex(GOLDEN_V1).unwrap();
+        blob[1..5].copy_from_slice(&[0, 0, 0, 0]);
+        assert_eq!(Err(Unspecified), open(PASSWORD, &blob));
+    }
+
+    #[test]
+    fn test_default_params() {
+        assert_eq!(DEFAULT_ITERATIONS, Params::default().iterations().get());
+    }
+}
@@ -6,7 +6,9 @@
 //! PBKDF2 derivation and verification.
 //!
 //! Use `derive` to derive PBKDF2 outputs. Use `verify` to verify secret
-//! against previously-derived outputs.
+//! against previously-derived outputs. Use `verify_with_params` to verify a
+//! secret against a stored record and learn whether the record's parameters
+//! should be upgraded.
 //!
 //! PBKDF2 is specified in [RFC 2898 Section 5.2] with test vectors given in
 //! [RFC 6070]. See also [NIST Special Publication 800-132].
@@ -113,7 +115,7 @@ use zeroize::Zeroize;
 /// A PBKDF2 algorithm.
 ///
 /// `max_output_len` is computed as u64 instead of usize to prevent overflowing on 32-bit machines.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Algorithm {
     algorithm: hmac::Algorithm,
     max_output_len: u64,
@@ -278,6 +280,147 @@ pub fn verify(
     result
 }
 
+/// The parameters used to derive a PBKDF2 value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Params {
+    algorithm: Algorithm,
+    iterations: NonZeroU32,
+}
+
+impl Params {
+    /// Constructs `Params` from an algorithm and an iteration count.
+    #[must_use]
+    pub fn new(algorithm: Algorithm, iterations: NonZeroU32) -> Self {
+        Self {
+            algorithm,
+            iterations,
+        }
+    }
+
+    /// The PBKDF2 algorithm.
+    #[must_use]
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// The iteration count.
+    #[must_use]
+    pub fn iterations(&self) -> NonZeroU32 {
+        self.iterations
+    }
+
+    /// The digest output length, used to order algorithms by strength.
+    fn strength(&self) -> usize {
+        self.algorithm.algorithm.digest_algorithm().output_len
+    }
+
+    /// Returns whether `self` is weaker than `policy` in either its digest or its iteration count.
+    fn is_below(&self, policy: &Params) -> bool {
+        self.strength() < policy.strength() || self.iterations < policy.iterations
+    }
+}
+
+/// A previously-derived PBKDF2 value together with the parameters it was derived with.
+#[derive(Clone, Copy, Debug)]
+pub struct StoredParams<'a> {
+    params: Params,
+    salt: &'a [u8],
+    previously_derived: &'a [u8],
+}
+
+impl<'a> StoredParams<'a> {
+    /// Constructs `StoredParams` from a stored record.
+    #[must_use]
+    pub fn new(params: Params, salt: &'a [u8], previously_derived: &'a [u8]) -> Self {
+        Self {
+            params,
+            salt,
+            previously_derived,
+        }
+    }
+
+    /// The parameters the stored value was derived with.
+    #[must_use]
+    pub fn params(&self) -> &Params {
+        &self.params
+    }
+
+    /// The salt the stored value was derived with.
+    #[must_use]
+    pub fn salt(&self) -> &'a [u8] {
+        self.salt
+    }
+
+    /// The stored value.
+    #[must_use]
+    pub fn previously_derived(&self) -> &'a [u8] {
+        self.previously_derived
+    }
+}
+
+/// The result of a successful `verify_with_params`.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerifyOutcome {
+    /// The secret matches and the stored parameters meet the policy.
+    Valid,
+
+    /// The secret matches but the stored parameters are below the policy. The
+    /// caller should derive a new value with `recommended` and replace the
+    /// stored record.
+    ValidButNeedsRehash {
+        /// The stronger of the stored parameters and the policy, in both digest
+        /// and iteration count.
+        recommended: Params,
+    },
+}
+
+/// Verifies `secret` against a stored PBKDF2 record, as `verify` does, and
+/// reports whether the record's parameters are below `policy`.
+///
+/// The stored value is always verified with the parameters it carries. Only
+/// once it has been verified are those parameters compared against `policy`:
+/// if the record's digest has a shorter output than the policy's, or its
+/// iteration count is lower, the outcome is
+/// `VerifyOutcome::ValidButNeedsRehash`.
+///
+/// # Errors
+/// `error::Unspecified` if the inputs were not verified.
+///
+/// # Panics
+///
+/// `verify_with_params` panics if the stored value's length is larger than
+/// (2**32 - 1) * the digest algorithm's output length, per the PBKDF2
+/// specification.
+pub fn verify_with_params(
+    stored: &StoredParams<'_>,
+    secret: &[u8],
+    policy: &Params,
+) -> Result<VerifyOutcome, Unspecified> {
+    verify(
+        stored.params.algorithm,
+        stored.params.iterations,
+        stored.salt,
+        secret,
+        stored.previously_derived,
+    )?;
+
+    if !stored.params.is_below(policy) {
+        return Ok(VerifyOutcome::Valid);
+    }
+    let algorithm = if stored.params.strength() < policy.strength() {
+        policy.algorithm
+    } else {
+        stored.params.algorithm
+    };
+    Ok(VerifyOutcome::ValidButNeedsRehash {
+        recommended: Params::new(
+            algorithm,
+            core::cmp::max(stored.params.iterations, policy.iterations),
+        ),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::pbkdf2;
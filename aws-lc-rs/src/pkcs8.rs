@@ -7,9 +7,11 @@
 //!
 //! [RFC 5208]: https://tools.ietf.org/html/rfc5208.
 
-use zeroize::Zeroize;
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 /// A generated PKCS#8 document.
+///
+/// The document contains a private key and is zeroized when dropped.
 pub struct Document {
     bytes: Box<[u8]>,
 }
@@ -27,12 +29,20 @@ impl AsRef<[u8]> for Document {
     }
 }
 
+impl Zeroize for Document {
+    fn zeroize(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
 impl Drop for Document {
     fn drop(&mut self) {
-        self.bytes.zeroize();
+        self.zeroize();
     }
 }
 
+impl ZeroizeOnDrop for Document {}
+
 #[derive(Copy, Clone)]
 pub(crate) enum Version {
     V1,
@@ -0,0 +1,593 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Runtime algorithm policy.
+//!
+//! A [`Registry`] resolves algorithm names and object identifiers (OIDs) to algorithms, and only
+//! resolves the algorithms it permits. Applications that select algorithms dynamically, e.g. from a
+//! configuration file or a certificate, should resolve them through a `Registry` so that the
+//! selection is subject to policy.
+//!
+//! [`Registry::approved`] only permits algorithms that are approved for use by new applications.
+//! When built with the `approved-only` feature, the legacy algorithms are removed from this crate
+//! entirely and [`Registry::new`] behaves like [`Registry::approved`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use aws_lc_rs::{digest, policy::Registry};
+//!
+//! let mut registry = Registry::approved();
+//! assert_eq!(Some(&digest::SHA256), registry.digest("SHA-256"));
+//! assert_eq!(
+//!     Some(&digest::SHA256),
+//!     registry.digest_by_oid("2.16.840.1.101.3.4.2.1")
+//! );
+//!
+//! assert!(registry.verification_algorithm("ECDSA_P256K1_SHA256_ASN1").is_none());
+//!
+//! assert!(registry.deny("SHA-256"));
+//! assert!(registry.digest("SHA-256").is_none());
+//! ```
+
+use crate::signature::VerificationAlgorithm;
+use crate::{digest, hmac, signature};
+use core::fmt;
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Digest(&'static digest::Algorithm),
+    Hmac(&'static hmac::Algorithm),
+    Verification(&'static dyn VerificationAlgorithm),
+}
+
+struct Entry {
+    name: &'static str,
+    oid: Option<&'static str>,
+    approved: bool,
+    kind: Kind,
+}
+
+macro_rules! entry {
+    ($kind:ident, $name:literal, $oid:expr, $approved:literal, $algorithm:expr) => {
+        Entry {
+            name: $name,
+            oid: $oid,
+            approved: $approved,
+            kind: Kind::$kind($algorithm),
+        }
+    };
+}
+
+static ENTRIES: &[Entry] = &[
+    entry!(
+        Digest,
+        "SHA-1",
+        Some("1.3.14.3.2.26"),
+        true,
+        &digest::SHA1_FOR_LEGACY_USE_ONLY
+    ),
+    entry!(
+        Digest,
+        "SHA-224",
+        Some("2.16.840.1.101.3.4.2.4"),
+        true,
+        &digest::SHA224
+    ),
+    entry!(
+        Digest,
+        "SHA-256",
+        Some("2.16.840.1.101.3.4.2.1"),
+        true,
+        &digest::SHA256
+    ),
+    entry!(
+        Digest,
+        "SHA-384",
+        Some("2.16.840.1.101.3.4.2.2"),
+        true,
+        &digest::SHA384
+    ),
+    entry!(
+        Digest,
+        "SHA-512",
+        Some("2.16.840.1.101.3.4.2.3"),
+        true,
+        &digest::SHA512
+    ),
+    entry!(
+        Digest,
+        "SHA-512/256",
+        Some("2.16.840.1.101.3.4.2.6"),
+        true,
+        &digest::SHA512_256
+    ),
+    entry!(
+        Digest,
+        "SHA3-256",
+        Some("2.16.840.1.101.3.4.2.8"),
+        true,
+        &digest::SHA3_256
+    ),
+    entry!(
+        Digest,
+        "SHA3-384",
+        Some("2.16.840.1.101.3.4.2.9"),
+        true,
+        &digest::SHA3_384
+    ),
+    entry!(
+        Digest,
+        "SHA3-512",
+        Some("2.16.840.1.101.3.4.2.10"),
+        true,
+        &digest::SHA3_512
+    ),
+    entry!(
+        Hmac,
+        "HMAC-SHA1",
+        Some("1.2.840.113549.2.7"),
+        true,
+        &hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY
+    ),
+    entry!(
+        Hmac,
+        "HMAC-SHA224",
+        Some("1.2.840.113549.2.8"),
+        true,
+        &hmac::HMAC_SHA224
+    ),
+    entry!(
+        Hmac,
+        "HMAC-SHA256",
+        Some("1.2.840.113549.2.9"),
+        true,
+        &hmac::HMAC_SHA256
+    ),
+    entry!(
+        Hmac,
+        "HMAC-SHA384",
+        Some("1.2.840.113549.2.10"),
+        true,
+        &hmac::HMAC_SHA384
+    ),
+    entry!(
+        Hmac,
+        "HMAC-SHA512",
+        Some("1.2.840.113549.2.11"),
+        true,
+        &hmac::HMAC_SHA512
+    ),
+    #[cfg(not(feature = "approved-only"))]
+    entry!(
+        Verification,
+        "RSA_PKCS1_1024_8192_SHA1_FOR_LEGACY_USE_ONLY",
+        None,
+        false,
+        &signature::RSA_PKCS1_1024_8192_SHA1_FOR_LEGACY_USE_ONLY
+    ),
+    #[cfg(not(feature = "approved-only"))]
+    entry!(
+        Verification,
+        "RSA_PKCS1_1024_8192_SHA256_FOR_LEGACY_USE_ONLY",
+        None,
+        false,
+        &signature::RSA_PKCS1_1024_8192_SHA256_FOR_LEGACY_USE_ONLY
+    ),
+    #[cfg(not(feature = "approved-only"))]
+    entry!(
+        Verification,
+        "RSA_PKCS1_1024_8192_SHA512_FOR_LEGACY_USE_ONLY",
+        None,
+        false,
+        &signature::RSA_PKCS1_1024_8192_SHA512_FOR_LEGACY_USE_ONLY
+    ),
+    #[cfg(not(feature = "approved-only"))]
+    entry!(
+        Verification,
+        "RSA_PKCS1_2048_8192_SHA1_FOR_LEGACY_USE_ONLY",
+        Some("1.2.840.113549.1.1.5"),
+        false,
+        &signature::RSA_PKCS1_2048_8192_SHA1_FOR_LEGACY_USE_ONLY
+    ),
+    entry!(
+        Verification,
+        "RSA_PKCS1_2048_8192_SHA256",
+        Some("1.2.840.113549.1.1.11"),
+        true,
+        &signature::RSA_PKCS1_2048_8192_SHA256
+    ),
+    entry!(
+        Verification,
+        "RSA_PKCS1_2048_8192_SHA384",
+        Some("1.2.840.113549.1.1.12"),
+        true,
+        &signature::RSA_PKCS1_2048_8192_SHA384
+    ),
+    entry!(
+        Verification,
+        "RSA_PKCS1_2048_8192_SHA512",
+        Some("1.2.840.113549.1.1.13"),
+        true,
+        &signature::RSA_PKCS1_2048_8192_SHA512
+    ),
+    entry!(
+        Verification,
+        "RSA_PKCS1_3072_8192_SHA384",
+        None,
+        true,
+        &signature::RSA_PKCS1_3072_8192_SHA384
+    ),
+    entry!(
+        Verification,
+        "RSA_PSS_2048_8192_SHA256",
+        None,
+        true,
+        &signature::RSA_PSS_2048_8192_SHA256
+    ),
+    entry!(
+        Verification,
+        "RSA_PSS_2048_8192_SHA384",
+        None,
+        true,
+        &signature::RSA_PSS_2048_8192_SHA384
+    ),
+    entry!(
+        Verification,
+        "RSA_PSS_2048_8192_SHA512",
+        None,
+        true,
+        &signature::RSA_PSS_2048_8192_SHA512
+    ),
+    entry!(
+        Verification,
+        "ECDSA_P256_SHA256_FIXED",
+        None,
+        true,
+        &signature::ECDSA_P256_SHA256_FIXED
+    ),
+    entry!(
+        Verification,
+        "ECDSA_P384_SHA384_FIXED",
+        None,
+        true,
+        &signature::ECDSA_P384_SHA384_FIXED
+    ),
+    entry!(
+        Verification,
+        "ECDSA_P384_SHA3_384_FIXED",
+        None,
+        true,
+        &signature::ECDSA_P384_SHA3_384_FIXED
+    ),
+    entry!(
+        Verification,
+        "ECDSA_P521_SHA512_FIXED",
+        None,
+        true,
+        &signature::ECDSA_P521_SHA512_FIXED
+    ),
+    entry!(
+        Verification,
+        "ECDSA_P521_SHA3_512_FIXED",
+        None,
+        true,
+        &signature::ECDSA_P521_SHA3_512_FIXED
+    ),
+    entry!(
+        Verification,
+        "ECDSA_P256K1_SHA256_FIXED",
+        None,
+        false,
+        &signature::ECDSA_P256K1_SHA256_FIXED
+    ),
+    entry!(
+        Verification,
+        "ECDSA_P256K1_SHA3_256_FIXED",
+        None,
+        false,
+        &signature::ECDSA_P256K1_SHA3_256_FIXED
+    ),
+    entry!(
+        Verification,
+        "ECDSA_P256_SHA256_ASN1",
+        None,
+        true,
+        &signature::ECDSA_P256_SHA256_ASN1
+    ),
+    entry!(
+        Verification,
+        "ECDSA_P256_SHA384_ASN1",
+        None,
+        true,
+        &signature::ECDSA_P256_SHA384_ASN1
+    ),
+    entry!(
+        Verification,
+        "ECDSA_P384_SHA256_ASN1",
+        None,
+        true,
+        &signature::ECDSA_P384_SHA256_ASN1
+    ),
+    entry!(
+        Verification,
+        "ECDSA_P384_SHA384_ASN1",
+        None,
+        true,
+        &signature::ECDSA_P384_SHA384_ASN1
+    ),
+    entry!(
+        Verification,
+        "ECDSA_P384_SHA3_384_ASN1",
+        None,
+        true,
+        &signature::ECDSA_P384_SHA3_384_ASN1
+    ),
+    entry!(
+        Verification,
+        "ECDSA_P521_SHA512_ASN1",
+        None,
+        true,
+        &signature::ECDSA_P521_SHA512_ASN1
+    ),
+    entry!(
+        Verification,
+        "ECDSA_P521_SHA3_512_ASN1",
+        None,
+        true,
+        &signature::ECDSA_P521_SHA3_512_ASN1
+    ),
+    entry!(
+        Verification,
+        "ECDSA_P256K1_SHA256_ASN1",
+        None,
+        false,
+        &signature::ECDSA_P256K1_SHA256_ASN1
+    ),
+    entry!(
+        Verification,
+        "ECDSA_P256K1_SHA3_256_ASN1",
+        None,
+        false,
+        &signature::ECDSA_P256K1_SHA3_256_ASN1
+    ),
+    entry!(
+        Verification,
+        "ED25519",
+        Some("1.3.101.112"),
+        false,
+        &signature::ED25519
+    ),
+];
+
+/// A set of permitted algorithms that can be looked up by name or OID.
+///
+/// Names are matched case-insensitively. Digests are named as in FIPS 180-4 and FIPS 202 (e.g.
+/// `SHA-256`, `SHA3-384`), HMAC algorithms as `HMAC-` followed by the digest name without the
+/// hyphen (e.g. `HMAC-SHA256`), and signature verification algorithms by the name of the
+/// corresponding constant in [`crate::signature`] (e.g. `ECDSA_P256_SHA256_ASN1`). OIDs are in
+/// dotted-decimal form.
+#[derive(Clone)]
+pub struct Registry {
+    entries: Vec<&'static Entry>,
+}
+
+impl Registry {
+    /// Constructs a `Registry` permitting every algorithm available in this build.
+    ///
+    /// With the `approved-only` feature this is the same as [`Registry::approved`].
+    #[must_use]
+    pub fn new() -> Self {
+        if cfg!(feature = "approved-only") {
+            Self::approved()
+        } else {
+            Self {
+                entries: ENTRIES.iter().collect(),
+            }
+        }
+    }
+
+    /// Constructs a `Registry` permitting only algorithms approved for use by new applications.
+    ///
+    /// This excludes RSA signature verification with moduli smaller than 2048 bits or with
+    /// SHA-1, and ECDSA on secp256k1. Ed25519 is also excluded: although FIPS 186-5 specifies it,
+    /// the bundled AWS-LC FIPS module does not report it as an approved service.
+    #[must_use]
+    pub fn approved() -> Self {
+        Self {
+            entries: ENTRIES.iter().filter(|entry| entry.approved).collect(),
+        }
+    }
+
+    /// Removes the algorithm named `name` from the registry.
+    ///
+    /// Returns `true` if the algorithm was permitted before the call.
+    pub fn deny(&mut self, name: &str) -> bool {
+        let len = self.entries.len();
+        self.entries
+            .retain(|entry| !entry.name.eq_ignore_ascii_case(name));
+        len != self.entries.len()
+    }
+
+    /// Returns the names of the permitted algorithms.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.entries.iter().map(|entry| entry.name)
+    }
+
+    /// Returns the permitted digest algorithm named `name`.
+    #[must_use]
+    pub fn digest(&self, name: &str) -> Option<&'static digest::Algorithm> {
+        self.find_by_name(name).and_then(|kind| match kind {
+            Kind::Digest(algorithm) => Some(algorithm),
+            _ => None,
+        })
+    }
+
+    /// Returns the permitted digest algorithm identified by `oid`.
+    #[must_use]
+    pub fn digest_by_oid(&self, oid: &str) -> Option<&'static digest::Algorithm> {
+        self.find_by_oid(oid).and_then(|kind| match kind {
+            Kind::Digest(algorithm) => Some(algorithm),
+            _ => None,
+        })
+    }
+
+    /// Returns the permitted HMAC algorithm named `name`.
+    #[must_use]
+    pub fn hmac(&self, name: &str) -> Option<&'static hmac::Algorithm> {
+        self.find_by_name(name).and_then(|kind| match kind {
+            Kind::Hmac(algorithm) => Some(algorithm),
+            _ => None,
+        })
+    }
+
+    /// Returns the permitted HMAC algorithm identified by `oid`.
+    #[must_use]
+    pub fn hmac_by_oid(&self, oid: &str) -> Option<&'static hmac::Algorithm> {
+        self.find_by_oid(oid).and_then(|kind| match kind {
+            Kind::Hmac(algorithm) => Some(algorithm),
+            _ => None,
+        })
+    }
+
+    /// Returns the permitted signature verification algorithm named `name`.
+    #[must_use]
+    pub fn verification_algorithm(&self, name: &str) -> Option<&'static dyn VerificationAlgorithm> {
+        self.find_by_name(name).and_then(|kind| match kind {
+            Kind::Verification(algorithm) => Some(algorithm),
+            _ => None,
+        })
+    }
+
+    /// Returns the permitted signature verification algorithm identified by `oid`.
+    ///
+    /// Only algorithms with an unambiguous OID can be found this way; for example, the
+    /// `ecdsa-with-SHA256` OID does not identify a curve.
+    #[must_use]
+    pub fn verification_algorithm_by_oid(
+        &self,
+        oid: &str,
+    ) -> Option<&'static dyn VerificationAlgorithm> {
+        self.find_by_oid(oid).and_then(|kind| match kind {
+            Kind::Verification(algorithm) => Some(algorithm),
+            _ => None,
+        })
+    }
+
+    fn find_by_name(&self, name: &str) -> Option<Kind> {
+        self.entries
+            .iter()
+            .find(|entry| entry.name.eq_ignore_ascii_case(name))
+            .map(|entry| entry.kind)
+    }
+
+    fn find_by_oid(&self, oid: &str) -> Option<Kind> {
+        self.entries
+            .iter()
+            .find(|entry| entry.oid == Some(oid))
+            .map(|entry| entry.kind)
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::policy::Registry;
+    use crate::{digest, hmac};
+
+    const LEGACY_RSA: &str = "RSA_PKCS1_1024_8192_SHA256_FOR_LEGACY_USE_ONLY";
+
+    #[test]
+    fn test_lookup() {
+        let registry = Registry::new();
+        assert_eq!(Some(&digest::SHA384), registry.digest("sha-384"));
+        assert_eq!(
+            Some(&digest::SHA3_256),
+            registry.digest_by_oid("2.16.840.1.101.3.4.2.8")
+        );
+        assert_eq!(Some(&hmac::HMAC_SHA512), registry.hmac("HMAC-SHA512"));
+        assert_eq!(
+            Some(&hmac::HMAC_SHA256),
+            registry.hmac_by_oid("1.2.840.113549.2.9")
+        );
+        assert!(registry
+            .verification_algorithm("ECDSA_P384_SHA384_ASN1")
+            .is_some());
+        assert!(registry
+            .verification_algorithm_by_oid("1.2.840.113549.1.1.11")
+            .is_some());
+
+        // Lookups are by kind
+        assert!(registry.digest("HMAC-SHA256").is_none());
+        assert!(registry.hmac_by_oid("2.16.840.1.101.3.4.2.1").is_none());
+        assert!(registry.digest("MD5").is_none());
+    }
+
+    #[test]
+    fn test_approved() {
+        let registry = Registry::approved();
+        assert!(registry.verification_algorithm(LEGACY_RSA).is_none());
+        assert!(registry
+            .verification_algorithm_by_oid("1.2.840.113549.1.1.5")
+            .is_none());
+        assert!(registry
+            .verification_algorithm("ECDSA_P256K1_SHA256_ASN1")
+            .is_none());
+        assert!(registry
+            .verification_algorithm("RSA_PKCS1_2048_8192_SHA256")
+            .is_some());
+        assert!(registry.verification_algorithm("ED25519").is_none());
+        assert!(registry
+            .verification_algorithm_by_oid("1.3.101.112")
+            .is_none());
+        assert_eq!(Some(&digest::SHA256), registry.digest("SHA-256"));
+    }
+
+    #[cfg(not(feature = "approved-only"))]
+    #[test]
+    fn test_legacy_available() {
+        let registry = Registry::new();
+        assert!(registry.verification_algorithm(LEGACY_RSA).is_some());
+        assert!(registry.verification_algorithm("ED25519").is_some());
+        assert!(registry.names().count() > Registry::approved().names().count());
+    }
+
+    #[cfg(feature = "approved-only")]
+    #[test]
+    fn test_legacy_unavailable() {
+        let registry = Registry::new();
+        assert!(registry.verification_algorithm(LEGACY_RSA).is_none());
+        assert!(registry
+            .verification_algorithm_by_oid("1.2.840.113549.1.1.5")
+            .is_none());
+        assert!(registry
+            .verification_algorithm("ECDSA_P256K1_SHA256_ASN1")
+            .is_none());
+        assert_eq!(
+            Registry::approved().names().collect::<Vec<_>>(),
+            registry.names().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_deny() {
+        let mut registry = Registry::default();
+        assert!(registry.deny("sha-512"));
+        assert!(!registry.deny("SHA-512"));
+        assert!(registry.digest("SHA-512").is_none());
+        assert!(registry.digest_by_oid("2.16.840.1.101.3.4.2.3").is_none());
+        assert!(!format!("{registry:?}").contains("\"SHA-512\""));
+        assert!(format!("{registry:?}").contains("\"SHA-256\""));
+    }
+}
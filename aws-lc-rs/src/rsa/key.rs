@@ -34,7 +34,7 @@ use mirai_annotations::verify_unreachable;
 #[cfg(feature = "ring-io")]
 use untrusted::Input;
 
-use zeroize::Zeroize;
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 use super::{
     encoding,
@@ -98,6 +98,8 @@ impl KeySize {
 }
 
 /// An RSA key pair, used for signing.
+///
+/// The private key is freed and zeroized by *AWS-LC* when dropped.
 #[allow(clippy::module_name_repetitions)]
 pub struct KeyPair {
     // https://github.com/aws/aws-lc/blob/ebaa07a207fee02bd68fe8d65f6b624afbf29394/include/openssl/evp.h#L295
@@ -357,6 +359,8 @@ impl Debug for KeyPair {
     }
 }
 
+impl ZeroizeOnDrop for KeyPair {}
+
 impl crate::signature::KeyPair for KeyPair {
     type PublicKey = PublicKey;
 
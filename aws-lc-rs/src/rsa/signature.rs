@@ -143,9 +143,13 @@ impl RsaParameters {
 #[derive(Debug)]
 #[allow(non_camel_case_types)]
 pub(crate) enum RsaVerificationAlgorithmId {
+    #[cfg(not(feature = "approved-only"))]
     RSA_PKCS1_1024_8192_SHA1_FOR_LEGACY_USE_ONLY,
+    #[cfg(not(feature = "approved-only"))]
     RSA_PKCS1_1024_8192_SHA256_FOR_LEGACY_USE_ONLY,
+    #[cfg(not(feature = "approved-only"))]
     RSA_PKCS1_1024_8192_SHA512_FOR_LEGACY_USE_ONLY,
+    #[cfg(not(feature = "approved-only"))]
     RSA_PKCS1_2048_8192_SHA1_FOR_LEGACY_USE_ONLY,
     RSA_PKCS1_2048_8192_SHA256,
     RSA_PKCS1_2048_8192_SHA384,
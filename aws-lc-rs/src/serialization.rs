@@ -0,0 +1,141 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Helpers for the `serde` feature.
+//!
+//! Byte strings are serialized as lower-case hex in human-readable formats and as raw bytes
+//! otherwise. Values whose length depends on an algorithm are serialized as a struct with an
+//! `algorithm` name and a `value`.
+
+use crate::hex;
+use core::fmt;
+use serde::de::{self, MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+const FIELDS: &[&str] = &["algorithm", "value"];
+
+pub(crate) struct Bytes<'a>(pub(crate) &'a [u8]);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(self.0, serializer)
+    }
+}
+
+pub(crate) struct ByteBuf(pub(crate) Vec<u8>);
+
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_bytes(deserializer).map(ByteBuf)
+    }
+}
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a hex string or a byte array")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        if v.len() % 2 != 0 {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+        hex::decode(v).map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(bytes)
+    }
+}
+
+pub(crate) fn serialize_bytes<S: Serializer>(
+    bytes: &[u8],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&hex::encode(bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+pub(crate) fn deserialize_bytes<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<u8>, D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(BytesVisitor)
+    } else {
+        deserializer.deserialize_byte_buf(BytesVisitor)
+    }
+}
+
+pub(crate) fn serialize_with_algorithm<S: Serializer>(
+    name: &'static str,
+    algorithm: &'static str,
+    bytes: &[u8],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut state = serializer.serialize_struct(name, FIELDS.len())?;
+    state.serialize_field(FIELDS[0], algorithm)?;
+    state.serialize_field(FIELDS[1], &Bytes(bytes))?;
+    state.end()
+}
+
+struct AlgorithmAndValueVisitor(&'static str);
+
+impl<'de> Visitor<'de> for AlgorithmAndValueVisitor {
+    type Value = (String, Vec<u8>);
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "struct {}", self.0)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let algorithm: String = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let value: ByteBuf = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Ok((algorithm, value.0))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut algorithm: Option<String> = None;
+        let mut value: Option<ByteBuf> = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "algorithm" if algorithm.is_none() => algorithm = Some(map.next_value()?),
+                "value" if value.is_none() => value = Some(map.next_value()?),
+                "algorithm" | "value" => return Err(de::Error::custom("duplicate field")),
+                _ => return Err(de::Error::unknown_field(&key, FIELDS)),
+            }
+        }
+        let algorithm = algorithm.ok_or_else(|| de::Error::missing_field(FIELDS[0]))?;
+        let value = value.ok_or_else(|| de::Error::missing_field(FIELDS[1]))?;
+        Ok((algorithm, value.0))
+    }
+}
+
+pub(crate) fn deserialize_with_algorithm<'de, D: Deserializer<'de>>(
+    name: &'static str,
+    deserializer: D,
+) -> Result<(String, Vec<u8>), D::Error> {
+    deserializer.deserialize_struct(name, FIELDS, AlgorithmAndValueVisitor(name))
+}
@@ -301,6 +301,33 @@ impl AsRef<[u8]> for Signature {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Signature {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::serialization::serialize_bytes(self.as_ref(), serializer)
+    }
+}
+
+/// Rejects empty values and values longer than the largest supported signature.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Signature {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let value = crate::serialization::deserialize_bytes(deserializer)?;
+        if value.is_empty() || value.len() > MAX_LEN {
+            return Err(D::Error::invalid_length(
+                value.len(),
+                &"a supported signature length",
+            ));
+        }
+        Ok(Signature::new(|bytes| {
+            bytes[..value.len()].copy_from_slice(&value);
+            value.len()
+        }))
+    }
+}
+
 /// Key pairs for signing messages (private key and public key).
 pub trait KeyPair: Debug + Send + Sized + Sync {
     /// The type of the public key.
@@ -400,6 +427,7 @@ impl<B: AsRef<[u8]>> UnparsedPublicKey<B> {
 }
 
 /// Verification of signatures using RSA keys of 1024-8192 bits, PKCS#1.5 padding, and SHA-1.
+#[cfg(not(feature = "approved-only"))]
 pub static RSA_PKCS1_1024_8192_SHA1_FOR_LEGACY_USE_ONLY: RsaParameters = RsaParameters::new(
     &digest::SHA1_FOR_LEGACY_USE_ONLY,
     &rsa::signature::RsaPadding::RSA_PKCS1_PADDING,
@@ -408,6 +436,7 @@ pub static RSA_PKCS1_1024_8192_SHA1_FOR_LEGACY_USE_ONLY: RsaParameters = RsaPara
 );
 
 /// Verification of signatures using RSA keys of 1024-8192 bits, PKCS#1.5 padding, and SHA-256.
+#[cfg(not(feature = "approved-only"))]
 pub static RSA_PKCS1_1024_8192_SHA256_FOR_LEGACY_USE_ONLY: RsaParameters = RsaParameters::new(
     &digest::SHA256,
     &rsa::signature::RsaPadding::RSA_PKCS1_PADDING,
@@ -416,6 +445,7 @@ pub static RSA_PKCS1_1024_8192_SHA256_FOR_LEGACY_USE_ONLY: RsaParameters = RsaPa
 );
 
 /// Verification of signatures using RSA keys of 1024-8192 bits, PKCS#1.5 padding, and SHA-512.
+#[cfg(not(feature = "approved-only"))]
 pub static RSA_PKCS1_1024_8192_SHA512_FOR_LEGACY_USE_ONLY: RsaParameters = RsaParameters::new(
     &digest::SHA512,
     &rsa::signature::RsaPadding::RSA_PKCS1_PADDING,
@@ -424,6 +454,7 @@ pub static RSA_PKCS1_1024_8192_SHA512_FOR_LEGACY_USE_ONLY: RsaParameters = RsaPa
 );
 
 /// Verification of signatures using RSA keys of 2048-8192 bits, PKCS#1.5 padding, and SHA-1.
+#[cfg(not(feature = "approved-only"))]
 pub static RSA_PKCS1_2048_8192_SHA1_FOR_LEGACY_USE_ONLY: RsaParameters = RsaParameters::new(
     &digest::SHA1_FOR_LEGACY_USE_ONLY,
     &rsa::signature::RsaPadding::RSA_PKCS1_PADDING,
@@ -16,16 +16,19 @@ use crate::{
         ECDSA_P384_SHA3_384_FIXED_SIGNING, ECDSA_P521_SHA3_512_ASN1,
         ECDSA_P521_SHA3_512_ASN1_SIGNING, ECDSA_P521_SHA3_512_FIXED,
         ECDSA_P521_SHA3_512_FIXED_SIGNING, ECDSA_P521_SHA512_ASN1, ECDSA_P521_SHA512_ASN1_SIGNING,
-        ECDSA_P521_SHA512_FIXED, ECDSA_P521_SHA512_FIXED_SIGNING,
-        RSA_PKCS1_1024_8192_SHA1_FOR_LEGACY_USE_ONLY,
-        RSA_PKCS1_1024_8192_SHA256_FOR_LEGACY_USE_ONLY,
-        RSA_PKCS1_1024_8192_SHA512_FOR_LEGACY_USE_ONLY, RSA_PKCS1_2048_8192_SHA256,
+        ECDSA_P521_SHA512_FIXED, ECDSA_P521_SHA512_FIXED_SIGNING, RSA_PKCS1_2048_8192_SHA256,
         RSA_PKCS1_2048_8192_SHA384, RSA_PKCS1_2048_8192_SHA512, RSA_PKCS1_SHA256, RSA_PKCS1_SHA384,
         RSA_PKCS1_SHA512, RSA_PSS_2048_8192_SHA256, RSA_PSS_2048_8192_SHA384,
         RSA_PSS_2048_8192_SHA512, RSA_PSS_SHA256, RSA_PSS_SHA384, RSA_PSS_SHA512,
     },
 };
 
+#[cfg(not(feature = "approved-only"))]
+use crate::signature::{
+    RSA_PKCS1_1024_8192_SHA1_FOR_LEGACY_USE_ONLY, RSA_PKCS1_1024_8192_SHA256_FOR_LEGACY_USE_ONLY,
+    RSA_PKCS1_1024_8192_SHA512_FOR_LEGACY_USE_ONLY,
+};
+
 mod keys;
 
 use keys::*;
@@ -400,6 +403,7 @@ rsa_sign_verify!(
     FipsServiceStatus::NonApproved
 );
 
+#[cfg(not(feature = "approved-only"))]
 macro_rules! rsa_verify {
     ($name:ident, $key:expr, $verify_alg:expr, $signature:expr, $verify_expect:path) => {
         #[test]
@@ -413,6 +417,7 @@ macro_rules! rsa_verify {
     };
 }
 
+#[cfg(not(feature = "approved-only"))]
 rsa_verify!(
     rsa_pkcs1_1024_sha1,
     &TEST_RSA_1024_PUBLIC_BYTES[..],
@@ -420,6 +425,7 @@ rsa_verify!(
     &TEST_MESSAGE_RSA_PKCS1_1024_SHA1,
     FipsServiceStatus::Approved
 );
+#[cfg(not(feature = "approved-only"))]
 rsa_verify!(
     rsa_pkcs1_1024_sha256,
     &TEST_RSA_1024_PUBLIC_BYTES[..],
@@ -435,6 +441,7 @@ rsa_verify!(
 //     &TEST_MESSAGE_RSA_PKCS1_1024_SHA384,
 //     FipsServiceStatus::Approved
 // );
+#[cfg(not(feature = "approved-only"))]
 rsa_verify!(
     rsa_pkcs1_1024_sha512,
     &TEST_RSA_1024_PUBLIC_BYTES[..],
@@ -112,6 +112,7 @@ pub const TEST_RSA_1024_PRIVATE_PKCS8_DER: [u8; 635] = [
     0x31, 0x3f, 0xc5, 0xb2, 0xcb, 0x52, 0x40, 0x6a, 0xca, 0xb1, 0x69,
 ];
 
+#[cfg(not(feature = "approved-only"))]
 pub const TEST_RSA_1024_PUBLIC_BYTES: [u8; 140] = [
     0x30, 0x81, 0x89, 0x02, 0x81, 0x81, 0x00, 0xc7, 0x79, 0x29, 0x48, 0x14, 0x74, 0x59, 0xaa, 0x6a,
     0x79, 0xec, 0x9a, 0x4e, 0x4c, 0x40, 0x71, 0x95, 0xa3, 0x0a, 0xf0, 0x19, 0x34, 0xd4, 0x21, 0x6a,
@@ -768,6 +769,7 @@ pub const TEST_RSA_8192_PRIVATE_PKCS8_DER: [u8; 4679] = [
 ];
 
 // `TEST_MESSAGE` sha1 signature signed by `TEST_RSA_1024_PRIVATE_PKCS8_DER`
+#[cfg(not(feature = "approved-only"))]
 pub const TEST_MESSAGE_RSA_PKCS1_1024_SHA1: [u8; 128] = [
     0x97, 0x49, 0x27, 0xe5, 0x90, 0xa6, 0x69, 0x83, 0xd5, 0xc9, 0x2e, 0x03, 0x02, 0x69, 0x75, 0xb9,
     0xf7, 0x13, 0x7d, 0x7c, 0x8b, 0xf5, 0x7d, 0xa4, 0xfa, 0x41, 0x59, 0x44, 0x86, 0x99, 0x07, 0x6d,
@@ -779,6 +781,7 @@ pub const TEST_MESSAGE_RSA_PKCS1_1024_SHA1: [u8; 128] = [
     0x6c, 0x92, 0xa3, 0xf8, 0xf3, 0xdd, 0xb7, 0xd8, 0x43, 0xed, 0x2b, 0x77, 0x1f, 0xd4, 0x66, 0xa0,
 ];
 
+#[cfg(not(feature = "approved-only"))]
 pub const TEST_MESSAGE_RSA_PKCS1_1024_SHA256: [u8; 128] = [
     0xaa, 0x54, 0x84, 0xbb, 0x98, 0xc9, 0x92, 0x42, 0xb2, 0x2b, 0xac, 0x5c, 0x7b, 0x1b, 0x8c, 0x4d,
     0xf0, 0xcf, 0x05, 0xa4, 0x61, 0xb6, 0x4e, 0x0e, 0x19, 0xcf, 0xa2, 0x4d, 0x7e, 0x93, 0x05, 0x12,
@@ -802,6 +805,7 @@ pub const TEST_MESSAGE_RSA_PKCS1_1024_SHA384: [u8; 128] = [
     0x4b, 0xa4, 0xa2, 0xf2, 0x7c, 0x1f, 0xae, 0xda, 0xe9, 0x74, 0x04, 0xa8, 0x77, 0x31, 0xde, 0x78,
 ];
 
+#[cfg(not(feature = "approved-only"))]
 pub const TEST_MESSAGE_RSA_PKCS1_1024_SHA512: [u8; 128] = [
     0x28, 0x70, 0x34, 0xdd, 0x0a, 0x4e, 0xb0, 0x0f, 0xe4, 0xa7, 0xe7, 0xdb, 0x74, 0x45, 0x90, 0xab,
     0xb6, 0x98, 0x81, 0x8e, 0xd2, 0x4a, 0xff, 0x17, 0xe4, 0x8b, 0x20, 0xf4, 0xd5, 0xa9, 0x19, 0x5e,
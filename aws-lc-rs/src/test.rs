@@ -13,6 +13,10 @@
 //! tests are the most complicated because they use named sections. Other tests
 //! avoid named sections and so are easier to understand.
 //!
+//! The module is a supported part of the public API when the `test-utils` feature is enabled,
+//! so that downstream crates can ship known-answer tests in the same style. Without the feature
+//! it remains available but undocumented, and may change without notice.
+//!
 //! # Examples
 //!
 //! ## Writing Tests
@@ -41,21 +45,51 @@
 //!
 //! Here's how you would consume the test data:
 //!
-//! ```ignore
-//! use aws_lc_rs::test;
+//! ```rust
+//! use aws_lc_rs::{digest, hmac, test};
 //!
-//! test::run(test::test_file!("hmac_tests.txt"), |section, test_case| {
+//! # let hmac_tests = test::File {
+//! #     file_name: "hmac_tests.txt",
+//! #     contents: r#"
+//! # HMAC = SHA256
+//! # Input = "Sample message for keylen<blocklen"
+//! # Key = 000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F
+//! # Output = A28CF43130EE696A98F14A37678B56BCFCBDD9E5CF69717FECF5480F0EBDF790
+//! # "#,
+//! # };
+//! // `hmac_tests` is usually loaded with `aws_lc_rs::test_file!("hmac_tests.txt")`.
+//! test::run(hmac_tests, |section, test_case| {
 //!     assert_eq!(section, ""); // This test doesn't use named sections.
 //!
-//!     let digest_alg = test_case.consume_digest_alg("HMAC");
+//!     let digest_alg = test_case.consume_digest_alg("HMAC").unwrap();
 //!     let input = test_case.consume_bytes("Input");
 //!     let key = test_case.consume_bytes("Key");
 //!     let output = test_case.consume_bytes("Output");
 //!
-//!     // Do the actual testing here
+//!     assert_eq!(digest_alg, &digest::SHA256);
+//!     let key = hmac::Key::new(hmac::HMAC_SHA256, &key);
+//!     assert_eq!(output, hmac::sign(&key, &input).as_ref());
+//!     Ok(())
 //! });
 //! ```
 //!
+//! ## Deterministic Randomness
+//!
+//! The types in [`rand`] implement `SecureRandom` with fixed output, for known-answer tests of
+//! operations that consume randomness:
+//!
+//! ```rust
+//! use aws_lc_rs::rand::SecureRandom;
+//! use aws_lc_rs::test;
+//!
+//! let expected = test::from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+//! let rng = test::rand::FixedSliceRandom { bytes: &expected };
+//!
+//! let mut nonce = [0u8; 16];
+//! rng.fill(&mut nonce).unwrap();
+//! assert_eq!("000102030405060708090a0b0c0d0e0f", test::to_hex(&nonce));
+//! ```
+//!
 //! Note that `consume_digest_alg` automatically maps the string "SHA1" to a
 //! reference to `digest::SHA1_FOR_LEGACY_USE_ONLY`, "SHA256" to
 //! `digest::SHA256`, etc.
@@ -108,8 +142,6 @@
 //! stack trace to the line in the test code that panicked: entry 9 in the
 //! stack trace pointing to line 652 of the file `example.rs`.
 
-#![doc(hidden)]
-
 extern crate alloc;
 
 use mirai_annotations::unrecoverable;
@@ -425,6 +457,7 @@ pub mod rand {
     /// with the given byte.
     #[derive(Debug)]
     pub struct FixedByteRandom {
+        /// The byte every output is filled with.
         pub byte: u8,
     }
 
@@ -440,6 +473,7 @@ pub mod rand {
     /// must match exactly.
     #[derive(Debug)]
     pub struct FixedSliceRandom<'a> {
+        /// The output of every call to `fill()`.
         pub bytes: &'a [u8],
     }
 
@@ -462,8 +496,9 @@ pub mod rand {
     /// entry in `bytes`.
     #[derive(Debug)]
     pub struct FixedSliceSequenceRandom<'a> {
-        /// The value.
+        /// The outputs of the successive calls to `fill()`.
         pub bytes: &'a [&'a [u8]],
+        /// The index of the next output in `bytes`.
         pub current: core::cell::UnsafeCell<usize>,
     }
 
@@ -33,6 +33,7 @@ use crate::{
 use core::ptr::null;
 
 use aws_lc::CRYPTO_tls1_prf;
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 /// The TLS PRF `P_hash` Algorithm
 pub struct Algorithm(AlgorithmID);
@@ -53,6 +54,8 @@ pub const P_SHA384: Algorithm = Algorithm(AlgorithmID::SHA384);
 pub const P_SHA512: Algorithm = Algorithm(AlgorithmID::SHA512);
 
 /// Encapsulates a PRF algorithm and secret bytes to be used to derive output.
+///
+/// The secret bytes are zeroized when dropped.
 pub struct Secret {
     algorithm: &'static Algorithm,
     secret: Box<[u8]>,
@@ -101,13 +104,20 @@ impl Secret {
     }
 }
 
+impl Zeroize for Secret {
+    fn zeroize(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
 impl Drop for Secret {
     fn drop(&mut self) {
-        use zeroize::Zeroize;
-        self.secret.zeroize();
+        self.zeroize();
     }
 }
 
+impl ZeroizeOnDrop for Secret {}
+
 impl AsRef<[u8]> for Secret {
     fn as_ref(&self) -> &[u8] {
         &self.secret
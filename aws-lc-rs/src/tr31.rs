@@ -0,0 +1,618 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! ANSI X9 TR-31 key blocks.
+//!
+//! Implements version `D` key blocks, which use the AES key derivation binding method: the
+//! key block protection key (KBPK) is used to derive a key block encryption key (KBEK) and a
+//! key block MAC key (KBMK) via a CMAC-based KDF. The MAC is computed over the header and the
+//! cleartext key field, and the key field is encrypted with AES-CBC using the MAC as the IV.
+//!
+//! Optional header blocks are not supported.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::cipher::{UnboundCipherKey, AES_128, AES_256};
+//! use aws_lc_rs::tr31::{self, Exportability, Header, KeyAlgorithm, KeyUsage, ModeOfUse};
+//!
+//! let kbpk_bytes = [0x42u8; 32];
+//! let working_key = [0x17u8; 16];
+//!
+//! let header = Header::new(
+//!     KeyUsage::DATA_ENCRYPTION,
+//!     KeyAlgorithm::Aes,
+//!     ModeOfUse::Both,
+//!     Exportability::Exportable,
+//! );
+//!
+//! let kbpk = UnboundCipherKey::new(&AES_256, &kbpk_bytes)?;
+//! let key_block = tr31::wrap(&kbpk, &header, &working_key)?;
+//!
+//! let (parsed_header, key) = tr31::unwrap(&kbpk, &key_block)?;
+//! assert_eq!(header, parsed_header);
+//! assert_eq!(&AES_128, key.algorithm());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::cipher::cmac::aes_cmac;
+use crate::cipher::{
+    AlgorithmId, DecryptingKey, DecryptionContext, EncryptingKey, EncryptionContext, OperatingMode,
+    UnboundCipherKey, AES_128, AES_128_KEY_LEN, AES_192, AES_192_KEY_LEN, AES_256, AES_256_KEY_LEN,
+};
+use crate::constant_time;
+use crate::error::Unspecified;
+use crate::hex;
+use crate::iv::FixedLength;
+use crate::rand;
+use zeroize::Zeroize;
+
+const VERSION_D: u8 = b'D';
+const HEADER_LEN: usize = 16;
+const BLOCK_LEN: usize = 16;
+const MAC_LEN: usize = 16;
+const KEY_LENGTH_FIELD_LEN: usize = 2;
+const MAX_KEY_BLOCK_LEN: usize = 9999;
+
+/// The intended use of the protected key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyUsage([u8; 2]);
+
+impl KeyUsage {
+    /// `B0`: Base Derivation Key (BDK).
+    pub const BASE_DERIVATION_KEY: KeyUsage = KeyUsage(*b"B0");
+
+    /// `D0`: Symmetric key for data encryption.
+    pub const DATA_ENCRYPTION: KeyUsage = KeyUsage(*b"D0");
+
+    /// `K0`: Key encryption or wrapping key.
+    pub const KEY_ENCRYPTION: KeyUsage = KeyUsage(*b"K0");
+
+    /// `K1`: TR-31 key block protection key.
+    pub const KEY_BLOCK_PROTECTION: KeyUsage = KeyUsage(*b"K1");
+
+    /// `P0`: PIN encryption key.
+    pub const PIN_ENCRYPTION: KeyUsage = KeyUsage(*b"P0");
+
+    /// Constructs a `KeyUsage` from its two character code.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `code` is not two ASCII alphanumeric characters.
+    pub fn new(code: [u8; 2]) -> Result<Self, Unspecified> {
+        if !code.iter().all(u8::is_ascii_alphanumeric) {
+            return Err(Unspecified);
+        }
+        Ok(KeyUsage(code))
+    }
+
+    /// Returns the two character code.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8; 2] {
+        &self.0
+    }
+}
+
+macro_rules! header_field_enum {
+    ($(#[$meta:meta])* $name:ident { $($(#[$vmeta:meta])* $variant:ident = $code:literal,)+ }) => {
+        $(#[$meta])*
+        #[non_exhaustive]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub enum $name {
+            $($(#[$vmeta])* $variant,)+
+        }
+
+        impl $name {
+            fn code(self) -> u8 {
+                match self {
+                    $($name::$variant => $code,)+
+                }
+            }
+
+            fn from_code(code: u8) -> Result<Self, Unspecified> {
+                match code {
+                    $($code => Ok($name::$variant),)+
+                    _ => Err(Unspecified),
+                }
+            }
+        }
+    };
+}
+
+header_field_enum!(
+    /// The algorithm with which the protected key is used.
+    KeyAlgorithm {
+        /// `A`: AES
+        Aes = b'A',
+        /// `D`: DEA
+        Des = b'D',
+        /// `E`: Elliptic Curve
+        Ec = b'E',
+        /// `H`: HMAC
+        Hmac = b'H',
+        /// `R`: RSA
+        Rsa = b'R',
+        /// `S`: DSA
+        Dsa = b'S',
+        /// `T`: Triple DEA
+        Tdea = b'T',
+    }
+);
+
+header_field_enum!(
+    /// The operations the protected key may perform.
+    ModeOfUse {
+        /// `B`: Both encrypt and decrypt / wrap and unwrap.
+        Both = b'B',
+        /// `C`: Both generate and verify.
+        GenerateAndVerify = b'C',
+        /// `D`: Decrypt / unwrap only.
+        DecryptOnly = b'D',
+        /// `E`: Encrypt / wrap only.
+        EncryptOnly = b'E',
+        /// `G`: Generate only.
+        GenerateOnly = b'G',
+        /// `N`: No special restrictions.
+        NoRestrictions = b'N',
+        /// `S`: Signature only.
+        SignatureOnly = b'S',
+        /// `T`: Both sign and decrypt.
+        SignAndDecrypt = b'T',
+        /// `V`: Verify only.
+        VerifyOnly = b'V',
+        /// `X`: Key used to derive other keys.
+        Derive = b'X',
+        /// `Y`: Key used to create key variants.
+        Variant = b'Y',
+    }
+);
+
+header_field_enum!(
+    /// Whether the protected key may be exported from its current environment.
+    Exportability {
+        /// `E`: Exportable under a trusted key.
+        Exportable = b'E',
+        /// `N`: Non-exportable.
+        NonExportable = b'N',
+        /// `S`: Sensitive; exportable under an untrusted key.
+        Sensitive = b'S',
+    }
+);
+
+/// The cleartext, authenticated header of a TR-31 key block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Header {
+    key_usage: KeyUsage,
+    algorithm: KeyAlgorithm,
+    mode_of_use: ModeOfUse,
+    key_version: [u8; 2],
+    exportability: Exportability,
+}
+
+impl Header {
+    /// Constructs a `Header` with key version number `00`.
+    #[must_use]
+    pub fn new(
+        key_usage: KeyUsage,
+        algorithm: KeyAlgorithm,
+        mode_of_use: ModeOfUse,
+        exportability: Exportability,
+    ) -> Self {
+        Self {
+            key_usage,
+            algorithm,
+            mode_of_use,
+            key_version: *b"00",
+            exportability,
+        }
+    }
+
+    /// Returns a copy of this header with the given two character key version number.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `key_version` is not two ASCII alphanumeric characters.
+    pub fn with_key_version(mut self, key_version: [u8; 2]) -> Result<Self, Unspecified> {
+        if !key_version.iter().all(u8::is_ascii_alphanumeric) {
+            return Err(Unspecified);
+        }
+        self.key_version = key_version;
+        Ok(self)
+    }
+
+    /// The intended use of the protected key.
+    #[must_use]
+    pub fn key_usage(&self) -> KeyUsage {
+        self.key_usage
+    }
+
+    /// The algorithm with which the protected key is used.
+    #[must_use]
+    pub fn algorithm(&self) -> KeyAlgorithm {
+        self.algorithm
+    }
+
+    /// The operations the protected key may perform.
+    #[must_use]
+    pub fn mode_of_use(&self) -> ModeOfUse {
+        self.mode_of_use
+    }
+
+    /// The two character key version number.
+    #[must_use]
+    pub fn key_version(&self) -> &[u8; 2] {
+        &self.key_version
+    }
+
+    /// Whether the protected key may be exported.
+    #[must_use]
+    pub fn exportability(&self) -> Exportability {
+        self.exportability
+    }
+
+    fn encode(&self, key_block_len: usize) -> Result<[u8; HEADER_LEN], Unspecified> {
+        if key_block_len > MAX_KEY_BLOCK_LEN {
+            return Err(Unspecified);
+        }
+        let mut header = [0u8; HEADER_LEN];
+        header[0] = VERSION_D;
+        header[1..5].copy_from_slice(format!("{key_block_len:04}").as_bytes());
+        header[5..7].copy_from_slice(&self.key_usage.0);
+        header[7] = self.algorithm.code();
+        header[8] = self.mode_of_use.code();
+        header[9..11].copy_from_slice(&self.key_version);
+        header[11] = self.exportability.code();
+        // No optional blocks; reserved field.
+        header[12..16].copy_from_slice(b"0000");
+        Ok(header)
+    }
+
+    fn decode(header: &[u8]) -> Result<(Self, usize), Unspecified> {
+        if header.len() != HEADER_LEN || header[0] != VERSION_D {
+            return Err(Unspecified);
+        }
+        let key_block_len = core::str::from_utf8(&header[1..5])
+            .map_err(|_| Unspecified)?
+            .parse::<usize>()
+            .map_err(|_| Unspecified)?;
+        if &header[12..16] != b"0000" {
+            return Err(Unspecified);
+        }
+        let header = Header::new(
+            KeyUsage::new([header[5], header[6]])?,
+            KeyAlgorithm::from_code(header[7])?,
+            ModeOfUse::from_code(header[8])?,
+            Exportability::from_code(header[11])?,
+        )
+        .with_key_version([header[9], header[10]])?;
+        Ok((header, key_block_len))
+    }
+}
+
+/// Wraps `key` in a version `D` TR-31 key block protected by `kbpk`.
+///
+/// The key field is padded with random bytes to a multiple of the AES block length.
+///
+/// # Errors
+/// `error::Unspecified` if `kbpk` is not an AES key, if `key` is empty or too long, or if
+/// random generation or encryption fails.
+pub fn wrap(kbpk: &UnboundCipherKey, header: &Header, key: &[u8]) -> Result<String, Unspecified> {
+    let padding_len = padding_len(key.len())?;
+    let mut padding = [0u8; BLOCK_LEN];
+    rand::fill(&mut padding[..padding_len])?;
+    wrap_with_padding(kbpk, header, key, &padding[..padding_len])
+}
+
+/// Unwraps a version `D` TR-31 key block protected by `kbpk`.
+///
+/// Returns the authenticated header and the protected key. Only AES keys (algorithm `A`) of a
+/// length supported by [`crate::cipher`] can be returned.
+///
+/// # Errors
+/// `error::Unspecified` if the key block is malformed, the MAC does not verify, or the
+/// protected key is not a supported AES key.
+pub fn unwrap(
+    kbpk: &UnboundCipherKey,
+    key_block: &str,
+) -> Result<(Header, UnboundCipherKey), Unspecified> {
+    let key_block = key_block.as_bytes();
+    if key_block.len() < HEADER_LEN + 2 * (BLOCK_LEN + MAC_LEN) {
+        return Err(Unspecified);
+    }
+    let (header_bytes, rest) = key_block.split_at(HEADER_LEN);
+    let (header, key_block_len) = Header::decode(header_bytes)?;
+    if key_block_len != key_block.len() {
+        return Err(Unspecified);
+    }
+
+    let (encrypted_hex, mac_hex) = rest.split_at(rest.len() - 2 * MAC_LEN);
+    let mut payload = decode_hex(encrypted_hex)?;
+    let mac = decode_hex(mac_hex)?;
+    if payload.is_empty() || payload.len() % BLOCK_LEN != 0 {
+        return Err(Unspecified);
+    }
+
+    let (kbek, kbmk) = derive_keys(kbpk)?;
+    let iv: [u8; BLOCK_LEN] = mac.as_slice().try_into()?;
+    DecryptingKey::new(kbek, OperatingMode::CBC)?.decrypt(
+        &mut payload,
+        DecryptionContext::Iv128(FixedLength::from(iv)),
+    )?;
+
+    let result = compute_mac(&kbmk, header_bytes, &payload)
+        .and_then(|expected| constant_time::verify_slices_are_equal(&expected, &mac))
+        .and_then(|()| {
+            let key = parse_key_field(&payload)?;
+            match header.algorithm {
+                KeyAlgorithm::Aes => {
+                    let algorithm = match key.len() {
+                        AES_128_KEY_LEN => &AES_128,
+                        AES_192_KEY_LEN => &AES_192,
+                        AES_256_KEY_LEN => &AES_256,
+                        _ => return Err(Unspecified),
+                    };
+                    UnboundCipherKey::new(algorithm, key)
+                }
+                _ => Err(Unspecified),
+            }
+        });
+    payload.zeroize();
+
+    Ok((header, result?))
+}
+
+fn wrap_with_padding(
+    kbpk: &UnboundCipherKey,
+    header: &Header,
+    key: &[u8],
+    padding: &[u8],
+) -> Result<String, Unspecified> {
+    let key_bits = u16::try_from(key.len() * 8).map_err(|_| Unspecified)?;
+    let payload_len = KEY_LENGTH_FIELD_LEN + key.len() + padding.len();
+    if key.is_empty() || payload_len % BLOCK_LEN != 0 {
+        return Err(Unspecified);
+    }
+    let key_block_len = HEADER_LEN + 2 * (payload_len + MAC_LEN);
+    let header_bytes = header.encode(key_block_len)?;
+
+    let mut payload = Vec::with_capacity(payload_len);
+    payload.extend_from_slice(&key_bits.to_be_bytes());
+    payload.extend_from_slice(key);
+    payload.extend_from_slice(padding);
+
+    let (kbek, kbmk) = derive_keys(kbpk)?;
+    let result = compute_mac(&kbmk, &header_bytes, &payload).and_then(|mac| {
+        EncryptingKey::new(kbek, OperatingMode::CBC)?.less_safe_encrypt(
+            &mut payload,
+            EncryptionContext::Iv128(FixedLength::from(mac)),
+        )?;
+        Ok(mac)
+    });
+    let mac = match result {
+        Ok(mac) => mac,
+        Err(err) => {
+            payload.zeroize();
+            return Err(err);
+        }
+    };
+
+    let mut key_block = String::with_capacity(key_block_len);
+    key_block.push_str(core::str::from_utf8(&header_bytes).map_err(|_| Unspecified)?);
+    key_block.push_str(&hex::encode_upper(&payload));
+    key_block.push_str(&hex::encode_upper(mac));
+    Ok(key_block)
+}
+
+fn padding_len(key_len: usize) -> Result<usize, Unspecified> {
+    if key_len == 0 {
+        return Err(Unspecified);
+    }
+    let unpadded_len = KEY_LENGTH_FIELD_LEN + key_len;
+    Ok((BLOCK_LEN - unpadded_len % BLOCK_LEN) % BLOCK_LEN)
+}
+
+fn parse_key_field(payload: &[u8]) -> Result<&[u8], Unspecified> {
+    let key_bits = u16::from_be_bytes(payload[..KEY_LENGTH_FIELD_LEN].try_into()?);
+    if key_bits % 8 != 0 {
+        return Err(Unspecified);
+    }
+    let key_len = usize::from(key_bits / 8);
+    payload
+        .get(KEY_LENGTH_FIELD_LEN..KEY_LENGTH_FIELD_LEN + key_len)
+        .filter(|key| !key.is_empty())
+        .ok_or(Unspecified)
+}
+
+fn compute_mac(
+    kbmk: &UnboundCipherKey,
+    header: &[u8],
+    payload: &[u8],
+) -> Result<[u8; MAC_LEN], Unspecified> {
+    let mut mac_input = Vec::with_capacity(header.len() + payload.len());
+    mac_input.extend_from_slice(header);
+    mac_input.extend_from_slice(payload);
+    let mac = aes_cmac(kbmk, &mac_input);
+    mac_input.zeroize();
+    mac
+}
+
+// Derives the KBEK and KBMK from the KBPK as specified for key block version `D`.
+fn derive_keys(
+    kbpk: &UnboundCipherKey,
+) -> Result<(UnboundCipherKey, UnboundCipherKey), Unspecified> {
+    let algorithm = kbpk.algorithm();
+    let (algorithm_indicator, key_len): (u16, usize) = match algorithm.id() {
+        AlgorithmId::Aes128 => (0x0002, AES_128_KEY_LEN),
+        AlgorithmId::Aes192 => (0x0003, AES_192_KEY_LEN),
+        AlgorithmId::Aes256 => (0x0004, AES_256_KEY_LEN),
+        AlgorithmId::Chacha20 => return Err(Unspecified),
+        #[cfg(not(feature = "approved-only"))]
+        AlgorithmId::TripleDes3Key => return Err(Unspecified),
+    };
+    let key_bits = u16::try_from(key_len * 8).map_err(|_| Unspecified)?;
+
+    let derive = |usage_indicator: u16| -> Result<UnboundCipherKey, Unspecified> {
+        let mut derived = [0u8; 2 * BLOCK_LEN];
+        for (counter, block) in (1u8..).zip(derived.chunks_mut(BLOCK_LEN)) {
+            if usize::from(counter - 1) * BLOCK_LEN >= key_len {
+                break;
+            }
+            let mut input = [0u8; 8];
+            input[0] = counter;
+            input[1..3].copy_from_slice(&usage_indicator.to_be_bytes());
+            input[3] = 0x00;
+            input[4..6].copy_from_slice(&algorithm_indicator.to_be_bytes());
+            input[6..8].copy_from_slice(&key_bits.to_be_bytes());
+            block.copy_from_slice(&aes_cmac(kbpk, &input)?);
+        }
+        let key = UnboundCipherKey::new(algorithm, &derived[..key_len]);
+        derived.zeroize();
+        key
+    };
+
+    Ok((derive(0x0000)?, derive(0x0001)?))
+}
+
+fn decode_hex(hex: &[u8]) -> Result<Vec<u8>, Unspecified> {
+    if hex.len() % 2 != 0 {
+        return Err(Unspecified);
+    }
+    let hex = core::str::from_utf8(hex).map_err(|_| Unspecified)?;
+    hex::decode(hex).map_err(|_| Unspecified)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cipher::{EncryptingKey, UnboundCipherKey, AES_128, AES_192, AES_256};
+    use crate::test::from_hex;
+
+    // ASC X9 TR-31 2018 sample: AES-256 KBPK protecting an AES-128 PIN encryption key.
+    const SAMPLE_KBPK: &str = "88E1AB2A2E3DD38C1FA039A536500CC8A87AB9D62DC92C01058FA79F44657DE6";
+    const SAMPLE_KEY: &str = "3F419E1CB7079442AA37474C2EFBF8B8";
+    const SAMPLE_PADDING: &str = "1C2965473CE206BB855B01533782";
+    const SAMPLE_KEY_BLOCK: &str = "D0112P0AE00E0000B82679114F470F540165EDFBF7E250FCEA43F810D2\
+                                    15F8D207E2E417C07156A27E8E31DA05F7425509593D03A457DC34";
+
+    fn sample_kbpk() -> UnboundCipherKey {
+        UnboundCipherKey::new(&AES_256, &from_hex(SAMPLE_KBPK).unwrap()).unwrap()
+    }
+
+    fn sample_header() -> Header {
+        Header::new(
+            KeyUsage::PIN_ENCRYPTION,
+            KeyAlgorithm::Aes,
+            ModeOfUse::EncryptOnly,
+            Exportability::Exportable,
+        )
+    }
+
+    // Two keys are equivalent if they encrypt a block identically.
+    fn assert_same_key(expected: &[u8], actual: UnboundCipherKey) {
+        let expected = UnboundCipherKey::new(actual.algorithm(), expected).unwrap();
+        let iv = [0u8; 16];
+        let mut a = [0u8; 16];
+        let mut b = [0u8; 16];
+        EncryptingKey::new(expected, OperatingMode::CBC)
+            .unwrap()
+            .less_safe_encrypt(&mut a, EncryptionContext::Iv128(iv.into()))
+            .unwrap();
+        EncryptingKey::new(actual, OperatingMode::CBC)
+            .unwrap()
+            .less_safe_encrypt(&mut b, EncryptionContext::Iv128(iv.into()))
+            .unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_sample_unwrap() {
+        let (header, key) = unwrap(&sample_kbpk(), SAMPLE_KEY_BLOCK).unwrap();
+        assert_eq!(sample_header(), header);
+        assert_eq!(&AES_128, key.algorithm());
+        assert_same_key(&from_hex(SAMPLE_KEY).unwrap(), key);
+    }
+
+    #[test]
+    fn test_sample_wrap() {
+        let key_block = wrap_with_padding(
+            &sample_kbpk(),
+            &sample_header(),
+            &from_hex(SAMPLE_KEY).unwrap(),
+            &from_hex(SAMPLE_PADDING).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(SAMPLE_KEY_BLOCK, key_block);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let header = Header::new(
+            KeyUsage::DATA_ENCRYPTION,
+            KeyAlgorithm::Aes,
+            ModeOfUse::Both,
+            Exportability::NonExportable,
+        )
+        .with_key_version(*b"12")
+        .unwrap();
+
+        for (kbpk_alg, kbpk_len) in [(&AES_128, 16), (&AES_192, 24), (&AES_256, 32)] {
+            let kbpk = UnboundCipherKey::new(kbpk_alg, &vec![0xa5; kbpk_len]).unwrap();
+            for key_len in [16, 24, 32] {
+                let key_bytes = vec![0x3c; key_len];
+                let key_block = wrap(&kbpk, &header, &key_bytes).unwrap();
+                assert_eq!(0, (key_block.len() - HEADER_LEN) % 32);
+                let (parsed, key) = unwrap(&kbpk, &key_block).unwrap();
+                assert_eq!(header, parsed);
+                assert_same_key(&key_bytes, key);
+            }
+        }
+    }
+
+    #[test]
+    fn test_header_tampering() {
+        for (index, replacement) in [(5, b'D'), (6, b'1'), (8, b'B'), (10, b'1'), (11, b'N')] {
+            let mut tampered = SAMPLE_KEY_BLOCK.as_bytes().to_vec();
+            tampered[index] = replacement;
+            let tampered = String::from_utf8(tampered).unwrap();
+            assert!(unwrap(&sample_kbpk(), &tampered).is_err());
+        }
+    }
+
+    #[test]
+    fn test_payload_tampering() {
+        for index in [HEADER_LEN, HEADER_LEN + 40, SAMPLE_KEY_BLOCK.len() - 1] {
+            let mut tampered = SAMPLE_KEY_BLOCK.as_bytes().to_vec();
+            tampered[index] = if tampered[index] == b'0' { b'1' } else { b'0' };
+            let tampered = String::from_utf8(tampered).unwrap();
+            assert!(unwrap(&sample_kbpk(), &tampered).is_err());
+        }
+    }
+
+    #[test]
+    fn test_wrong_kbpk() {
+        let kbpk = UnboundCipherKey::new(&AES_256, &[0u8; 32]).unwrap();
+        assert!(unwrap(&kbpk, SAMPLE_KEY_BLOCK).is_err());
+    }
+
+    #[test]
+    fn test_malformed() {
+        let kbpk = sample_kbpk();
+        // Truncated
+        assert!(unwrap(&kbpk, &SAMPLE_KEY_BLOCK[..SAMPLE_KEY_BLOCK.len() - 2]).is_err());
+        // Wrong version
+        assert!(unwrap(&kbpk, &SAMPLE_KEY_BLOCK.replacen('D', "B", 1)).is_err());
+        // Length field mismatch
+        assert!(unwrap(&kbpk, &SAMPLE_KEY_BLOCK.replacen("0112", "0111", 1)).is_err());
+        // Optional blocks are unsupported
+        assert!(unwrap(&kbpk, &SAMPLE_KEY_BLOCK.replacen("E0000", "E0100", 1)).is_err());
+        assert!(unwrap(&kbpk, "").is_err());
+    }
+
+    #[test]
+    fn test_invalid_header_fields() {
+        assert!(KeyUsage::new(*b"P-").is_err());
+        assert!(sample_header().with_key_version(*b" 1").is_err());
+        assert!(wrap(&sample_kbpk(), &sample_header(), &[]).is_err());
+    }
+}
@@ -9,4 +9,5 @@
 //! Features contained within this module, or child modules are subject to changes, relocation,
 //! or removal across minor releases, and thus are not subject to semantic versioning policies.
 
+pub mod ec;
 pub mod kem;
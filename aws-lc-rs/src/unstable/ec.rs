@@ -0,0 +1,438 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Low-level scalar and point operations on the NIST P-256 and P-384 curves.
+//!
+//! These are building blocks for protocols that this crate does not implement itself, such as
+//! blinded tokens, Pedersen commitments, or OPRFs. They provide no protection against misuse:
+//! prefer the [`crate::agreement`] and [`crate::signature`] modules whenever they fit.
+//!
+//! # ⚠️ Warning
+//! The API of this module is subject to changes, relocation, or removal across minor releases,
+//! and thus is not subject to semantic versioning policies.
+//!
+//! # Example
+//!
+//! ```
+//! use aws_lc_rs::unstable::ec::{Point, PointEncoding, Scalar, P256};
+//!
+//! // A Diffie-Hellman exchange from the underlying operations.
+//! let a = Scalar::generate(&P256)?;
+//! let b = Scalar::generate(&P256)?;
+//! let a_public = Point::mul_base(&a)?;
+//! let b_public = Point::mul_base(&b)?;
+//!
+//! let a_shared = b_public.mul(&a)?;
+//! let b_shared = a_public.mul(&b)?;
+//! assert_eq!(
+//!     a_shared.to_sec1(PointEncoding::Compressed)?,
+//!     b_shared.to_sec1(PointEncoding::Compressed)?
+//! );
+//! # Ok::<(), aws_lc_rs::error::Unspecified>(())
+//! ```
+
+use crate::ec::{ec_group_from_nid, ec_point_from_bytes};
+use crate::error::Unspecified;
+use crate::ptr::{ConstPointer, LcPtr};
+use core::fmt::{Debug, Formatter};
+use core::ptr::{null, null_mut};
+
+use aws_lc::{
+    point_conversion_form_t, BN_CTX_new, BN_bn2bin_padded, BN_cmp, BN_new, BN_nnmod,
+    BN_rand_range_ex, EC_GROUP_get0_generator, EC_GROUP_get0_order, EC_POINT_add, EC_POINT_cmp,
+    EC_POINT_dup, EC_POINT_is_at_infinity, EC_POINT_mul, EC_POINT_new, EC_POINT_point2oct,
+    NID_X9_62_prime256v1, NID_secp384r1, BIGNUM, EC_GROUP, EC_POINT,
+};
+#[cfg(not(feature = "fips"))]
+use aws_lc::{EC_hash_to_curve_p256_xmd_sha256_sswu, EC_hash_to_curve_p384_xmd_sha384_sswu};
+
+/// An elliptic curve.
+pub struct Curve {
+    id: CurveID,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CurveID {
+    P256,
+    P384,
+}
+
+crate::derive_debug_via_id!(Curve);
+
+impl PartialEq for Curve {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Curve {}
+
+impl Curve {
+    fn nid(&self) -> i32 {
+        match self.id {
+            CurveID::P256 => NID_X9_62_prime256v1,
+            CurveID::P384 => NID_secp384r1,
+        }
+    }
+
+    /// The length of an encoded scalar, which is also the length of a field element.
+    #[must_use]
+    pub fn scalar_len(&self) -> usize {
+        match self.id {
+            CurveID::P256 => 32,
+            CurveID::P384 => 48,
+        }
+    }
+
+    fn group(&self) -> Result<LcPtr<EC_GROUP>, Unspecified> {
+        Ok(ec_group_from_nid(self.nid())?)
+    }
+}
+
+/// NIST P-256 (secp256r1).
+pub static P256: Curve = Curve { id: CurveID::P256 };
+
+/// NIST P-384 (secp384r1).
+pub static P384: Curve = Curve { id: CurveID::P384 };
+
+fn group_order(group: &LcPtr<EC_GROUP>) -> Result<ConstPointer<BIGNUM>, Unspecified> {
+    Ok(ConstPointer::new(unsafe { EC_GROUP_get0_order(**group) })?)
+}
+
+/// A scalar modulo the order of the group of a curve.
+pub struct Scalar {
+    curve: &'static Curve,
+    value: LcPtr<BIGNUM>,
+}
+
+impl Scalar {
+    /// Generates a uniformly random non-zero scalar.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the scalar could not be generated.
+    pub fn generate(curve: &'static Curve) -> Result<Self, Unspecified> {
+        let group = curve.group()?;
+        let order = group_order(&group)?;
+        let value = LcPtr::new(unsafe { BN_new() })?;
+        if 1 != unsafe { BN_rand_range_ex(*value, 1, *order) } {
+            return Err(Unspecified);
+        }
+        Ok(Self { curve, value })
+    }
+
+    /// Parses the big-endian encoding of a scalar of `curve.scalar_len()` bytes, which must be
+    /// less than the group order.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `bytes` is not a valid scalar encoding.
+    pub fn from_be_bytes(curve: &'static Curve, bytes: &[u8]) -> Result<Self, Unspecified> {
+        if bytes.len() != curve.scalar_len() {
+            return Err(Unspecified);
+        }
+        let group = curve.group()?;
+        let order = group_order(&group)?;
+        let value = LcPtr::<BIGNUM>::try_from(bytes)?;
+        if unsafe { BN_cmp(*value, *order) } >= 0 {
+            return Err(Unspecified);
+        }
+        Ok(Self { curve, value })
+    }
+
+    /// Interprets `bytes` as a big-endian integer of any length and reduces it modulo the group
+    /// order.
+    ///
+    /// To derive a scalar with negligible bias, `bytes` should be uniformly random and at least
+    /// 16 bytes longer than `curve.scalar_len()`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the reduction fails.
+    pub fn from_be_bytes_reduced(curve: &'static Curve, bytes: &[u8]) -> Result<Self, Unspecified> {
+        let group = curve.group()?;
+        let order = group_order(&group)?;
+        let unreduced = LcPtr::<BIGNUM>::try_from(bytes)?;
+        let value = LcPtr::new(unsafe { BN_new() })?;
+        let ctx = LcPtr::new(unsafe { BN_CTX_new() })?;
+        if 1 != unsafe { BN_nnmod(*value, *unreduced, *order, *ctx) } {
+            return Err(Unspecified);
+        }
+        Ok(Self { curve, value })
+    }
+
+    /// The curve of the scalar.
+    #[must_use]
+    pub fn curve(&self) -> &'static Curve {
+        self.curve
+    }
+
+    /// The big-endian encoding of the scalar, of `curve().scalar_len()` bytes.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the encoding fails.
+    pub fn to_be_bytes(&self) -> Result<Vec<u8>, Unspecified> {
+        let mut bytes = vec![0u8; self.curve.scalar_len()];
+        if 1 != unsafe { BN_bn2bin_padded(bytes.as_mut_ptr(), bytes.len(), *self.value) } {
+            return Err(Unspecified);
+        }
+        Ok(bytes)
+    }
+}
+
+impl Debug for Scalar {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Scalar")
+            .field("curve", self.curve)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The SEC1 encoding of a point.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointEncoding {
+    /// `0x04 || x || y`.
+    Uncompressed,
+    /// `0x02 || x` or `0x03 || x`, depending on the parity of `y`.
+    Compressed,
+}
+
+/// A point on a curve.
+///
+/// Points decoded with [`Point::from_sec1`] are never the point at infinity, but the result of
+/// an addition can be; such a point cannot be encoded.
+pub struct Point {
+    curve: &'static Curve,
+    point: LcPtr<EC_POINT>,
+}
+
+impl Point {
+    /// The generator of the group.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on an internal error.
+    pub fn generator(curve: &'static Curve) -> Result<Self, Unspecified> {
+        let group = curve.group()?;
+        let point = LcPtr::new(unsafe { EC_POINT_dup(EC_GROUP_get0_generator(*group), *group) })?;
+        Ok(Self { curve, point })
+    }
+
+    /// Decodes a point from its compressed or uncompressed SEC1 encoding.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `bytes` does not encode a point on the curve.
+    pub fn from_sec1(curve: &'static Curve, bytes: &[u8]) -> Result<Self, Unspecified> {
+        let group = curve.group()?;
+        let point = ec_point_from_bytes(&group, bytes)?;
+        if 1 == unsafe { EC_POINT_is_at_infinity(*group, *point) } {
+            return Err(Unspecified);
+        }
+        Ok(Self { curve, point })
+    }
+
+    /// Computes `scalar * G`, with `G` the generator of the group.
+    ///
+    /// For a private key `scalar`, this is the public key of
+    /// [`agreement::PrivateKey::compute_public_key`](crate::agreement::PrivateKey::compute_public_key).
+    ///
+    /// # Errors
+    /// `error::Unspecified` on an internal error.
+    pub fn mul_base(scalar: &Scalar) -> Result<Self, Unspecified> {
+        let curve = scalar.curve;
+        let group = curve.group()?;
+        let point = LcPtr::new(unsafe { EC_POINT_new(*group) })?;
+        if 1 != unsafe { EC_POINT_mul(*group, *point, *scalar.value, null(), null(), null_mut()) } {
+            return Err(Unspecified);
+        }
+        Ok(Self { curve, point })
+    }
+
+    /// Hashes `msg` to a point with the random-oracle encoding of RFC 9380, using the
+    /// `P256_XMD:SHA-256_SSWU_RO_` or `P384_XMD:SHA-384_SSWU_RO_` suite and the domain
+    /// separation tag `dst`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `dst` is empty or longer than 255 bytes.
+    #[cfg(not(feature = "fips"))]
+    pub fn hash_to_curve(
+        curve: &'static Curve,
+        dst: &[u8],
+        msg: &[u8],
+    ) -> Result<Self, Unspecified> {
+        if dst.is_empty() || dst.len() > 255 {
+            return Err(Unspecified);
+        }
+        let group = curve.group()?;
+        let point = LcPtr::new(unsafe { EC_POINT_new(*group) })?;
+        let hash_to_curve = match curve.id {
+            CurveID::P256 => EC_hash_to_curve_p256_xmd_sha256_sswu,
+            CurveID::P384 => EC_hash_to_curve_p384_xmd_sha384_sswu,
+        };
+        if 1 != unsafe {
+            hash_to_curve(
+                *group,
+                *point,
+                dst.as_ptr(),
+                dst.len(),
+                msg.as_ptr(),
+                msg.len(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        Ok(Self { curve, point })
+    }
+
+    /// The curve of the point.
+    #[must_use]
+    pub fn curve(&self) -> &'static Curve {
+        self.curve
+    }
+
+    /// Computes `scalar * self`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `scalar` belongs to a different curve.
+    pub fn mul(&self, scalar: &Scalar) -> Result<Self, Unspecified> {
+        if self.curve != scalar.curve {
+            return Err(Unspecified);
+        }
+        let group = self.curve.group()?;
+        let point = LcPtr::new(unsafe { EC_POINT_new(*group) })?;
+        if 1 != unsafe {
+            EC_POINT_mul(
+                *group,
+                *point,
+                null(),
+                *self.point,
+                *scalar.value,
+                null_mut(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        Ok(Self {
+            curve: self.curve,
+            point,
+        })
+    }
+
+    /// Computes `self + other`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `other` belongs to a different curve.
+    pub fn add(&self, other: &Point) -> Result<Self, Unspecified> {
+        if self.curve != other.curve {
+            return Err(Unspecified);
+        }
+        let group = self.curve.group()?;
+        let point = LcPtr::new(unsafe { EC_POINT_new(*group) })?;
+        if 1 != unsafe { EC_POINT_add(*group, *point, *self.point, *other.point, null_mut()) } {
+            return Err(Unspecified);
+        }
+        Ok(Self {
+            curve: self.curve,
+            point,
+        })
+    }
+
+    /// The SEC1 encoding of the point.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the point is the point at infinity.
+    pub fn to_sec1(&self, encoding: PointEncoding) -> Result<Vec<u8>, Unspecified> {
+        let group = self.curve.group()?;
+        let (form, len) = match encoding {
+            PointEncoding::Uncompressed => (
+                point_conversion_form_t::POINT_CONVERSION_UNCOMPRESSED,
+                1 + 2 * self.curve.scalar_len(),
+            ),
+            PointEncoding::Compressed => (
+                point_conversion_form_t::POINT_CONVERSION_COMPRESSED,
+                1 + self.curve.scalar_len(),
+            ),
+        };
+        let mut bytes = vec![0u8; len];
+        let out_len = unsafe {
+            EC_POINT_point2oct(
+                *group,
+                *self.point,
+                form,
+                bytes.as_mut_ptr(),
+                bytes.len(),
+                null_mut(),
+            )
+        };
+        if out_len != len {
+            return Err(Unspecified);
+        }
+        Ok(bytes)
+    }
+}
+
+impl PartialEq for Point {
+    fn eq(&self, other: &Self) -> bool {
+        self.curve == other.curve
+            && self.curve.group().map_or(false, |group| {
+                0 == unsafe { EC_POINT_cmp(*group, *self.point, *other.point, null_mut()) }
+            })
+    }
+}
+
+impl Debug for Point {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Point")
+            .field("curve", self.curve)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::unstable::ec::{Point, PointEncoding, Scalar, P256, P384};
+
+    #[test]
+    fn test_scalar_encoding() {
+        for curve in [&P256, &P384] {
+            let scalar = Scalar::generate(curve).unwrap();
+            let bytes = scalar.to_be_bytes().unwrap();
+            assert_eq!(curve.scalar_len(), bytes.len());
+            let decoded = Scalar::from_be_bytes(curve, &bytes).unwrap();
+            assert_eq!(bytes, decoded.to_be_bytes().unwrap());
+
+            assert!(Scalar::from_be_bytes(curve, &bytes[1..]).is_err());
+            assert!(Scalar::from_be_bytes(curve, &vec![0xff; curve.scalar_len()]).is_err());
+
+            let mut wide = vec![0u8; 16];
+            wide.extend_from_slice(&bytes);
+            let reduced = Scalar::from_be_bytes_reduced(curve, &wide).unwrap();
+            assert_eq!(bytes, reduced.to_be_bytes().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_point_arithmetic() {
+        for curve in [&P256, &P384] {
+            let a = Scalar::generate(curve).unwrap();
+            let b = Scalar::generate(curve).unwrap();
+            let a_g = Point::mul_base(&a).unwrap();
+            let b_g = Point::mul_base(&b).unwrap();
+            assert_eq!(a_g, Point::generator(curve).unwrap().mul(&a).unwrap());
+            assert_eq!(a_g.mul(&b).unwrap(), b_g.mul(&a).unwrap());
+            assert_eq!(a_g.add(&b_g).unwrap(), b_g.add(&a_g).unwrap());
+
+            for encoding in [PointEncoding::Uncompressed, PointEncoding::Compressed] {
+                let bytes = a_g.to_sec1(encoding).unwrap();
+                assert_eq!(a_g, Point::from_sec1(curve, &bytes).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_curve_mismatch() {
+        let p256_scalar = Scalar::generate(&P256).unwrap();
+        let p384_point = Point::generator(&P384).unwrap();
+        assert!(p384_point.mul(&p256_scalar).is_err());
+        assert!(p384_point
+            .add(&Point::mul_base(&p256_scalar).unwrap())
+            .is_err());
+    }
+}
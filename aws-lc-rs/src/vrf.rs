@@ -0,0 +1,525 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Elliptic curve verifiable random functions (ECVRF).
+//!
+//! A verifiable random function maps an input `alpha` to a pseudorandom output `beta` using a
+//! secret key, and produces a proof that lets anyone holding the public key verify that `beta`
+//! is the correct output for `alpha`. This module implements the `ECVRF-P256-SHA256-TAI`
+//! ciphersuite of [RFC 9381].
+//!
+//! The secret key is a big-endian P-256 scalar and the public key is the compressed public
+//! point.
+//!
+//! # Example
+//!
+//! ```
+//! use aws_lc_rs::vrf::{self, VrfKeyPair};
+//!
+//! # fn main() -> Result<(), aws_lc_rs::error::Unspecified> {
+//! let key_pair = VrfKeyPair::generate(&vrf::ECVRF_P256_SHA256_TAI)?;
+//!
+//! // The prover computes the output and a proof of it.
+//! let proof = key_pair.prove(b"round 42")?;
+//! let beta = proof.to_hash();
+//!
+//! // A verifier checks the proof with the public key and obtains the same output.
+//! let public_key = vrf::UnparsedPublicKey::new(
+//!     &vrf::ECVRF_P256_SHA256_TAI,
+//!     key_pair.public_key().as_ref(),
+//! );
+//! let verified = public_key.verify(b"round 42", proof.as_ref())?;
+//! assert_eq!(beta.as_ref(), verified.as_ref());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [RFC 9381]: https://www.rfc-editor.org/rfc/rfc9381
+
+mod p256;
+
+use crate::derive_debug_via_id;
+use crate::digest;
+use crate::error::{KeyRejected, Unspecified};
+use crate::hex;
+use crate::ptr::LcPtr;
+use crate::rand;
+use aws_lc::EC_POINT;
+use core::fmt::{Debug, Formatter};
+use p256::{P256, POINT_LEN, SUITE_STRING};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// The length of the challenge in a proof, `cLen`.
+const C_LEN: usize = 16;
+
+/// The length of a scalar, `qLen`.
+const Q_LEN: usize = 32;
+
+/// The maximum length of a proof.
+pub const MAX_PROOF_LEN: usize = POINT_LEN + C_LEN + Q_LEN;
+
+/// The maximum length of an output.
+pub const MAX_OUTPUT_LEN: usize = digest::MAX_OUTPUT_LEN;
+
+const ENCODE_TO_CURVE_DOMAIN_SEPARATOR_FRONT: u8 = 0x01;
+const CHALLENGE_GENERATION_DOMAIN_SEPARATOR_FRONT: u8 = 0x02;
+const PROOF_TO_HASH_DOMAIN_SEPARATOR_FRONT: u8 = 0x03;
+const DOMAIN_SEPARATOR_BACK: u8 = 0x00;
+
+/// An ECVRF ciphersuite.
+pub struct Algorithm {
+    id: AlgorithmID,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AlgorithmID {
+    EcvrfP256Sha256Tai,
+}
+
+derive_debug_via_id!(Algorithm);
+
+impl PartialEq for Algorithm {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Algorithm {}
+
+impl Algorithm {
+    /// The length of a proof.
+    #[must_use]
+    pub fn proof_len(&self) -> usize {
+        POINT_LEN + C_LEN + Q_LEN
+    }
+
+    /// The length of an output.
+    #[must_use]
+    pub fn output_len(&self) -> usize {
+        P256::digest_algorithm().output_len
+    }
+}
+
+/// `ECVRF-P256-SHA256-TAI`, suite string `0x01`.
+pub static ECVRF_P256_SHA256_TAI: Algorithm = Algorithm {
+    id: AlgorithmID::EcvrfP256Sha256Tai,
+};
+
+/// The secret key `int2octets(x)`, which is also the key for nonce generation.
+struct SecretKey([u8; Q_LEN]);
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// A VRF output, `beta`.
+#[derive(Clone, Copy)]
+pub struct Output {
+    value: [u8; MAX_OUTPUT_LEN],
+    len: usize,
+}
+
+impl AsRef<[u8]> for Output {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.value[..self.len]
+    }
+}
+
+impl Debug for Output {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&format!("Output(\"{}\")", hex::encode(self.as_ref())))
+    }
+}
+
+/// A VRF proof, `pi`.
+#[derive(Clone, Copy)]
+pub struct Proof {
+    algorithm: &'static Algorithm,
+    value: [u8; MAX_PROOF_LEN],
+    output: Output,
+}
+
+impl Proof {
+    /// Parses a proof produced with the ciphersuite `algorithm`.
+    ///
+    /// Only the encoding of the proof is checked; it must still be verified with
+    /// [`UnparsedPublicKey::verify`] before its output can be trusted.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `proof` is not a well-formed proof for `algorithm`.
+    pub fn new(algorithm: &'static Algorithm, proof: &[u8]) -> Result<Self, Unspecified> {
+        parse_proof(&P256::new()?, algorithm, proof).map(|(proof, _)| proof)
+    }
+
+    /// The ciphersuite of the proof.
+    #[must_use]
+    pub fn algorithm(&self) -> &'static Algorithm {
+        self.algorithm
+    }
+
+    /// The VRF output `beta` of the proof (`ECVRF_proof_to_hash`).
+    ///
+    /// The output of a proof that has not been verified must not be trusted.
+    #[must_use]
+    pub fn to_hash(&self) -> Output {
+        self.output
+    }
+}
+
+impl AsRef<[u8]> for Proof {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.value
+    }
+}
+
+impl Debug for Proof {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&format!(
+            "Proof {{ algorithm: {:?}, value: \"{}\" }}",
+            self.algorithm,
+            hex::encode(self.as_ref())
+        ))
+    }
+}
+
+/// The public key of a [`VrfKeyPair`].
+#[derive(Clone, Copy)]
+pub struct PublicKey {
+    algorithm: &'static Algorithm,
+    value: [u8; POINT_LEN],
+}
+
+impl AsRef<[u8]> for PublicKey {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.value
+    }
+}
+
+impl Debug for PublicKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&format!(
+            "PublicKey {{ algorithm: {:?}, bytes: \"{}\" }}",
+            self.algorithm,
+            hex::encode(self.as_ref())
+        ))
+    }
+}
+
+/// A VRF key pair.
+pub struct VrfKeyPair {
+    algorithm: &'static Algorithm,
+    secret: SecretKey,
+    public_key: PublicKey,
+}
+
+impl Debug for VrfKeyPair {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("VrfKeyPair")
+            .field("public_key", &self.public_key)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The secret key is zeroized when dropped.
+impl ZeroizeOnDrop for VrfKeyPair {}
+
+impl VrfKeyPair {
+    /// Generates a new key pair for the ciphersuite `algorithm`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the key pair could not be generated.
+    pub fn generate(algorithm: &'static Algorithm) -> Result<Self, Unspecified> {
+        let mut secret_key = [0u8; Q_LEN];
+        loop {
+            rand::fill(&mut secret_key)?;
+            let key_pair = Self::from_secret_key(algorithm, &secret_key);
+            if let Ok(key_pair) = key_pair {
+                secret_key.zeroize();
+                return Ok(key_pair);
+            }
+        }
+    }
+
+    /// Constructs a key pair from the secret key `SK` of the ciphersuite `algorithm`, a 32-byte
+    /// big-endian scalar in `[1, n - 1]`.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `secret_key` is not a valid secret key for `algorithm`.
+    pub fn from_secret_key(
+        algorithm: &'static Algorithm,
+        secret_key: &[u8],
+    ) -> Result<Self, KeyRejected> {
+        let suite = P256::new().map_err(|Unspecified| KeyRejected::unexpected_error())?;
+        if secret_key.len() != Q_LEN {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        let x = suite
+            .scalar_from_bytes(secret_key)
+            .filter(|x| !x.is_zero())
+            .ok_or_else(KeyRejected::invalid_encoding)?;
+        let mut secret = SecretKey([0u8; Q_LEN]);
+        secret.0.copy_from_slice(secret_key);
+
+        let mut public_key = PublicKey {
+            algorithm,
+            value: [0u8; POINT_LEN],
+        };
+        suite
+            .mul_base(&x)
+            .and_then(|y| suite.encode_point(&y, &mut public_key.value))
+            .map_err(|Unspecified| KeyRejected::unexpected_error())?;
+
+        Ok(VrfKeyPair {
+            algorithm,
+            secret,
+            public_key,
+        })
+    }
+
+    /// The ciphersuite of the key pair.
+    #[must_use]
+    pub fn algorithm(&self) -> &'static Algorithm {
+        self.algorithm
+    }
+
+    /// The public key, `PK_string`.
+    #[must_use]
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    /// Computes the proof `pi` for the input `alpha` (`ECVRF_prove`).
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `alpha` cannot be encoded to a point, which happens with
+    /// negligible probability, or on an internal error.
+    pub fn prove(&self, alpha: &[u8]) -> Result<Proof, Unspecified> {
+        prove(&P256::new()?, self, alpha)
+    }
+}
+
+/// An unparsed VRF public key.
+#[derive(Clone)]
+pub struct UnparsedPublicKey<B: AsRef<[u8]>> {
+    algorithm: &'static Algorithm,
+    bytes: B,
+}
+
+impl<B: Copy + AsRef<[u8]>> Copy for UnparsedPublicKey<B> {}
+
+impl<B: AsRef<[u8]>> Debug for UnparsedPublicKey<B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&format!(
+            "UnparsedPublicKey {{ algorithm: {:?}, bytes: \"{}\" }}",
+            self.algorithm,
+            hex::encode(self.bytes.as_ref())
+        ))
+    }
+}
+
+impl<B: AsRef<[u8]>> UnparsedPublicKey<B> {
+    /// Constructs a new `UnparsedPublicKey`.
+    ///
+    /// No validation of `bytes` is done until `verify()` is called.
+    #[inline]
+    pub fn new(algorithm: &'static Algorithm, bytes: B) -> Self {
+        Self { algorithm, bytes }
+    }
+
+    /// Verifies that `proof` is a valid proof for the input `alpha` under this public key
+    /// (`ECVRF_verify`), returning the VRF output `beta`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the public key is invalid or the proof is not verified.
+    pub fn verify(&self, alpha: &[u8], proof: &[u8]) -> Result<Output, Unspecified> {
+        verify(
+            &P256::new()?,
+            self.algorithm,
+            self.bytes.as_ref(),
+            alpha,
+            proof,
+        )
+    }
+}
+
+/// `ECVRF_encode_to_curve_try_and_increment`, with `encode_to_curve_salt` the public key.
+fn encode_to_curve(
+    suite: &P256,
+    public_key: &[u8],
+    alpha: &[u8],
+) -> Result<LcPtr<EC_POINT>, Unspecified> {
+    for ctr in 0..=u8::MAX {
+        let mut ctx = digest::Context::new(P256::digest_algorithm());
+        ctx.update(&[SUITE_STRING, ENCODE_TO_CURVE_DOMAIN_SEPARATOR_FRONT]);
+        ctx.update(public_key);
+        ctx.update(alpha);
+        ctx.update(&[ctr, DOMAIN_SEPARATOR_BACK]);
+        if let Some(point) = suite.hash_to_point(ctx.finish().as_ref()) {
+            return Ok(point);
+        }
+    }
+    Err(Unspecified)
+}
+
+/// `ECVRF_challenge_generation`, returning `int_to_string(c, cLen)`.
+fn challenge(suite: &P256, points: [&LcPtr<EC_POINT>; 5]) -> Result<[u8; C_LEN], Unspecified> {
+    let mut ctx = digest::Context::new(P256::digest_algorithm());
+    ctx.update(&[SUITE_STRING, CHALLENGE_GENERATION_DOMAIN_SEPARATOR_FRONT]);
+    let mut encoded = [0u8; POINT_LEN];
+    for point in points {
+        suite.encode_point(point, &mut encoded)?;
+        ctx.update(&encoded);
+    }
+    ctx.update(&[DOMAIN_SEPARATOR_BACK]);
+    let mut c = [0u8; C_LEN];
+    c.copy_from_slice(&ctx.finish().as_ref()[..C_LEN]);
+    Ok(c)
+}
+
+/// `ECVRF_proof_to_hash` for a decoded `Gamma`. The cofactor is 1, so `Gamma` is hashed as is.
+fn proof_to_hash(suite: &P256, gamma: &LcPtr<EC_POINT>) -> Result<Output, Unspecified> {
+    let mut encoded = [0u8; POINT_LEN];
+    suite.encode_point(gamma, &mut encoded)?;
+
+    let mut ctx = digest::Context::new(P256::digest_algorithm());
+    ctx.update(&[SUITE_STRING, PROOF_TO_HASH_DOMAIN_SEPARATOR_FRONT]);
+    ctx.update(&encoded);
+    ctx.update(&[DOMAIN_SEPARATOR_BACK]);
+    let beta = ctx.finish();
+
+    let mut output = Output {
+        value: [0u8; MAX_OUTPUT_LEN],
+        len: beta.as_ref().len(),
+    };
+    output.value[..output.len].copy_from_slice(beta.as_ref());
+    Ok(output)
+}
+
+/// `ECVRF_decode_proof`, also computing the output of the proof.
+fn parse_proof(
+    suite: &P256,
+    algorithm: &'static Algorithm,
+    proof: &[u8],
+) -> Result<(Proof, (LcPtr<EC_POINT>, p256::Scalar)), Unspecified> {
+    if proof.len() != MAX_PROOF_LEN {
+        return Err(Unspecified);
+    }
+    let (gamma_string, rest) = proof.split_at(POINT_LEN);
+    let (_, s_string) = rest.split_at(C_LEN);
+    let gamma = suite.decode_point(gamma_string).ok_or(Unspecified)?;
+    let s = suite.scalar_from_bytes(s_string).ok_or(Unspecified)?;
+
+    let mut value = [0u8; MAX_PROOF_LEN];
+    value.copy_from_slice(proof);
+    let output = proof_to_hash(suite, &gamma)?;
+    Ok((
+        Proof {
+            algorithm,
+            value,
+            output,
+        },
+        (gamma, s),
+    ))
+}
+
+fn prove(suite: &P256, key_pair: &VrfKeyPair, alpha: &[u8]) -> Result<Proof, Unspecified> {
+    let public_key = key_pair.public_key.as_ref();
+    let secret_key = &key_pair.secret.0;
+    let x = suite.scalar_from_bytes(secret_key).ok_or(Unspecified)?;
+
+    let h = encode_to_curve(suite, public_key, alpha)?;
+    let mut h_string = [0u8; POINT_LEN];
+    suite.encode_point(&h, &mut h_string)?;
+
+    let gamma = suite.mul(&h, &x)?;
+    let k = suite.nonce(secret_key, &h_string)?;
+    let y = suite.decode_point(public_key).ok_or(Unspecified)?;
+    let u = suite.mul_base(&k)?;
+    let v = suite.mul(&h, &k)?;
+    let c_string = challenge(suite, [&y, &h, &gamma, &u, &v])?;
+    let c = suite.scalar_from_bytes(&c_string).ok_or(Unspecified)?;
+    let s = suite.mul_add(&k, &c, &x)?;
+
+    let mut value = [0u8; MAX_PROOF_LEN];
+    let (gamma_string, rest) = value.split_at_mut(POINT_LEN);
+    suite.encode_point(&gamma, gamma_string)?;
+    rest[..C_LEN].copy_from_slice(&c_string);
+    rest[C_LEN..].copy_from_slice(&s.to_be_bytes()?);
+
+    Ok(Proof {
+        algorithm: key_pair.algorithm,
+        value,
+        output: proof_to_hash(suite, &gamma)?,
+    })
+}
+
+fn verify(
+    suite: &P256,
+    algorithm: &'static Algorithm,
+    public_key: &[u8],
+    alpha: &[u8],
+    proof: &[u8],
+) -> Result<Output, Unspecified> {
+    // The point at infinity has no compressed encoding, and every other point has prime order,
+    // so any decoded public key is valid.
+    let y = suite.decode_point(public_key).ok_or(Unspecified)?;
+
+    let (proof, (gamma, s)) = parse_proof(suite, algorithm, proof)?;
+    let c_string = &proof.as_ref()[POINT_LEN..POINT_LEN + C_LEN];
+    let c = suite.scalar_from_bytes(c_string).ok_or(Unspecified)?;
+
+    let h = encode_to_curve(suite, public_key, alpha)?;
+    // U = s*B - c*Y, V = s*H - c*Gamma
+    let u = suite.sub(&suite.mul_base(&s)?, &suite.mul(&y, &c)?)?;
+    let v = suite.sub(&suite.mul(&h, &s)?, &suite.mul(&gamma, &c)?)?;
+    let expected = challenge(suite, [&y, &h, &gamma, &u, &v])?;
+
+    crate::constant_time::verify_slices_are_equal(c_string, &expected)?;
+    Ok(proof.to_hash())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::vrf::{Proof, UnparsedPublicKey, VrfKeyPair, ECVRF_P256_SHA256_TAI};
+
+    #[test]
+    fn test_round_trip() {
+        let algorithm = &ECVRF_P256_SHA256_TAI;
+        let key_pair = VrfKeyPair::generate(algorithm).unwrap();
+        let proof = key_pair.prove(b"alpha").unwrap();
+        assert_eq!(algorithm.proof_len(), proof.as_ref().len());
+        assert_eq!(algorithm.output_len(), proof.to_hash().as_ref().len());
+
+        let public_key = UnparsedPublicKey::new(algorithm, key_pair.public_key());
+        let beta = public_key.verify(b"alpha", proof.as_ref()).unwrap();
+        assert_eq!(proof.to_hash().as_ref(), beta.as_ref());
+        assert!(public_key.verify(b"beta", proof.as_ref()).is_err());
+
+        let parsed = Proof::new(algorithm, proof.as_ref()).unwrap();
+        assert_eq!(beta.as_ref(), parsed.to_hash().as_ref());
+    }
+
+    #[test]
+    fn test_rejects_invalid_secret_keys() {
+        assert!(VrfKeyPair::from_secret_key(&ECVRF_P256_SHA256_TAI, &[0u8; 32]).is_err());
+        assert!(VrfKeyPair::from_secret_key(&ECVRF_P256_SHA256_TAI, &[0xffu8; 32]).is_err());
+        assert!(VrfKeyPair::from_secret_key(&ECVRF_P256_SHA256_TAI, &[1u8; 31]).is_err());
+    }
+
+    #[test]
+    fn test_debug() {
+        let key_pair = VrfKeyPair::from_secret_key(&ECVRF_P256_SHA256_TAI, &[1u8; 32]).unwrap();
+        assert_eq!(
+            format!(
+                "VrfKeyPair {{ public_key: PublicKey {{ algorithm: EcvrfP256Sha256Tai, bytes: \"{}\" }}, .. }}",
+                crate::hex::encode(key_pair.public_key())
+            ),
+            format!("{key_pair:?}")
+        );
+    }
+}
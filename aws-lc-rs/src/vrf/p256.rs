@@ -0,0 +1,262 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! The group and scalar operations of the `ECVRF-P256-SHA256-TAI` suite, using *AWS-LC*.
+
+use super::Q_LEN;
+use crate::ec::{ec_group_from_nid, ec_point_from_bytes};
+use crate::error::Unspecified;
+use crate::ptr::{ConstPointer, LcPtr};
+use crate::{digest, hmac};
+use aws_lc::{
+    point_conversion_form_t, BN_CTX_new, BN_MONT_CTX_new_consttime, BN_bn2bin_padded, BN_cmp,
+    BN_is_zero, BN_mod_add_quick, BN_mod_mul_montgomery, BN_new, BN_nnmod, BN_to_montgomery,
+    EC_GROUP_get0_order, EC_POINT_add, EC_POINT_copy, EC_POINT_invert, EC_POINT_mul, EC_POINT_new,
+    EC_POINT_point2oct, NID_X9_62_prime256v1, BIGNUM, BN_CTX, BN_MONT_CTX, EC_GROUP, EC_POINT,
+};
+use core::ptr::null;
+use zeroize::Zeroize;
+
+/// The prefix of the compressed encoding of a point with an even y-coordinate.
+const EVEN_Y_PREFIX: u8 = 0x02;
+
+/// The suite string of `ECVRF-P256-SHA256-TAI`.
+pub(super) const SUITE_STRING: u8 = 0x01;
+
+/// The length of a compressed point, `ptLen`.
+pub(super) const POINT_LEN: usize = 33;
+
+/// An integer modulo the group order `q`.
+pub(super) struct Scalar(LcPtr<BIGNUM>);
+
+impl Scalar {
+    pub(super) fn is_zero(&self) -> bool {
+        1 == unsafe { BN_is_zero(*self.0) }
+    }
+
+    /// `int_to_string` with length `qLen`.
+    pub(super) fn to_be_bytes(&self) -> Result<[u8; Q_LEN], Unspecified> {
+        let mut bytes = [0u8; Q_LEN];
+        if 1 != unsafe { BN_bn2bin_padded(bytes.as_mut_ptr(), bytes.len(), *self.0) } {
+            return Err(Unspecified);
+        }
+        Ok(bytes)
+    }
+}
+
+/// The P-256 group, with a Montgomery context for arithmetic modulo its order.
+pub(super) struct P256 {
+    group: LcPtr<EC_GROUP>,
+    ctx: LcPtr<BN_CTX>,
+    order_mont: LcPtr<BN_MONT_CTX>,
+}
+
+impl P256 {
+    pub(super) fn new() -> Result<Self, Unspecified> {
+        let group = ec_group_from_nid(NID_X9_62_prime256v1)?;
+        let ctx = LcPtr::new(unsafe { BN_CTX_new() })?;
+        let order = ConstPointer::new(unsafe { EC_GROUP_get0_order(*group) })?;
+        let order_mont = LcPtr::new(unsafe { BN_MONT_CTX_new_consttime(*order, *ctx) })?;
+        Ok(P256 {
+            group,
+            ctx,
+            order_mont,
+        })
+    }
+
+    pub(super) fn digest_algorithm() -> &'static digest::Algorithm {
+        &digest::SHA256
+    }
+
+    fn order(&self) -> Result<ConstPointer<BIGNUM>, Unspecified> {
+        Ok(ConstPointer::new(unsafe {
+            EC_GROUP_get0_order(*self.group)
+        })?)
+    }
+
+    /// `string_to_int`, for a value that must be less than the group order.
+    pub(super) fn scalar_from_bytes(&self, bytes: &[u8]) -> Option<Scalar> {
+        if bytes.len() > Q_LEN {
+            return None;
+        }
+        let order = self.order().ok()?;
+        let value = LcPtr::<BIGNUM>::try_from(bytes).ok()?;
+        if unsafe { BN_cmp(*value, *order) } >= 0 {
+            return None;
+        }
+        Some(Scalar(value))
+    }
+
+    /// `string_to_int` reduced modulo the group order.
+    fn scalar_from_bytes_reduced(&self, bytes: &[u8]) -> Result<Scalar, Unspecified> {
+        let order = self.order()?;
+        let unreduced = LcPtr::<BIGNUM>::try_from(bytes)?;
+        let value = LcPtr::new(unsafe { BN_new() })?;
+        if 1 != unsafe { BN_nnmod(*value, *unreduced, *order, *self.ctx) } {
+            return Err(Unspecified);
+        }
+        Ok(Scalar(value))
+    }
+
+    /// Computes `k + c*x mod q`, in constant time with respect to `k` and `x`.
+    pub(super) fn mul_add(
+        &self,
+        k: &Scalar,
+        c: &Scalar,
+        x: &Scalar,
+    ) -> Result<Scalar, Unspecified> {
+        let order = self.order()?;
+        let x_mont = LcPtr::new(unsafe { BN_new() })?;
+        let product = LcPtr::new(unsafe { BN_new() })?;
+        let result = LcPtr::new(unsafe { BN_new() })?;
+        if 1 != unsafe { BN_to_montgomery(*x_mont, *x.0, *self.order_mont, *self.ctx) }
+            || 1 != unsafe {
+                BN_mod_mul_montgomery(*product, *x_mont, *c.0, *self.order_mont, *self.ctx)
+            }
+            || 1 != unsafe { BN_mod_add_quick(*result, *product, *k.0, *order) }
+        {
+            return Err(Unspecified);
+        }
+        Ok(Scalar(result))
+    }
+
+    /// Section 5.4.2.1 of RFC 9381: the deterministic nonce of Section 3.2 of RFC 6979, with
+    /// `h_string` as the message and SHA-256 as the hash function. `secret_key` is
+    /// `int2octets(x)`.
+    pub(super) fn nonce(
+        &self,
+        secret_key: &[u8; Q_LEN],
+        h_string: &[u8],
+    ) -> Result<Scalar, Unspecified> {
+        // qlen = hlen, so bits2octets(h1) is h1 reduced modulo q.
+        let h1 = digest::digest(&digest::SHA256, h_string);
+        let h1 = self.scalar_from_bytes_reduced(h1.as_ref())?.to_be_bytes()?;
+
+        let mut v = [0x01u8; Q_LEN];
+        let mut k = [0x00u8; Q_LEN];
+        for separator in [0x00u8, 0x01] {
+            let key = hmac::Key::new(hmac::HMAC_SHA256, &k);
+            let mut ctx = hmac::Context::with_key(&key);
+            ctx.update(&v);
+            ctx.update(&[separator]);
+            ctx.update(secret_key);
+            ctx.update(&h1);
+            k.copy_from_slice(ctx.sign().as_ref());
+            let tag = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, &k), &v);
+            v.copy_from_slice(tag.as_ref());
+        }
+
+        loop {
+            let key = hmac::Key::new(hmac::HMAC_SHA256, &k);
+            let tag = hmac::sign(&key, &v);
+            v.copy_from_slice(tag.as_ref());
+            if let Some(nonce) = self.scalar_from_bytes(&v) {
+                if !nonce.is_zero() {
+                    k.zeroize();
+                    v.zeroize();
+                    return Ok(nonce);
+                }
+            }
+            let mut ctx = hmac::Context::with_key(&key);
+            ctx.update(&v);
+            ctx.update(&[0x00]);
+            k.copy_from_slice(ctx.sign().as_ref());
+            let tag = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, &k), &v);
+            v.copy_from_slice(tag.as_ref());
+        }
+    }
+
+    fn new_point(&self) -> Result<LcPtr<EC_POINT>, Unspecified> {
+        Ok(LcPtr::new(unsafe { EC_POINT_new(*self.group) })?)
+    }
+
+    /// Computes `n*G + m*Q`, where either term may be omitted.
+    fn mul_points(
+        &self,
+        n: Option<&Scalar>,
+        q_and_m: Option<(&LcPtr<EC_POINT>, &Scalar)>,
+    ) -> Result<LcPtr<EC_POINT>, Unspecified> {
+        let result = self.new_point()?;
+        if 1 != unsafe {
+            EC_POINT_mul(
+                *self.group,
+                *result,
+                n.map_or(null(), |n| *n.0),
+                q_and_m.map_or(null(), |(q, _)| **q),
+                q_and_m.map_or(null(), |(_, m)| *m.0),
+                *self.ctx,
+            )
+        } {
+            return Err(Unspecified);
+        }
+        Ok(result)
+    }
+
+    pub(super) fn decode_point(&self, bytes: &[u8]) -> Option<LcPtr<EC_POINT>> {
+        // Only the compressed encoding is accepted.
+        if bytes.len() != POINT_LEN || bytes[0] & !1 != EVEN_Y_PREFIX {
+            return None;
+        }
+        ec_point_from_bytes(&self.group, bytes).ok()
+    }
+
+    pub(super) fn encode_point(
+        &self,
+        point: &LcPtr<EC_POINT>,
+        out: &mut [u8],
+    ) -> Result<(), Unspecified> {
+        let out_len = unsafe {
+            EC_POINT_point2oct(
+                *self.group,
+                **point,
+                point_conversion_form_t::POINT_CONVERSION_COMPRESSED,
+                out.as_mut_ptr(),
+                out.len(),
+                *self.ctx,
+            )
+        };
+        if out_len != POINT_LEN || out.len() != POINT_LEN {
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
+
+    /// `interpret_hash_value_as_a_point`. The cofactor of P-256 is 1, so there is no cofactor
+    /// to clear.
+    pub(super) fn hash_to_point(&self, hash: &[u8]) -> Option<LcPtr<EC_POINT>> {
+        let mut encoded = [0u8; POINT_LEN];
+        encoded[0] = EVEN_Y_PREFIX;
+        encoded[1..].copy_from_slice(&hash[..Q_LEN]);
+        self.decode_point(&encoded)
+    }
+
+    pub(super) fn mul_base(&self, scalar: &Scalar) -> Result<LcPtr<EC_POINT>, Unspecified> {
+        self.mul_points(Some(scalar), None)
+    }
+
+    pub(super) fn mul(
+        &self,
+        point: &LcPtr<EC_POINT>,
+        scalar: &Scalar,
+    ) -> Result<LcPtr<EC_POINT>, Unspecified> {
+        self.mul_points(None, Some((point, scalar)))
+    }
+
+    pub(super) fn sub(
+        &self,
+        a: &LcPtr<EC_POINT>,
+        b: &LcPtr<EC_POINT>,
+    ) -> Result<LcPtr<EC_POINT>, Unspecified> {
+        let negated = self.new_point()?;
+        if 1 != unsafe { EC_POINT_copy(*negated, **b) }
+            || 1 != unsafe { EC_POINT_invert(*self.group, *negated, *self.ctx) }
+        {
+            return Err(Unspecified);
+        }
+        let result = self.new_point()?;
+        if 1 != unsafe { EC_POINT_add(*self.group, *result, **a, *negated, *self.ctx) } {
+            return Err(Unspecified);
+        }
+        Ok(result)
+    }
+}
@@ -157,10 +157,13 @@ fn test_rsa_verify() {
         d74f3fc707c60ba82af29face1c5269a54d4185e8efce9e566c3ca0c202ac1a4cc180d5320bf599a",
     );
 
-    let params = &[
+    #[cfg(not(feature = "approved-only"))]
+    let params: &[&signature::RsaParameters] = &[
         &signature::RSA_PKCS1_1024_8192_SHA256_FOR_LEGACY_USE_ONLY,
         &signature::RSA_PKCS1_2048_8192_SHA256,
     ];
+    #[cfg(feature = "approved-only")]
+    let params: &[&signature::RsaParameters] = &[&signature::RSA_PKCS1_2048_8192_SHA256];
 
     for &alg in params {
         let actual_result = signature::UnparsedPublicKey::new(alg, &public_key).verify(&msg, &sig);
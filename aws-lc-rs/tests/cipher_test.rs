@@ -2,11 +2,21 @@
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
 use aws_lc_rs::cipher::{
-    DecryptingKey, EncryptingKey, EncryptionContext, OperatingMode, PaddedBlockDecryptingKey,
-    PaddedBlockEncryptingKey, UnboundCipherKey, AES_128, AES_256,
+    AlgorithmId, DecryptingKey, DecryptionContext, EncryptingKey, EncryptionContext,
+    GcmDecryptingKey, GcmEncryptingKey, Iv128, OperatingMode, PaddedBlockDecryptingKey,
+    PaddedBlockEncryptingKey, SivDecryptingKey, SivEncryptingKey, SivKey, StreamingDecryptingKey,
+    StreamingEncryptingKey, UnboundCipherKey, XtsDecryptingKey, XtsEncryptingKey, AES_128, AES_192,
+    AES_256, CHACHA20,
 };
+#[cfg(not(feature = "fips"))]
+use aws_lc_rs::cipher::{CcmDecryptingKey, CcmEncryptingKey, CcmKey};
+#[cfg(not(feature = "approved-only"))]
+use aws_lc_rs::cipher::{TRIPLE_DES_3KEY_FOR_LEGACY_USE_ONLY, TRIPLE_DES_CBC_IV_LEN};
+use aws_lc_rs::hkdf;
 use aws_lc_rs::iv::FixedLength;
+use aws_lc_rs::key_wrap::{self, AesKek, KeyWrap};
 use aws_lc_rs::test::from_hex;
+use aws_lc_rs::test::rand::FixedSliceRandom;
 
 macro_rules! padded_cipher_kat {
     ($name:ident, $alg:expr, $mode:expr, $constructor:ident, $key:literal, $iv: literal, $plaintext:literal, $ciphertext:literal) => {
@@ -74,6 +84,39 @@ macro_rules! cipher_kat {
     };
 }
 
+macro_rules! stream_cipher_kat {
+    ($name:ident, $alg:expr, $constructor:ident, $key:literal, $nonce: literal, $plaintext:literal, $ciphertext:literal) => {
+        #[test]
+        fn $name() {
+            let key = from_hex($key).unwrap();
+            let input = from_hex($plaintext).unwrap();
+            let expected_ciphertext = from_hex($ciphertext).unwrap();
+
+            let nonce = from_hex($nonce).unwrap();
+            let fixed_nonce = FixedLength::try_from(nonce.as_slice()).unwrap();
+            let context = EncryptionContext::Nonce96(fixed_nonce);
+
+            let unbound_key = UnboundCipherKey::new($alg, &key).unwrap();
+
+            let encrypting_key = EncryptingKey::$constructor(unbound_key).unwrap();
+            assert_eq!(OperatingMode::Stream, encrypting_key.mode());
+            assert_eq!($alg, encrypting_key.algorithm());
+            let mut in_out = input.clone();
+            let context = encrypting_key
+                .less_safe_encrypt(in_out.as_mut_slice(), context)
+                .unwrap();
+            assert_eq!(expected_ciphertext.as_slice(), in_out);
+
+            let unbound_key2 = UnboundCipherKey::new($alg, &key).unwrap();
+            let decrypting_key = DecryptingKey::$constructor(unbound_key2).unwrap();
+            assert_eq!(OperatingMode::Stream, decrypting_key.mode());
+            assert_eq!($alg, decrypting_key.algorithm());
+            let plaintext = decrypting_key.decrypt(&mut in_out, context).unwrap();
+            assert_eq!(input.as_slice(), plaintext);
+        }
+    };
+}
+
 macro_rules! padded_cipher_rt {
     ($name:ident, $alg:expr, $mode:expr, $constructor:ident, $key:literal, $plaintext:literal) => {
         #[test]
@@ -337,3 +380,1821 @@ padded_cipher_rt!(
     "d4a8206dcae01242f9db79a4ecfe277d0f7bb8ccbafd8f9809adb39f35aa9b41",
     "a39c1fdf77ea3e1f18178c0ec237c70a34"
 );
+
+cipher_kat!(
+    test_kat_aes_128_cfb128_16_bytes,
+    &AES_128,
+    OperatingMode::CFB128,
+    cfb128,
+    "a2c30d2e92fd52c52aea70964ef0ea86",
+    "5bb7c88c28c3a8faf69e5bd9106dc43c",
+    "e12403b8102190c15778e9d7e9a0a908",
+    "c484118469ceb3aabeece42ee34e7912"
+);
+
+cipher_kat!(
+    test_kat_aes_128_cfb128_17_bytes,
+    &AES_128,
+    OperatingMode::CFB128,
+    cfb128,
+    "93ec91a437ce03d7a6d66d7ee6803798",
+    "bbf6d951372857dda7833576e935e396",
+    "bffd6c3baf71e9e514a2861ccaa89729c0",
+    "585c7b6cf1536abb3dae680d18a75d6c48"
+);
+
+cipher_kat!(
+    test_kat_aes_128_cfb128_33_bytes,
+    &AES_128,
+    OperatingMode::CFB128,
+    cfb128,
+    "267d7525498ad576fae339cbe73d8df3",
+    "d8cf179f341534ca0aefb7cc450af404",
+    "cd8917023cc5291475da897e6dc488be0bda430a12dc8b4da0653152f91dac4189",
+    "20de18ce80b42fe84b4c436efb44966a2099a5033114668c5ba08fbe64ab4d98b6"
+);
+
+cipher_kat!(
+    test_kat_aes_256_cfb128_16_bytes,
+    &AES_256,
+    OperatingMode::CFB128,
+    cfb128,
+    "669fc14f257cc5509f46305eb5d3f37b598f1e1b461f1cd62ff43cec95eec864",
+    "4a7e977bef3201114ec7df5b97d31622",
+    "af7f4341b063bf6a65fb11fe885cd963",
+    "3ab561a2d38918098b95be62ebd95fb6"
+);
+
+cipher_kat!(
+    test_kat_aes_256_cfb128_17_bytes,
+    &AES_256,
+    OperatingMode::CFB128,
+    cfb128,
+    "aa7f74c269e77468483f130bcb8ce055e171d80ad0b12e9b34f582ed06b46087",
+    "17c4865960ddb5029d1da51ffa824099",
+    "a73a4468e5ee8dc730c6b38f9f2cdcf8e1",
+    "ab515fced1494d9549aceea95315bd9eca"
+);
+
+cipher_kat!(
+    test_kat_aes_256_cfb128_33_bytes,
+    &AES_256,
+    OperatingMode::CFB128,
+    cfb128,
+    "07ce171e9b7860d2350a70f60989016022493e243fea8064245d453c47ca580b",
+    "8a2c4d0a11b6171876f0dedf847ac171",
+    "e9a1842541e0798de2ca9b08fa142d9abc44fec44c2723856d4f4f073e2f96cab2",
+    "379e39c1c7175a0caf13f0b1a1b9daaf319d10759153b9d04501750a577d2f2102"
+);
+
+cipher_rt!(
+    test_rt_aes_128_cfb128_15_bytes,
+    &AES_128,
+    OperatingMode::CFB128,
+    cfb128,
+    "9aeefe39b0cc47f831a1d61a87422583",
+    "fa305d52dc424c60b09b4d79bc717b"
+);
+
+cipher_rt!(
+    test_rt_aes_256_cfb128_17_bytes,
+    &AES_256,
+    OperatingMode::CFB128,
+    cfb128,
+    "aa7f74c269e77468483f130bcb8ce055e171d80ad0b12e9b34f582ed06b46087",
+    "a73a4468e5ee8dc730c6b38f9f2cdcf8e1"
+);
+
+// RFC 7539, Appendix A.2, Test Vector #1.
+stream_cipher_kat!(
+    test_kat_chacha20_rfc7539_a2_1,
+    &CHACHA20,
+    chacha20,
+    "0000000000000000000000000000000000000000000000000000000000000000",
+    "000000000000000000000000",
+    "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+    "76b8e0ada0f13d90405d6ae55386bd28bdd219b8a08ded1aa836efcc8b770dc7da41597c5157488d7724e03fb8d84a376a43b8f41518a11cc387b669b2ee6586"
+);
+
+// RFC 7539, Section 2.4.2. The RFC encrypts starting from block counter 1, so the first 64-byte
+// block here is a zero prefix that consumes block 0.
+stream_cipher_kat!(
+    test_kat_chacha20_rfc7539_2_4_2,
+    &CHACHA20,
+    chacha20,
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+    "000000000000004a00000000",
+    "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004c616469657320616e642047656e746c656d656e206f662074686520636c617373206f66202739393a204966204920636f756c64206f6666657220796f75206f6e6c79206f6e652074697020666f7220746865206675747572652c2073756e73637265656e20776f756c642062652069742e",
+    "af051e40bba0354981329a806a140eafd258a22a6dcb4bb9f6569cb3efe2deaf837bd87ca20b5ba12081a306af0eb35c41a239d20dfc74c81771560d9c9c1e4b6e2e359a2568f98041ba0728dd0d6981e97e7aec1d4360c20a27afccfd9fae0bf91b65c5524733ab8f593dabcd62b3571639d624e65152ab8f530c359f0861d807ca0dbf500d6a6156a38e088a22b65e52bc514d16ccf806818ce91ab77937365af90bbf74a35be6b40b8eedf2785e42874d"
+);
+
+// RFC 8439, Section 2.4.2, using the initial block counter of 1 from the RFC.
+#[test]
+fn test_kat_chacha20_rfc8439_2_4_2_with_counter() {
+    let key = from_hex("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f").unwrap();
+    let nonce: [u8; 12] = from_hex("000000000000004a00000000")
+        .unwrap()
+        .try_into()
+        .unwrap();
+    let plaintext = from_hex("4c616469657320616e642047656e746c656d656e206f662074686520636c617373206f66202739393a204966204920636f756c64206f6666657220796f75206f6e6c79206f6e652074697020666f7220746865206675747572652c2073756e73637265656e20776f756c642062652069742e").unwrap();
+    let expected_ciphertext = from_hex("6e2e359a2568f98041ba0728dd0d6981e97e7aec1d4360c20a27afccfd9fae0bf91b65c5524733ab8f593dabcd62b3571639d624e65152ab8f530c359f0861d807ca0dbf500d6a6156a38e088a22b65e52bc514d16ccf806818ce91ab77937365af90bbf74a35be6b40b8eedf2785e42874d").unwrap();
+
+    let encrypting_key =
+        EncryptingKey::chacha20(UnboundCipherKey::new(&CHACHA20, &key).unwrap()).unwrap();
+    let mut in_out = plaintext.clone();
+    let context = encrypting_key
+        .less_safe_encrypt(
+            &mut in_out,
+            EncryptionContext::Nonce96Counter32(FixedLength::from(nonce), 1),
+        )
+        .unwrap();
+    assert_eq!(expected_ciphertext, in_out);
+    assert_eq!("Nonce96Counter32", format!("{context:?}"));
+
+    let decrypting_key =
+        DecryptingKey::chacha20(UnboundCipherKey::new(&CHACHA20, &key).unwrap()).unwrap();
+    let mut streaming_key = StreamingDecryptingKey::new(decrypting_key, context).unwrap();
+    let mut decrypted = Vec::new();
+    let mut output = [0u8; 32];
+    for chunk in in_out.chunks(13) {
+        let update = streaming_key.update(chunk, &mut output).unwrap();
+        decrypted.extend_from_slice(update.written());
+    }
+    streaming_key.finish(&mut output).unwrap();
+    assert_eq!(plaintext, decrypted);
+}
+
+#[test]
+fn test_chacha20_counter_limit() {
+    let key = [0u8; 32];
+    let encrypting_key =
+        EncryptingKey::chacha20(UnboundCipherKey::new(&CHACHA20, &key).unwrap()).unwrap();
+
+    // The last block before the counter wraps can still be used.
+    let mut in_out = [0u8; 64];
+    let context = EncryptionContext::Nonce96Counter32(FixedLength::from([0u8; 12]), u32::MAX);
+    assert!(encrypting_key
+        .less_safe_encrypt(&mut in_out, context)
+        .is_ok());
+
+    let mut in_out = [0u8; 65];
+    let context = EncryptionContext::Nonce96Counter32(FixedLength::from([0u8; 12]), u32::MAX);
+    assert!(encrypting_key
+        .less_safe_encrypt(&mut in_out, context)
+        .is_err());
+}
+
+stream_cipher_kat!(
+    test_kat_chacha20_15_bytes,
+    &CHACHA20,
+    chacha20,
+    "93efc1b216686cf1171efa7f8733becb3b7d045a813fc97f33e750255aa6168e",
+    "c538c9a1b3ae882611895168",
+    "0177bcf43e6d1cf1a9815ee2b4bda1",
+    "178287a98f6eca43d43a46ec088ae9"
+);
+
+stream_cipher_kat!(
+    test_kat_chacha20_65_bytes,
+    &CHACHA20,
+    chacha20,
+    "7e1da4a5b047af79cfe39d36205aac17244c9c9706aa398691d3114dd21e000c",
+    "ccafc42a6dd59755ada53491",
+    "666794292abc113c8c46b41c8eaaf1e29f4763fad252f071d8ab6de56127a3a04bc5e9caf93b77e934482c28050f4810af0c1fd3568cc6ab516cc926c3a0a82077",
+    "cbb4398370a8b24148ac3467ee5edc38cf6335877ccdfc9e2014b8813e3801e0fd6958e194e09d2534f5994a2c5d13acae6aab25781b9c8f6ad244d393d311501d"
+);
+
+// NIST SP 800-38A, F.5.3 CTR-AES192.Encrypt
+cipher_kat!(
+    test_kat_aes_192_ctr_64_bytes,
+    &AES_192,
+    OperatingMode::CTR,
+    ctr,
+    "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+    "f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+    "1abc932417521ca24f2b0459fe7e6e0b090339ec0aa6faefd5ccc2c6f4ce8e941e36b26bd1ebc670d1bd1d665620abf74f78a7f6d29809585a97daec58c6b050"
+);
+
+cipher_kat!(
+    test_kat_aes_192_ctr_15_bytes,
+    &AES_192,
+    OperatingMode::CTR,
+    ctr,
+    "6a0b9b8f5f433a66f596a272fc4a51a722e35a783a6be1bc",
+    "00544c9f215386c7917b92fcede780ee",
+    "2382cc9744e4eac539deb8aff178c9",
+    "cbaf60d7a2daf47016034509ea73a9"
+);
+
+cipher_kat!(
+    test_kat_aes_192_ctr_17_bytes,
+    &AES_192,
+    OperatingMode::CTR,
+    ctr,
+    "3929ef7a7bdc6e2f6e766f9a01607ff7db6f90bc8aedb9ee",
+    "a4d7739e9dec29a89f4f7925269e22bd",
+    "8940df5c4f0531106f762479fff05966d0",
+    "34e29bdef3296a746bea57fc81916f16ea"
+);
+
+padded_cipher_kat!(
+    test_kat_aes_192_cbc_15_bytes,
+    &AES_192,
+    OperatingMode::CBC,
+    cbc_pkcs7,
+    "2351766da1c8f3226d4e9877fc890db1041671bd9dd80bf8",
+    "4ed6e64917ed44c9be45386c925dbf49",
+    "c01c5a6efddacc8c6e6bc2cb4b097e",
+    "6c5be238837aa169507f3095f26b0e62"
+);
+
+padded_cipher_rt!(
+    test_rt_aes_192_cbc_17_bytes,
+    &AES_192,
+    OperatingMode::CBC,
+    cbc_pkcs7,
+    "2351766da1c8f3226d4e9877fc890db1041671bd9dd80bf8",
+    "c01c5a6efddacc8c6e6bc2cb4b097e3c01"
+);
+
+cipher_rt!(
+    test_rt_aes_192_ctr_17_bytes,
+    &AES_192,
+    OperatingMode::CTR,
+    ctr,
+    "3929ef7a7bdc6e2f6e766f9a01607ff7db6f90bc8aedb9ee",
+    "8940df5c4f0531106f762479fff05966d0"
+);
+
+// NIST SP 800-38A, F.4.1 OFB-AES128.Encrypt
+cipher_kat!(
+    test_kat_aes_128_ofb_64_bytes,
+    &AES_128,
+    OperatingMode::OFB,
+    ofb,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+    "3b3fd92eb72dad20333449f8e83cfb4a7789508d16918f03f53c52dac54ed8259740051e9c5fecf64344f7a82260edcc304c6528f659c77866a510d9c1d6ae5e"
+);
+
+// NIST SP 800-38A, F.4.5 OFB-AES256.Encrypt
+cipher_kat!(
+    test_kat_aes_256_ofb_64_bytes,
+    &AES_256,
+    OperatingMode::OFB,
+    ofb,
+    "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+    "dc7e84bfda79164b7ecd8486985d38604febdc6740d20b3ac88f6ad82a4fb08d71ab47a086e86eedf39d1c5bba97c4080126141d67f37be8538f5a8be740e484"
+);
+
+cipher_kat!(
+    test_kat_aes_128_ofb_15_bytes,
+    &AES_128,
+    OperatingMode::OFB,
+    ofb,
+    "212bf96b011a3d32f8675ecc4abb7b93",
+    "7c6f3ef27ff0ad157e2af1d8397a930a",
+    "d3b62aed38598454783f6c6d6f7c6d",
+    "7efa266037cee73c0c1a5a72d44446"
+);
+
+cipher_kat!(
+    test_kat_aes_128_ofb_17_bytes,
+    &AES_128,
+    OperatingMode::OFB,
+    ofb,
+    "e9052f7a3dd84d09ea67ea262109108b",
+    "876fc2596b5cff2693668d476c6ba280",
+    "3274cfc989c181278c6af7efa1850da87b",
+    "fbba6cc74b729e9b52ba2fbf25621ea53b"
+);
+
+cipher_kat!(
+    test_kat_aes_256_ofb_15_bytes,
+    &AES_256,
+    OperatingMode::OFB,
+    ofb,
+    "f162eabe5182f737e553d95ff8d0cc4a04d4efc437868a84c8f2dfc9d07b21c8",
+    "2cbe6168c853d97bf07b886de9e7a6e4",
+    "6c0dea9837f75796860e8b7e016126",
+    "d7660d88bb63431ddfe0310b3d8167"
+);
+
+cipher_kat!(
+    test_kat_aes_256_ofb_17_bytes,
+    &AES_256,
+    OperatingMode::OFB,
+    ofb,
+    "8cba1cccfff56d6a6cc3dab2abcc49b74ae43608ae3b7ea6862dc1d72e26a5e4",
+    "e0700eb40f03e1443ab6adc25a569b4a",
+    "deecf069aa378ed70971047ab61680dc99",
+    "b316da2463637a76d8ee80eede4cb2bb32"
+);
+
+cipher_rt!(
+    test_rt_aes_256_ofb_17_bytes,
+    &AES_256,
+    OperatingMode::OFB,
+    ofb,
+    "8cba1cccfff56d6a6cc3dab2abcc49b74ae43608ae3b7ea6862dc1d72e26a5e4",
+    "deecf069aa378ed70971047ab61680dc99"
+);
+
+// NIST SP 800-38A, F.2.1 CBC-AES128.Encrypt
+cipher_kat!(
+    test_kat_aes_128_cbc_64_bytes,
+    &AES_128,
+    OperatingMode::CBC,
+    cbc,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+    "7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b273bed6b8e3c1743b7116e69e222295163ff1caa1681fac09120eca307586e1a7"
+);
+
+// NIST SP 800-38A, F.2.3 CBC-AES192.Encrypt
+cipher_kat!(
+    test_kat_aes_192_cbc_64_bytes,
+    &AES_192,
+    OperatingMode::CBC,
+    cbc,
+    "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+    "4f021db243bc633d7178183a9fa071e8b4d9ada9ad7dedf4e5e738763f69145a571b242012fb7ae07fa9baac3df102e008b0e27988598881d920a9e64f5615cd"
+);
+
+cipher_rt!(
+    test_rt_aes_128_cbc_16_bytes_unpadded,
+    &AES_128,
+    OperatingMode::CBC,
+    cbc,
+    "000102030405060708090a0b0c0d0e0f",
+    "00112233445566778899aabbccddeeff"
+);
+
+cipher_rt!(
+    test_rt_aes_192_cbc_32_bytes_unpadded,
+    &AES_192,
+    OperatingMode::CBC,
+    cbc,
+    "000102030405060708090a0b0c0d0e0f1011121314151617",
+    "00112233445566778899aabbccddeeff00112233445566778899aabbccddeeff"
+);
+
+cipher_rt!(
+    test_rt_aes_256_cbc_48_bytes_unpadded,
+    &AES_256,
+    OperatingMode::CBC,
+    cbc,
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+    "00112233445566778899aabbccddeeff00112233445566778899aabbccddeeff00112233445566778899aabbccddeeff"
+);
+
+#[test]
+fn test_cbc_rejects_partial_block() {
+    let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+
+    let encrypting_key =
+        EncryptingKey::cbc(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+    let mut in_out = [0u8; 15];
+    assert!(encrypting_key.encrypt(&mut in_out).is_err());
+
+    let decrypting_key =
+        DecryptingKey::cbc(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+    let context = EncryptionContext::Iv128(FixedLength::from([0u8; 16])).into();
+    assert!(decrypting_key.decrypt(&mut in_out, context).is_err());
+}
+
+padded_cipher_kat!(
+    test_kat_aes_128_cbc_iso7816_16_bytes,
+    &AES_128,
+    OperatingMode::CBC,
+    cbc_iso7816,
+    "000102030405060708090a0b0c0d0e0f",
+    "00000000000000000000000000000000",
+    "00112233445566778899aabbccddeeff",
+    "69c4e0d86a7b0430d8cdb78070b4c55a8e17c55e98d0f4cb1a31eb14b39c547a"
+);
+
+padded_cipher_kat!(
+    test_kat_aes_192_cbc_iso7816_10_bytes,
+    &AES_192,
+    OperatingMode::CBC,
+    cbc_iso7816,
+    "000102030405060708090a0b0c0d0e0f1011121314151617",
+    "000102030405060708090a0b0c0d0e0f",
+    "00112233445566778899",
+    "4784dd726c1e8b2897eb27ef67ff258d"
+);
+
+padded_cipher_kat!(
+    test_kat_aes_256_cbc_iso7816_15_bytes,
+    &AES_256,
+    OperatingMode::CBC,
+    cbc_iso7816,
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+    "00000000000000000000000000000000",
+    "00112233445566778899aabbccddee",
+    "4ca8c956695b6870e5c94c943eecb88c"
+);
+
+padded_cipher_kat!(
+    test_kat_aes_128_cbc_ansi_x923_16_bytes,
+    &AES_128,
+    OperatingMode::CBC,
+    cbc_ansi_x923,
+    "000102030405060708090a0b0c0d0e0f",
+    "00000000000000000000000000000000",
+    "00112233445566778899aabbccddeeff",
+    "69c4e0d86a7b0430d8cdb78070b4c55aadaa4e685362cef4037189ecdaed24f1"
+);
+
+padded_cipher_kat!(
+    test_kat_aes_192_cbc_ansi_x923_10_bytes,
+    &AES_192,
+    OperatingMode::CBC,
+    cbc_ansi_x923,
+    "000102030405060708090a0b0c0d0e0f1011121314151617",
+    "000102030405060708090a0b0c0d0e0f",
+    "00112233445566778899",
+    "acca0e08cfcb4c49f47517fbeacb9f0e"
+);
+
+padded_cipher_kat!(
+    test_kat_aes_256_cbc_ansi_x923_12_bytes,
+    &AES_256,
+    OperatingMode::CBC,
+    cbc_ansi_x923,
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+    "00000000000000000000000000000000",
+    "00112233445566778899aabb",
+    "7880e86bbd5ba5597856c58883732d89"
+);
+
+#[test]
+fn test_cbc_ansi_x923_rejects_non_zero_padding() {
+    let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+    // PKCS#7 padding is only valid ANSI X9.23 padding when a single byte is added.
+    let mut pkcs7_padded = [0u8; 16];
+    pkcs7_padded[12..].fill(0x04);
+    let mut non_zero_padding = [0u8; 16];
+    non_zero_padding[15] = 0x08;
+    non_zero_padding[8] = 0x01;
+
+    for plaintext in [pkcs7_padded, non_zero_padding] {
+        let encrypting_key =
+            EncryptingKey::cbc(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+        let mut in_out = plaintext.to_vec();
+        let context = encrypting_key.encrypt(&mut in_out).unwrap();
+
+        let decrypting_key =
+            PaddedBlockDecryptingKey::cbc_ansi_x923(UnboundCipherKey::new(&AES_128, &key).unwrap())
+                .unwrap();
+        assert!(decrypting_key.decrypt(&mut in_out, context).is_err());
+    }
+}
+
+#[test]
+fn test_cbc_pkcs7_rejects_ansi_x923_padding() {
+    let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+    let encrypting_key =
+        PaddedBlockEncryptingKey::cbc_ansi_x923(UnboundCipherKey::new(&AES_128, &key).unwrap())
+            .unwrap();
+    let mut in_out = from_hex("00112233445566778899aabb").unwrap();
+    let context = encrypting_key.encrypt(&mut in_out).unwrap();
+
+    let decrypting_key =
+        PaddedBlockDecryptingKey::cbc_pkcs7(UnboundCipherKey::new(&AES_128, &key).unwrap())
+            .unwrap();
+    assert!(decrypting_key.decrypt(&mut in_out, context).is_err());
+}
+
+#[test]
+fn test_cbc_iso7816_rejects_malformed_padding() {
+    let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+    let mut marker_then_non_zero = [0u8; 16];
+    marker_then_non_zero[8] = 0x80;
+    marker_then_non_zero[12] = 0x01;
+    let mut marker_in_first_block = [0u8; 32];
+    marker_in_first_block[15] = 0x80;
+    let mut pkcs7_padded = [0u8; 16];
+    pkcs7_padded[8..].fill(0x08);
+
+    for plaintext in [
+        &[0u8; 16][..],
+        &marker_then_non_zero[..],
+        &marker_in_first_block[..],
+        &pkcs7_padded[..],
+    ] {
+        let encrypting_key =
+            EncryptingKey::cbc(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+        let mut in_out = plaintext.to_vec();
+        let context = encrypting_key.encrypt(&mut in_out).unwrap();
+
+        let decrypting_key =
+            PaddedBlockDecryptingKey::cbc_iso7816(UnboundCipherKey::new(&AES_128, &key).unwrap())
+                .unwrap();
+        assert!(decrypting_key.decrypt(&mut in_out, context).is_err());
+    }
+}
+
+macro_rules! ecb_cipher_kat {
+    ($name:ident, $alg:expr, $key:literal, $plaintext:literal, $ciphertext:literal) => {
+        #[test]
+        #[cfg(not(feature = "approved-only"))]
+        fn $name() {
+            let key = from_hex($key).unwrap();
+            let input = from_hex($plaintext).unwrap();
+            let expected_ciphertext = from_hex($ciphertext).unwrap();
+
+            let unbound_key = UnboundCipherKey::new($alg, &key).unwrap();
+            let encrypting_key = EncryptingKey::less_safe_ecb(unbound_key).unwrap();
+            assert_eq!(OperatingMode::ECB, encrypting_key.mode());
+            let mut in_out = input.clone();
+            let context = encrypting_key.encrypt(in_out.as_mut_slice()).unwrap();
+            assert!(matches!(context, DecryptionContext::None));
+            assert_eq!(expected_ciphertext, in_out);
+
+            let unbound_key2 = UnboundCipherKey::new($alg, &key).unwrap();
+            let decrypting_key = DecryptingKey::less_safe_ecb(unbound_key2).unwrap();
+            assert_eq!(OperatingMode::ECB, decrypting_key.mode());
+            let plaintext = decrypting_key.decrypt(&mut in_out, context).unwrap();
+            assert_eq!(input.as_slice(), plaintext);
+        }
+    };
+}
+
+// FIPS-197, Appendix C.1
+ecb_cipher_kat!(
+    test_kat_aes_128_ecb_16_bytes,
+    &AES_128,
+    "000102030405060708090a0b0c0d0e0f",
+    "00112233445566778899aabbccddeeff",
+    "69c4e0d86a7b0430d8cdb78070b4c55a"
+);
+
+// FIPS-197, Appendix C.2
+ecb_cipher_kat!(
+    test_kat_aes_192_ecb_16_bytes,
+    &AES_192,
+    "000102030405060708090a0b0c0d0e0f1011121314151617",
+    "00112233445566778899aabbccddeeff",
+    "dda97ca4864cdfe06eaf70a0ec0d7191"
+);
+
+// FIPS-197, Appendix C.3, repeated over two blocks
+ecb_cipher_kat!(
+    test_kat_aes_256_ecb_32_bytes,
+    &AES_256,
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+    "00112233445566778899aabbccddeeff00112233445566778899aabbccddeeff",
+    "8ea2b7ca516745bfeafc49904b4960898ea2b7ca516745bfeafc49904b496089"
+);
+
+#[test]
+#[cfg(not(feature = "approved-only"))]
+fn test_ecb_rejects_partial_block() {
+    let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+    let encrypting_key =
+        EncryptingKey::less_safe_ecb(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+    let mut in_out = [0u8; 17];
+    assert!(encrypting_key.encrypt(&mut in_out).is_err());
+
+    let chacha_key = [0u8; 32];
+    let encrypting_key =
+        EncryptingKey::less_safe_ecb(UnboundCipherKey::new(&CHACHA20, &chacha_key).unwrap())
+            .unwrap();
+    assert!(encrypting_key.encrypt(&mut [0u8; 64]).is_err());
+}
+
+// NIST SP 800-38A, F.3.7 CFB8-AES128.Encrypt
+cipher_kat!(
+    test_kat_aes_128_cfb8_18_bytes,
+    &AES_128,
+    OperatingMode::CFB8,
+    cfb8,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d",
+    "3b79424c9c0dd436bace9e0ed4586a4f32b9"
+);
+
+// NIST SP 800-38A, F.3.9 CFB8-AES192.Encrypt
+cipher_kat!(
+    test_kat_aes_192_cfb8_18_bytes,
+    &AES_192,
+    OperatingMode::CFB8,
+    cfb8,
+    "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d",
+    "cda2521ef0a905ca44cd057cbf0d47a0678a"
+);
+
+// NIST SP 800-38A, F.3.11 CFB8-AES256.Encrypt
+cipher_kat!(
+    test_kat_aes_256_cfb8_18_bytes,
+    &AES_256,
+    OperatingMode::CFB8,
+    cfb8,
+    "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d",
+    "dc1f1a8520a64db55fcc8ac554844e889700"
+);
+
+cipher_kat!(
+    test_kat_aes_128_cfb8_15_bytes,
+    &AES_128,
+    OperatingMode::CFB8,
+    cfb8,
+    "212bf96b011a3d32f8675ecc4abb7b93",
+    "7c6f3ef27ff0ad157e2af1d8397a930a",
+    "d3b62aed38598454783f6c6d6f7c6d",
+    "7e24462cc7c26bcf0d1180264801e1"
+);
+
+cipher_rt!(
+    test_rt_aes_192_cfb8_17_bytes,
+    &AES_192,
+    OperatingMode::CFB8,
+    cfb8,
+    "000102030405060708090a0b0c0d0e0f1011121314151617",
+    "deecf069aa378ed70971047ab61680dc99"
+);
+
+macro_rules! xts_kat {
+    ($name:ident, $alg:expr, $data_key:literal, $tweak_key:literal, $tweak:literal, $plaintext:literal, $ciphertext:literal) => {
+        #[test]
+        fn $name() {
+            let data_key = from_hex($data_key).unwrap();
+            let tweak_key = from_hex($tweak_key).unwrap();
+            let tweak: [u8; 16] = from_hex($tweak).unwrap().try_into().unwrap();
+            let input = from_hex($plaintext).unwrap();
+            let expected_ciphertext = from_hex($ciphertext).unwrap();
+
+            let encrypting_key = XtsEncryptingKey::new(
+                UnboundCipherKey::new($alg, &data_key).unwrap(),
+                UnboundCipherKey::new($alg, &tweak_key).unwrap(),
+            )
+            .unwrap();
+            assert_eq!($alg, encrypting_key.algorithm());
+            let mut in_out = input.clone();
+            encrypting_key.encrypt(&tweak, &mut in_out).unwrap();
+            assert_eq!(expected_ciphertext, in_out);
+
+            let decrypting_key = XtsDecryptingKey::new(
+                UnboundCipherKey::new($alg, &data_key).unwrap(),
+                UnboundCipherKey::new($alg, &tweak_key).unwrap(),
+            )
+            .unwrap();
+            assert_eq!($alg, decrypting_key.algorithm());
+            decrypting_key.decrypt(&tweak, &mut in_out).unwrap();
+            assert_eq!(input, in_out);
+        }
+    };
+}
+
+// IEEE 1619-2007, XTS-AES-128 Vector 2
+xts_kat!(
+    test_kat_aes_128_xts_32_bytes,
+    &AES_128,
+    "11111111111111111111111111111111",
+    "22222222222222222222222222222222",
+    "33333333330000000000000000000000",
+    "4444444444444444444444444444444444444444444444444444444444444444",
+    "c454185e6a16936e39334038acef838bfb186fff7480adc4289382ecd6d394f0"
+);
+
+// IEEE 1619-2007, XTS-AES-128 Vector 15
+xts_kat!(
+    test_kat_aes_128_xts_17_bytes,
+    &AES_128,
+    "fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0",
+    "bfbebdbcbbbab9b8b7b6b5b4b3b2b1b0",
+    "9a785634120000000000000000000000",
+    "000102030405060708090a0b0c0d0e0f10",
+    "6c1625db4671522d3d7599601de7ca09ed"
+);
+
+xts_kat!(
+    test_kat_aes_128_xts_36_bytes,
+    &AES_128,
+    "fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0",
+    "bfbebdbcbbbab9b8b7b6b5b4b3b2b1b0",
+    "9a785634120000000000000000000000",
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20212223",
+    "edbf9dace45d6f6a7306e64be5dd824ba9363c270da7bc924c32ff3ff7d0905d2538f572"
+);
+
+xts_kat!(
+    test_kat_aes_256_xts_48_bytes,
+    &AES_256,
+    "2718281828459045235360287471352662497757247093699959574966967627",
+    "3141592653589793238462643383279502884197169399375105820974944592",
+    "ff000000000000000000000000000000",
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f",
+    "1c3b3a102f770386e4836c99e370cf9bea00803f5e482357a4ae12d414a3e63b5d31e276f8fe4a8d66b317f9ac683f44"
+);
+
+xts_kat!(
+    test_kat_aes_256_xts_37_bytes,
+    &AES_256,
+    "2718281828459045235360287471352662497757247093699959574966967627",
+    "3141592653589793238462643383279502884197169399375105820974944592",
+    "ff000000000000000000000000000000",
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f2021222324",
+    "1c3b3a102f770386e4836c99e370cf9bd500010cf8fe25a2cf7dce0764caf96cea00803f5e"
+);
+
+macro_rules! xts_decrypt_kat {
+    ($name:ident, $alg:expr, $key:literal, $tweak:literal, $ciphertext:literal, $plaintext:literal) => {
+        #[test]
+        fn $name() {
+            let key = from_hex($key).unwrap();
+            let tweak: [u8; 16] = from_hex($tweak).unwrap().try_into().unwrap();
+            let input = from_hex($ciphertext).unwrap();
+            let expected_plaintext = from_hex($plaintext).unwrap();
+
+            let decrypting_key = XtsDecryptingKey::from_double_length_key($alg, &key).unwrap();
+            let mut in_out = input.clone();
+            decrypting_key.decrypt(&tweak, &mut in_out).unwrap();
+            assert_eq!(expected_plaintext, in_out);
+
+            let encrypting_key = XtsEncryptingKey::from_double_length_key($alg, &key).unwrap();
+            encrypting_key.encrypt(&tweak, &mut in_out).unwrap();
+            assert_eq!(input, in_out);
+        }
+    };
+}
+
+// IEEE 1619-2007, XTS-AES-128 Vector 3
+xts_decrypt_kat!(
+    test_kat_aes_128_xts_decrypt_32_bytes,
+    &AES_128,
+    "fffefdfcfbfaf9f8f7f6f5f4f3f2f1f022222222222222222222222222222222",
+    "33333333330000000000000000000000",
+    "af85336b597afc1a900b2eb21ec949d292df4c047e0b21532186a5971a227a89",
+    "4444444444444444444444444444444444444444444444444444444444444444"
+);
+
+// IEEE 1619-2007, XTS-AES-128 Vector 16
+xts_decrypt_kat!(
+    test_kat_aes_128_xts_decrypt_18_bytes,
+    &AES_128,
+    "fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0bfbebdbcbbbab9b8b7b6b5b4b3b2b1b0",
+    "9a785634120000000000000000000000",
+    "d069444b7a7e0cab09e24447d24deb1fedbf",
+    "000102030405060708090a0b0c0d0e0f1011"
+);
+
+xts_decrypt_kat!(
+    test_kat_aes_256_xts_decrypt_37_bytes,
+    &AES_256,
+    "27182818284590452353602874713526624977572470936999595749669676273141592653589793238462643383279502884197169399375105820974944592",
+    "ff000000000000000000000000000000",
+    "1c3b3a102f770386e4836c99e370cf9bd500010cf8fe25a2cf7dce0764caf96cea00803f5e",
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f2021222324"
+);
+
+#[test]
+fn test_streaming_encrypt_one_shot_decrypt() {
+    let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+    let plaintext: Vec<u8> = (0..100u8).collect();
+
+    let encrypting_key =
+        EncryptingKey::ctr(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+    let mut streaming_key = StreamingEncryptingKey::new(encrypting_key).unwrap();
+    assert_eq!(OperatingMode::CTR, streaming_key.mode());
+    assert_eq!(&AES_128, streaming_key.algorithm());
+
+    let mut ciphertext = Vec::new();
+    let mut output = [0u8; 48];
+    for chunk in plaintext.chunks(7) {
+        let update = streaming_key.update(chunk, &mut output).unwrap();
+        ciphertext.extend_from_slice(update.written());
+    }
+    let (context, update) = streaming_key.finish(&mut output).unwrap();
+    assert!(update.written().is_empty());
+    assert_eq!(plaintext.len(), ciphertext.len());
+
+    let decrypting_key =
+        DecryptingKey::ctr(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+    let decrypted = decrypting_key.decrypt(&mut ciphertext, context).unwrap();
+    assert_eq!(plaintext.as_slice(), decrypted);
+}
+
+#[test]
+fn test_streaming_decrypt_cbc() {
+    let key = from_hex("2b7e151628aed2a6abf7158809cf4f3c").unwrap();
+    let iv = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+    let ciphertext = from_hex("7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b273bed6b8e3c1743b7116e69e222295163ff1caa1681fac09120eca307586e1a7").unwrap();
+    let expected = from_hex("6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710").unwrap();
+
+    let decrypting_key =
+        DecryptingKey::cbc(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+    let iv: [u8; 16] = iv.try_into().unwrap();
+    let context = DecryptionContext::Iv128(FixedLength::from(iv));
+    let mut streaming_key = StreamingDecryptingKey::new(decrypting_key, context).unwrap();
+
+    let mut plaintext = Vec::new();
+    let mut output = [0u8; 32];
+    for chunk in ciphertext.chunks(10) {
+        let update = streaming_key.update(chunk, &mut output).unwrap();
+        plaintext.extend_from_slice(update.written());
+    }
+    streaming_key.finish(&mut output).unwrap();
+    assert_eq!(expected, plaintext);
+}
+
+macro_rules! gcm_kat {
+    ($name:ident, $alg:expr, $key:literal, $nonce:literal, $aad:literal, $plaintext:literal, $ciphertext:literal, $tag:literal) => {
+        #[test]
+        fn $name() {
+            let key = from_hex($key).unwrap();
+            let nonce: [u8; 12] = from_hex($nonce).unwrap().try_into().unwrap();
+            let aad = from_hex($aad).unwrap();
+            let plaintext = from_hex($plaintext).unwrap();
+            let expected_ciphertext = from_hex($ciphertext).unwrap();
+            let expected_tag = from_hex($tag).unwrap();
+
+            let encrypting_key = GcmEncryptingKey::new($alg, &key).unwrap();
+            let mut in_out = plaintext.clone();
+            let (context, tag) = encrypting_key
+                .less_safe_encrypt(&mut in_out, &aad, EncryptionContext::from(nonce))
+                .unwrap();
+            assert_eq!(expected_ciphertext, in_out);
+            assert_eq!(expected_tag.as_slice(), tag.as_ref());
+
+            let decrypting_key = GcmDecryptingKey::new($alg, &key).unwrap();
+            let decrypted = decrypting_key
+                .decrypt(&mut in_out, &aad, &expected_tag, context)
+                .unwrap();
+            assert_eq!(plaintext.as_slice(), decrypted);
+
+            let mut forged_tag = expected_tag.clone();
+            forged_tag[0] ^= 1;
+            let mut in_out = expected_ciphertext.clone();
+            assert!(decrypting_key
+                .decrypt(
+                    &mut in_out,
+                    &aad,
+                    &forged_tag,
+                    DecryptionContext::from(nonce)
+                )
+                .is_err());
+        }
+    };
+}
+
+// The GCM specification, Test Case 1.
+gcm_kat!(
+    test_kat_aes_128_gcm_test_case_1,
+    &AES_128,
+    "00000000000000000000000000000000",
+    "000000000000000000000000",
+    "",
+    "",
+    "",
+    "58e2fccefa7e3061367f1d57a4e7455a"
+);
+
+// The GCM specification, Test Case 2.
+gcm_kat!(
+    test_kat_aes_128_gcm_test_case_2,
+    &AES_128,
+    "00000000000000000000000000000000",
+    "000000000000000000000000",
+    "",
+    "00000000000000000000000000000000",
+    "0388dace60b6a392f328c2b971b2fe78",
+    "ab6e47d42cec13bdf53a67b21257bddf"
+);
+
+// The GCM specification, Test Case 4.
+gcm_kat!(
+    test_kat_aes_128_gcm_test_case_4,
+    &AES_128,
+    "feffe9928665731c6d6a8f9467308308",
+    "cafebabefacedbaddecaf888",
+    "feedfacedeadbeeffeedfacedeadbeefabaddad2",
+    "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39",
+    "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091",
+    "5bc94fbc3221a5db94fae95ae7121a47"
+);
+
+// The GCM specification, Test Case 16.
+gcm_kat!(
+    test_kat_aes_256_gcm_test_case_16,
+    &AES_256,
+    "feffe9928665731c6d6a8f9467308308feffe9928665731c6d6a8f9467308308",
+    "cafebabefacedbaddecaf888",
+    "feedfacedeadbeeffeedfacedeadbeefabaddad2",
+    "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39",
+    "522dc1f099567d07f47f37a32a84427d643a8cdcbfe5c0c97598a2bd2555d1aa8cb08e48590dbb3da7b08b1056828838c5f61e6393ba7a0abcc9f662",
+    "76fc6ece0f4e1768cddf8853bb2d551b"
+);
+
+macro_rules! siv_kat {
+    ($name:ident, $alg:expr, $key:literal, [$($ad:literal),*], $plaintext:literal, $output:literal) => {
+        #[test]
+        fn $name() {
+            let key = from_hex($key).unwrap();
+            let (mac_key, ctr_key) = key.split_at(key.len() / 2);
+            let associated_data: Vec<Vec<u8>> = vec![$(from_hex($ad).unwrap()),*];
+            let associated_data: Vec<&[u8]> = associated_data.iter().map(Vec::as_slice).collect();
+            let plaintext = from_hex($plaintext).unwrap();
+            let expected_output = from_hex($output).unwrap();
+
+            let siv_key = SivKey::new(
+                UnboundCipherKey::new($alg, mac_key).unwrap(),
+                UnboundCipherKey::new($alg, ctr_key).unwrap(),
+            )
+            .unwrap();
+            let sealing_key = SivEncryptingKey::new(siv_key);
+            assert_eq!($alg, sealing_key.algorithm());
+            let mut in_out = plaintext.clone();
+            sealing_key.seal(&associated_data, &mut in_out).unwrap();
+            assert_eq!(expected_output, in_out);
+
+            let siv_key = SivKey::new(
+                UnboundCipherKey::new($alg, mac_key).unwrap(),
+                UnboundCipherKey::new($alg, ctr_key).unwrap(),
+            )
+            .unwrap();
+            let opening_key = SivDecryptingKey::new(siv_key);
+            let opened = opening_key.open(&associated_data, &mut in_out).unwrap();
+            assert_eq!(plaintext.as_slice(), opened);
+        }
+    };
+}
+
+// RFC 5297, Appendix A.1. Deterministic Authenticated Encryption Example.
+siv_kat!(
+    test_kat_aes_siv_rfc5297_a_1,
+    &AES_128,
+    "fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff",
+    ["101112131415161718191a1b1c1d1e1f2021222324252627"],
+    "112233445566778899aabbccddee",
+    "85632d07c6e8f37f950acd320a2ecc9340c02b9690c4dc04daef7f6afe5c"
+);
+
+// RFC 5297, Appendix A.2. Nonce-Based Authenticated Encryption Example.
+siv_kat!(
+    test_kat_aes_siv_rfc5297_a_2,
+    &AES_128,
+    "7f7e7d7c7b7a79787776757473727170404142434445464748494a4b4c4d4e4f",
+    [
+        "00112233445566778899aabbccddeeffdeaddadadeaddadaffeeddccbbaa99887766554433221100",
+        "102030405060708090a0",
+        "09f911029d74e35bd84156c5635688c0"
+    ],
+    "7468697320697320736f6d6520706c61696e7465787420746f20656e6372797074207573696e67205349562d414553",
+    "7bdb6e3b432667eb06f4d14bff2fbd0fcb900f2fddbe404326601965c889bf17dba77ceb094fa663b7a3f748ba8af829ea64ad544a272e9c485b62a3fd5c0d"
+);
+
+#[test]
+fn test_aes_siv_rejects_mismatched_associated_data() {
+    let key = from_hex("fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff").unwrap();
+    let (mac_key, ctr_key) = key.split_at(16);
+    let new_key = || {
+        SivKey::new(
+            UnboundCipherKey::new(&AES_128, mac_key).unwrap(),
+            UnboundCipherKey::new(&AES_128, ctr_key).unwrap(),
+        )
+        .unwrap()
+    };
+
+    let sealing_key = SivEncryptingKey::new(new_key());
+    let opening_key = SivDecryptingKey::new(new_key());
+    let mut sealed = b"attack at dawn".to_vec();
+    sealing_key
+        .seal(&[b"header", b"nonce"], &mut sealed)
+        .unwrap();
+
+    for associated_data in [
+        &[&b"header"[..]][..],
+        &[&b"header"[..], &b"nonce2"[..]][..],
+        &[&b"nonce"[..], &b"header"[..]][..],
+        &[&b"headernonce"[..]][..],
+    ] {
+        let mut in_out = sealed.clone();
+        assert!(opening_key.open(associated_data, &mut in_out).is_err());
+    }
+
+    let opened = opening_key
+        .open(&[b"header", b"nonce"], &mut sealed)
+        .unwrap();
+    assert_eq!(b"attack at dawn", opened);
+}
+
+// NIST SP 800-67 Rev. 2, Appendix B. The example uses ECB mode, which is equivalent to CBC mode
+// with a zero IV applied to each block separately.
+#[test]
+#[cfg(not(feature = "approved-only"))]
+fn test_kat_triple_des_sp800_67_example() {
+    let key = from_hex("0123456789abcdef23456789abcdef01456789abcdef0123").unwrap();
+    let plaintext = from_hex("54686520717566636b2062726f776e20666f78206a756d70").unwrap();
+    let expected_ciphertext = from_hex("a826fd8ce53b855fcce21c8112256fe668d5c05dd9b6b900").unwrap();
+
+    let encrypting_key = EncryptingKey::cbc(
+        UnboundCipherKey::new(&TRIPLE_DES_3KEY_FOR_LEGACY_USE_ONLY, &key).unwrap(),
+    )
+    .unwrap();
+    let decrypting_key = DecryptingKey::cbc(
+        UnboundCipherKey::new(&TRIPLE_DES_3KEY_FOR_LEGACY_USE_ONLY, &key).unwrap(),
+    )
+    .unwrap();
+
+    for (block, expected_block) in plaintext.chunks(8).zip(expected_ciphertext.chunks(8)) {
+        let mut in_out = block.to_vec();
+        let context = EncryptionContext::Iv64(FixedLength::from([0u8; TRIPLE_DES_CBC_IV_LEN]));
+        let context = encrypting_key
+            .less_safe_encrypt(&mut in_out, context)
+            .unwrap();
+        assert_eq!(expected_block, in_out.as_slice());
+
+        let decrypted = decrypting_key.decrypt(&mut in_out, context).unwrap();
+        assert_eq!(block, decrypted);
+    }
+}
+
+#[test]
+#[cfg(not(feature = "approved-only"))]
+fn test_kat_triple_des_cbc_pkcs7() {
+    let key = from_hex("0123456789abcdef23456789abcdef01456789abcdef0123").unwrap();
+    let iv: [u8; TRIPLE_DES_CBC_IV_LEN] = from_hex("f69f2445df4f9b17").unwrap().try_into().unwrap();
+    let plaintext = from_hex("54686520717566636b2062726f776e20666f78206a756d70").unwrap();
+
+    for (len, expected_ciphertext) in [
+        (
+            24,
+            "a5c282bad0de3774becd2e04386b589fb5057d8552fc4336af45148b1b044b00",
+        ),
+        (13, "a5c282bad0de3774621066a92004a8bc"),
+    ] {
+        let expected_ciphertext = from_hex(expected_ciphertext).unwrap();
+
+        let encrypting_key = PaddedBlockEncryptingKey::cbc_pkcs7(
+            UnboundCipherKey::new(&TRIPLE_DES_3KEY_FOR_LEGACY_USE_ONLY, &key).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            &TRIPLE_DES_3KEY_FOR_LEGACY_USE_ONLY,
+            encrypting_key.algorithm()
+        );
+        let mut in_out = plaintext[..len].to_vec();
+        let context = encrypting_key
+            .less_safe_encrypt(&mut in_out, EncryptionContext::Iv64(FixedLength::from(iv)))
+            .unwrap();
+        assert_eq!(expected_ciphertext, in_out);
+        assert_eq!("Iv64", format!("{context:?}"));
+
+        let decrypting_key = PaddedBlockDecryptingKey::cbc_pkcs7(
+            UnboundCipherKey::new(&TRIPLE_DES_3KEY_FOR_LEGACY_USE_ONLY, &key).unwrap(),
+        )
+        .unwrap();
+        let decrypted = decrypting_key.decrypt(&mut in_out, context).unwrap();
+        assert_eq!(&plaintext[..len], decrypted);
+    }
+}
+
+#[test]
+fn test_algorithm_introspection() {
+    for (alg, id, key_len) in [
+        (&AES_128, AlgorithmId::Aes128, 16),
+        (&AES_192, AlgorithmId::Aes192, 24),
+        (&AES_256, AlgorithmId::Aes256, 32),
+        (&CHACHA20, AlgorithmId::Chacha20, 32),
+        #[cfg(not(feature = "approved-only"))]
+        (
+            &TRIPLE_DES_3KEY_FOR_LEGACY_USE_ONLY,
+            AlgorithmId::TripleDes3Key,
+            24,
+        ),
+    ] {
+        assert_eq!(&id, alg.id());
+        assert_eq!(key_len, alg.key_len());
+
+        let key_bytes = vec![0x5a; key_len];
+        let key = UnboundCipherKey::new(alg, &key_bytes).unwrap();
+        assert_eq!(alg, key.algorithm());
+        assert_eq!(key_len, key.key_len());
+        assert_eq!(format!("{id:?}"), format!("{:?}", key.algorithm().id()));
+    }
+}
+
+#[test]
+fn test_cloned_unbound_key_encrypts_identically() {
+    let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+    let iv = from_hex("00112233445566778899aabbccddeeff").unwrap();
+    let plaintext: Vec<u8> = (0..50u8).collect();
+
+    let unbound_key = UnboundCipherKey::new(&AES_128, &key).unwrap();
+    let cloned_key = unbound_key.clone();
+    assert_eq!(unbound_key.algorithm(), cloned_key.algorithm());
+
+    let encrypting_key = EncryptingKey::ctr(unbound_key).unwrap();
+    let mut first = plaintext.clone();
+    let context = EncryptionContext::Iv128(FixedLength::<16>::try_from(iv.as_slice()).unwrap());
+    encrypting_key
+        .less_safe_encrypt(&mut first, context)
+        .unwrap();
+    drop(encrypting_key);
+
+    let encrypting_key = EncryptingKey::ctr(cloned_key).unwrap();
+    let mut second = plaintext.clone();
+    let context = EncryptionContext::Iv128(FixedLength::<16>::try_from(iv.as_slice()).unwrap());
+    encrypting_key
+        .less_safe_encrypt(&mut second, context)
+        .unwrap();
+
+    assert_ne!(plaintext, first);
+    assert_eq!(first, second);
+}
+
+#[test]
+fn test_cloned_unbound_key_round_trip() {
+    let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+    let plaintext = b"one secret, two keys";
+
+    let unbound_key = UnboundCipherKey::new(&AES_128, &key).unwrap();
+    let encrypting_key = PaddedBlockEncryptingKey::cbc_pkcs7(unbound_key.clone()).unwrap();
+    let decrypting_key = PaddedBlockDecryptingKey::cbc_pkcs7(unbound_key).unwrap();
+
+    let mut in_out = plaintext.to_vec();
+    let context = encrypting_key.encrypt(&mut in_out).unwrap();
+    assert_eq!(
+        plaintext,
+        decrypting_key.decrypt(&mut in_out, context).unwrap()
+    );
+}
+
+#[test]
+fn test_decrypting_key_from_encrypting_key() {
+    let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+    let plaintext = b"verify before committing";
+
+    let encrypting_key =
+        PaddedBlockEncryptingKey::cbc_pkcs7(UnboundCipherKey::new(&AES_128, &key).unwrap())
+            .unwrap();
+    let mut in_out = plaintext.to_vec();
+    let context = encrypting_key.encrypt(&mut in_out).unwrap();
+    let decrypting_key = PaddedBlockDecryptingKey::try_from(&encrypting_key).unwrap();
+    assert_eq!(encrypting_key.algorithm(), decrypting_key.algorithm());
+    assert_eq!(encrypting_key.mode(), decrypting_key.mode());
+    assert_eq!(
+        plaintext,
+        decrypting_key.decrypt(&mut in_out, context).unwrap()
+    );
+
+    let encrypting_key =
+        EncryptingKey::ctr(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+    let mut in_out = plaintext.to_vec();
+    let context = encrypting_key.encrypt(&mut in_out).unwrap();
+    let decrypting_key = DecryptingKey::try_from(&encrypting_key).unwrap();
+    assert_eq!(encrypting_key.algorithm(), decrypting_key.algorithm());
+    assert_eq!(encrypting_key.mode(), decrypting_key.mode());
+    assert_eq!(
+        plaintext,
+        decrypting_key.decrypt(&mut in_out, context).unwrap()
+    );
+}
+
+#[test]
+fn test_cbc_pkcs7_decrypt_unpadded() {
+    let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+    let plaintext = b"framed application data";
+
+    let encrypting_key =
+        PaddedBlockEncryptingKey::cbc_pkcs7(UnboundCipherKey::new(&AES_128, &key).unwrap())
+            .unwrap();
+    let mut ciphertext = plaintext.to_vec();
+    let context = encrypting_key.encrypt(&mut ciphertext).unwrap();
+    let iv: &[u8] = (&context).try_into().unwrap();
+    let iv = iv.to_vec();
+
+    let decrypting_key =
+        PaddedBlockDecryptingKey::cbc_pkcs7(UnboundCipherKey::new(&AES_128, &key).unwrap())
+            .unwrap();
+
+    let mut in_out = ciphertext.clone();
+    let unpadded = decrypting_key.decrypt(&mut in_out, context).unwrap();
+    assert_eq!(plaintext, unpadded);
+
+    let mut in_out = ciphertext.clone();
+    let context = DecryptionContext::Iv128(FixedLength::try_from(iv.as_slice()).unwrap());
+    let raw = decrypting_key
+        .decrypt_unpadded(&mut in_out, context)
+        .unwrap();
+    assert_eq!(ciphertext.len(), raw.len());
+    assert_eq!(0, raw.len() % 16);
+    assert_eq!(plaintext, &raw[..plaintext.len()]);
+    assert!(raw[plaintext.len()..].iter().all(|b| *b == 9));
+
+    // Zero bytes the peer used as its own padding are preserved.
+    let mut zero_padded = plaintext.to_vec();
+    zero_padded.resize(32, 0);
+    let encrypting_key =
+        EncryptingKey::cbc(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+    let mut in_out = zero_padded.clone();
+    let context = encrypting_key.encrypt(&mut in_out).unwrap();
+    let iv: &[u8] = (&context).try_into().unwrap();
+    let iv = iv.to_vec();
+    assert!(decrypting_key
+        .decrypt(&mut in_out.clone(), context)
+        .is_err());
+    let context = DecryptionContext::Iv128(FixedLength::try_from(iv.as_slice()).unwrap());
+    let raw = decrypting_key
+        .decrypt_unpadded(&mut in_out, context)
+        .unwrap();
+    assert_eq!(zero_padded, raw);
+
+    let mut partial_block = ciphertext[..20].to_vec();
+    let context = DecryptionContext::Iv128(FixedLength::try_from(iv.as_slice()).unwrap());
+    assert!(decrypting_key
+        .decrypt_unpadded(&mut partial_block, context)
+        .is_err());
+}
+
+#[test]
+fn test_decryption_context_eq_and_hash() {
+    use std::collections::HashSet;
+
+    let iv: [u8; 16] = from_hex("000102030405060708090a0b0c0d0e0f")
+        .unwrap()
+        .try_into()
+        .unwrap();
+    let mut other_iv = iv;
+    other_iv[15] ^= 1;
+
+    let context = DecryptionContext::Iv128(FixedLength::from(iv));
+    assert_eq!(context, DecryptionContext::Iv128(FixedLength::from(iv)));
+    assert_ne!(
+        context,
+        DecryptionContext::Iv128(FixedLength::from(other_iv))
+    );
+    assert_ne!(context, DecryptionContext::None);
+    assert_ne!(DecryptionContext::None, context);
+    assert_eq!(DecryptionContext::None, DecryptionContext::None);
+
+    let nonce = [7u8; 12];
+    assert_ne!(
+        DecryptionContext::Nonce96(FixedLength::from(nonce)),
+        DecryptionContext::Nonce96Counter32(FixedLength::from(nonce), 0)
+    );
+    assert_ne!(
+        DecryptionContext::Nonce96Counter32(FixedLength::from(nonce), 0),
+        DecryptionContext::Nonce96Counter32(FixedLength::from(nonce), 1)
+    );
+
+    let mut seen = HashSet::new();
+    assert!(seen.insert(DecryptionContext::Iv128(FixedLength::from(iv))));
+    assert!(!seen.insert(DecryptionContext::Iv128(FixedLength::from(iv))));
+    assert!(seen.insert(DecryptionContext::Iv128(FixedLength::from(other_iv))));
+    assert!(seen.insert(DecryptionContext::None));
+    assert_eq!(3, seen.len());
+}
+
+#[test]
+fn test_key_reuse_generates_distinct_ivs() {
+    use std::collections::HashSet;
+
+    const RECORDS: usize = 10_000;
+    let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+    let unbound = || UnboundCipherKey::new(&AES_128, &key).unwrap();
+
+    let encrypting_key = EncryptingKey::ctr(unbound()).unwrap();
+    let decrypting_key = DecryptingKey::ctr(unbound()).unwrap();
+    let mut seen = HashSet::with_capacity(RECORDS);
+    for record in 0..RECORDS {
+        let plaintext = record.to_be_bytes();
+        let mut in_out = plaintext;
+        let context = encrypting_key.encrypt(&mut in_out).unwrap();
+        let iv: [u8; 16] = (&context).try_into().unwrap();
+        assert!(seen.insert(iv));
+        if record % 1000 == 0 {
+            assert_eq!(
+                plaintext,
+                decrypting_key.decrypt(&mut in_out, context).unwrap()
+            );
+        }
+    }
+
+    let encrypting_key = PaddedBlockEncryptingKey::cbc_pkcs7(unbound()).unwrap();
+    let mut seen = HashSet::with_capacity(RECORDS);
+    for record in 0..RECORDS {
+        let mut in_out = record.to_be_bytes().to_vec();
+        let context = encrypting_key.encrypt(&mut in_out).unwrap();
+        let iv: [u8; 16] = (&context).try_into().unwrap();
+        assert!(seen.insert(iv));
+    }
+}
+
+#[test]
+fn test_iv128_bytes_round_trip() {
+    let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+    let plaintext = b"iv prepended to the ciphertext";
+
+    let encrypting_key =
+        EncryptingKey::ctr(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+    let mut ciphertext = plaintext.to_vec();
+    let context = encrypting_key.encrypt(&mut ciphertext).unwrap();
+    let iv = match &context {
+        DecryptionContext::Iv128(iv) => *iv.as_bytes(),
+        _ => panic!("unexpected context"),
+    };
+
+    let mut message = iv.to_vec();
+    message.extend_from_slice(&ciphertext);
+
+    let (iv, ciphertext) = message.split_at(16);
+    let iv: [u8; 16] = iv.try_into().unwrap();
+    let context: DecryptionContext = Iv128::from(iv).into();
+    let mut in_out = ciphertext.to_vec();
+    let decrypting_key =
+        DecryptingKey::ctr(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+    assert_eq!(
+        plaintext,
+        decrypting_key.decrypt(&mut in_out, context).unwrap()
+    );
+}
+
+#[test]
+fn test_context_byte_conversions() {
+    let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+    let plaintext = b"iv prepended to the ciphertext";
+
+    let encrypting_key =
+        EncryptingKey::ctr(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+    let mut ciphertext = plaintext.to_vec();
+    let context = encrypting_key.encrypt(&mut ciphertext).unwrap();
+    let iv: [u8; 16] = (&context).try_into().unwrap();
+    let iv_vec: Vec<u8> = (&context).try_into().unwrap();
+    assert_eq!(iv.as_slice(), iv_vec);
+    assert!(<[u8; 12]>::try_from(&context).is_err());
+
+    let decrypting_key =
+        DecryptingKey::ctr(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+    assert_eq!(
+        plaintext,
+        decrypting_key
+            .decrypt(&mut ciphertext, DecryptionContext::from(iv))
+            .unwrap()
+    );
+
+    let nonce = [7u8; 12];
+    let context = DecryptionContext::from(nonce);
+    assert!(matches!(context, DecryptionContext::Nonce96(_)));
+    assert_eq!(nonce, <[u8; 12]>::try_from(&context).unwrap());
+    assert!(matches!(
+        EncryptionContext::from([7u8; 8]),
+        EncryptionContext::Iv64(_)
+    ));
+    assert!(<[u8; 16]>::try_from(&DecryptionContext::None).is_err());
+    assert!(Vec::<u8>::try_from(&DecryptionContext::None).is_err());
+}
+
+#[test]
+fn test_context_from_slice() {
+    let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+    let plaintext = b"the iv is stored next to this ciphertext";
+
+    let encrypting_key =
+        PaddedBlockEncryptingKey::cbc_pkcs7(UnboundCipherKey::new(&AES_128, &key).unwrap())
+            .unwrap();
+    let mut ciphertext = plaintext.to_vec();
+    let context = encrypting_key.encrypt(&mut ciphertext).unwrap();
+    let stored: Vec<u8> = (&context).try_into().unwrap();
+
+    let context = DecryptionContext::try_from(stored.as_slice()).unwrap();
+    assert!(matches!(context, DecryptionContext::Iv128(_)));
+    let decrypting_key =
+        PaddedBlockDecryptingKey::cbc_pkcs7(UnboundCipherKey::new(&AES_128, &key).unwrap())
+            .unwrap();
+    assert_eq!(
+        plaintext,
+        decrypting_key.decrypt(&mut ciphertext, context).unwrap()
+    );
+
+    assert!(matches!(
+        DecryptionContext::try_from([0u8; 12].as_slice()).unwrap(),
+        DecryptionContext::Nonce96(_)
+    ));
+    assert!(matches!(
+        EncryptionContext::try_from([0u8; 8].as_slice()).unwrap(),
+        EncryptionContext::Iv64(_)
+    ));
+    for len in [0, 15, 17, 32] {
+        assert!(DecryptionContext::try_from(vec![0u8; len].as_slice()).is_err());
+    }
+}
+
+#[test]
+fn test_streaming_context_before_encryption() {
+    let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+    let plaintext: Vec<u8> = (0..64u8).collect();
+
+    for (encrypting_key, decrypting_key) in [
+        (
+            EncryptingKey::cbc as fn(_) -> _,
+            DecryptingKey::cbc as fn(_) -> _,
+        ),
+        (EncryptingKey::ctr, DecryptingKey::ctr),
+    ] {
+        let encrypting_key =
+            encrypting_key(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+        let mut streaming_key = StreamingEncryptingKey::new(encrypting_key).unwrap();
+
+        // Write the IV as a header before any ciphertext.
+        let header: &[u8] = streaming_key.context().try_into().unwrap();
+        let mut message = header.to_vec();
+        let mut output = [0u8; 48];
+        for chunk in plaintext.chunks(24) {
+            let update = streaming_key.update(chunk, &mut output).unwrap();
+            message.extend_from_slice(update.written());
+        }
+        let expected_header = message[..16].to_vec();
+        let (context, _) = streaming_key.finish(&mut output).unwrap();
+        let context_bytes: &[u8] = (&context).try_into().unwrap();
+        assert_eq!(expected_header, context_bytes);
+
+        let (iv, ciphertext) = message.split_at_mut(16);
+        let context = DecryptionContext::Iv128(FixedLength::try_from(&*iv).unwrap());
+        let decrypting_key =
+            decrypting_key(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+        let decrypted = decrypting_key.decrypt(ciphertext, context).unwrap();
+        assert_eq!(plaintext.as_slice(), decrypted);
+    }
+}
+
+#[test]
+fn test_unbound_key_from_hkdf() {
+    // RFC 5869, A.1: Test Case 1, expanded to each cipher's key length.
+    let ikm = from_hex("0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b").unwrap();
+    let salt = from_hex("000102030405060708090a0b0c").unwrap();
+    let info = from_hex("f0f1f2f3f4f5f6f7f8f9").unwrap();
+    let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, &salt).extract(&ikm);
+    let plaintext = b"derived directly from HKDF output";
+
+    for (algorithm, expected_key, expected_ciphertext) in [
+        (
+            &AES_128,
+            "3cb25f25faacd57a90434f64d0362f2a",
+            "2df9c8769bff0d5ee4fae2aa0051196baf00a85829bcc061230f46fbcb39a4fd9e",
+        ),
+        (
+            &AES_256,
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf",
+            "7f227f1a3328f6b2f8c47377b42931a0b9bd8417a92093db5dc6cdc9314f98bbc1",
+        ),
+    ] {
+        let key: UnboundCipherKey = prk.expand(&[&info], algorithm).unwrap().into();
+        assert_eq!(algorithm, key.algorithm());
+        assert_eq!(from_hex(expected_key).unwrap(), *key.less_safe_key_bytes());
+
+        let encrypting_key = EncryptingKey::ctr(key).unwrap();
+        let iv: [u8; 16] = from_hex("000102030405060708090a0b0c0d0e0f")
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let mut in_out = plaintext.to_vec();
+        encrypting_key
+            .less_safe_encrypt(&mut in_out, EncryptionContext::from(iv))
+            .unwrap();
+        assert_eq!(from_hex(expected_ciphertext).unwrap(), in_out);
+    }
+}
+
+#[test]
+fn test_encrypt_with_rng() {
+    // NIST SP 800-38A, F.5.1 CTR-AES128.Encrypt and F.2.1 CBC-AES128.Encrypt, first block.
+    let key = from_hex("2b7e151628aed2a6abf7158809cf4f3c").unwrap();
+    let plaintext = from_hex("6bc1bee22e409f96e93d7e117393172a").unwrap();
+
+    let iv = from_hex("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff").unwrap();
+    let rng = FixedSliceRandom { bytes: &iv };
+    let encrypting_key =
+        EncryptingKey::ctr(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+    let mut in_out = plaintext.clone();
+    let context = encrypting_key.encrypt_with_rng(&mut in_out, &rng).unwrap();
+    assert_eq!(
+        from_hex("874d6191b620e3261bef6864990db6ce").unwrap(),
+        in_out
+    );
+    let context_bytes: &[u8] = (&context).try_into().unwrap();
+    assert_eq!(iv.as_slice(), context_bytes);
+
+    let iv = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+    let rng = FixedSliceRandom { bytes: &iv };
+    let encrypting_key =
+        PaddedBlockEncryptingKey::cbc_pkcs7(UnboundCipherKey::new(&AES_128, &key).unwrap())
+            .unwrap();
+    let mut in_out = plaintext.clone();
+    let context = encrypting_key.encrypt_with_rng(&mut in_out, &rng).unwrap();
+    assert_eq!(
+        from_hex("7649abac8119b246cee98e9b12e9197d8964e0b149c10b7b682e6e39aaeb731c").unwrap(),
+        in_out
+    );
+    let decrypting_key =
+        PaddedBlockDecryptingKey::cbc_pkcs7(UnboundCipherKey::new(&AES_128, &key).unwrap())
+            .unwrap();
+    assert_eq!(
+        plaintext.as_slice(),
+        decrypting_key.decrypt(&mut in_out, context).unwrap()
+    );
+
+    let nonce = [7u8; 12];
+    let rng = FixedSliceRandom { bytes: &nonce };
+    let encrypting_key =
+        EncryptingKey::chacha20(UnboundCipherKey::new(&CHACHA20, &[1u8; 32]).unwrap()).unwrap();
+    let mut in_out = plaintext.clone();
+    let context = encrypting_key.encrypt_with_rng(&mut in_out, &rng).unwrap();
+    assert!(matches!(context, DecryptionContext::Nonce96(_)));
+    let context_bytes: &[u8] = (&context).try_into().unwrap();
+    assert_eq!(nonce.as_slice(), context_bytes);
+}
+
+#[test]
+fn test_generated_key_wrapped_for_export() {
+    let rng = aws_lc_rs::rand::SystemRandom::new();
+    let kek_bytes = UnboundCipherKey::generate(&AES_256, &rng)
+        .unwrap()
+        .less_safe_key_bytes();
+    let data_key = UnboundCipherKey::generate(&AES_256, &rng).unwrap();
+
+    // Encrypt under the data key, then export it wrapped under the KEK.
+    let encrypting_key = EncryptingKey::ctr(data_key.clone()).unwrap();
+    let mut in_out = b"protected by a wrapped data key".to_vec();
+    let context = encrypting_key.encrypt(&mut in_out).unwrap();
+
+    let mut wrapped = [0u8; 32 + 8];
+    let wrapped = AesKek::new(&key_wrap::AES_256, &kek_bytes)
+        .unwrap()
+        .wrap(&data_key.less_safe_key_bytes(), &mut wrapped)
+        .unwrap();
+
+    let mut unwrapped = [0u8; 32];
+    let unwrapped = AesKek::new(&key_wrap::AES_256, &kek_bytes)
+        .unwrap()
+        .unwrap(wrapped, &mut unwrapped)
+        .unwrap();
+    let data_key = UnboundCipherKey::new(&AES_256, unwrapped).unwrap();
+
+    let decrypting_key = DecryptingKey::ctr(data_key).unwrap();
+    assert_eq!(
+        b"protected by a wrapped data key",
+        decrypting_key.decrypt(&mut in_out, context).unwrap()
+    );
+}
+
+#[test]
+fn test_encrypt_only_key() {
+    let key_bytes =
+        from_hex("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f").unwrap();
+    let key = || UnboundCipherKey::new_encrypt_only(&AES_256, &key_bytes).unwrap();
+    let plaintext: Vec<u8> = (0..64).collect();
+    assert!(UnboundCipherKey::new_encrypt_only(&AES_256, &key_bytes[..16]).is_err());
+
+    // Decrypting keys that need the AES decryption key schedule compute it from the key bytes.
+    for (encrypting_key, decrypting_key) in [
+        (
+            EncryptingKey::cbc as fn(_) -> _,
+            DecryptingKey::cbc as fn(_) -> _,
+        ),
+        #[cfg(not(feature = "approved-only"))]
+        (EncryptingKey::less_safe_ecb, DecryptingKey::less_safe_ecb),
+        (EncryptingKey::ctr, DecryptingKey::ctr),
+        (EncryptingKey::cfb128, DecryptingKey::cfb128),
+        (EncryptingKey::ofb, DecryptingKey::ofb),
+    ] {
+        let mut in_out = plaintext.clone();
+        let context = encrypting_key(key()).unwrap().encrypt(&mut in_out).unwrap();
+
+        // A key with both key schedules decrypts the ciphertext too.
+        let mut expected = in_out.clone();
+        let full_key = UnboundCipherKey::new(&AES_256, &key_bytes).unwrap();
+        let full_decrypting_key = decrypting_key(full_key).unwrap();
+        let full_context = match Vec::<u8>::try_from(&context) {
+            Ok(iv) => DecryptionContext::try_from(iv.as_slice()).unwrap(),
+            Err(_) => DecryptionContext::None,
+        };
+        full_decrypting_key
+            .decrypt(&mut expected, full_context)
+            .unwrap();
+        assert_eq!(plaintext, expected);
+
+        assert_eq!(
+            plaintext.as_slice(),
+            decrypting_key(key())
+                .unwrap()
+                .decrypt(&mut in_out, context)
+                .unwrap()
+        );
+    }
+
+    let encrypting_key = PaddedBlockEncryptingKey::cbc_pkcs7(key()).unwrap();
+    let mut in_out = plaintext.clone();
+    let context = encrypting_key.encrypt(&mut in_out).unwrap();
+    let iv: [u8; 16] = (&context).try_into().unwrap();
+    let ciphertext = in_out.clone();
+    assert_eq!(
+        plaintext.as_slice(),
+        PaddedBlockDecryptingKey::cbc_pkcs7(key())
+            .unwrap()
+            .decrypt(&mut in_out, context)
+            .unwrap()
+    );
+
+    let decrypting_key = PaddedBlockDecryptingKey::cbc_pkcs7(key()).unwrap();
+    let mut streaming_key =
+        StreamingDecryptingKey::new_padded(decrypting_key, DecryptionContext::from(iv)).unwrap();
+    let mut output = vec![0u8; ciphertext.len() + 16];
+    let written = streaming_key
+        .update(&ciphertext, &mut output)
+        .unwrap()
+        .written()
+        .len();
+    let finished = streaming_key.finish(&mut output[written..]).unwrap();
+    let total = written + finished.written().len();
+    assert_eq!(plaintext.as_slice(), &output[..total]);
+}
+
+#[test]
+fn test_encrypt_batch() {
+    let key_bytes = [0x42u8; 32];
+    for (alg, encrypting_key, decrypting_key) in [
+        (
+            &AES_128,
+            EncryptingKey::ctr as fn(_) -> _,
+            DecryptingKey::ctr as fn(_) -> _,
+        ),
+        (&AES_256, EncryptingKey::ctr, DecryptingKey::ctr),
+        (&AES_256, EncryptingKey::cbc, DecryptingKey::cbc),
+        #[cfg(not(feature = "approved-only"))]
+        (
+            &AES_128,
+            EncryptingKey::less_safe_ecb,
+            DecryptingKey::less_safe_ecb,
+        ),
+        (&CHACHA20, EncryptingKey::chacha20, DecryptingKey::chacha20),
+    ] {
+        let key_bytes = &key_bytes[..alg.key_len()];
+        let encrypting_key =
+            encrypting_key(UnboundCipherKey::new(alg, key_bytes).unwrap()).unwrap();
+        let decrypting_key =
+            decrypting_key(UnboundCipherKey::new(alg, key_bytes).unwrap()).unwrap();
+        let mode = encrypting_key.mode();
+
+        let plaintexts: Vec<Vec<u8>> = (0..50u8)
+            .map(|i| vec![i; 96 + 16 * usize::from(i % 8)])
+            .collect();
+        let mut records = plaintexts.clone();
+        let contexts = encrypting_key.encrypt_batch(&mut records).unwrap();
+        assert_eq!(plaintexts.len(), contexts.len());
+
+        let mut ivs = Vec::new();
+        for ((plaintext, mut record), context) in plaintexts.iter().zip(records).zip(contexts) {
+            assert_ne!(plaintext, &record);
+            let iv: &[u8] = (&context).try_into().unwrap_or(&[]);
+            ivs.push(iv.to_vec());
+            assert_eq!(
+                plaintext.as_slice(),
+                decrypting_key.decrypt(&mut record, context).unwrap()
+            );
+        }
+        if alg.iv_len(mode) != Some(0) {
+            ivs.sort();
+            ivs.dedup();
+            assert_eq!(plaintexts.len(), ivs.len());
+        }
+    }
+
+    let encrypting_key =
+        EncryptingKey::ctr(UnboundCipherKey::new(&AES_128, &key_bytes[..16]).unwrap()).unwrap();
+    let mut records: [&mut [u8]; 0] = [];
+    assert!(encrypting_key
+        .encrypt_batch(&mut records)
+        .unwrap()
+        .is_empty());
+
+    // A record that is not a whole number of blocks fails the batch before any is encrypted.
+    let encrypting_key =
+        EncryptingKey::cbc(UnboundCipherKey::new(&AES_128, &key_bytes[..16]).unwrap()).unwrap();
+    let mut first = [1u8; 32];
+    let mut second = [2u8; 20];
+    let mut records: [&mut [u8]; 2] = [&mut first, &mut second];
+    assert!(encrypting_key.encrypt_batch(&mut records).is_err());
+    assert_eq!([1u8; 32], first);
+    assert_eq!([2u8; 20], second);
+}
+
+#[cfg(not(feature = "fips"))]
+fn ccm_keys(key: &[u8]) -> (CcmEncryptingKey, CcmDecryptingKey) {
+    let algorithm = match key.len() {
+        16 => &AES_128,
+        24 => &AES_192,
+        _ => &AES_256,
+    };
+    let ccm_key = || CcmKey::new(UnboundCipherKey::new(algorithm, key).unwrap()).unwrap();
+    (
+        CcmEncryptingKey::new(ccm_key()),
+        CcmDecryptingKey::new(ccm_key()),
+    )
+}
+
+#[test]
+#[cfg(not(feature = "fips"))]
+fn test_ccm_kat() {
+    // NIST SP 800-38C Appendix C, Examples 1 to 3, and RFC 3610 Packet Vector #1. The AES-192
+    // and AES-256 vectors were computed with pyca/cryptography.
+    let vectors: [(&str, &str, &str, &str, usize, &str); 6] = [
+        (
+            "404142434445464748494a4b4c4d4e4f",
+            "10111213141516",
+            "0001020304050607",
+            "20212223",
+            4,
+            "7162015b4dac255d",
+        ),
+        (
+            "404142434445464748494a4b4c4d4e4f",
+            "1011121314151617",
+            "000102030405060708090a0b0c0d0e0f",
+            "202122232425262728292a2b2c2d2e2f",
+            6,
+            "d2a1f0e051ea5f62081a7792073d593d1fc64fbfaccd",
+        ),
+        (
+            "404142434445464748494a4b4c4d4e4f",
+            "101112131415161718191a1b",
+            "000102030405060708090a0b0c0d0e0f10111213",
+            "202122232425262728292a2b2c2d2e2f3031323334353637",
+            8,
+            "e3b201a9f5b71a7a9b1ceaeccd97e70b6176aad9a4428aa5484392fbc1b09951",
+        ),
+        (
+            "c0c1c2c3c4c5c6c7c8c9cacbcccdcecf",
+            "00000003020100a0a1a2a3a4a5",
+            "0001020304050607",
+            "08090a0b0c0d0e0f101112131415161718191a1b1c1d1e",
+            8,
+            "588c979a61c663d2f066d0c2c0f989806d5f6b61dac38417e8d12cfdf926e0",
+        ),
+        (
+            "000102030405060708090a0b0c0d0e0f1011121314151617",
+            "00112233445566",
+            "",
+            "7468697320697320612074657374206f6620434356",
+            16,
+            "72bb40a3b76ad5b0bd677c98d7ab4c21e0040eba0b8aa9cbc5bb965ba73e0c86049eaa9883",
+        ),
+        (
+            "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+            "0102030405060708090a0b0c0d",
+            "6865616465722064617461",
+            "000102030405060708090a0b0c0d0e0f",
+            10,
+            "d4e39d9c709be4e2c43f1a7ac06e4918255b9b7f09d05d14233c",
+        ),
+    ];
+    for (key, nonce, aad, plaintext, tag_len, expected) in vectors {
+        let (sealing_key, opening_key) = ccm_keys(&from_hex(key).unwrap());
+        let nonce = from_hex(nonce).unwrap();
+        let aad = from_hex(aad).unwrap();
+        let plaintext = from_hex(plaintext).unwrap();
+        let expected = from_hex(expected).unwrap();
+
+        let sealed = sealing_key.seal(&nonce, &aad, &plaintext, tag_len).unwrap();
+        assert_eq!(expected, sealed);
+        let opened = opening_key.open(&nonce, &aad, &sealed, tag_len).unwrap();
+        assert_eq!(plaintext, opened);
+    }
+
+    // NIST SP 800-38C Appendix C, Example 4. The 65536 bytes of associated data have their
+    // length encoded with 32 bits.
+    let (sealing_key, opening_key) =
+        ccm_keys(&from_hex("404142434445464748494a4b4c4d4e4f").unwrap());
+    let nonce = from_hex("101112131415161718191a1b1c").unwrap();
+    let aad: Vec<u8> = (0..65536).map(|i| i as u8).collect();
+    let plaintext =
+        from_hex("202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f").unwrap();
+    let expected = from_hex(
+        "69915dad1e84c6376a68c2967e4dab615ae0fd1faec44cc484828529463ccf72b4ac6bec93e8598e7f0dadbcea5b",
+    )
+    .unwrap();
+    let sealed = sealing_key.seal(&nonce, &aad, &plaintext, 14).unwrap();
+    assert_eq!(expected, sealed);
+    assert_eq!(
+        plaintext,
+        opening_key.open(&nonce, &aad, &sealed, 14).unwrap()
+    );
+    assert!(opening_key
+        .open(&nonce, &aad[..65535], &sealed, 14)
+        .is_err());
+}
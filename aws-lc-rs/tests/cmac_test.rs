@@ -0,0 +1,132 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use aws_lc_rs::cipher::cmac::{self, Cmac, CMAC_TAG_LEN};
+use aws_lc_rs::cipher::{UnboundCipherKey, AES_128, AES_192, AES_256};
+use aws_lc_rs::test::from_hex;
+
+const SP800_38B_MESSAGE: &str = "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710";
+
+macro_rules! cmac_kat {
+    ($name:ident, $alg:expr, $key:literal, $message_len:literal, $tag:literal) => {
+        #[test]
+        fn $name() {
+            let key = from_hex($key).unwrap();
+            let message = from_hex(SP800_38B_MESSAGE).unwrap();
+            let message = &message[..$message_len];
+            let expected_tag = from_hex($tag).unwrap();
+
+            let mut cmac = Cmac::new(UnboundCipherKey::new($alg, &key).unwrap()).unwrap();
+            assert_eq!($alg, cmac.algorithm());
+            for chunk in message.chunks(7) {
+                cmac.update(chunk);
+            }
+            let tag = cmac.finalize();
+            assert_eq!(CMAC_TAG_LEN, tag.as_ref().len());
+            assert_eq!(expected_tag.as_slice(), tag.as_ref());
+
+            let mut cmac = Cmac::new(UnboundCipherKey::new($alg, &key).unwrap()).unwrap();
+            cmac.update(message);
+            cmac.verify(&expected_tag).unwrap();
+
+            let unbound_key = UnboundCipherKey::new($alg, &key).unwrap();
+            cmac::verify(&unbound_key, message, &expected_tag).unwrap();
+            let mut modified_tag = expected_tag.clone();
+            modified_tag[0] ^= 1;
+            assert!(cmac::verify(&unbound_key, message, &modified_tag).is_err());
+            assert!(
+                cmac::verify(&unbound_key, message, &expected_tag[..CMAC_TAG_LEN - 1]).is_err()
+            );
+        }
+    };
+}
+
+// NIST SP 800-38B, Appendix D.1 AES-128.
+cmac_kat!(
+    test_cmac_aes_128_example_1,
+    &AES_128,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    0,
+    "bb1d6929e95937287fa37d129b756746"
+);
+cmac_kat!(
+    test_cmac_aes_128_example_2,
+    &AES_128,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    16,
+    "070a16b46b4d4144f79bdd9dd04a287c"
+);
+cmac_kat!(
+    test_cmac_aes_128_example_3,
+    &AES_128,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    40,
+    "dfa66747de9ae63030ca32611497c827"
+);
+cmac_kat!(
+    test_cmac_aes_128_example_4,
+    &AES_128,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    64,
+    "51f0bebf7e3b9d92fc49741779363cfe"
+);
+
+// NIST SP 800-38B, Appendix D.2 AES-192.
+cmac_kat!(
+    test_cmac_aes_192_example_1,
+    &AES_192,
+    "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+    0,
+    "d17ddf46adaacde531cac483de7a9367"
+);
+cmac_kat!(
+    test_cmac_aes_192_example_2,
+    &AES_192,
+    "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+    16,
+    "9e99a7bf31e710900662f65e617c5184"
+);
+cmac_kat!(
+    test_cmac_aes_192_example_3,
+    &AES_192,
+    "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+    40,
+    "8a1de5be2eb31aad089a82e6ee908b0e"
+);
+cmac_kat!(
+    test_cmac_aes_192_example_4,
+    &AES_192,
+    "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+    64,
+    "a1d5df0eed790f794d77589659f39a11"
+);
+
+// NIST SP 800-38B, Appendix D.3 AES-256.
+cmac_kat!(
+    test_cmac_aes_256_example_1,
+    &AES_256,
+    "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    0,
+    "028962f61b7bf89efc6b551f4667d983"
+);
+cmac_kat!(
+    test_cmac_aes_256_example_2,
+    &AES_256,
+    "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    16,
+    "28a7023f452e8f82bd4bf28d8c37c35c"
+);
+cmac_kat!(
+    test_cmac_aes_256_example_3,
+    &AES_256,
+    "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    40,
+    "aaf3d8f1de5640c232f5b169b9c911e6"
+);
+cmac_kat!(
+    test_cmac_aes_256_example_4,
+    &AES_256,
+    "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    64,
+    "e1992190549f6ed5696a2c056c315410"
+);
@@ -0,0 +1,11 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use aws_lc_rs::cipher::{Builder, OperatingMode, AES_128};
+
+fn main() {
+    let _ = Builder::new()
+        .algorithm(&AES_128)
+        .mode(OperatingMode::ECB)
+        .build_encryptor(&[0u8; 16]);
+}
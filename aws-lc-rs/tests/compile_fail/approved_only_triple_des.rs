@@ -0,0 +1,8 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use aws_lc_rs::cipher::{UnboundCipherKey, TRIPLE_DES_3KEY_FOR_LEGACY_USE_ONLY};
+
+fn main() {
+    let _ = UnboundCipherKey::new(&TRIPLE_DES_3KEY_FOR_LEGACY_USE_ONLY, &[0u8; 24]);
+}
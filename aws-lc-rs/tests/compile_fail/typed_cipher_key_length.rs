@@ -0,0 +1,8 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use aws_lc_rs::cipher::typed::{Aes256, Ctr, TypedEncryptingKey};
+
+fn main() {
+    let _key = TypedEncryptingKey::<Aes256, Ctr>::new(&[0u8; 16]).unwrap();
+}
@@ -0,0 +1,13 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use aws_lc_rs::cipher::typed::{Aes128, Aes256, CbcPkcs7, Ctr, TypedEncryptingKey};
+
+fn encrypt(key: &TypedEncryptingKey<Aes128, CbcPkcs7>, in_out: &mut Vec<u8>) {
+    key.encrypt(in_out).unwrap();
+}
+
+fn main() {
+    let key = TypedEncryptingKey::<Aes256, Ctr>::new(&[0u8; 32]).unwrap();
+    encrypt(&key, &mut Vec::new());
+}
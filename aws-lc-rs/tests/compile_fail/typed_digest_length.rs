@@ -0,0 +1,9 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use aws_lc_rs::digest;
+use aws_lc_rs::digest::typed::Sha256;
+
+fn main() {
+    let _bytes: [u8; 64] = digest::digest_typed::<Sha256>(b"hello, world").into_bytes();
+}
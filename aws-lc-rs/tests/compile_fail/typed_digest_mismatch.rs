@@ -0,0 +1,9 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use aws_lc_rs::digest;
+use aws_lc_rs::digest::typed::{Output, Sha256, Sha512_256};
+
+fn main() {
+    let _output: Output<Sha256> = digest::digest_typed::<Sha512_256>(b"hello, world");
+}
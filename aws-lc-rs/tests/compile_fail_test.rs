@@ -0,0 +1,21 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+#[test]
+fn typed_cipher_compile_fail() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/compile_fail/typed_cipher_*.rs");
+}
+
+#[test]
+fn typed_digest_compile_fail() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/compile_fail/typed_digest_*.rs");
+}
+
+#[cfg(feature = "approved-only")]
+#[test]
+fn approved_only_compile_fail() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/compile_fail/approved_only_*.rs");
+}
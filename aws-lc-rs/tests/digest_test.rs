@@ -31,6 +31,38 @@ fn digest_misc() {
     });
 }
 
+#[test]
+fn digest_typed() {
+    use aws_lc_rs::digest::typed::{
+        Output, Sha1ForLegacyUseOnly, Sha224, Sha256, Sha384, Sha3_256, Sha3_384, Sha3_512, Sha512,
+        Sha512_256,
+    };
+
+    fn check<A: digest::typed::DigestAlgorithm>(data: &[u8]) {
+        let typed = digest::digest_typed::<A>(data);
+        let untyped = digest::digest(A::algorithm(), data);
+        assert_eq!(untyped.as_ref(), typed.as_ref());
+        assert_eq!(untyped.as_ref(), typed.into_bytes().as_ref());
+        assert_eq!(typed, Output::<A>::try_from(untyped).unwrap());
+        assert_eq!(untyped.as_ref(), digest::Digest::from(typed).as_ref());
+    }
+
+    let data = b"hello, world";
+    check::<Sha1ForLegacyUseOnly>(data);
+    check::<Sha224>(data);
+    check::<Sha256>(data);
+    check::<Sha384>(data);
+    check::<Sha512>(data);
+    check::<Sha512_256>(data);
+    check::<Sha3_256>(data);
+    check::<Sha3_384>(data);
+    check::<Sha3_512>(data);
+
+    let sha256: [u8; 32] = digest::digest_typed::<Sha256>(data).into_bytes();
+    let sha512_256: [u8; 32] = digest::digest_typed::<Sha512_256>(data).into_bytes();
+    assert_ne!(sha256, sha512_256);
+}
+
 mod digest_shavs {
     use aws_lc_rs::{digest, test};
 
@@ -263,6 +263,67 @@ fn ecdsa_test_public_key_coverage() {
     );
 }
 
+#[test]
+fn ecdsa_affine_coordinates_test() {
+    let rng = SystemRandom::new();
+    for (alg, verification_alg, field_len) in [
+        (
+            &signature::ECDSA_P256_SHA256_FIXED_SIGNING,
+            &signature::ECDSA_P256_SHA256_FIXED,
+            32,
+        ),
+        (
+            &signature::ECDSA_P384_SHA384_FIXED_SIGNING,
+            &signature::ECDSA_P384_SHA384_FIXED,
+            48,
+        ),
+        (
+            &signature::ECDSA_P521_SHA512_FIXED_SIGNING,
+            &signature::ECDSA_P521_SHA512_FIXED,
+            66,
+        ),
+    ] {
+        // Find a key whose x-coordinate has a leading zero byte.
+        let key_pair = loop {
+            let key_pair = EcdsaKeyPair::generate(alg).unwrap();
+            if key_pair.public_key().to_affine_coordinates().0[0] == 0 {
+                break key_pair;
+            }
+        };
+        let public_key = key_pair.public_key();
+        let (x, y) = public_key.to_affine_coordinates();
+        assert_eq!(field_len, x.len());
+        assert_eq!(field_len, y.len());
+        assert_eq!(&public_key.as_ref()[1..=field_len], x.as_slice());
+        assert_eq!(&public_key.as_ref()[1 + field_len..], y.as_slice());
+
+        let decoded = signature::EcdsaPublicKey::from_affine_coordinates(alg, &x, &y).unwrap();
+        assert_eq!(public_key.as_ref(), decoded.as_ref());
+
+        // The coordinate without its leading zero byte is padded back to the field size.
+        let stripped =
+            signature::EcdsaPublicKey::from_affine_coordinates(alg, &x[1..], &y).unwrap();
+        assert_eq!(public_key.as_ref(), stripped.as_ref());
+        assert_eq!((x.clone(), y.clone()), stripped.to_affine_coordinates());
+
+        let message = b"affine coordinates";
+        let signature = key_pair.sign(&rng, message).unwrap();
+        UnparsedPublicKey::new(verification_alg, decoded.as_ref())
+            .verify(message, signature.as_ref())
+            .unwrap();
+
+        // Not on the curve.
+        let mut invalid_y = y.clone();
+        *invalid_y.last_mut().unwrap() ^= 1;
+        assert!(signature::EcdsaPublicKey::from_affine_coordinates(alg, &x, &invalid_y).is_err());
+
+        // Longer than the field size.
+        let mut long_x = vec![0u8];
+        long_x.extend_from_slice(&x);
+        assert!(signature::EcdsaPublicKey::from_affine_coordinates(alg, &long_x, &y).is_err());
+    }
+}
+
 #[test]
 fn signature_ecdsa_sign_fixed_sign_and_verify_test() {
     test_signature_ecdsa_sign_fixed_sign_and_verify(test_file!("data/ecdsa_sign_fixed_tests.txt"));
@@ -3,7 +3,7 @@
 // Modifications copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
-use aws_lc_rs::{aead, cipher, digest, error, hkdf, hmac, test, test_file};
+use aws_lc_rs::{aead, cipher, digest, error, hkdf, hmac, iv, test, test_file};
 
 #[test]
 fn hkdf_tests() {
@@ -130,6 +130,60 @@ fn hkdf_key_types() {
     }
 }
 
+/// The `info` of `HKDF-Expand-Label` (RFC 8446, Section 7.1) with an empty context.
+fn tls13_label(label: &[u8], len: usize) -> Vec<u8> {
+    let mut info = Vec::from(u16::try_from(len).unwrap().to_be_bytes());
+    info.push(u8::try_from(6 + label.len()).unwrap());
+    info.extend_from_slice(b"tls13 ");
+    info.extend_from_slice(label);
+    info.push(0);
+    info
+}
+
+#[test]
+fn hkdf_tls13_traffic_iv() {
+    // RFC 8448, Section 3: the handshake and application traffic IVs of the simple 1-RTT
+    // handshake.
+    for (secret, expected_iv) in [
+        (
+            "b67b7d690cc16c4e75e54213cb2d37b4e9c912bcded9105d42befd59d391ad38",
+            "5d313eb2671276ee13000b30",
+        ),
+        (
+            "b3eddb126e067f35a780b3abf45e2d8f3b1a950738f52e9600746a0e27a55a21",
+            "5bd3c71b836e0b76bb73265f",
+        ),
+        (
+            "a11af9f05531f856ad47116b45a950328204b4f44bfb6b3a4b4f1f3fcb631643",
+            "cf782b88dd83549aadf1e984",
+        ),
+    ] {
+        let secret = test::from_hex(secret).unwrap();
+        let expected_iv = test::from_hex(expected_iv).unwrap();
+        let prk = hkdf::Prk::new_less_safe(hkdf::HKDF_SHA256, &secret);
+        let info = tls13_label(b"iv", aead::NONCE_LEN);
+
+        let derived_iv: iv::FixedLength<12> =
+            prk.expand(&[&info], iv::IvLength::<12>).unwrap().into();
+        assert_eq!(expected_iv.as_slice(), derived_iv.as_ref());
+
+        let nonce = aead::Nonce::from(prk.expand(&[&info], iv::IvLength::<12>).unwrap());
+        assert_eq!(expected_iv.as_slice(), nonce.as_ref());
+
+        // Nonce prefixes and block-cipher IVs of other lengths.
+        let info = tls13_label(b"iv", 4);
+        let prefix: iv::FixedLength<4> = prk.expand(&[&info], iv::IvLength::<4>).unwrap().into();
+        let expected_prefix: My<Vec<u8>> = prk.expand(&[&info], My(4)).unwrap().into();
+        assert_eq!(expected_prefix.0.as_slice(), prefix.as_ref());
+
+        let info = tls13_label(b"iv", 16);
+        let block_iv: iv::FixedLength<16> =
+            prk.expand(&[&info], iv::IvLength::<16>).unwrap().into();
+        let expected_block_iv: My<Vec<u8>> = prk.expand(&[&info], My(16)).unwrap().into();
+        assert_eq!(expected_block_iv.0.as_slice(), block_iv.as_ref());
+    }
+}
+
 #[test]
 fn hkdf_clone_tests() {
     for &alg in &[
@@ -131,3 +131,21 @@ fn hmac_thread_safeness() {
         assert_eq!(thread_signature.as_ref(), signature.as_ref());
     }
 }
+
+#[test]
+fn hmac_typed_tag() {
+    use aws_lc_rs::digest::typed::{Sha256, Sha384};
+
+    let key = hmac::Key::new(hmac::HMAC_SHA384, b"this is a test!");
+    let tag = hmac::sign_typed::<Sha384>(&key, b"message").unwrap();
+    let untyped = sign(&key, b"message");
+    assert_eq!(untyped.as_ref(), tag.as_ref());
+    assert_eq!(untyped.as_ref(), &tag.into_bytes()[..]);
+    assert_eq!(
+        untyped.as_ref(),
+        hmac::Tag::from(hmac::TypedTag::<Sha384>::try_from(untyped).unwrap()).as_ref()
+    );
+
+    assert!(hmac::sign_typed::<Sha256>(&key, b"message").is_err());
+    assert!(hmac::TypedTag::<Sha256>::try_from(untyped).is_err());
+}
@@ -53,6 +53,66 @@ fn pbkdf2_tests() {
     });
 }
 
+#[test]
+fn pbkdf2_verify_with_params() {
+    let policy = pbkdf2::Params::new(pbkdf2::PBKDF2_HMAC_SHA256, NonZeroU32::new(10_000).unwrap());
+    let salt = b"0123456789abcdef";
+    let password = b"correct horse battery staple";
+
+    for (algorithm, iterations, expected_recommendation) in [
+        // Below the policy in digest only.
+        (
+            pbkdf2::PBKDF2_HMAC_SHA1,
+            10_000,
+            Some((pbkdf2::PBKDF2_HMAC_SHA256, 10_000)),
+        ),
+        // Below the policy in iterations only.
+        (
+            pbkdf2::PBKDF2_HMAC_SHA512,
+            1_000,
+            Some((pbkdf2::PBKDF2_HMAC_SHA512, 10_000)),
+        ),
+        // Below the policy in both.
+        (
+            pbkdf2::PBKDF2_HMAC_SHA1,
+            1_000,
+            Some((pbkdf2::PBKDF2_HMAC_SHA256, 10_000)),
+        ),
+        // Exactly the policy.
+        (pbkdf2::PBKDF2_HMAC_SHA256, 10_000, None),
+        // Above the policy.
+        (pbkdf2::PBKDF2_HMAC_SHA384, 20_000, None),
+    ] {
+        let params = pbkdf2::Params::new(algorithm, NonZeroU32::new(iterations).unwrap());
+        let mut hash = [0u8; 32];
+        pbkdf2::derive(algorithm, params.iterations(), salt, password, &mut hash);
+        let stored = pbkdf2::StoredParams::new(params, salt, &hash);
+
+        let expected = match expected_recommendation {
+            None => pbkdf2::VerifyOutcome::Valid,
+            Some((algorithm, iterations)) => pbkdf2::VerifyOutcome::ValidButNeedsRehash {
+                recommended: pbkdf2::Params::new(algorithm, NonZeroU32::new(iterations).unwrap()),
+            },
+        };
+        assert_eq!(
+            Ok(expected),
+            pbkdf2::verify_with_params(&stored, password, &policy)
+        );
+
+        // An incorrect password fails regardless of the parameters.
+        assert_eq!(
+            Err(error::Unspecified),
+            pbkdf2::verify_with_params(&stored, b"wrong password", &policy)
+        );
+
+        // The stored parameters, not the policy, are used for verification.
+        let mismatched = pbkdf2::StoredParams::new(policy, salt, &hash);
+        if params != policy {
+            assert!(pbkdf2::verify_with_params(&mismatched, password, &policy).is_err());
+        }
+    }
+}
+
 /// The API documentation specifies that derive/verify should panic, if the designated output length
 /// is too long. Ring checks for an output array length while pbkdf2 is being ran, while we check
 /// the array length before everything is processed.
@@ -130,22 +130,31 @@ fn test_signature_rsa_pss_sign() {
 
 #[test]
 fn test_signature_rsa_pkcs1_verify() {
-    let sha1_params = &[
+    #[cfg(not(feature = "approved-only"))]
+    let sha1_params: &[&RsaParameters] = &[
         &signature::RSA_PKCS1_1024_8192_SHA1_FOR_LEGACY_USE_ONLY,
         &signature::RSA_PKCS1_2048_8192_SHA1_FOR_LEGACY_USE_ONLY,
     ];
-    let sha256_params = &[
+    #[cfg(feature = "approved-only")]
+    let sha1_params: &[&RsaParameters] = &[];
+    #[cfg(not(feature = "approved-only"))]
+    let sha256_params: &[&RsaParameters] = &[
         &signature::RSA_PKCS1_1024_8192_SHA256_FOR_LEGACY_USE_ONLY,
         &signature::RSA_PKCS1_2048_8192_SHA256,
     ];
-    let sha384_params = &[
+    #[cfg(feature = "approved-only")]
+    let sha256_params: &[&RsaParameters] = &[&signature::RSA_PKCS1_2048_8192_SHA256];
+    let sha384_params: &[&RsaParameters] = &[
         &signature::RSA_PKCS1_2048_8192_SHA384,
         &signature::RSA_PKCS1_3072_8192_SHA384,
     ];
-    let sha512_params = &[
+    #[cfg(not(feature = "approved-only"))]
+    let sha512_params: &[&RsaParameters] = &[
         &signature::RSA_PKCS1_1024_8192_SHA512_FOR_LEGACY_USE_ONLY,
         &signature::RSA_PKCS1_2048_8192_SHA512,
     ];
+    #[cfg(feature = "approved-only")]
+    let sha512_params: &[&RsaParameters] = &[&signature::RSA_PKCS1_2048_8192_SHA512];
     test::run(
         test_file!("data/rsa_pkcs1_verify_tests.txt"),
         |section, test_case| {
@@ -0,0 +1,228 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+#![cfg(feature = "serde")]
+
+use aws_lc_rs::cipher::{
+    DecryptionContext, PaddedBlockDecryptingKey, PaddedBlockEncryptingKey, UnboundCipherKey,
+    AES_128,
+};
+use aws_lc_rs::signature::{Ed25519KeyPair, KeyPair, Signature};
+use aws_lc_rs::{agreement, digest, hmac, rand};
+
+type Ed25519PublicKey = <Ed25519KeyPair as KeyPair>::PublicKey;
+
+#[test]
+fn digest_round_trip() {
+    for algorithm in [
+        &digest::SHA1_FOR_LEGACY_USE_ONLY,
+        &digest::SHA256,
+        &digest::SHA512_256,
+        &digest::SHA3_384,
+    ] {
+        let digest = digest::digest(algorithm, b"hello, world");
+
+        let json = serde_json::to_string(&digest).unwrap();
+        let decoded: digest::Digest = serde_json::from_str(&json).unwrap();
+        assert_eq!(digest.algorithm(), decoded.algorithm());
+        assert_eq!(digest.as_ref(), decoded.as_ref());
+
+        let bytes = bincode::serialize(&digest).unwrap();
+        let decoded: digest::Digest = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(digest.algorithm(), decoded.algorithm());
+        assert_eq!(digest.as_ref(), decoded.as_ref());
+    }
+}
+
+#[test]
+fn digest_json_format() {
+    let digest = digest::digest(&digest::SHA256, b"abc");
+    assert_eq!(
+        r#"{"algorithm":"SHA256","value":"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"}"#,
+        serde_json::to_string(&digest).unwrap()
+    );
+}
+
+#[test]
+fn digest_rejects_mismatch() {
+    // SHA-256 output under the SHA-384 algorithm
+    let json = r#"{"algorithm":"SHA384","value":"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"}"#;
+    assert!(serde_json::from_str::<digest::Digest>(json).is_err());
+
+    let json = r#"{"algorithm":"MD5","value":"900150983cd24fb0d6963f7d28e17f72"}"#;
+    assert!(serde_json::from_str::<digest::Digest>(json).is_err());
+
+    let json = r#"{"algorithm":"SHA256","value":"not hex"}"#;
+    assert!(serde_json::from_str::<digest::Digest>(json).is_err());
+
+    let json = r#"{"algorithm":"SHA256"}"#;
+    assert!(serde_json::from_str::<digest::Digest>(json).is_err());
+}
+
+#[test]
+fn tag_round_trip() {
+    let key = hmac::Key::new(hmac::HMAC_SHA384, b"key");
+    let tag = hmac::sign(&key, b"message");
+
+    let json = serde_json::to_string(&tag).unwrap();
+    let decoded: hmac::Tag = serde_json::from_str(&json).unwrap();
+    assert_eq!(tag.as_ref(), decoded.as_ref());
+    hmac::verify(&key, b"message", decoded.as_ref()).unwrap();
+
+    let bytes = bincode::serialize(&tag).unwrap();
+    let decoded: hmac::Tag = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(tag.as_ref(), decoded.as_ref());
+
+    assert!(serde_json::from_str::<hmac::Tag>(r#""0011""#).is_err());
+    assert!(
+        bincode::deserialize::<hmac::Tag>(&bincode::serialize(&[0u8; 33][..]).unwrap()).is_err()
+    );
+}
+
+#[test]
+fn signature_and_public_key_round_trip() {
+    let rng = rand::SystemRandom::new();
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&rng).unwrap();
+    let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+    let signature = key_pair.sign(b"message");
+
+    let json = serde_json::to_string(&signature).unwrap();
+    let decoded_signature: Signature = serde_json::from_str(&json).unwrap();
+    let json = serde_json::to_string(key_pair.public_key()).unwrap();
+    let decoded_key: Ed25519PublicKey = serde_json::from_str(&json).unwrap();
+
+    aws_lc_rs::signature::UnparsedPublicKey::new(&aws_lc_rs::signature::ED25519, decoded_key)
+        .verify(b"message", decoded_signature.as_ref())
+        .unwrap();
+
+    let bytes = bincode::serialize(&signature).unwrap();
+    let decoded: Signature = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(signature.as_ref(), decoded.as_ref());
+    let bytes = bincode::serialize(key_pair.public_key()).unwrap();
+    let decoded: Ed25519PublicKey = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(key_pair.public_key().as_ref(), decoded.as_ref());
+
+    assert!(serde_json::from_str::<Ed25519PublicKey>(r#""00""#).is_err());
+    assert!(serde_json::from_str::<Signature>(r#""""#).is_err());
+}
+
+#[test]
+fn agreement_public_key_round_trip() {
+    let rng = rand::SystemRandom::new();
+    for algorithm in [
+        &agreement::ECDH_P256,
+        &agreement::ECDH_P521,
+        &agreement::X25519,
+    ] {
+        let private_key = agreement::EphemeralPrivateKey::generate(algorithm, &rng).unwrap();
+        let public_key = private_key.compute_public_key().unwrap();
+
+        let json = serde_json::to_string(&public_key).unwrap();
+        let decoded: agreement::PublicKey = serde_json::from_str(&json).unwrap();
+        assert_eq!(public_key.algorithm(), decoded.algorithm());
+        assert_eq!(public_key.as_ref(), decoded.as_ref());
+
+        let bytes = bincode::serialize(&public_key).unwrap();
+        let decoded: agreement::PublicKey = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(public_key.algorithm(), decoded.algorithm());
+        assert_eq!(public_key.as_ref(), decoded.as_ref());
+    }
+
+    // An X25519 public key under the P-256 algorithm
+    let json = format!(
+        r#"{{"algorithm":"ECDH_P256","value":"{}"}}"#,
+        "09".repeat(32)
+    );
+    assert!(serde_json::from_str::<agreement::PublicKey>(&json).is_err());
+}
+
+#[test]
+fn decryption_context_round_trip() {
+    let key = [0x42u8; 16];
+    let plaintext = b"an envelope holding its iv";
+    let encrypting_key =
+        PaddedBlockEncryptingKey::cbc_pkcs7(UnboundCipherKey::new(&AES_128, &key).unwrap())
+            .unwrap();
+    let decrypting_key =
+        PaddedBlockDecryptingKey::cbc_pkcs7(UnboundCipherKey::new(&AES_128, &key).unwrap())
+            .unwrap();
+
+    let mut ciphertext = plaintext.to_vec();
+    let context = encrypting_key.encrypt(&mut ciphertext).unwrap();
+    let json = serde_json::to_string(&context).unwrap();
+    let bytes = bincode::serialize(&context).unwrap();
+
+    let decoded: DecryptionContext = serde_json::from_str(&json).unwrap();
+    assert_eq!(context, decoded);
+    let mut in_out = ciphertext.clone();
+    assert_eq!(
+        plaintext,
+        decrypting_key.decrypt(&mut in_out, decoded).unwrap()
+    );
+
+    let decoded: DecryptionContext = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(context, decoded);
+    assert_eq!(
+        plaintext,
+        decrypting_key.decrypt(&mut ciphertext, decoded).unwrap()
+    );
+
+    for context in [
+        DecryptionContext::from([1u8; 8]),
+        DecryptionContext::from([2u8; 12]),
+        DecryptionContext::Nonce96Counter32([3u8; 12].into(), 7),
+        DecryptionContext::None,
+    ] {
+        let json = serde_json::to_string(&context).unwrap();
+        assert_eq!(
+            context,
+            serde_json::from_str::<DecryptionContext>(&json).unwrap()
+        );
+        let bytes = bincode::serialize(&context).unwrap();
+        assert_eq!(
+            context,
+            bincode::deserialize::<DecryptionContext>(&bytes).unwrap()
+        );
+    }
+}
+
+#[test]
+fn decryption_context_json_format() {
+    let context = DecryptionContext::from([
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f,
+    ]);
+    assert_eq!(
+        r#"{"Iv128":"000102030405060708090a0b0c0d0e0f"}"#,
+        serde_json::to_string(&context).unwrap()
+    );
+    let context = DecryptionContext::Nonce96Counter32([0u8; 12].into(), 1);
+    assert_eq!(
+        r#"{"Nonce96Counter32":["000000000000000000000000",1]}"#,
+        serde_json::to_string(&context).unwrap()
+    );
+    assert_eq!(
+        r#""None""#,
+        serde_json::to_string(&DecryptionContext::None).unwrap()
+    );
+}
+
+#[test]
+fn decryption_context_rejects_invalid() {
+    // A 96-bit nonce under the 128-bit IV variant
+    let json = format!(r#"{{"Iv128":"{}"}}"#, "00".repeat(12));
+    assert!(serde_json::from_str::<DecryptionContext>(&json).is_err());
+
+    let json = format!(r#"{{"Iv256":"{}"}}"#, "00".repeat(32));
+    assert!(serde_json::from_str::<DecryptionContext>(&json).is_err());
+
+    let json = format!(r#"{{"Nonce96Counter32":["{}"]}}"#, "00".repeat(12));
+    assert!(serde_json::from_str::<DecryptionContext>(&json).is_err());
+
+    // The encoding starts with the variant index as a little-endian u32.
+    let mut bytes = bincode::serialize(&DecryptionContext::from([0u8; 12])).unwrap();
+    bytes[0] = 0;
+    assert!(bincode::deserialize::<DecryptionContext>(&bytes).is_err());
+    bytes[0] = 5;
+    assert!(bincode::deserialize::<DecryptionContext>(&bytes).is_err());
+}
@@ -0,0 +1,81 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use aws_lc_rs::rand::SecureRandom;
+use aws_lc_rs::{digest, test, test_file};
+
+#[test]
+fn test_vector_file() {
+    let mut digests = 0;
+    let mut randoms = 0;
+    test::run(
+        test_file!("data/test_utils_tests.txt"),
+        |section, test_case| {
+            match section {
+                "Digest" => {
+                    let algorithm = test_case.consume_digest_alg("Hash").unwrap();
+                    let input = test_case.consume_bytes("Input");
+                    let output = test_case.consume_bytes("Output");
+                    assert_eq!(output, digest::digest(algorithm, &input).as_ref());
+                    digests += 1;
+                }
+                "Random" => {
+                    let output = test_case.consume_bytes("Output");
+                    let fills = test_case.consume_usize("Fills");
+                    let rng = test::rand::FixedSliceRandom { bytes: &output };
+                    for _ in 0..fills {
+                        let mut buf = vec![0u8; output.len()];
+                        rng.fill(&mut buf)?;
+                        assert_eq!(output, buf);
+                    }
+                    randoms += 1;
+                }
+                _ => unreachable!("unexpected section: {section}"),
+            }
+            Ok(())
+        },
+    );
+    assert_eq!((4, 2), (digests, randoms));
+}
+
+#[test]
+#[should_panic(expected = "Test failed.")]
+fn test_vector_file_unconsumed_attribute() {
+    test::run(
+        test_file!("data/test_utils_tests.txt"),
+        |_section, test_case| {
+            test_case.consume_string("Output");
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn hex_round_trip() {
+    let bytes = test::from_hex("00ff7F80").unwrap();
+    assert_eq!(&[0x00, 0xff, 0x7f, 0x80], bytes.as_slice());
+    assert_eq!("00ff7f80", test::to_hex(&bytes));
+    assert_eq!("00FF7F80", test::to_hex_upper(&bytes));
+    assert_eq!(bytes, test::from_dirty_hex("00:ff:7f:80"));
+    assert!(test::from_hex("0g").is_err());
+}
+
+#[test]
+fn fixed_randoms() {
+    let rng = test::rand::FixedByteRandom { byte: 0x5a };
+    let mut buf = [0u8; 7];
+    rng.fill(&mut buf).unwrap();
+    assert_eq!([0x5a; 7], buf);
+
+    let outputs: [&[u8]; 2] = [&[1, 2, 3], &[4]];
+    let rng = test::rand::FixedSliceSequenceRandom {
+        bytes: &outputs,
+        current: core::cell::UnsafeCell::new(0),
+    };
+    let mut first = [0u8; 3];
+    let mut second = [0u8; 1];
+    rng.fill(&mut first).unwrap();
+    rng.fill(&mut second).unwrap();
+    assert_eq!([1, 2, 3], first);
+    assert_eq!([4], second);
+}
@@ -0,0 +1,138 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+#![cfg(feature = "unstable")]
+
+use aws_lc_rs::agreement::{self, PrivateKey};
+use aws_lc_rs::test;
+use aws_lc_rs::unstable::ec::{Point, PointEncoding, Scalar, P256, P384};
+#[cfg(not(feature = "fips"))]
+use aws_lc_rs::{test_file, unstable::ec::Curve};
+
+#[cfg(not(feature = "fips"))]
+fn curve_from_name(name: &str) -> &'static Curve {
+    match name {
+        "P-256" => &P256,
+        "P-384" => &P384,
+        _ => panic!("Unrecognized curve: {name}"),
+    }
+}
+
+#[cfg(not(feature = "fips"))]
+#[test]
+fn hash_to_curve_tests() {
+    test::run(
+        test_file!("data/ec_hash_to_curve_tests.txt"),
+        |section, test_case| {
+            assert_eq!(section, "");
+            let curve = curve_from_name(&test_case.consume_string("Curve"));
+            let dst = test_case.consume_bytes("DST");
+            let msg = test_case.consume_bytes("Msg");
+            let expected = test_case.consume_bytes("P");
+
+            let point = Point::hash_to_curve(curve, &dst, &msg).unwrap();
+            assert_eq!(
+                expected,
+                point.to_sec1(PointEncoding::Uncompressed).unwrap()
+            );
+            assert_eq!(point, Point::from_sec1(curve, &expected).unwrap());
+
+            Ok(())
+        },
+    );
+}
+
+#[cfg(not(feature = "fips"))]
+#[test]
+fn hash_to_curve_rejects_invalid_dst() {
+    assert!(Point::hash_to_curve(&P256, b"", b"msg").is_err());
+    assert!(Point::hash_to_curve(&P384, &[0x41; 256], b"msg").is_err());
+}
+
+#[test]
+fn mul_base_matches_agreement() {
+    for (curve, algorithm) in [
+        (&P256, &agreement::ECDH_P256),
+        (&P384, &agreement::ECDH_P384),
+    ] {
+        let scalar = Scalar::generate(curve).unwrap();
+        let private_key =
+            PrivateKey::from_private_key(algorithm, &scalar.to_be_bytes().unwrap()).unwrap();
+        let public_key = private_key.compute_public_key().unwrap();
+
+        let point = Point::mul_base(&scalar).unwrap();
+        assert_eq!(
+            public_key.as_ref(),
+            point.to_sec1(PointEncoding::Uncompressed).unwrap()
+        );
+    }
+}
+
+#[test]
+fn mul_matches_agreement() {
+    for (curve, algorithm) in [
+        (&P256, &agreement::ECDH_P256),
+        (&P384, &agreement::ECDH_P384),
+    ] {
+        let my_private_key = PrivateKey::generate(algorithm).unwrap();
+        let peer_scalar = Scalar::generate(curve).unwrap();
+        let peer_public_key = Point::mul_base(&peer_scalar).unwrap();
+        let peer_public_key_bytes = peer_public_key
+            .to_sec1(PointEncoding::Uncompressed)
+            .unwrap();
+
+        let my_point =
+            Point::from_sec1(curve, my_private_key.compute_public_key().unwrap().as_ref()).unwrap();
+        let shared_point = my_point.mul(&peer_scalar).unwrap();
+        let shared_x =
+            &shared_point.to_sec1(PointEncoding::Uncompressed).unwrap()[1..=curve.scalar_len()];
+
+        agreement::agree(
+            &my_private_key,
+            &agreement::UnparsedPublicKey::new(algorithm, &peer_public_key_bytes),
+            aws_lc_rs::error::Unspecified,
+            |key_material| {
+                assert_eq!(shared_x, key_material);
+                Ok(())
+            },
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn point_decoding() {
+    for curve in [&P256, &P384] {
+        let generator = Point::generator(curve).unwrap();
+        let uncompressed = generator.to_sec1(PointEncoding::Uncompressed).unwrap();
+        let compressed = generator.to_sec1(PointEncoding::Compressed).unwrap();
+        assert_eq!(1 + 2 * curve.scalar_len(), uncompressed.len());
+        assert_eq!(1 + curve.scalar_len(), compressed.len());
+        assert_eq!(uncompressed[1..=curve.scalar_len()], compressed[1..]);
+        assert_eq!(generator, Point::from_sec1(curve, &compressed).unwrap());
+
+        // Not on the curve.
+        let mut invalid = uncompressed.clone();
+        *invalid.last_mut().unwrap() ^= 1;
+        assert!(Point::from_sec1(curve, &invalid).is_err());
+
+        // The point at infinity.
+        assert!(Point::from_sec1(curve, &[0]).is_err());
+    }
+}
+
+#[test]
+fn scalar_reduction() {
+    // n + 1 reduces to 1.
+    let order_plus_one =
+        test::from_hex("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632552").unwrap();
+    assert!(Scalar::from_be_bytes(&P256, &order_plus_one).is_err());
+    let reduced = Scalar::from_be_bytes_reduced(&P256, &order_plus_one).unwrap();
+    let mut one = vec![0u8; 32];
+    one[31] = 1;
+    assert_eq!(one, reduced.to_be_bytes().unwrap());
+    assert_eq!(
+        Point::generator(&P256).unwrap(),
+        Point::mul_base(&reduced).unwrap()
+    );
+}
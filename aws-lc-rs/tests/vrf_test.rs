@@ -0,0 +1,95 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use aws_lc_rs::vrf::{self, Proof, UnparsedPublicKey, VrfKeyPair};
+use aws_lc_rs::{test, test_file};
+
+#[test]
+fn test_vrf_traits() {
+    test::compile_time_assert_send::<VrfKeyPair>();
+    test::compile_time_assert_sync::<VrfKeyPair>();
+    test::compile_time_assert_send::<Proof>();
+    test::compile_time_assert_sync::<Proof>();
+}
+
+#[test]
+fn ecvrf_tests() {
+    test::run(test_file!("data/ecvrf_tests.txt"), |section, test_case| {
+        assert_eq!(section, "");
+        let algorithm = &vrf::ECVRF_P256_SHA256_TAI;
+        let secret_key = test_case.consume_bytes("SK");
+        let public_key = test_case.consume_bytes("PK");
+        let alpha = test_case.consume_bytes("Alpha");
+        let pi = test_case.consume_bytes("Pi");
+        let beta = test_case.consume_bytes("Beta");
+
+        let key_pair = VrfKeyPair::from_secret_key(algorithm, &secret_key).unwrap();
+        assert_eq!(public_key, key_pair.public_key().as_ref());
+
+        let proof = key_pair.prove(&alpha).unwrap();
+        assert_eq!(pi, proof.as_ref());
+        assert_eq!(beta, proof.to_hash().as_ref());
+
+        let parsed = Proof::new(algorithm, &pi).unwrap();
+        assert_eq!(beta, parsed.to_hash().as_ref());
+
+        let verifier = UnparsedPublicKey::new(algorithm, &public_key);
+        assert_eq!(beta, verifier.verify(&alpha, &pi).unwrap().as_ref());
+
+        // A different input.
+        let mut other_alpha = alpha.clone();
+        other_alpha.push(0);
+        assert!(verifier.verify(&other_alpha, &pi).is_err());
+
+        // A mutated gamma, challenge or response.
+        let point_len = algorithm.proof_len() - 48;
+        for i in [point_len - 1, point_len, pi.len() - 1] {
+            let mut mutated = pi.clone();
+            mutated[i] ^= 1;
+            assert!(verifier.verify(&alpha, &mutated).is_err());
+        }
+
+        // A truncated proof.
+        assert!(verifier.verify(&alpha, &pi[..pi.len() - 1]).is_err());
+        assert!(Proof::new(algorithm, &pi[..pi.len() - 1]).is_err());
+
+        // A different public key.
+        let other_key_pair = VrfKeyPair::generate(algorithm).unwrap();
+        let other_verifier = UnparsedPublicKey::new(algorithm, other_key_pair.public_key());
+        assert!(other_verifier.verify(&alpha, &pi).is_err());
+
+        Ok(())
+    });
+}
+
+#[test]
+fn ecvrf_rejects_unreduced_response() {
+    let algorithm = &vrf::ECVRF_P256_SHA256_TAI;
+    let key_pair = VrfKeyPair::generate(algorithm).unwrap();
+    let proof = key_pair.prove(b"alpha").unwrap();
+    let mut pi = proof.as_ref().to_vec();
+    let len = pi.len();
+    // s = 2^256 - 1 is not less than the group order.
+    pi[len - 32..].fill(0xff);
+    assert!(Proof::new(algorithm, &pi).is_err());
+    let verifier = UnparsedPublicKey::new(algorithm, key_pair.public_key());
+    assert!(verifier.verify(b"alpha", &pi).is_err());
+}
+
+#[test]
+fn ecvrf_rejects_invalid_public_keys() {
+    let key_pair = VrfKeyPair::generate(&vrf::ECVRF_P256_SHA256_TAI).unwrap();
+    let proof = key_pair.prove(b"alpha").unwrap();
+
+    // An uncompressed point is not accepted.
+    let mut uncompressed = key_pair.public_key().as_ref().to_vec();
+    uncompressed[0] = 0x04;
+    let verifier = UnparsedPublicKey::new(&vrf::ECVRF_P256_SHA256_TAI, &uncompressed);
+    assert!(verifier.verify(b"alpha", proof.as_ref()).is_err());
+
+    // An x-coordinate that is not on the curve.
+    let mut off_curve = key_pair.public_key().as_ref().to_vec();
+    off_curve[1..].fill(0xff);
+    let verifier = UnparsedPublicKey::new(&vrf::ECVRF_P256_SHA256_TAI, &off_curve);
+    assert!(verifier.verify(b"alpha", proof.as_ref()).is_err());
+}
@@ -0,0 +1,147 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use aws_lc_rs::{
+    aead, agreement, cipher, hkdf, hmac, iv, kem, key_wrap, pkcs8, rand, signature, vrf,
+};
+use aws_lc_rs::{rand::SystemRandom, tls_prf};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use zeroize::ZeroizeOnDrop;
+
+const PENDING: u8 = 0;
+const ZEROED: u8 = 1;
+const NOT_ZEROED: u8 = 2;
+
+/// An allocator that inspects a single tracked allocation when it is freed.
+struct InspectingAllocator;
+
+static TRACKED_PTR: AtomicUsize = AtomicUsize::new(0);
+static TRACKED_LEN: AtomicUsize = AtomicUsize::new(0);
+static TRACKED_STATE: AtomicU8 = AtomicU8::new(PENDING);
+
+unsafe impl GlobalAlloc for InspectingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if ptr as usize == TRACKED_PTR.load(Ordering::SeqCst) {
+            let len = TRACKED_LEN.load(Ordering::SeqCst).min(layout.size());
+            let bytes = core::slice::from_raw_parts(ptr, len);
+            let state = if bytes.iter().all(|b| *b == 0) {
+                ZEROED
+            } else {
+                NOT_ZEROED
+            };
+            TRACKED_STATE.store(state, Ordering::SeqCst);
+            TRACKED_PTR.store(0, Ordering::SeqCst);
+        }
+        System.dealloc(ptr, layout);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: InspectingAllocator = InspectingAllocator;
+
+lazy_static::lazy_static! {
+    // Only one allocation can be tracked at a time.
+    static ref TRACKING: Mutex<()> = Mutex::new(());
+}
+
+fn assert_cleared_on_drop<T: AsRef<[u8]> + ZeroizeOnDrop>(value: T) {
+    let _guard = TRACKING
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let bytes = value.as_ref();
+    assert!(bytes.iter().any(|b| *b != 0));
+
+    TRACKED_STATE.store(PENDING, Ordering::SeqCst);
+    TRACKED_LEN.store(bytes.len(), Ordering::SeqCst);
+    TRACKED_PTR.store(bytes.as_ptr() as usize, Ordering::SeqCst);
+    drop(value);
+
+    assert_eq!(ZEROED, TRACKED_STATE.load(Ordering::SeqCst));
+}
+
+#[test]
+fn pkcs8_document_cleared_on_drop() {
+    let rng = SystemRandom::new();
+
+    assert_cleared_on_drop(signature::Ed25519KeyPair::generate_pkcs8(&rng).unwrap());
+    assert_cleared_on_drop(
+        signature::EcdsaKeyPair::generate_pkcs8(&signature::ECDSA_P256_SHA256_ASN1_SIGNING, &rng)
+            .unwrap(),
+    );
+}
+
+#[test]
+fn tls_prf_secret_cleared_on_drop() {
+    let mut secret = [0u8; 48];
+    rand::fill(&mut secret).unwrap();
+    let secret = tls_prf::Secret::new(&tls_prf::P_SHA256, &secret).unwrap();
+    assert_cleared_on_drop(secret.derive(b"label", b"seed", 32).unwrap());
+
+    let mut secret = [0u8; 48];
+    rand::fill(&mut secret).unwrap();
+    assert_cleared_on_drop(tls_prf::Secret::new(&tls_prf::P_SHA384, &secret).unwrap());
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn kem_shared_secret_cleared_on_drop() {
+    use aws_lc_rs::unstable::kem::{get_algorithm, AlgorithmId};
+
+    let algorithm = get_algorithm(AlgorithmId::Kyber512_R3).unwrap();
+    let decapsulation_key = kem::DecapsulationKey::generate(algorithm).unwrap();
+    let encapsulation_key = decapsulation_key.encapsulation_key().unwrap();
+    let (ciphertext, shared_secret) = encapsulation_key.encapsulate().unwrap();
+    assert_cleared_on_drop(shared_secret);
+    assert_cleared_on_drop(decapsulation_key.decapsulate(ciphertext).unwrap());
+}
+
+#[test]
+fn secret_types_zeroize_on_drop() {
+    struct Nonces;
+
+    impl aead::NonceSequence for Nonces {
+        fn advance(&mut self) -> Result<aead::Nonce, aws_lc_rs::error::Unspecified> {
+            Err(aws_lc_rs::error::Unspecified)
+        }
+    }
+
+    fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+
+    assert_zeroize_on_drop::<cipher::UnboundCipherKey>();
+    assert_zeroize_on_drop::<cipher::PaddedBlockEncryptingKey>();
+    assert_zeroize_on_drop::<cipher::PaddedBlockDecryptingKey>();
+    assert_zeroize_on_drop::<cipher::EncryptingKey>();
+    assert_zeroize_on_drop::<cipher::DecryptingKey>();
+    assert_zeroize_on_drop::<aead::UnboundKey>();
+    assert_zeroize_on_drop::<aead::LessSafeKey>();
+    assert_zeroize_on_drop::<aead::OpeningKey<Nonces>>();
+    assert_zeroize_on_drop::<aead::SealingKey<Nonces>>();
+    assert_zeroize_on_drop::<aead::RandomizedNonceKey>();
+    assert_zeroize_on_drop::<aead::TlsRecordSealingKey>();
+    assert_zeroize_on_drop::<aead::TlsRecordOpeningKey>();
+    assert_zeroize_on_drop::<aead::quic::HeaderProtectionKey>();
+    assert_zeroize_on_drop::<aead::chacha20_poly1305_openssh::SealingKey>();
+    assert_zeroize_on_drop::<aead::chacha20_poly1305_openssh::OpeningKey>();
+    assert_zeroize_on_drop::<hmac::Key>();
+    assert_zeroize_on_drop::<hmac::Context>();
+    assert_zeroize_on_drop::<hkdf::Salt>();
+    assert_zeroize_on_drop::<hkdf::Prk>();
+    assert_zeroize_on_drop::<agreement::PrivateKey>();
+    assert_zeroize_on_drop::<agreement::EphemeralPrivateKey>();
+    assert_zeroize_on_drop::<signature::EcdsaKeyPair>();
+    assert_zeroize_on_drop::<signature::RsaKeyPair>();
+    assert_zeroize_on_drop::<signature::Ed25519KeyPair>();
+    assert_zeroize_on_drop::<kem::DecapsulationKey>();
+    assert_zeroize_on_drop::<kem::SharedSecret>();
+    assert_zeroize_on_drop::<key_wrap::AesKek>();
+    assert_zeroize_on_drop::<tls_prf::Secret>();
+    assert_zeroize_on_drop::<pkcs8::Document>();
+    assert_zeroize_on_drop::<iv::FixedLength<16>>();
+    assert_zeroize_on_drop::<vrf::VrfKeyPair>();
+}